@@ -16,7 +16,9 @@ fn llvm_args_to_string_id(profiler: &SelfProfiler, pass_name: &str, ir_name: &st
         components.push(StringComponent::Value(SEPARATOR_BYTE));
         components.push(StringComponent::Ref(ir_name));
     }
-    EventId::from_label(profiler.alloc_string(components.as_slice()))
+    let event_id = EventId::from_label(profiler.alloc_string(components.as_slice()));
+    profiler.map_event_id_to_label(event_id, pass_name);
+    event_id
 }
 
 pub struct LlvmSelfProfiler<'a> {
@@ -27,7 +29,7 @@ pub struct LlvmSelfProfiler<'a> {
 
 impl<'a> LlvmSelfProfiler<'a> {
     pub fn new(profiler: Arc<SelfProfiler>) -> Self {
-        let llvm_pass_event_kind = profiler.alloc_string("LLVM Pass");
+        let llvm_pass_event_kind = profiler.get_or_alloc_cached_string("LLVM Pass");
         Self { profiler, stack: Vec::default(), llvm_pass_event_kind }
     }
 
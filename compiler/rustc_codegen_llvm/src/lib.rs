@@ -37,6 +37,7 @@
 use rustc_session::config::{OptLevel, OutputFilenames, PrintKind, PrintRequest};
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
+use rustc_target::json::Json;
 
 use std::any::Any;
 use std::ffi::CStr;
@@ -257,6 +258,35 @@ pub fn new() -> Box<dyn CodegenBackend> {
     }
 }
 
+const RELOCATION_MODELS: &[&str] =
+    &["static", "pic", "pie", "dynamic-no-pic", "ropi", "rwpi", "ropi-rwpi", "default"];
+
+const CODE_MODELS: &[&str] = &["tiny", "small", "kernel", "medium", "large"];
+
+const TLS_MODELS: &[&str] =
+    &["global-dynamic", "local-dynamic", "initial-exec", "local-exec", "emulated"];
+
+const STACK_PROTECTOR_STRATEGIES: &[(&str, &str)] = &[
+    ("all", "Generate stack canaries in all functions."),
+    (
+        "strong",
+        "Generate stack canaries in a function if it either:
+- has a local variable of `[T; N]` type, regardless of `T` and `N`
+- takes the address of a local variable.
+
+  (Note that a local variable being borrowed is not equivalent to its
+  address being taken: e.g. some borrows may be removed by optimization,
+  while by-value argument passing may be implemented with reference to a
+  local stack variable in the ABI.)",
+    ),
+    (
+        "basic",
+        "Generate stack canaries in functions with local variables of `[T; N]`
+type, where `T` is byte-sized and `N` >= 8.",
+    ),
+    ("none", "Do not generate stack canaries."),
+];
+
 impl CodegenBackend for LlvmCodegenBackend {
     fn locale_resource(&self) -> &'static str {
         crate::DEFAULT_LOCALE_RESOURCE
@@ -275,66 +305,62 @@ fn print(&self, req: &PrintRequest, out: &mut dyn PrintBackendInfo, sess: &Sessi
         match req.kind {
             PrintKind::RelocationModels => {
                 writeln!(out, "Available relocation models:");
-                for name in &[
-                    "static",
-                    "pic",
-                    "pie",
-                    "dynamic-no-pic",
-                    "ropi",
-                    "rwpi",
-                    "ropi-rwpi",
-                    "default",
-                ] {
+                for name in RELOCATION_MODELS {
                     writeln!(out, "    {name}");
                 }
                 writeln!(out);
             }
             PrintKind::CodeModels => {
                 writeln!(out, "Available code models:");
-                for name in &["tiny", "small", "kernel", "medium", "large"] {
+                for name in CODE_MODELS {
                     writeln!(out, "    {name}");
                 }
                 writeln!(out);
             }
             PrintKind::TlsModels => {
                 writeln!(out, "Available TLS models:");
-                for name in
-                    &["global-dynamic", "local-dynamic", "initial-exec", "local-exec", "emulated"]
-                {
+                for name in TLS_MODELS {
                     writeln!(out, "    {name}");
                 }
                 writeln!(out);
             }
             PrintKind::StackProtectorStrategies => {
-                writeln!(
-                    out,
-                    r#"Available stack protector strategies:
-    all
-        Generate stack canaries in all functions.
-
-    strong
-        Generate stack canaries in a function if it either:
-        - has a local variable of `[T; N]` type, regardless of `T` and `N`
-        - takes the address of a local variable.
-
-          (Note that a local variable being borrowed is not equivalent to its
-          address being taken: e.g. some borrows may be removed by optimization,
-          while by-value argument passing may be implemented with reference to a
-          local stack variable in the ABI.)
-
-    basic
-        Generate stack canaries in functions with local variables of `[T; N]`
-        type, where `T` is byte-sized and `N` >= 8.
-
-    none
-        Do not generate stack canaries.
-"#
-                );
+                writeln!(out, "Available stack protector strategies:");
+                for (name, description) in STACK_PROTECTOR_STRATEGIES {
+                    writeln!(out, "    {name}");
+                    for line in description.lines() {
+                        if line.is_empty() {
+                            writeln!(out);
+                        } else {
+                            writeln!(out, "        {line}");
+                        }
+                    }
+                    writeln!(out);
+                }
             }
             _other => llvm_util::print(req, out, sess),
         }
     }
 
+    fn print_json(&self, req: &PrintRequest, sess: &Session) -> Json {
+        match req.kind {
+            PrintKind::RelocationModels => Json::from(RELOCATION_MODELS),
+            PrintKind::CodeModels => Json::from(CODE_MODELS),
+            PrintKind::TlsModels => Json::from(TLS_MODELS),
+            PrintKind::StackProtectorStrategies => Json::from(
+                STACK_PROTECTOR_STRATEGIES
+                    .iter()
+                    .map(|&(name, description)| {
+                        [("name", Json::from(name)), ("description", Json::from(description))]
+                            .into_iter()
+                            .collect::<Json>()
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            _other => llvm_util::print_json(req, sess),
+        }
+    }
+
     fn print_passes(&self) {
         llvm_util::print_passes();
     }
@@ -2160,6 +2160,9 @@ pub fn LLVMRustPrintTargetCPUs(
         print: unsafe extern "C" fn(out: *mut c_void, string: *const c_char, len: usize),
         out: *mut c_void,
     );
+    pub fn LLVMRustGetTargetCPUsCount(T: &TargetMachine) -> size_t;
+    pub fn LLVMRustGetTargetCPU(T: &TargetMachine, Index: size_t) -> *const c_char;
+    pub fn LLVMRustIsHostArch(T: &TargetMachine) -> bool;
     pub fn LLVMRustGetTargetFeaturesCount(T: &TargetMachine) -> size_t;
     pub fn LLVMRustGetTargetFeature(
         T: &TargetMachine,
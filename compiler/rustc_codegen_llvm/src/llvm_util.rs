@@ -14,6 +14,7 @@
 use rustc_session::config::{PrintKind, PrintRequest};
 use rustc_session::Session;
 use rustc_span::symbol::Symbol;
+use rustc_target::json::Json;
 use rustc_target::spec::{MergeFunctions, PanicStrategy};
 use rustc_target::target_features::RUSTC_SPECIFIC_FEATURES;
 
@@ -372,7 +373,12 @@ fn llvm_target_features(tm: &llvm::TargetMachine) -> Vec<(&str, &str)> {
     ret
 }
 
-fn print_target_features(out: &mut dyn PrintBackendInfo, sess: &Session, tm: &llvm::TargetMachine) {
+/// Returns the target features known to rustc and the remaining code-generation features only
+/// known to LLVM, each with the description LLVM provides for it.
+fn target_features_with_descriptions<'a>(
+    sess: &Session,
+    tm: &'a llvm::TargetMachine,
+) -> (Vec<(&'a str, &'a str)>, Vec<(&'a str, &'a str)>) {
     let mut llvm_target_features = llvm_target_features(tm);
     let mut known_llvm_target_features = FxHashSet::<&'static str>::default();
     let mut rustc_target_features = sess
@@ -399,6 +405,11 @@ fn print_target_features(out: &mut dyn PrintBackendInfo, sess: &Session, tm: &ll
         "Enables C Run-time Libraries to be statically linked",
     )]);
     llvm_target_features.retain(|(f, _d)| !known_llvm_target_features.contains(f));
+    (rustc_target_features, llvm_target_features)
+}
+
+fn print_target_features(out: &mut dyn PrintBackendInfo, sess: &Session, tm: &llvm::TargetMachine) {
+    let (rustc_target_features, llvm_target_features) = target_features_with_descriptions(sess, tm);
 
     let max_feature_len = llvm_target_features
         .iter()
@@ -453,6 +464,70 @@ pub(crate) fn print(req: &PrintRequest, mut out: &mut dyn PrintBackendInfo, sess
     }
 }
 
+fn llvm_target_cpus(tm: &llvm::TargetMachine) -> Vec<&str> {
+    let len = unsafe { llvm::LLVMRustGetTargetCPUsCount(tm) };
+    (0..len)
+        .map(|i| unsafe {
+            let cpu = llvm::LLVMRustGetTargetCPU(tm, i);
+            CStr::from_ptr(cpu).to_str().unwrap_or_else(|e| {
+                bug!("LLVM returned a non-utf8 CPU string: {}", e);
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn print_json(req: &PrintRequest, sess: &Session) -> Json {
+    require_inited();
+    let tm = create_informational_target_machine(sess);
+    let entry = |name: &str, description: Option<String>| {
+        [("name", Json::from(name)), ("description", Json::from(description))]
+            .into_iter()
+            .collect::<Json>()
+    };
+    match req.kind {
+        PrintKind::TargetCPUs => {
+            let target_cpu = handle_native(sess.target.cpu.as_ref());
+            let mut cpus = Vec::new();
+            // Like the text output, only offer "native" when it is a CPU of the target's arch.
+            if unsafe { llvm::LLVMRustIsHostArch(&tm) } {
+                let host_cpu = handle_native("native");
+                let description =
+                    format!("Select the CPU of the current host (currently {host_cpu}).");
+                cpus.push(entry("native", Some(description)));
+            }
+            cpus.extend(llvm_target_cpus(&tm).into_iter().map(|cpu| {
+                let description = (cpu == target_cpu).then(|| {
+                    format!(
+                        "This is the default target CPU for the current build target \
+                         (currently {}).",
+                        sess.target.llvm_target
+                    )
+                });
+                entry(cpu, description)
+            }));
+            Json::from(cpus)
+        }
+        PrintKind::TargetFeatures => {
+            let (rustc_target_features, llvm_target_features) =
+                target_features_with_descriptions(sess, &tm);
+            let features = |features: Vec<(&str, &str)>| {
+                Json::from(
+                    features
+                        .into_iter()
+                        .map(|(feature, desc)| {
+                            entry(feature, (!desc.is_empty()).then(|| desc.to_owned()))
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            };
+            [("rustc", features(rustc_target_features)), ("llvm", features(llvm_target_features))]
+                .into_iter()
+                .collect()
+        }
+        _ => bug!("rustc_codegen_llvm can't handle print request: {:?}", req),
+    }
+}
+
 fn handle_native(name: &str) -> &str {
     if name != "native" {
         return name;
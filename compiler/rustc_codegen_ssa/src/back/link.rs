@@ -13,8 +13,9 @@
 use rustc_middle::middle::dependency_format::Linkage;
 use rustc_middle::middle::exported_symbols::SymbolExportKind;
 use rustc_session::config::LinkerFeaturesCli;
+use rustc_session::config::SplitDwarfKind;
 use rustc_session::config::{self, CFGuard, CrateType, DebugInfo, OutFileName, Strip};
-use rustc_session::config::{OutputFilenames, OutputType, PrintKind, SplitDwarfKind};
+use rustc_session::config::{OutputFilenames, OutputType, PrintFormat, PrintKind, PrintRequest};
 use rustc_session::cstore::DllImport;
 use rustc_session::output::{check_file_is_writeable, invalid_output_for_target, out_filename};
 use rustc_session::search_paths::PathKind;
@@ -23,6 +24,7 @@
 /// need out of the shared crate context before we get rid of it.
 use rustc_session::{filesearch, Session};
 use rustc_span::symbol::Symbol;
+use rustc_target::json::Json;
 use rustc_target::spec::crt_objects::CrtObjects;
 use rustc_target::spec::LinkSelfContainedDefault;
 use rustc_target::spec::LinkerFlavorCli;
@@ -603,7 +605,7 @@ fn link_staticlib(
 
     for print in &sess.opts.prints {
         if print.kind == PrintKind::NativeStaticLibs {
-            print_native_static_libs(sess, &print.out, &all_native_libs, &all_rust_dylibs);
+            print_native_static_libs(sess, print, &all_native_libs, &all_rust_dylibs);
         }
     }
 
@@ -750,8 +752,30 @@ fn link_natively(
 
     for print in &sess.opts.prints {
         if print.kind == PrintKind::LinkArgs {
-            let content = format!("{cmd:?}");
-            print.out.overwrite(&content, sess);
+            match print.format {
+                PrintFormat::Text => {
+                    let content = format!("{cmd:?}");
+                    print.out.overwrite(&content, sess);
+                }
+                PrintFormat::Json => {
+                    let command = cmd.command();
+                    let os_str_to_json = |s: &OsStr| Json::from(s.to_string_lossy().into_owned());
+                    let env = command
+                        .get_envs()
+                        .map(|(k, v)| {
+                            (k.to_string_lossy().into_owned(), v.map_or(Json::Null, os_str_to_json))
+                        })
+                        .collect::<Json>();
+                    let data = [
+                        ("program", os_str_to_json(command.get_program())),
+                        ("args", command.get_args().map(os_str_to_json).collect::<Vec<_>>().into()),
+                        ("env", env),
+                    ]
+                    .into_iter()
+                    .collect();
+                    print.write_json(data, sess);
+                }
+            }
         }
     }
 
@@ -1483,7 +1507,7 @@ enum RlibFlavor {
 
 fn print_native_static_libs(
     sess: &Session,
-    out: &OutFileName,
+    print: &PrintRequest,
     all_native_libs: &[NativeLib],
     all_rust_dylibs: &[&Path],
 ) {
@@ -1558,6 +1582,12 @@ fn print_native_static_libs(
         }
     }
 
+    if print.format == PrintFormat::Json {
+        print.write_json(Json::from(lib_args), sess);
+        return;
+    }
+
+    let out = &print.out;
     match out {
         OutFileName::Real(path) => {
             out.overwrite(&lib_args.join(" "), sess);
@@ -9,6 +9,7 @@
 };
 use jobserver::{Acquired, Client};
 use rustc_ast::attr;
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_data_structures::memmap::Mmap;
 use rustc_data_structures::profiling::{SelfProfilerRef, VerboseTimingGuard};
@@ -29,6 +30,7 @@
 use rustc_middle::bug;
 use rustc_middle::dep_graph::{WorkProduct, WorkProductId};
 use rustc_middle::middle::exported_symbols::SymbolExportInfo;
+use rustc_middle::query::on_disk_cache::SharedCacheStore;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::{self, CrateType, Lto, OutFileName, OutputFilenames, OutputType};
 use rustc_session::config::{Passes, SwitchWithOptPath};
@@ -47,8 +49,9 @@
 use std::path::{Path, PathBuf};
 use std::str;
 use std::sync::mpsc::{channel, Receiver, Sender};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 use tracing::debug;
 
 const PRE_LTO_BC_EXT: &str = "pre-lto.bc";
@@ -386,6 +389,8 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     ///
     /// Depends on [`CodegenBackend::supports_parallel()`] and `-Zno_parallel_backend`.
     pub parallel: bool,
+    /// The time spent optimizing each module, for `-Z item-time-report`.
+    pub optimization_times: Option<Arc<Mutex<Vec<(String, Duration)>>>>,
 }
 
 impl<B: WriteBackendMethods> CodegenContext<B> {
@@ -489,6 +494,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
 
     let (shared_emitter, shared_emitter_main) = SharedEmitter::new();
     let (codegen_worker_send, codegen_worker_receive) = channel();
+    let optimization_times = sess.prof.time_items().then(Default::default);
 
     let coordinator_thread = start_executing_work(
         backend.clone(),
@@ -502,6 +508,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
         Arc::new(metadata_config),
         Arc::new(allocator_config),
         coordinator_send.clone(),
+        optimization_times.clone(),
     );
 
     OngoingCodegen {
@@ -518,6 +525,8 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
             phantom: PhantomData,
         },
         output_filenames: tcx.output_filenames(()).clone(),
+        shared_cache: None,
+        optimization_times,
     }
 }
 
@@ -534,22 +543,7 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
     let _timer = sess.timer("copy_all_cgu_workproducts_to_incr_comp_cache_dir");
 
     for module in compiled_modules.modules.iter().filter(|m| m.kind == ModuleKind::Regular) {
-        let mut files = Vec::new();
-        if let Some(object_file_path) = &module.object {
-            files.push((OutputType::Object.extension(), object_file_path.as_path()));
-        }
-        if let Some(dwarf_object_file_path) = &module.dwarf_object {
-            files.push(("dwo", dwarf_object_file_path.as_path()));
-        }
-        if let Some(path) = &module.assembly {
-            files.push((OutputType::Assembly.extension(), path.as_path()));
-        }
-        if let Some(path) = &module.llvm_ir {
-            files.push((OutputType::LlvmAssembly.extension(), path.as_path()));
-        }
-        if let Some(path) = &module.bytecode {
-            files.push((OutputType::Bitcode.extension(), path.as_path()));
-        }
+        let files = work_product_files(module);
         if let Some((id, product)) =
             copy_cgu_workproduct_to_incr_comp_cache_dir(sess, &module.name, files.as_slice())
         {
@@ -560,6 +554,43 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
     work_products
 }
 
+/// Adds the freshly compiled CGUs with a key in `keys` to the shared cache.
+fn add_cgus_to_shared_cache(
+    sess: &Session,
+    shared_cache: &dyn SharedCacheStore,
+    keys: &FxHashMap<String, Fingerprint>,
+    compiled_modules: &CompiledModules,
+) {
+    let _timer = sess.timer("add_cgus_to_shared_cache");
+
+    for module in compiled_modules.modules.iter().filter(|m| m.kind == ModuleKind::Regular) {
+        if let Some(&key) = keys.get(&module.name) {
+            shared_cache.store_work_product(sess, key, &work_product_files(module));
+        }
+    }
+}
+
+/// The files of `module` that are saved in a work product, by extension.
+fn work_product_files(module: &CompiledModule) -> Vec<(&'static str, &Path)> {
+    let mut files = Vec::new();
+    if let Some(object_file_path) = &module.object {
+        files.push((OutputType::Object.extension(), object_file_path.as_path()));
+    }
+    if let Some(dwarf_object_file_path) = &module.dwarf_object {
+        files.push(("dwo", dwarf_object_file_path.as_path()));
+    }
+    if let Some(path) = &module.assembly {
+        files.push((OutputType::Assembly.extension(), path.as_path()));
+    }
+    if let Some(path) = &module.llvm_ir {
+        files.push((OutputType::LlvmAssembly.extension(), path.as_path()));
+    }
+    if let Some(path) = &module.bytecode {
+        files.push((OutputType::Bitcode.extension(), path.as_path()));
+    }
+    files
+}
+
 fn produce_final_output_artifacts(
     sess: &Session,
     compiled_modules: &CompiledModules,
@@ -649,7 +680,11 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::StableMir => {}
         }
     }
 
@@ -869,9 +904,13 @@ fn execute_optimize_work_item<B: ExtraBackendMethods>(
 ) -> Result<WorkItemResult<B>, FatalError> {
     let dcx = cgcx.create_dcx();
 
+    let start = Instant::now();
     unsafe {
         B::optimize(cgcx, &dcx, &module, module_config)?;
     }
+    if let Some(optimization_times) = &cgcx.optimization_times {
+        optimization_times.lock().unwrap().push((module.name.clone(), start.elapsed()));
+    }
 
     // After we've done the initial round of optimizations we need to
     // decide whether to synchronously codegen this module or ship it
@@ -918,10 +957,13 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     module: CachedModuleCodegen,
     module_config: &ModuleConfig,
 ) -> WorkItemResult<B> {
-    let incr_comp_session_dir = cgcx.incr_comp_session_dir.as_ref().unwrap();
-
     let load_from_incr_comp_dir = |output_path: PathBuf, saved_path: &str| {
-        let source_file = in_incr_comp_dir(incr_comp_session_dir, saved_path);
+        // Work products from the shared cache are only used without incremental
+        // compilation, and refer to their files by absolute path.
+        let source_file = match &cgcx.incr_comp_session_dir {
+            Some(incr_comp_session_dir) => in_incr_comp_dir(incr_comp_session_dir, saved_path),
+            None => PathBuf::from(saved_path),
+        };
         debug!(
             "copying preexisting module `{}` from {:?} to {}",
             module.name,
@@ -1089,6 +1131,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
     metadata_config: Arc<ModuleConfig>,
     allocator_config: Arc<ModuleConfig>,
     tx_to_llvm_workers: Sender<Box<dyn Any + Send>>,
+    optimization_times: Option<Arc<Mutex<Vec<(String, Duration)>>>>,
 ) -> thread::JoinHandle<Result<CompiledModules, ()>> {
     let coordinator_send = tx_to_llvm_workers;
     let sess = tcx.sess;
@@ -1189,6 +1232,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         split_debuginfo: tcx.sess.split_debuginfo(),
         split_dwarf_kind: tcx.sess.opts.unstable_opts.split_dwarf_kind,
         parallel: backend.supports_parallel() && !sess.opts.unstable_opts.no_parallel_backend,
+        optimization_times,
     };
 
     // This is the "main loop" of parallel work happening for parallel codegen.
@@ -2012,6 +2056,11 @@ pub struct OngoingCodegen<B: ExtraBackendMethods> {
     pub shared_emitter_main: SharedEmitterMain,
     pub output_filenames: Arc<OutputFilenames>,
     pub coordinator: Coordinator<B>,
+    /// The cache given with `-Zshared-cache`, and the keys of the CGUs that need
+    /// to be added to it once they have been compiled.
+    pub shared_cache: Option<(Lrc<dyn SharedCacheStore>, FxHashMap<String, Fingerprint>)>,
+    /// The time spent optimizing each module, for `-Z item-time-report`.
+    pub optimization_times: Option<Arc<Mutex<Vec<(String, Duration)>>>>,
 }
 
 impl<B: ExtraBackendMethods> OngoingCodegen<B> {
@@ -2032,8 +2081,17 @@ pub fn join(self, sess: &Session) -> (CodegenResults, FxIndexMap<WorkProductId,
 
         sess.dcx().abort_if_errors();
 
+        if let Some(optimization_times) = self.optimization_times {
+            for (name, time) in optimization_times.lock().unwrap().drain(..) {
+                sess.code_stats.record_optimization_time(name, time);
+            }
+        }
+
         let work_products =
             copy_all_cgu_workproducts_to_incr_comp_cache_dir(sess, &compiled_modules);
+        if let Some((shared_cache, keys)) = self.shared_cache {
+            add_cgus_to_shared_cache(sess, &*shared_cache, &keys, &compiled_modules);
+        }
         produce_final_output_artifacts(sess, &compiled_modules, &self.output_filenames);
 
         // FIXME: time_llvm_passes support - does this use a global context or
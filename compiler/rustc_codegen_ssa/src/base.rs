@@ -11,6 +11,7 @@
 use crate::mir;
 use crate::mir::operand::OperandValue;
 use crate::mir::place::PlaceRef;
+use crate::shared_cache::shared_cache_keys;
 use crate::traits::*;
 use crate::{CachedModuleCodegen, CompiledModule, CrateInfo, ModuleCodegen, ModuleKind};
 
@@ -632,7 +633,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         })
     });
 
-    let ongoing_codegen =
+    let mut ongoing_codegen =
         start_async_codegen(backend.clone(), tcx, target_cpu, metadata, metadata_module);
 
     // Codegen an allocator shim, if necessary.
@@ -683,10 +684,31 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     };
 
     // Calculate the CGU reuse
-    let cgu_reuse = tcx.sess.time("find_cgu_reuse", || {
+    let mut cgu_reuse = tcx.sess.time("find_cgu_reuse", || {
         codegen_units.iter().map(|cgu| determine_cgu_reuse(tcx, cgu)).collect::<Vec<_>>()
     });
 
+    // Reuse CGUs from the shared cache, and remember the keys of the others so
+    // that they can be added to it once they are compiled. The cache isn't
+    // open with incremental compilation, where CGUs are reused from the
+    // incremental cache instead.
+    let mut shared_work_products = FxHashMap::default();
+    if let Some(shared_cache) = tcx.query_system.shared_cache.clone() {
+        let keys =
+            tcx.sess.time("compute_shared_cache_keys", || shared_cache_keys(tcx, &codegen_units));
+        let mut keys_to_store = FxHashMap::default();
+        for (i, (cgu, key)) in codegen_units.iter().zip(keys).enumerate() {
+            let Some(key) = key else { continue };
+            if let Some(work_product) = shared_cache.load_work_product(key, cgu.name().as_str()) {
+                cgu_reuse[i] = CguReuse::PostLto;
+                shared_work_products.insert(i, work_product);
+            } else {
+                keys_to_store.insert(cgu.name().to_string(), key);
+            }
+        }
+        ongoing_codegen.shared_cache = Some((shared_cache, keys_to_store));
+    }
+
     crate::assert_module_sources::assert_module_sources(tcx, &|cgu_reuse_tracker| {
         for (i, cgu) in codegen_units.iter().enumerate() {
             let cgu_reuse = cgu_reuse[i];
@@ -778,7 +800,9 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
                     &ongoing_codegen.coordinator.sender,
                     CachedModuleCodegen {
                         name: cgu.name().to_string(),
-                        source: cgu.previous_work_product(tcx),
+                        source: shared_work_products
+                            .remove(&i)
+                            .unwrap_or_else(|| cgu.previous_work_product(tcx)),
                     },
                 );
             }
@@ -48,6 +48,7 @@
 pub mod meth;
 pub mod mir;
 pub mod mono_item;
+pub mod shared_cache;
 pub mod size_of_val;
 pub mod target_features;
 pub mod traits;
@@ -103,7 +103,14 @@ fn define<Bx: BuilderMethods<'a, 'tcx>>(&self, cx: &'a Bx::CodegenCx) {
                 }
             }
             MonoItem::Fn(instance) => {
-                base::codegen_instance::<Bx>(cx, instance);
+                let tcx = cx.tcx();
+                if tcx.sess.prof.time_items() {
+                    tcx.sess.code_stats.time_item(Some(instance.def_id()), "codegen", || {
+                        base::codegen_instance::<Bx>(cx, instance)
+                    });
+                } else {
+                    base::codegen_instance::<Bx>(cx, instance);
+                }
             }
         }
 
@@ -0,0 +1,231 @@
+//! Reusing codegen units from the cache given with `-Zshared-cache`, see
+//! `rustc_incremental::SharedCache` for how the cache itself works.
+//!
+//! Entries of the cache can be reused by any compilation session, so the key of
+//! a codegen unit has to cover everything that affects the code generated for
+//! it. Nothing about the crate as a whole goes into it though, only the
+//! compiler version and the options, so that a change to one item doesn't
+//! invalidate the codegen units that don't depend on it:
+//!
+//! - the items of the codegen unit, with their linkage and visibility, their
+//!   symbol names and attributes, the MIR bodies of functions (including the
+//!   spans, which end up in the debuginfo) and the initializers of statics,
+//! - the values of the constants used by those bodies,
+//! - the layouts of all the types that appear in those bodies, and the
+//!   definitions of the ADTs among them, which the debuginfo refers to,
+//! - and the interface of every item that is used by one of the items of the
+//!   codegen unit: its symbol name, attributes, linkage and visibility, and
+//!   its ABI or, for statics, its layout.
+//!
+//! Codegen units with global assembly are never cached, since the assembly can
+//! refer to anything by name.
+//!
+//! The name of the codegen unit and the symbol names of its items are part of
+//! the key, and both are specific to the crate being compiled. So codegen units
+//! are only reused by later builds of the same crate, and not by other crates
+//! that instantiate the same upstream items.
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem, MonoItemData, UsageMap};
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{self, ConstOperand, Location};
+use rustc_middle::ty::{
+    self, EarlyBinder, Ty, TyCtxt, TypeSuperVisitable, TypeVisitable, TypeVisitableExt, TypeVisitor,
+};
+use rustc_query_system::ich::StableHashingContext;
+use rustc_span::DUMMY_SP;
+
+use crate::back::write::{compute_per_cgu_lto_type, ComputedLtoType};
+use crate::ModuleKind;
+
+/// Computes the keys of `codegen_units` in the shared cache. A key is `None` if
+/// the codegen unit can't be cached.
+///
+/// With LTO, no codegen unit can be cached, since modules from the cache would
+/// still need to take part in it, which isn't supported.
+pub fn shared_cache_keys<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    codegen_units: &[&CodegenUnit<'tcx>],
+) -> Vec<Option<Fingerprint>> {
+    let lto_type = compute_per_cgu_lto_type(
+        &tcx.sess.lto(),
+        &tcx.sess.opts,
+        tcx.crate_types(),
+        ModuleKind::Regular,
+    );
+    if !matches!(lto_type, ComputedLtoType::No) {
+        return vec![None; codegen_units.len()];
+    }
+
+    let session_key: Fingerprint = tcx.with_stable_hashing_context(|mut hcx| {
+        let mut hasher = StableHasher::new();
+        tcx.sess.cfg_version.hash_stable(&mut hcx, &mut hasher);
+        tcx.sess.opts.dep_tracking_hash(false).hash_stable(&mut hcx, &mut hasher);
+        hasher.finish()
+    });
+    // The partitioning doesn't keep the usage map around, since it is large, so the mono items
+    // are collected again. This is still much cheaper than the codegen it may save.
+    let usage_map = &tcx.collect_mono_item_graph().usage_map;
+    let item_data: FxHashMap<_, _> = codegen_units
+        .iter()
+        .flat_map(|cgu| cgu.items())
+        .map(|(&item, &data)| (item, data))
+        .collect();
+
+    codegen_units
+        .iter()
+        .map(|cgu| {
+            let mut hasher =
+                KeyHasher { tcx, usage_map, item_data: &item_data, hasher: StableHasher::new() };
+            tcx.with_stable_hashing_context(|mut hcx| {
+                session_key.hash_stable(&mut hcx, &mut hasher.hasher);
+                cgu.name().hash_stable(&mut hcx, &mut hasher.hasher);
+                for (item, data) in cgu.items_in_deterministic_order(tcx) {
+                    hasher.hash_item(&mut hcx, item, data)?;
+                }
+                Some(hasher.hasher.finish())
+            })
+        })
+        .collect()
+}
+
+struct KeyHasher<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    usage_map: &'a UsageMap<'tcx>,
+    item_data: &'a FxHashMap<MonoItem<'tcx>, MonoItemData>,
+    hasher: StableHasher,
+}
+
+impl<'a, 'tcx> KeyHasher<'a, 'tcx> {
+    /// Hashes an item of the codegen unit, and the interface of the items it uses.
+    fn hash_item(
+        &mut self,
+        hcx: &mut StableHashingContext<'_>,
+        item: MonoItem<'tcx>,
+        data: MonoItemData,
+    ) -> Option<()> {
+        let tcx = self.tcx;
+        let param_env = ty::ParamEnv::reveal_all();
+        item.hash_stable(hcx, &mut self.hasher);
+        data.hash_stable(hcx, &mut self.hasher);
+        item.symbol_name(tcx).hash_stable(hcx, &mut self.hasher);
+
+        match item {
+            MonoItem::Fn(instance) => {
+                tcx.codegen_fn_attrs(instance.def_id()).hash_stable(hcx, &mut self.hasher);
+                // The body is hashed before instantiation, which is cheaper, since the
+                // instance it is instantiated with has been hashed above already. This
+                // is the body that is actually codegened, which differs from the
+                // `instance_mir` if a driver transforms it.
+                let body = tcx.codegen_instance_mir(instance);
+                body.hash_stable(hcx, &mut self.hasher);
+
+                let mut consts = ConstCollector { consts: Vec::new() };
+                consts.visit_body(body);
+                for constant in consts.consts {
+                    let constant = instance.instantiate_mir_and_normalize_erasing_regions(
+                        tcx,
+                        param_env,
+                        EarlyBinder::bind(constant),
+                    );
+                    constant
+                        .eval(tcx, param_env, DUMMY_SP)
+                        .ok()?
+                        .hash_stable(hcx, &mut self.hasher);
+                }
+
+                let mut types = TypeCollector { types: FxIndexSet::default() };
+                body.visit_with(&mut types);
+                for ty in types.types {
+                    let ty = instance.instantiate_mir_and_normalize_erasing_regions(
+                        tcx,
+                        param_env,
+                        EarlyBinder::bind(ty),
+                    );
+                    self.hash_type(hcx, ty)?;
+                }
+            }
+            MonoItem::Static(def_id) => {
+                tcx.codegen_fn_attrs(def_id).hash_stable(hcx, &mut self.hasher);
+                tcx.eval_static_initializer(def_id).ok()?.hash_stable(hcx, &mut self.hasher);
+                self.hash_type(hcx, tcx.type_of(def_id).instantiate_identity())?;
+            }
+            MonoItem::GlobalAsm(_) => return None,
+        }
+
+        for &(used, _) in self.usage_map.get_used_items(item) {
+            self.hash_used_item(hcx, used)?;
+        }
+        Some(())
+    }
+
+    /// Hashes the parts of an item that matter to the code that uses it.
+    fn hash_used_item(
+        &mut self,
+        hcx: &mut StableHashingContext<'_>,
+        item: MonoItem<'tcx>,
+    ) -> Option<()> {
+        let tcx = self.tcx;
+        item.hash_stable(hcx, &mut self.hasher);
+        item.symbol_name(tcx).hash_stable(hcx, &mut self.hasher);
+        self.item_data.get(&item).hash_stable(hcx, &mut self.hasher);
+        match item {
+            MonoItem::Fn(instance) => {
+                tcx.codegen_fn_attrs(instance.def_id()).hash_stable(hcx, &mut self.hasher);
+                let fn_abi = tcx
+                    .fn_abi_of_instance(
+                        ty::ParamEnv::reveal_all().and((instance, ty::List::empty())),
+                    )
+                    .ok()?;
+                fn_abi.hash_stable(hcx, &mut self.hasher);
+            }
+            MonoItem::Static(def_id) => {
+                tcx.codegen_fn_attrs(def_id).hash_stable(hcx, &mut self.hasher);
+                self.hash_type(hcx, tcx.type_of(def_id).instantiate_identity())?;
+            }
+            MonoItem::GlobalAsm(_) => return None,
+        }
+        Some(())
+    }
+
+    fn hash_type(&mut self, hcx: &mut StableHashingContext<'_>, ty: Ty<'tcx>) -> Option<()> {
+        ty.hash_stable(hcx, &mut self.hasher);
+        if let ty::Adt(adt_def, _) = ty.kind() {
+            adt_def.hash_stable(hcx, &mut self.hasher);
+        }
+        let layout = self.tcx.layout_of(ty::ParamEnv::reveal_all().and(ty)).ok()?;
+        layout.layout.hash_stable(hcx, &mut self.hasher);
+        Some(())
+    }
+}
+
+/// Collects the constants that are evaluated during codegen, including promoteds.
+struct ConstCollector<'tcx> {
+    consts: Vec<mir::Const<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for ConstCollector<'tcx> {
+    fn visit_constant(&mut self, constant: &ConstOperand<'tcx>, _: Location) {
+        if let mir::Const::Unevaluated(..) | mir::Const::Ty(..) = constant.const_ {
+            self.consts.push(constant.const_);
+        }
+    }
+}
+
+/// Collects the types whose layout is needed to generate code for a body. Types
+/// under binders, like the arguments of function pointers, are skipped: their
+/// layouts are only needed when they are instantiated, which makes them appear
+/// in the body on their own.
+struct TypeCollector<'tcx> {
+    types: FxIndexSet<Ty<'tcx>>,
+}
+
+impl<'tcx> TypeVisitor<TyCtxt<'tcx>> for TypeCollector<'tcx> {
+    fn visit_ty(&mut self, ty: Ty<'tcx>) {
+        if !ty.has_escaping_bound_vars() && self.types.insert(ty) {
+            ty.super_visit_with(self);
+        }
+    }
+}
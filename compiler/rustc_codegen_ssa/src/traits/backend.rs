@@ -21,6 +21,7 @@
 };
 use rustc_span::symbol::Symbol;
 use rustc_target::abi::call::FnAbi;
+use rustc_target::json::Json;
 
 use std::fmt;
 
@@ -63,6 +64,10 @@ pub trait CodegenBackend {
 
     fn init(&self, _sess: &Session) {}
     fn print(&self, _req: &PrintRequest, _out: &mut dyn PrintBackendInfo, _sess: &Session) {}
+    /// Like `print`, but for `--print-format=json`. Returns the `data` of the JSON document.
+    fn print_json(&self, _req: &PrintRequest, _sess: &Session) -> Json {
+        Json::Null
+    }
     fn target_features(&self, _sess: &Session, _allow_unstable: bool) -> Vec<Symbol> {
         vec![]
     }
@@ -79,6 +79,15 @@
 //! invocation) and allocate the corresponding strings together with a mapping
 //! for `DepNodeIndex as StringId`.
 //!
+//!
+//! ## Built-in Outputs
+//!
+//! With `-Z self-profile-summary` and `-Z self-profile-chrome-trace`, events
+//! are additionally recorded to a temporary file by the `summary` module, from
+//! which a summary table and a Chrome trace are written at the end of the
+//! session by `SelfProfilerRef::write_recorded_outputs`. This makes the
+//! profiles usable without the `measureme` tools, at the cost of more overhead.
+//!
 //! [mm]: https://github.com/rust-lang/measureme/
 
 use crate::fx::FxHashMap;
@@ -90,7 +99,8 @@
 use std::fmt::Display;
 use std::fs;
 use std::intrinsics::unlikely;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -101,6 +111,8 @@
 use smallvec::SmallVec;
 use tracing::warn;
 
+mod summary;
+
 bitflags::bitflags! {
     #[derive(Clone, Copy)]
     struct EventFilter: u16 {
@@ -172,19 +184,29 @@ pub struct SelfProfilerRef {
 
     // Print verbose generic activities to stderr.
     print_verbose_generic_activities: Option<TimePassesFormat>,
+
+    // Whether `-Z item-time-report` times queries and codegen, which is
+    // checked for every query.
+    time_items: bool,
 }
 
 impl SelfProfilerRef {
     pub fn new(
         profiler: Option<Arc<SelfProfiler>>,
         print_verbose_generic_activities: Option<TimePassesFormat>,
+        time_items: bool,
     ) -> SelfProfilerRef {
         // If there is no SelfProfiler then the filter mask is set to NONE,
         // ensuring that nothing ever tries to actually access it.
         let event_filter_mask =
             profiler.as_ref().map_or(EventFilter::empty(), |p| p.event_filter_mask);
 
-        SelfProfilerRef { profiler, event_filter_mask, print_verbose_generic_activities }
+        SelfProfilerRef {
+            profiler,
+            event_filter_mask,
+            print_verbose_generic_activities,
+            time_items,
+        }
     }
 
     /// This shim makes sure that calls only get executed if the filter mask
@@ -280,7 +302,9 @@ pub fn generic_activity_with_arg<A>(
             let event_label = profiler.get_or_alloc_cached_string(event_label);
             let event_id = if profiler.event_filter_mask.contains(EventFilter::FUNCTION_ARGS) {
                 let event_arg = profiler.get_or_alloc_cached_string(event_arg);
-                builder.from_label_and_arg(event_label, event_arg)
+                let event_id = builder.from_label_and_arg(event_label, event_arg);
+                profiler.map_event_id_to_label(event_id, event_label);
+                event_id
             } else {
                 builder.from_label(event_label)
             };
@@ -340,7 +364,9 @@ pub fn generic_activity_with_arg_recorder<F>(
                     );
                 }
 
-                builder.from_label_and_args(event_label, &recorder.args)
+                let event_id = builder.from_label_and_args(event_label, &recorder.args);
+                profiler.map_event_id_to_label(event_id, event_label);
+                event_id
             } else {
                 builder.from_label(event_label)
             };
@@ -389,7 +415,9 @@ pub fn generic_activity_with_args(
                     .iter()
                     .map(|s| profiler.get_or_alloc_cached_string(&s[..]))
                     .collect();
-                builder.from_label_and_args(event_label, &event_args)
+                let event_id = builder.from_label_and_args(event_label, &event_args);
+                profiler.map_event_id_to_label(event_id, event_label);
+                event_id
             } else {
                 builder.from_label(event_label)
             };
@@ -474,6 +502,13 @@ fn instant_query_event(
             EventId::from_virtual(event_id),
             thread_id,
         );
+        if let Some(recorder) = &profiler.recorder {
+            recorder.record_instant(
+                event_kind(profiler),
+                EventId::from_virtual(event_id),
+                thread_id,
+            );
+        }
     }
 
     pub fn with_profiler(&self, f: impl FnOnce(&SelfProfiler)) {
@@ -499,10 +534,29 @@ pub fn enabled(&self) -> bool {
     pub fn llvm_recording_enabled(&self) -> bool {
         self.event_filter_mask.contains(EventFilter::LLVM)
     }
+
+    /// Whether the time spent on each item is recorded for `-Z item-time-report`.
+    #[inline]
+    pub fn time_items(&self) -> bool {
+        self.time_items
+    }
     #[inline]
     pub fn get_self_profiler(&self) -> Option<Arc<SelfProfiler>> {
         self.profiler.clone()
     }
+
+    /// Writes the outputs of `-Z self-profile-summary` and
+    /// `-Z self-profile-chrome-trace`, returning the paths of those that
+    /// couldn't be written. Events recorded afterwards are left out of them.
+    pub fn write_recorded_outputs(&self) -> Vec<(PathBuf, io::Error)> {
+        match &self.profiler {
+            Some(profiler) => match &profiler.recorder {
+                Some(recorder) => recorder.write_outputs(profiler),
+                None => vec![],
+            },
+            None => vec![],
+        }
+    }
 }
 
 /// A helper for recording costly arguments to self-profiling events. Used with
@@ -545,6 +599,10 @@ pub struct SelfProfiler {
     query_blocked_event_kind: StringId,
     query_cache_hit_event_kind: StringId,
     artifact_size_event_kind: StringId,
+
+    /// The in-memory recording of events for `-Z self-profile-summary` and
+    /// `-Z self-profile-chrome-trace`, if either is enabled.
+    recorder: Option<summary::EventRecorder>,
 }
 
 impl SelfProfiler {
@@ -553,6 +611,8 @@ pub fn new(
         crate_name: Option<&str>,
         event_filters: Option<&[String]>,
         counter_name: &str,
+        write_summary: bool,
+        write_chrome_trace: bool,
     ) -> Result<SelfProfiler, Box<dyn Error + Send + Sync>> {
         fs::create_dir_all(output_directory)?;
 
@@ -566,6 +626,20 @@ pub fn new(
         let profiler =
             Profiler::with_counter(&path, measureme::counters::Counter::by_name(counter_name)?)?;
 
+        let output_path = |extension: &str| -> PathBuf {
+            output_directory.join(format!("{crate_name}-{pid:07}.{extension}"))
+        };
+        let recorder = (write_summary || write_chrome_trace)
+            .then(|| {
+                summary::EventRecorder::new(
+                    crate_name,
+                    output_directory,
+                    write_summary.then(|| output_path("summary.txt")),
+                    write_chrome_trace.then(|| output_path("chrome_trace.json")),
+                )
+            })
+            .transpose()?;
+
         let query_event_kind = profiler.alloc_string("Query");
         let generic_activity_event_kind = profiler.alloc_string("GenericActivity");
         let incremental_load_result_event_kind = profiler.alloc_string("IncrementalLoadResult");
@@ -619,6 +693,7 @@ pub fn new(
             query_blocked_event_kind,
             query_cache_hit_event_kind,
             artifact_size_event_kind,
+            recorder,
         })
     }
 
@@ -659,6 +734,9 @@ pub fn get_or_alloc_cached_string<A>(&self, s: A) -> StringId
 
     pub fn map_query_invocation_id_to_string(&self, from: QueryInvocationId, to: StringId) {
         let from = StringId::new_virtual(from.0);
+        if let Some(recorder) = &self.recorder {
+            recorder.map_to_label(std::iter::once(from), to);
+        }
         self.profiler.map_virtual_to_concrete_string(from, to);
     }
 
@@ -667,7 +745,23 @@ pub fn bulk_map_query_invocation_id_to_single_string<I>(&self, from: I, to: Stri
         I: Iterator<Item = QueryInvocationId> + ExactSizeIterator,
     {
         let from = from.map(|qid| StringId::new_virtual(qid.0));
-        self.profiler.bulk_map_virtual_to_single_concrete_string(from, to);
+        if let Some(recorder) = &self.recorder {
+            let from: Vec<_> = from.collect();
+            recorder.map_to_label(from.iter().copied(), to);
+            self.profiler.bulk_map_virtual_to_single_concrete_string(from.into_iter(), to);
+        } else {
+            self.profiler.bulk_map_virtual_to_single_concrete_string(from, to);
+        }
+    }
+
+    /// Records that `event_id` was built from the label `label` and some
+    /// arguments. The events with that id are then attributed to the label in
+    /// the outputs of `-Z self-profile-summary` and `-Z self-profile-chrome-trace`,
+    /// which don't record the arguments.
+    pub fn map_event_id_to_label(&self, event_id: EventId, label: StringId) {
+        if let Some(recorder) = &self.recorder {
+            recorder.map_to_label(std::iter::once(event_id.to_string_id()), label);
+        }
     }
 
     pub fn query_key_recording_enabled(&self) -> bool {
@@ -680,7 +774,7 @@ pub fn event_id_builder(&self) -> EventIdBuilder<'_> {
 }
 
 #[must_use]
-pub struct TimingGuard<'a>(Option<measureme::TimingGuard<'a>>);
+pub struct TimingGuard<'a>(Option<measureme::TimingGuard<'a>>, Option<summary::RecordingGuard<'a>>);
 
 impl<'a> TimingGuard<'a> {
     #[inline]
@@ -693,7 +787,9 @@ pub fn start(
         let raw_profiler = &profiler.profiler;
         let timing_guard =
             raw_profiler.start_recording_interval_event(event_kind, event_id, thread_id);
-        TimingGuard(Some(timing_guard))
+        let recording_guard =
+            profiler.recorder.as_ref().map(|r| r.start(event_kind, event_id, thread_id));
+        TimingGuard(Some(timing_guard), recording_guard)
     }
 
     #[inline]
@@ -702,6 +798,9 @@ pub fn finish_with_query_invocation_id(self, query_invocation_id: QueryInvocatio
             outline(|| {
                 let event_id = StringId::new_virtual(query_invocation_id.0);
                 let event_id = EventId::from_virtual(event_id);
+                if let Some(recording_guard) = self.1 {
+                    recording_guard.finish_with_override_event_id(event_id);
+                }
                 guard.finish_with_override_event_id(event_id);
             });
         }
@@ -709,7 +808,7 @@ pub fn finish_with_query_invocation_id(self, query_invocation_id: QueryInvocatio
 
     #[inline]
     pub fn none() -> TimingGuard<'a> {
-        TimingGuard(None)
+        TimingGuard(None, None)
     }
 
     #[inline(always)]
@@ -0,0 +1,472 @@
+//! Recording of self-profiling events, used to write the outputs of `-Z self-profile-summary`
+//! and `-Z self-profile-chrome-trace` directly from the compiler, without post-processing the
+//! `.mm_profdata` file with the `measureme` tools.
+//!
+//! Events are recorded with the same event kind and `event_id` as in the `measureme` profile,
+//! and streamed to a temporary file as they finish, so that long compilations don't keep them
+//! all in memory. Their self time is computed as they are recorded, assuming that the events of
+//! each thread are properly nested, as the `TimingGuard`s producing them are.
+//!
+//! The labels of the events are only resolved when the outputs are written: the concrete
+//! strings are looked up in the string cache of the `SelfProfiler`, and virtual and composite
+//! `event_id`s (query invocations, or labels with arguments) are first mapped to those strings
+//! through `EventRecorder::map_to_label`. The recorded events are then read back once, feeding
+//! both the summary and the trace.
+
+use super::SelfProfiler;
+use crate::fx::FxHashMap;
+use measureme::{EventId, StringId};
+use parking_lot::Mutex;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Seek, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Instant;
+
+pub(super) struct EventRecorder {
+    crate_name: String,
+    start_time: Instant,
+    /// `None` once the outputs have been written.
+    recording: Mutex<Option<Recording>>,
+    /// Maps virtual and composite `StringId`s to the `StringId` of their label.
+    labels: Mutex<FxHashMap<StringId, StringId>>,
+    summary_path: Option<PathBuf>,
+    chrome_trace_path: Option<PathBuf>,
+}
+
+struct Recording {
+    /// The encoded events, in the order in which they finished.
+    events: BufWriter<File>,
+    self_times: SelfTimes,
+    /// The first error writing `events`, which is reported for every output.
+    error: Option<io::Error>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct RecordedEvent {
+    pub(super) kind: StringId,
+    pub(super) id: StringId,
+    pub(super) thread_id: u32,
+    /// Nanoseconds since the start of the recording.
+    pub(super) start: u64,
+    /// Nanoseconds since the start of the recording. Equal to `start` for instant events.
+    pub(super) end: u64,
+    /// The duration minus the durations of the events directly nested in this one on the same
+    /// thread. Zero for instant events.
+    pub(super) self_time: u64,
+    pub(super) is_instant: bool,
+}
+
+const ENCODED_EVENT_SIZE: usize = 8 + 8 + 4 + 8 + 8 + 8 + 1;
+
+impl RecordedEvent {
+    pub(super) fn encode(&self) -> [u8; ENCODED_EVENT_SIZE] {
+        let mut bytes = [0; ENCODED_EVENT_SIZE];
+        bytes[0..8].copy_from_slice(&self.kind.as_u64().to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.id.as_u64().to_le_bytes());
+        bytes[16..20].copy_from_slice(&self.thread_id.to_le_bytes());
+        bytes[20..28].copy_from_slice(&self.start.to_le_bytes());
+        bytes[28..36].copy_from_slice(&self.end.to_le_bytes());
+        bytes[36..44].copy_from_slice(&self.self_time.to_le_bytes());
+        bytes[44] = self.is_instant as u8;
+        bytes
+    }
+
+    pub(super) fn decode(bytes: &[u8; ENCODED_EVENT_SIZE]) -> RecordedEvent {
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        RecordedEvent {
+            kind: StringId::new(u64_at(0)),
+            id: StringId::new(u64_at(8)),
+            thread_id: u32::from_le_bytes(bytes[16..20].try_into().unwrap()),
+            start: u64_at(20),
+            end: u64_at(28),
+            self_time: u64_at(36),
+            is_instant: bytes[44] != 0,
+        }
+    }
+}
+
+/// Tracks the events in progress on each thread to compute the self time of events as they
+/// finish.
+#[derive(Default)]
+pub(super) struct SelfTimes {
+    /// For each event in progress, innermost last, the time spent in the events that finished
+    /// while directly nested in it.
+    nested_time: FxHashMap<u32, Vec<u64>>,
+}
+
+impl SelfTimes {
+    pub(super) fn start(&mut self, thread_id: u32) {
+        self.nested_time.entry(thread_id).or_default().push(0);
+    }
+
+    /// Finishes the innermost event in progress on the thread, returning its self time.
+    pub(super) fn finish(&mut self, thread_id: u32, duration: u64) -> u64 {
+        let stack = self.nested_time.entry(thread_id).or_default();
+        let nested_time = stack.pop().unwrap_or(0);
+        if let Some(parent) = stack.last_mut() {
+            *parent += duration;
+        }
+        duration.saturating_sub(nested_time)
+    }
+}
+
+impl Recording {
+    fn record(&mut self, event: RecordedEvent) {
+        if self.error.is_none() {
+            self.error = self.events.write_all(&event.encode()).err();
+        }
+    }
+
+    /// Returns a reader over the recorded events.
+    fn into_events(self) -> io::Result<BufReader<File>> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let mut file = self.events.into_inner().map_err(|err| err.into_error())?;
+        file.rewind()?;
+        Ok(BufReader::new(file))
+    }
+}
+
+fn read_event(events: &mut impl Read) -> io::Result<Option<RecordedEvent>> {
+    let mut bytes = [0; ENCODED_EVENT_SIZE];
+    match events.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(RecordedEvent::decode(&bytes))),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+impl EventRecorder {
+    pub(super) fn new(
+        crate_name: &str,
+        output_directory: &Path,
+        summary_path: Option<PathBuf>,
+        chrome_trace_path: Option<PathBuf>,
+    ) -> io::Result<EventRecorder> {
+        let recording = Recording {
+            events: BufWriter::new(tempfile::tempfile_in(output_directory)?),
+            self_times: SelfTimes::default(),
+            error: None,
+        };
+        Ok(EventRecorder {
+            crate_name: crate_name.to_owned(),
+            start_time: Instant::now(),
+            recording: Mutex::new(Some(recording)),
+            labels: Default::default(),
+            summary_path,
+            chrome_trace_path,
+        })
+    }
+
+    fn now(&self) -> u64 {
+        self.start_time.elapsed().as_nanos() as u64
+    }
+
+    pub(super) fn start(
+        &self,
+        kind: StringId,
+        event_id: EventId,
+        thread_id: u32,
+    ) -> RecordingGuard<'_> {
+        if let Some(recording) = &mut *self.recording.lock() {
+            recording.self_times.start(thread_id);
+        }
+        RecordingGuard {
+            recorder: self,
+            kind,
+            id: event_id.to_string_id(),
+            thread_id,
+            start: self.now(),
+        }
+    }
+
+    pub(super) fn record_instant(&self, kind: StringId, event_id: EventId, thread_id: u32) {
+        let now = self.now();
+        if let Some(recording) = &mut *self.recording.lock() {
+            recording.record(RecordedEvent {
+                kind,
+                id: event_id.to_string_id(),
+                thread_id,
+                start: now,
+                end: now,
+                self_time: 0,
+                is_instant: true,
+            });
+        }
+    }
+
+    pub(super) fn map_to_label(&self, from: impl Iterator<Item = StringId>, label: StringId) {
+        let mut labels = self.labels.lock();
+        labels.extend(from.map(|from| (from, label)));
+    }
+
+    /// Writes the requested outputs, returning those that couldn't be written. Events recorded
+    /// afterwards are ignored.
+    pub(super) fn write_outputs(&self, profiler: &SelfProfiler) -> Vec<(PathBuf, io::Error)> {
+        let Some(recording) = self.recording.lock().take() else {
+            return vec![];
+        };
+        let labels = self.labels.lock();
+        let string_cache = profiler.string_cache.read();
+        let mut names: FxHashMap<StringId, &str> =
+            string_cache.iter().map(|(name, &id)| (id, &name[..])).collect();
+        names.extend([
+            (profiler.query_event_kind, "Query"),
+            (profiler.generic_activity_event_kind, "GenericActivity"),
+            (profiler.incremental_load_result_event_kind, "IncrementalLoadResult"),
+            (profiler.incremental_result_hashing_event_kind, "IncrementalResultHashing"),
+            (profiler.query_blocked_event_kind, "QueryBlocked"),
+            (profiler.query_cache_hit_event_kind, "QueryCacheHit"),
+        ]);
+        let names = Names { names, labels: &labels };
+
+        let mut failures = vec![];
+        let mut trace = self.chrome_trace_path.as_ref().and_then(|path| {
+            ChromeTrace::create(path).map_err(|err| failures.push((path.clone(), err))).ok()
+        });
+        let mut items: FxHashMap<&str, ItemStats> = FxHashMap::default();
+
+        let read_result = recording.into_events().and_then(|mut events| {
+            while let Some(event) = read_event(&mut events)? {
+                if self.summary_path.is_some() {
+                    add_to_summary(&mut items, &event, &names, profiler);
+                }
+                if let Some(t) = &mut trace {
+                    if let Err(err) = t.write_event(&event, &names) {
+                        failures.push((t.path.clone(), err));
+                        trace = None;
+                    }
+                }
+            }
+            Ok(())
+        });
+        if let Err(err) = read_result {
+            // Without the events, none of the outputs can be written.
+            let unwritten = self.summary_path.iter().chain(trace.as_ref().map(|t| &t.path));
+            for path in unwritten {
+                failures.push((path.clone(), io::Error::new(err.kind(), err.to_string())));
+            }
+            return failures;
+        }
+
+        if let Some(path) = &self.summary_path {
+            let mut items: Vec<_> = items.into_iter().collect();
+            items.sort_by(|(a_label, a), (b_label, b)| {
+                b.self_time.cmp(&a.self_time).then_with(|| a_label.cmp(b_label))
+            });
+            let text = format_summary(&self.crate_name, &items, profiler);
+            if let Err(err) = fs::write(path, text) {
+                failures.push((path.clone(), err));
+            }
+        }
+        if let Some(trace) = trace {
+            let path = trace.path.clone();
+            if let Err(err) = trace.finish() {
+                failures.push((path, err));
+            }
+        }
+        failures
+    }
+}
+
+/// Resolves the kinds and labels of recorded events.
+struct Names<'a> {
+    names: FxHashMap<StringId, &'a str>,
+    labels: &'a FxHashMap<StringId, StringId>,
+}
+
+impl<'a> Names<'a> {
+    fn kind(&self, event: &RecordedEvent) -> &'a str {
+        self.names.get(&event.kind).copied().unwrap_or("<unknown>")
+    }
+
+    /// Returns the label of the event, or its kind if the label is unknown.
+    fn label(&self, event: &RecordedEvent) -> &'a str {
+        // Query invocations map to composite ids when query keys are recorded, which in turn
+        // map to the query name, so at most two steps are needed.
+        let mut id = event.id;
+        for _ in 0..3 {
+            if let Some(name) = self.names.get(&id) {
+                return name;
+            }
+            match self.labels.get(&id) {
+                Some(&label) => id = label,
+                None => break,
+            }
+        }
+        self.kind(event)
+    }
+}
+
+#[must_use]
+pub(super) struct RecordingGuard<'a> {
+    recorder: &'a EventRecorder,
+    kind: StringId,
+    id: StringId,
+    thread_id: u32,
+    start: u64,
+}
+
+impl RecordingGuard<'_> {
+    pub(super) fn finish_with_override_event_id(mut self, event_id: EventId) {
+        self.id = event_id.to_string_id();
+    }
+}
+
+impl Drop for RecordingGuard<'_> {
+    fn drop(&mut self) {
+        let end = self.recorder.now();
+        if let Some(recording) = &mut *self.recorder.recording.lock() {
+            let self_time = recording.self_times.finish(self.thread_id, end - self.start);
+            recording.record(RecordedEvent {
+                kind: self.kind,
+                id: self.id,
+                thread_id: self.thread_id,
+                start: self.start,
+                end,
+                self_time,
+                is_instant: false,
+            });
+        }
+    }
+}
+
+/// The statistics of all events with the same label. Times are in nanoseconds.
+#[derive(Default)]
+struct ItemStats {
+    self_time: u64,
+    invocations: u64,
+    cache_hits: u64,
+    blocked_time: u64,
+    incremental_load_time: u64,
+}
+
+fn add_to_summary<'a>(
+    items: &mut FxHashMap<&'a str, ItemStats>,
+    event: &RecordedEvent,
+    names: &Names<'a>,
+    profiler: &SelfProfiler,
+) {
+    let stats = items.entry(names.label(event)).or_default();
+    let duration = event.end - event.start;
+    stats.self_time += event.self_time;
+    if event.kind == profiler.query_event_kind {
+        stats.invocations += 1;
+    } else if event.kind == profiler.query_cache_hit_event_kind {
+        stats.cache_hits += 1;
+    } else if event.kind == profiler.query_blocked_event_kind {
+        stats.blocked_time += duration;
+    } else if event.kind == profiler.incremental_load_result_event_kind {
+        stats.incremental_load_time += duration;
+    } else if event.kind == profiler.generic_activity_event_kind {
+        stats.invocations += 1;
+    }
+}
+
+fn format_summary(
+    crate_name: &str,
+    items: &[(&str, ItemStats)],
+    profiler: &SelfProfiler,
+) -> String {
+    let ms = |nanos: u64| format!("{:.3}ms", nanos as f64 / 1_000_000.0);
+    let total_self_time: u64 = items.iter().map(|(_, stats)| stats.self_time).sum();
+    let label_width = items.iter().map(|(label, _)| label.len()).max().unwrap_or(0).max(4);
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "self-profile summary of `{crate_name}`, total self time: {}",
+        ms(total_self_time)
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "{:<label_width$}  {:>14}  {:>10}  {:>11}  {:>10}  {:>14}  {:>21}",
+        "Item",
+        "Self time",
+        "% of total",
+        "Invocations",
+        "Cache hits",
+        "Blocked time",
+        "Incremental load time",
+    )
+    .unwrap();
+    for (label, stats) in items {
+        let percent = if total_self_time == 0 {
+            0.0
+        } else {
+            stats.self_time as f64 * 100.0 / total_self_time as f64
+        };
+        writeln!(
+            out,
+            "{:<label_width$}  {:>14}  {:>9.2}%  {:>11}  {:>10}  {:>14}  {:>21}",
+            label,
+            ms(stats.self_time),
+            percent,
+            stats.invocations,
+            stats.cache_hits,
+            ms(stats.blocked_time),
+            ms(stats.incremental_load_time),
+        )
+        .unwrap();
+    }
+    if !profiler.event_filter_mask.contains(super::EventFilter::QUERY_CACHE_HITS) {
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "note: query cache hits are only recorded with \
+             `-Z self-profile-events=default,query-cache-hit`"
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Writes events one at a time in the [Trace Event Format] understood by `chrome://tracing` and
+/// Perfetto.
+///
+/// [Trace Event Format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+struct ChromeTrace {
+    path: PathBuf,
+    file: BufWriter<File>,
+    pid: u32,
+    is_empty: bool,
+}
+
+impl ChromeTrace {
+    fn create(path: &Path) -> io::Result<ChromeTrace> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(b"[")?;
+        Ok(ChromeTrace { path: path.to_owned(), file, pid: process::id(), is_empty: true })
+    }
+
+    fn write_event(&mut self, event: &RecordedEvent, names: &Names<'_>) -> io::Result<()> {
+        let separator = if mem::take(&mut self.is_empty) { "\n" } else { ",\n" };
+        let name = serde_json::to_string(names.label(event))?;
+        let kind = serde_json::to_string(names.kind(event))?;
+        let micros = |nanos: u64| nanos as f64 / 1000.0;
+        let (pid, tid, ts) = (self.pid, event.thread_id, micros(event.start));
+        if event.is_instant {
+            write!(
+                self.file,
+                r#"{separator}{{"name":{name},"cat":{kind},"ph":"i","s":"t","ts":{ts:.3},"pid":{pid},"tid":{tid}}}"#,
+            )
+        } else {
+            let dur = micros(event.end - event.start);
+            write!(
+                self.file,
+                r#"{separator}{{"name":{name},"cat":{kind},"ph":"X","ts":{ts:.3},"dur":{dur:.3},"pid":{pid},"tid":{tid}}}"#,
+            )
+        }
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        self.file.write_all(b"\n]\n")?;
+        self.file.flush()
+    }
+}
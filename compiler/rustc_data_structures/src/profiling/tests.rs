@@ -1,4 +1,6 @@
+use super::summary::{RecordedEvent, SelfTimes};
 use super::JsonTimePassesEntry;
+use measureme::StringId;
 
 #[test]
 fn with_rss() {
@@ -17,3 +19,39 @@ fn no_rss() {
         r#"{"pass":"typeck","time":56.1,"rss_start":null,"rss_end":null}"#
     )
 }
+
+#[test]
+fn self_time_of_nested_events() {
+    let mut self_times = SelfTimes::default();
+    // 0..100 on thread 1, with 10..30 (itself containing 15..20) and 40..50 nested in it.
+    self_times.start(1);
+    self_times.start(1);
+    self_times.start(1);
+    assert_eq!(self_times.finish(1, 5), 5);
+    assert_eq!(self_times.finish(1, 20), 15);
+    // Events on other threads don't take time from their parents, even if they overlap.
+    self_times.start(2);
+    self_times.start(1);
+    assert_eq!(self_times.finish(1, 10), 10);
+    assert_eq!(self_times.finish(2, 50), 50);
+    assert_eq!(self_times.finish(1, 100), 70);
+    // Events finishing out of order only get wrong self times, without underflowing.
+    self_times.start(3);
+    self_times.start(3);
+    assert_eq!(self_times.finish(3, 50), 50);
+    assert_eq!(self_times.finish(3, 10), 0);
+}
+
+#[test]
+fn encoded_event_round_trips() {
+    let event = RecordedEvent {
+        kind: StringId::new(7u64),
+        id: StringId::new_virtual(42u64),
+        thread_id: 3,
+        start: 10,
+        end: 1 << 40,
+        self_time: 5,
+        is_instant: false,
+    };
+    assert_eq!(RecordedEvent::decode(&event.encode()), event);
+}
@@ -0,0 +1,98 @@
+//! Re-checking the sources patched by `-Zapply-suggestions`.
+//!
+//! After the suggestions of a compilation were applied, the crate is checked again with the
+//! same arguments, reading the patched files from memory instead of the original ones. This
+//! catches suggestions that are machine-applicable on their own but break the code together
+//! with other ones.
+
+use crate::{run_compiler, Callbacks, Compilation};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::apply_suggestions::AppliedSuggestions;
+use rustc_interface::{interface, Queries};
+use rustc_session::config::{ErrorOutputType, OutputType, OutputTypes};
+use rustc_session::lint::Level;
+use rustc_session::EarlyDiagCtxt;
+use rustc_span::source_map::{FileLoader, RealFileLoader};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Loads the patched files from memory and all other files from the file system.
+struct PatchedFileLoader {
+    files: FxHashMap<PathBuf, String>,
+}
+
+impl FileLoader for PatchedFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.files.contains_key(path) || RealFileLoader.file_exists(path)
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        match self.files.get(path) {
+            Some(contents) => Ok(contents.clone()),
+            None => RealFileLoader.read_file(path),
+        }
+    }
+
+    fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
+        match self.files.get(path) {
+            Some(contents) => Ok(contents.as_bytes().into()),
+            None => RealFileLoader.read_binary_file(path),
+        }
+    }
+}
+
+/// Checks the crate without applying suggestions again, and without producing any output.
+struct RecheckCallbacks;
+
+impl Callbacks for RecheckCallbacks {
+    // JUSTIFICATION: the session doesn't exist at this point.
+    #[allow(rustc::bad_opt_access)]
+    fn config(&mut self, config: &mut interface::Config) {
+        let opts = &mut config.opts;
+        opts.unstable_opts.apply_suggestions = None;
+        opts.unstable_opts.write_lint_baseline = None;
+        // Only errors matter, the warnings were already emitted by the first compilation.
+        opts.lint_cap = Some(Level::Allow);
+        opts.incremental = None;
+        opts.prints.clear();
+        // Metadata isn't written before the analysis stops, and this disables writing dep-info.
+        opts.output_types = OutputTypes::new(&[(OutputType::Metadata, None)]);
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        _queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        Compilation::Stop
+    }
+}
+
+/// Checks the crate built from `at_args` again with the patched files of `applied`, and warns
+/// if the patched sources don't compile.
+pub(crate) fn recheck(
+    at_args: &[String],
+    applied: AppliedSuggestions,
+    error_format: ErrorOutputType,
+    using_internal_features: Arc<AtomicBool>,
+) {
+    let files = applied.files.into_iter().map(|file| (file.path, file.patched)).collect();
+    let file_loader = Box::new(PatchedFileLoader { files });
+    let result = crate::catch_fatal_errors(|| {
+        run_compiler(
+            at_args,
+            &mut RecheckCallbacks,
+            Some(file_loader),
+            None,
+            using_internal_features,
+        )
+    });
+    if !matches!(result, Ok(Ok(()))) {
+        EarlyDiagCtxt::new(error_format).early_warn(
+            "the sources don't compile after applying the machine-applicable suggestions",
+        );
+    }
+}
@@ -18,6 +18,7 @@
 use rustc_ast as ast;
 use rustc_codegen_ssa::{traits::CodegenBackend, CodegenErrors, CodegenResults};
 use rustc_const_eval::CTRL_C_RECEIVED;
+use rustc_data_structures::defer;
 use rustc_data_structures::profiling::{
     get_resident_set_size, print_time_passes_entry, TimePassesFormat,
 };
@@ -32,17 +33,19 @@
 use rustc_lint::unerased_lint_store;
 use rustc_metadata::creader::MetadataLoader;
 use rustc_metadata::locator;
+use rustc_middle::ty::TyCtxt;
 use rustc_session::config::{nightly_options, CG_OPTIONS, Z_OPTIONS};
-use rustc_session::config::{ErrorOutputType, Input, OutFileName, OutputType};
+use rustc_session::config::{ErrorOutputType, Input, OutFileName, OutputType, PrintFormat};
 use rustc_session::getopts::{self, Matches};
 use rustc_session::lint::{Lint, LintId};
 use rustc_session::output::collect_crate_types;
 use rustc_session::{config, filesearch, EarlyDiagCtxt, Session};
+use rustc_smir::rustc_internal::snapshot::write_smir_snapshot;
 use rustc_span::def_id::LOCAL_CRATE;
 use rustc_span::source_map::FileLoader;
 use rustc_span::symbol::sym;
 use rustc_span::FileName;
-use rustc_target::json::ToJson;
+use rustc_target::json::{Json, ToJson};
 use rustc_target::spec::{Target, TargetTriple};
 use std::cmp::max;
 use std::collections::BTreeMap;
@@ -79,6 +82,7 @@
 #[allow(unused_imports)]
 use {do_not_use_print as print, do_not_use_print as println};
 
+mod apply_suggestions;
 pub mod args;
 pub mod pretty;
 #[macro_use]
@@ -95,8 +99,9 @@ pub(super) fn install() {}
 }
 
 use crate::session_diagnostics::{
-    RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch, RLinkRustcVersionMismatch,
-    RLinkWrongFileType, RlinkCorruptFile, RlinkNotAFile, RlinkUnableToRead,
+    CantEmitStableMir, RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch,
+    RLinkRustcVersionMismatch, RLinkWrongFileType, RlinkCorruptFile, RlinkNotAFile,
+    RlinkUnableToRead,
 };
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
@@ -282,6 +287,9 @@ fn run_compiler(
 ) -> interface::Result<()> {
     let mut default_early_dcx = EarlyDiagCtxt::new(ErrorOutputType::default());
 
+    // Kept to check the sources again after `-Zapply-suggestions`.
+    let original_args = at_args;
+
     // Throw away the first argument, the name of the binary.
     // In case of at_args being empty, as might be the case by
     // passing empty argument array to execve under some platforms,
@@ -299,8 +307,17 @@ fn run_compiler(
     let sopts = config::build_session_options(&mut default_early_dcx, &matches);
 
     if let Some(ref code) = matches.opt_str("explain") {
-        handle_explain(&default_early_dcx, diagnostics_registry(), code, sopts.color);
+        let json = match matches.opt_str("explain-format").as_deref() {
+            None | Some("text") => false,
+            Some("json") => true,
+            Some(arg) => default_early_dcx.early_fatal(format!(
+                "argument for `--explain-format` must be `text` or `json` (instead was `{arg}`)"
+            )),
+        };
+        handle_explain(&default_early_dcx, diagnostics_registry(), code, sopts.color, json);
         return Ok(());
+    } else if matches.opt_present("explain-format") {
+        default_early_dcx.early_fatal("`--explain-format` can only be used with `--explain`");
     }
 
     let (odir, ofile) = make_output(&matches);
@@ -345,10 +362,17 @@ fn run_compiler(
 
     callbacks.config(&mut config);
 
-    interface::run_compiler(config, |compiler| {
+    let error_format = config.opts.error_format;
+    let recheck_using_internal_features = config.using_internal_features.clone();
+    let mut applied_suggestions = None;
+
+    let result = interface::run_compiler(config, |compiler| {
         let sess = &compiler.sess;
         let codegen_backend = &*compiler.codegen_backend;
 
+        // Apply the suggestions however the compilation ends, like `finish_diagnostics`.
+        let _apply_suggestions = defer(|| applied_suggestions = sess.apply_recorded_suggestions());
+
         // This is used for early exits unrelated to errors. E.g. when just
         // printing some information without compiling, or exiting immediately
         // after parsing, etc.
@@ -370,6 +394,13 @@ fn run_compiler(
             return early_exit();
         }
 
+        if let Some(session_dir) = &sess.opts.unstable_opts.inspect_incr_cache {
+            let baseline_dir = sess.opts.unstable_opts.inspect_incr_cache_baseline.as_deref();
+            let report = rustc_incremental::inspect_session_dir(sess, session_dir, baseline_dir);
+            safe_print!("{report}");
+            return early_exit();
+        }
+
         if !has_input {
             early_dcx.early_fatal("no input filename given"); // this is fatal
         }
@@ -438,6 +469,10 @@ fn run_compiler(
 
             queries.global_ctxt()?.enter(|tcx| tcx.analysis(()))?;
 
+            if sess.opts.output_types.contains_key(&OutputType::StableMir) {
+                queries.global_ctxt()?.enter(|tcx| emit_stable_mir(tcx));
+            }
+
             if callbacks.after_analysis(compiler, queries) == Compilation::Stop {
                 return early_exit();
             }
@@ -475,7 +510,19 @@ fn run_compiler(
         }
 
         Ok(())
-    })
+    });
+
+    match applied_suggestions {
+        Some(Err(guar)) => return Err(guar),
+        Some(Ok(applied)) if !applied.files.is_empty() => apply_suggestions::recheck(
+            original_args,
+            applied,
+            error_format,
+            recheck_using_internal_features,
+        ),
+        _ => {}
+    }
+    result
 }
 
 // Extract output directory and file from matches.
@@ -531,34 +578,25 @@ pub enum Compilation {
     Continue,
 }
 
-fn handle_explain(early_dcx: &EarlyDiagCtxt, registry: Registry, code: &str, color: ColorConfig) {
+fn handle_explain(
+    early_dcx: &EarlyDiagCtxt,
+    registry: Registry,
+    code: &str,
+    color: ColorConfig,
+    json: bool,
+) {
     // Allow "E0123" or "0123" form.
     let upper_cased_code = code.to_ascii_uppercase();
     let start = if upper_cased_code.starts_with('E') { 1 } else { 0 };
     if let Ok(code) = upper_cased_code[start..].parse::<u32>()
-        && let Ok(description) = registry.try_find_description(ErrCode::from_u32(code))
+        && let Ok(explanation) = registry.try_find_explanation(ErrCode::from_u32(code))
     {
-        let mut is_in_code_block = false;
-        let mut text = String::new();
-        // Slice off the leading newline and print.
-        for line in description.lines() {
-            let indent_level =
-                line.find(|c: char| !c.is_whitespace()).unwrap_or_else(|| line.len());
-            let dedented_line = &line[indent_level..];
-            if dedented_line.starts_with("```") {
-                is_in_code_block = !is_in_code_block;
-                text.push_str(&line[..(indent_level + 3)]);
-            } else if is_in_code_block && dedented_line.starts_with("# ") {
-                continue;
-            } else {
-                text.push_str(line);
-            }
-            text.push('\n');
-        }
-        if io::stdout().is_terminal() {
-            show_md_content_with_pager(&text, color);
+        if json {
+            safe_println!("{}", serde_json::to_string_pretty(&explanation).unwrap());
+        } else if io::stdout().is_terminal() {
+            show_md_content_with_pager(&explanation.markdown, color);
         } else {
-            safe_print!("{text}");
+            safe_print!("{}", explanation.markdown);
         }
     } else {
         early_dcx.early_fatal(format!("{code} is not a valid error code"));
@@ -667,6 +705,19 @@ fn process_rlink(sess: &Session, compiler: &interface::Compiler) {
     }
 }
 
+/// Write the Stable MIR snapshot of the local crate requested with `--emit=stable-mir`.
+fn emit_stable_mir(tcx: TyCtxt<'_>) {
+    let result = match tcx.output_filenames(()).path(OutputType::StableMir) {
+        OutFileName::Stdout => write_smir_snapshot(tcx, io::stdout().lock()),
+        OutFileName::Real(path) => {
+            File::create(&path).and_then(|file| write_smir_snapshot(tcx, io::BufWriter::new(file)))
+        }
+    };
+    if let Err(error) = result {
+        tcx.dcx().emit_fatal(CantEmitStableMir { error });
+    }
+}
+
 fn list_metadata(early_dcx: &EarlyDiagCtxt, sess: &Session, metadata_loader: &dyn MetadataLoader) {
     match sess.io.input {
         Input::File(ref ifile) => {
@@ -727,17 +778,44 @@ fn print_crate_info(
         macro println_info($($arg:tt)*) {
             crate_info.write_fmt(format_args!("{}\n", format_args!($($arg)*))).unwrap()
         }
+        // With `--print-format=json`, requests fill in `json_info` rather than `crate_info`.
+        let json = req.format == PrintFormat::Json;
+        let mut json_info = None;
 
         match req.kind {
             TargetList => {
                 let mut targets = rustc_target::spec::TARGETS.to_vec();
                 targets.sort_unstable();
-                println_info!("{}", targets.join("\n"));
+                if json {
+                    json_info = Some(Json::from(targets));
+                } else {
+                    println_info!("{}", targets.join("\n"));
+                }
+            }
+            Sysroot => {
+                if json {
+                    json_info = Some(Json::from(sess.sysroot.display().to_string()));
+                } else {
+                    println_info!("{}", sess.sysroot.display());
+                }
+            }
+            TargetLibdir => {
+                let libdir = &sess.target_tlib_path.dir;
+                if json {
+                    json_info = Some(Json::from(libdir.display().to_string()));
+                } else {
+                    println_info!("{}", libdir.display());
+                }
             }
-            Sysroot => println_info!("{}", sess.sysroot.display()),
-            TargetLibdir => println_info!("{}", sess.target_tlib_path.dir.display()),
             TargetSpec => {
-                println_info!("{}", serde_json::to_string_pretty(&sess.target.to_json()).unwrap());
+                if json {
+                    json_info = Some(sess.target.to_json());
+                } else {
+                    println_info!(
+                        "{}",
+                        serde_json::to_string_pretty(&sess.target.to_json()).unwrap()
+                    );
+                }
             }
             AllTargetSpecs => {
                 let mut targets = BTreeMap::new();
@@ -746,7 +824,11 @@ fn print_crate_info(
                     let target = Target::expect_builtin(&triple);
                     targets.insert(name, target.to_json());
                 }
-                println_info!("{}", serde_json::to_string_pretty(&targets).unwrap());
+                if json {
+                    json_info = Some(targets.to_json());
+                } else {
+                    println_info!("{}", serde_json::to_string_pretty(&targets).unwrap());
+                }
             }
             FileNames => {
                 let Some(attrs) = attrs.as_ref() else {
@@ -756,10 +838,20 @@ fn print_crate_info(
                 let t_outputs = rustc_interface::util::build_output_filenames(attrs, sess);
                 let id = rustc_session::output::find_crate_name(sess, attrs);
                 let crate_types = collect_crate_types(sess, attrs);
-                for &style in &crate_types {
-                    let fname =
-                        rustc_session::output::filename_for_input(sess, style, id, &t_outputs);
-                    println_info!("{}", fname.as_path().file_name().unwrap().to_string_lossy());
+                let fnames = crate_types
+                    .iter()
+                    .map(|&style| {
+                        let fname =
+                            rustc_session::output::filename_for_input(sess, style, id, &t_outputs);
+                        fname.as_path().file_name().unwrap().to_string_lossy().into_owned()
+                    })
+                    .collect::<Vec<_>>();
+                if json {
+                    json_info = Some(Json::from(fnames));
+                } else {
+                    for fname in fnames {
+                        println_info!("{fname}");
+                    }
                 }
             }
             CrateName => {
@@ -768,14 +860,18 @@ fn print_crate_info(
                     return Compilation::Continue;
                 };
                 let id = rustc_session::output::find_crate_name(sess, attrs);
-                println_info!("{id}");
+                if json {
+                    json_info = Some(Json::from(id.as_str()));
+                } else {
+                    println_info!("{id}");
+                }
             }
             Cfg => {
                 let mut cfgs = sess
                     .psess
                     .config
                     .iter()
-                    .filter_map(|&(name, value)| {
+                    .filter(|&&(name, value)| {
                         // Note that crt-static is a specially recognized cfg
                         // directive that's printed out here as part of
                         // rust-lang/rust#37406, but in general the
@@ -784,30 +880,49 @@ fn print_crate_info(
                         // specifically allowing the crt-static cfg and that's
                         // it, this is intended to get into Cargo and then go
                         // through to build scripts.
-                        if (name != sym::target_feature || value != Some(sym::crt_dash_static))
+                        !((name != sym::target_feature || value != Some(sym::crt_dash_static))
                             && !sess.is_nightly_build()
-                            && find_gated_cfg(|cfg_sym| cfg_sym == name).is_some()
-                        {
-                            return None;
-                        }
-
-                        if let Some(value) = value {
-                            Some(format!("{name}=\"{value}\""))
-                        } else {
-                            Some(name.to_string())
-                        }
+                            && find_gated_cfg(|cfg_sym| cfg_sym == name).is_some())
                     })
-                    .collect::<Vec<String>>();
-
-                cfgs.sort();
-                for cfg in cfgs {
-                    println_info!("{cfg}");
+                    .map(|&(name, value)| (name.to_string(), value.map(|value| value.to_string())))
+                    .collect::<Vec<_>>();
+
+                if json {
+                    cfgs.sort();
+                    let cfgs = cfgs
+                        .into_iter()
+                        .map(|(name, value)| {
+                            [("name", Json::from(name)), ("value", Json::from(value))]
+                                .into_iter()
+                                .collect::<Json>()
+                        })
+                        .collect::<Vec<_>>();
+                    json_info = Some(Json::from(cfgs));
+                } else {
+                    let mut cfgs = cfgs
+                        .into_iter()
+                        .map(|(name, value)| {
+                            if let Some(value) = value {
+                                format!("{name}=\"{value}\"")
+                            } else {
+                                name
+                            }
+                        })
+                        .collect::<Vec<String>>();
+                    cfgs.sort();
+                    for cfg in cfgs {
+                        println_info!("{cfg}");
+                    }
                 }
             }
             CallingConventions => {
                 let mut calling_conventions = rustc_target::spec::abi::all_names();
                 calling_conventions.sort_unstable();
-                println_info!("{}", calling_conventions.join("\n"));
+                if json {
+                    json_info = Some(Json::from(calling_conventions));
+                } else {
+                    println_info!("{}", calling_conventions.join("\n"));
+                }
             }
             RelocationModels
             | CodeModels
@@ -815,7 +930,11 @@ fn print_crate_info(
             | TargetCPUs
             | StackProtectorStrategies
             | TargetFeatures => {
-                codegen_backend.print(req, &mut crate_info, sess);
+                if json {
+                    json_info = Some(codegen_backend.print_json(req, sess));
+                } else {
+                    codegen_backend.print(req, &mut crate_info, sess);
+                }
             }
             // Any output here interferes with Cargo's parsing of other printed output
             NativeStaticLibs => {}
@@ -823,8 +942,14 @@ fn print_crate_info(
             SplitDebuginfo => {
                 use rustc_target::spec::SplitDebuginfo::{Off, Packed, Unpacked};
 
-                for split in &[Off, Packed, Unpacked] {
-                    if sess.target.options.supported_split_debuginfo.contains(split) {
+                let splits = [Off, Packed, Unpacked]
+                    .into_iter()
+                    .filter(|split| sess.target.options.supported_split_debuginfo.contains(split))
+                    .collect::<Vec<_>>();
+                if json {
+                    json_info = Some(splits.to_json());
+                } else {
+                    for split in splits {
                         println_info!("{split}");
                     }
                 }
@@ -835,7 +960,11 @@ fn print_crate_info(
                 if sess.target.is_like_osx {
                     let (major, minor) = current_apple_deployment_target(&sess.target)
                         .expect("unknown Apple target OS");
-                    println_info!("deployment_target={}", format!("{major}.{minor}"))
+                    if json {
+                        json_info = Some(Json::from(format!("{major}.{minor}")));
+                    } else {
+                        println_info!("deployment_target={}", format!("{major}.{minor}"))
+                    }
                 } else {
                     early_dcx
                         .early_fatal("only Apple targets currently support deployment version info")
@@ -843,7 +972,11 @@ fn print_crate_info(
             }
         }
 
-        req.out.overwrite(&crate_info, sess);
+        if let Some(json_info) = json_info {
+            req.write_json(json_info, sess);
+        } else {
+            req.out.overwrite(&crate_info, sess);
+        }
     }
     Compilation::Stop
 }
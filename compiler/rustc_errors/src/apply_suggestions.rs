@@ -0,0 +1,371 @@
+//! In-process application of machine-applicable suggestions, used by `-Zapply-suggestions`.
+//!
+//! While `-Zapply-suggestions` is active, the suggestions of every emitted diagnostic that are
+//! marked [`Applicability::MachineApplicable`] are recorded. At the end of the compilation they
+//! are applied to the original sources, without compiling the crate again.
+//!
+//! A suggestion is applied completely or not at all. When suggestions overlap, the outcome
+//! doesn't depend on the order in which the diagnostics were emitted: suggestions are
+//! considered in the order of the position of their edits, and a suggestion is skipped if one
+//! of its edits overlaps an edit of a suggestion that was already accepted. Identical edits
+//! don't conflict, so that e.g. two suggestions adding the same `use` item can both be applied.
+
+use crate::{Applicability, DiagInner, SubstitutionPart};
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{FileName, SourceFileAndBytePos};
+use std::fmt::Write;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// The number of unchanged lines shown around the changes of a diff.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// A replacement of a byte range of a source file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Edit {
+    file: PathBuf,
+    lo: usize,
+    hi: usize,
+    snippet: String,
+}
+
+impl Edit {
+    fn overlaps(&self, other: &Edit) -> bool {
+        self.file == other.file
+            && if self.lo == self.hi && other.lo == other.hi {
+                // Two insertions at the same position would have to be ordered.
+                self.lo == other.lo
+            } else {
+                self.lo < other.hi && other.lo < self.hi
+            }
+    }
+}
+
+/// The machine-applicable suggestions emitted during a compilation.
+#[derive(Debug, Default)]
+pub struct RecordedSuggestions {
+    /// Each suggestion is a list of edits that have to be applied together.
+    suggestions: Vec<Vec<Edit>>,
+    /// The original contents of the files that the suggestions edit.
+    sources: FxIndexMap<PathBuf, Lrc<String>>,
+    /// The number of suggestions that can't be applied, e.g. because they have several
+    /// alternatives or edit a file that isn't part of the local crate.
+    unappliable: usize,
+}
+
+impl RecordedSuggestions {
+    /// Records the machine-applicable suggestions of `diag`.
+    pub(crate) fn record(&mut self, diag: &DiagInner, sm: &SourceMap) {
+        let Ok(suggestions) = &diag.suggestions else { return };
+        for suggestion in suggestions {
+            if suggestion.applicability != Applicability::MachineApplicable {
+                continue;
+            }
+            // Suggestions with several alternatives need someone to pick one.
+            let [substitution] = &suggestion.substitutions[..] else {
+                self.unappliable += 1;
+                continue;
+            };
+            let edits: Option<Vec<_>> =
+                substitution.parts.iter().map(|part| self.edit_for(part, sm)).collect();
+            match edits {
+                Some(edits) => self.suggestions.push(edits),
+                None => self.unappliable += 1,
+            }
+        }
+    }
+
+    fn edit_for(&mut self, part: &SubstitutionPart, sm: &SourceMap) -> Option<Edit> {
+        if part.span.is_dummy() {
+            return None;
+        }
+        let SourceFileAndBytePos { sf, pos } = sm.lookup_byte_offset(part.span.lo());
+        let FileName::Real(name) = &sf.name else { return None };
+        let path = name.local_path()?;
+        // The patched file couldn't be written to the output directory.
+        relative_path(path)?;
+        let src = sf.src.as_ref()?;
+        // The sources of files with a byte order mark or CRLF line endings are normalized, so
+        // their byte offsets don't match the files on disk.
+        if !sf.normalized_pos.is_empty() || part.span.hi() > sf.end_position() {
+            return None;
+        }
+
+        self.sources.entry(path.to_owned()).or_insert_with(|| src.clone());
+        Some(Edit {
+            file: path.to_owned(),
+            lo: pos.to_usize(),
+            hi: (part.span.hi() - sf.start_pos).to_usize(),
+            snippet: part.snippet.clone(),
+        })
+    }
+
+    /// Applies the recorded suggestions to the original sources.
+    pub fn apply(self) -> AppliedSuggestions {
+        let mut suggestions = self.suggestions;
+        for edits in &mut suggestions {
+            edits.sort();
+            edits.dedup();
+        }
+        // Sorting makes the result independent of the order of the diagnostics, and identical
+        // suggestions, e.g. from a lint emitted for several expansions of a macro, are only
+        // applied once.
+        suggestions.sort();
+        suggestions.dedup();
+
+        let mut accepted: FxIndexMap<PathBuf, Vec<Edit>> = FxIndexMap::default();
+        let mut applied = 0;
+        let mut overlapping = 0;
+        for edits in suggestions {
+            let conflicts = edits.iter().enumerate().any(|(i, edit)| {
+                edits[..i].iter().any(|other| edit.overlaps(other))
+                    || accepted.get(&edit.file).is_some_and(|file_edits| {
+                        file_edits.iter().any(|other| edit != other && edit.overlaps(other))
+                    })
+            });
+            if conflicts {
+                overlapping += 1;
+                continue;
+            }
+            for edit in edits {
+                let file_edits = accepted.entry(edit.file.clone()).or_default();
+                if !file_edits.contains(&edit) {
+                    file_edits.push(edit);
+                }
+            }
+            applied += 1;
+        }
+
+        accepted.sort_keys();
+        let files = accepted
+            .into_iter()
+            .filter_map(|(path, mut edits)| {
+                edits.sort();
+                let original = String::clone(&self.sources[&path]);
+                let mut patched = String::with_capacity(original.len());
+                let mut pos = 0;
+                for edit in &edits {
+                    patched.push_str(&original[pos..edit.lo]);
+                    patched.push_str(&edit.snippet);
+                    pos = edit.hi;
+                }
+                patched.push_str(&original[pos..]);
+                (patched != original).then_some(PatchedFile { path, original, patched, edits })
+            })
+            .collect();
+
+        AppliedSuggestions { files, applied, overlapping, unappliable: self.unappliable }
+    }
+}
+
+/// The result of applying the recorded suggestions, see the
+/// [module-level documentation](self).
+#[derive(Debug)]
+pub struct AppliedSuggestions {
+    /// The files changed by the suggestions, sorted by path.
+    pub files: Vec<PatchedFile>,
+    /// The number of applied suggestions.
+    pub applied: usize,
+    /// The number of suggestions that were skipped because they overlap with an applied one.
+    pub overlapping: usize,
+    /// The number of suggestions that can't be applied.
+    pub unappliable: usize,
+}
+
+impl AppliedSuggestions {
+    /// Writes the patched files to `dir`, at their path relative to the working directory.
+    pub fn write_files(&self, dir: &Path) -> io::Result<()> {
+        for file in &self.files {
+            let path = dir.join(file.relative_path());
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, &file.patched)?;
+        }
+        Ok(())
+    }
+
+    /// A unified diff of all the patched files.
+    pub fn unified_diff(&self) -> String {
+        self.files.iter().map(|file| file.unified_diff()).collect()
+    }
+}
+
+/// A source file with the suggestions applied.
+#[derive(Debug)]
+pub struct PatchedFile {
+    /// The path of the original file.
+    pub path: PathBuf,
+    pub original: String,
+    pub patched: String,
+    /// The applied edits, sorted by position.
+    edits: Vec<Edit>,
+}
+
+/// A range of lines of the original file that is changed by some edits.
+struct Change {
+    /// The first changed line.
+    start: usize,
+    /// The line after the last changed line.
+    end: usize,
+    /// The new contents of the lines.
+    new_lines: Vec<String>,
+}
+
+impl PatchedFile {
+    /// The path of the file relative to the working directory, or to the root of the file
+    /// system for absolute paths, see [`relative_path`].
+    pub fn relative_path(&self) -> PathBuf {
+        relative_path(&self.path)
+            .expect("suggestions for files outside of the working directory aren't recorded")
+    }
+
+    /// A unified diff from the original to the patched file.
+    pub fn unified_diff(&self) -> String {
+        let old_lines: Vec<&str> = self.original.split_inclusive('\n').collect();
+        let changes = self.changes(&old_lines);
+
+        let name = self.relative_path().to_string_lossy().replace('\\', "/");
+        let mut diff = format!("--- a/{name}\n+++ b/{name}\n");
+        // The difference between the line numbers of the patched and the original file.
+        let mut offset = 0isize;
+        let mut i = 0;
+        while i < changes.len() {
+            // Changes whose context lines overlap or touch go into the same hunk.
+            let mut j = i + 1;
+            while j < changes.len()
+                && changes[j].start <= changes[j - 1].end + 2 * DIFF_CONTEXT_LINES
+            {
+                j += 1;
+            }
+            let hunk = &changes[i..j];
+            let start = hunk[0].start.saturating_sub(DIFF_CONTEXT_LINES);
+            let end = (hunk[hunk.len() - 1].end + DIFF_CONTEXT_LINES).min(old_lines.len());
+            let new_len = hunk.iter().fold(end - start, |len, change| {
+                len - (change.end - change.start) + change.new_lines.len()
+            });
+            let new_start = start.checked_add_signed(offset).unwrap();
+            let _ = writeln!(
+                diff,
+                "@@ -{} +{} @@",
+                hunk_range(start, end - start),
+                hunk_range(new_start, new_len)
+            );
+
+            let mut line = start;
+            for change in hunk {
+                for old_line in &old_lines[line..change.start] {
+                    push_diff_line(&mut diff, ' ', old_line);
+                }
+                for old_line in &old_lines[change.start..change.end] {
+                    push_diff_line(&mut diff, '-', old_line);
+                }
+                for new_line in &change.new_lines {
+                    push_diff_line(&mut diff, '+', new_line);
+                }
+                offset += change.new_lines.len() as isize - (change.end - change.start) as isize;
+                line = change.end;
+            }
+            for old_line in &old_lines[line..end] {
+                push_diff_line(&mut diff, ' ', old_line);
+            }
+            i = j;
+        }
+        diff
+    }
+
+    /// Groups the edits into changes of whole lines. Edits that touch the same line are part
+    /// of the same change.
+    fn changes(&self, old_lines: &[&str]) -> Vec<Change> {
+        let mut line_starts = Vec::with_capacity(old_lines.len());
+        let mut pos = 0;
+        for line in old_lines {
+            line_starts.push(pos);
+            pos += line.len();
+        }
+        let line_of = |pos: usize| line_starts.partition_point(|&start| start <= pos).max(1) - 1;
+        let line_start = |line: usize| line_starts.get(line).copied().unwrap_or(pos);
+
+        let mut groups: Vec<(usize, usize, Vec<&Edit>)> = Vec::new();
+        for edit in &self.edits {
+            let start = line_of(edit.lo);
+            let end = if old_lines.is_empty() {
+                0
+            } else if edit.hi > edit.lo {
+                line_of(edit.hi - 1) + 1
+            } else {
+                start + 1
+            };
+            match groups.last_mut() {
+                Some((_, group_end, group_edits)) if start < *group_end => {
+                    *group_end = end.max(*group_end);
+                    group_edits.push(edit);
+                }
+                _ => groups.push((start, end, vec![edit])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .filter_map(|(start, end, edits)| {
+                let (lo, hi) = (line_start(start), line_start(end));
+                let mut new_text = String::new();
+                let mut pos = lo;
+                for edit in edits {
+                    new_text.push_str(&self.original[pos..edit.lo]);
+                    new_text.push_str(&edit.snippet);
+                    pos = edit.hi;
+                }
+                new_text.push_str(&self.original[pos..hi]);
+                (new_text != self.original[lo..hi]).then(|| Change {
+                    start,
+                    end,
+                    new_lines: new_text.split_inclusive('\n').map(str::to_owned).collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The range of a hunk header, where the line numbers start at 1 and an empty range starts at
+/// the line before it.
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{len}", start + 1)
+    }
+}
+
+fn push_diff_line(diff: &mut String, prefix: char, line: &str) {
+    diff.push(prefix);
+    diff.push_str(line);
+    if !line.ends_with('\n') {
+        diff.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+/// The path of a file relative to the working directory, or to the root of the file system for
+/// absolute paths, with the `.` and `..` components resolved. Returns `None` if the path goes
+/// above that directory with `..`, as the file couldn't be written to the directory the path is
+/// joined to.
+fn relative_path(path: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => relative.push(name),
+            Component::ParentDir => {
+                if !relative.pop() {
+                    return None;
+                }
+            }
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+    Some(relative)
+}
@@ -0,0 +1,164 @@
+use super::*;
+
+const SOURCE: &str = "fn main() {\n    let mut x = 1;\n    let y = x;\n}\n";
+
+fn edit(lo: usize, hi: usize, snippet: &str) -> Edit {
+    Edit { file: PathBuf::from("src/main.rs"), lo, hi, snippet: snippet.to_owned() }
+}
+
+/// Applies `suggestions`, given as lists of `(lo, hi, snippet)` edits of `source`.
+fn apply(source: &str, suggestions: &[&[(usize, usize, &str)]]) -> AppliedSuggestions {
+    let mut recorded = RecordedSuggestions::default();
+    recorded.sources.insert(PathBuf::from("src/main.rs"), Lrc::new(source.to_owned()));
+    for suggestion in suggestions {
+        recorded
+            .suggestions
+            .push(suggestion.iter().map(|&(lo, hi, snippet)| edit(lo, hi, snippet)).collect());
+    }
+    recorded.apply()
+}
+
+fn patched(applied: &AppliedSuggestions) -> &str {
+    match &applied.files[..] {
+        [file] => &file.patched,
+        files => panic!("expected one patched file, found {}", files.len()),
+    }
+}
+
+#[test]
+fn applies_suggestion() {
+    // Remove the `mut ` of `let mut x`.
+    let applied = apply(SOURCE, &[&[(20, 24, "")]]);
+    assert_eq!(patched(&applied), "fn main() {\n    let x = 1;\n    let y = x;\n}\n");
+    assert_eq!((applied.applied, applied.overlapping), (1, 0));
+}
+
+#[test]
+fn skips_overlapping_suggestion() {
+    // Both suggestions edit `x`, the one that starts first is applied whatever the order.
+    let rename: &[_] = &[(24, 25, "_x")];
+    let remove_mut: &[_] = &[(20, 25, "x")];
+    for suggestions in [[rename, remove_mut], [remove_mut, rename]] {
+        let applied = apply(SOURCE, &suggestions);
+        assert_eq!(patched(&applied), "fn main() {\n    let x = 1;\n    let y = x;\n}\n");
+        assert_eq!((applied.applied, applied.overlapping), (1, 1));
+    }
+}
+
+#[test]
+fn skips_whole_suggestion() {
+    // The first part of the second suggestion overlaps the first suggestion, so its second part
+    // isn't applied either.
+    let applied = apply(SOURCE, &[&[(20, 24, "")], &[(20, 25, "z"), (43, 44, "z")]]);
+    assert_eq!(patched(&applied), "fn main() {\n    let x = 1;\n    let y = x;\n}\n");
+    assert_eq!((applied.applied, applied.overlapping), (1, 1));
+}
+
+#[test]
+fn identical_edits_dont_conflict() {
+    let applied = apply(SOURCE, &[&[(0, 0, "use std::mem;\n")], &[(0, 0, "use std::mem;\n")]]);
+    assert_eq!(patched(&applied), format!("use std::mem;\n{SOURCE}"));
+    assert_eq!((applied.applied, applied.overlapping), (1, 0));
+
+    let applied = apply(
+        SOURCE,
+        &[&[(0, 0, "use std::mem;\n"), (20, 24, "")], &[(0, 0, "use std::mem;\n"), (39, 39, "_")]],
+    );
+    assert_eq!(
+        patched(&applied),
+        "use std::mem;\nfn main() {\n    let x = 1;\n    let _y = x;\n}\n"
+    );
+    assert_eq!((applied.applied, applied.overlapping), (2, 0));
+}
+
+#[test]
+fn insertions_at_the_same_position_conflict() {
+    let applied = apply(SOURCE, &[&[(39, 39, "_")], &[(39, 39, "r#")]]);
+    assert_eq!(patched(&applied), "fn main() {\n    let mut x = 1;\n    let _y = x;\n}\n");
+    assert_eq!((applied.applied, applied.overlapping), (1, 1));
+}
+
+#[test]
+fn no_op_suggestions_dont_patch_files() {
+    let applied = apply(SOURCE, &[&[(24, 25, "x")]]);
+    assert!(applied.files.is_empty());
+    assert_eq!(applied.applied, 1);
+}
+
+#[test]
+fn unified_diff() {
+    let applied = apply(SOURCE, &[&[(20, 24, "")]]);
+    assert_eq!(
+        applied.unified_diff(),
+        "--- a/src/main.rs\n\
+        +++ b/src/main.rs\n\
+        @@ -1,4 +1,4 @@\n \
+        fn main() {\n\
+        -    let mut x = 1;\n\
+        +    let x = 1;\n \
+        \x20   let y = x;\n \
+        }\n"
+    );
+}
+
+#[test]
+fn unified_diff_hunks() {
+    let source: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+    let line_start = |line: usize| source.match_indices("line ").nth(line - 1).unwrap().0;
+    let applied = apply(
+        &source,
+        &[
+            // Line 2 becomes two lines.
+            &[(line_start(2), line_start(3), "line 2a\nline 2b\n")],
+            // Line 18 is removed.
+            &[(line_start(18), line_start(19), "")],
+        ],
+    );
+    assert_eq!(
+        applied.unified_diff(),
+        "--- a/src/main.rs\n\
+        +++ b/src/main.rs\n\
+        @@ -1,5 +1,6 @@\n \
+        line 1\n\
+        -line 2\n\
+        +line 2a\n\
+        +line 2b\n \
+        line 3\n \
+        line 4\n \
+        line 5\n\
+        @@ -15,6 +16,5 @@\n \
+        line 15\n \
+        line 16\n \
+        line 17\n\
+        -line 18\n \
+        line 19\n \
+        line 20\n"
+    );
+}
+
+#[test]
+fn unified_diff_without_trailing_newline() {
+    let applied = apply("fn main() {}", &[&[(0, 0, "#[allow(unused)]\n")]]);
+    assert_eq!(
+        applied.unified_diff(),
+        "--- a/src/main.rs\n\
+        +++ b/src/main.rs\n\
+        @@ -1,1 +1,2 @@\n\
+        -fn main() {}\n\
+        \\ No newline at end of file\n\
+        +#[allow(unused)]\n\
+        +fn main() {}\n\
+        \\ No newline at end of file\n"
+    );
+}
+
+#[test]
+fn relative_paths() {
+    let relative = |path: &str| relative_path(Path::new(path));
+    assert_eq!(relative("./src/lib.rs").as_deref(), Some(Path::new("src/lib.rs")));
+    assert_eq!(relative("src/../lib.rs").as_deref(), Some(Path::new("lib.rs")));
+    assert_eq!(relative("/home/src/lib.rs").as_deref(), Some(Path::new("home/src/lib.rs")));
+    // Paths that escape the directory they are joined to can't be written.
+    assert_eq!(relative("../other/lib.rs"), None);
+    assert_eq!(relative("src/../../lib.rs"), None);
+}
@@ -0,0 +1,201 @@
+//! Structured representations of the long-form error code explanations.
+//!
+//! The explanations in `rustc_error_codes` are markdown documents following RFC 1567: a short
+//! description of the error, followed by prose and code examples. This module splits them into
+//! their title, summary and example code blocks so that tools can render them and run the
+//! examples without having to parse markdown themselves.
+
+use crate::ErrCode;
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+/// A parsed error code explanation.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Explanation {
+    /// The error code, e.g. "E0308".
+    pub code: String,
+    /// The one-line description of the error.
+    pub title: String,
+    /// The first paragraph describing when the error occurs, if any.
+    pub summary: Option<String>,
+    /// The Rust code blocks of the explanation, in order.
+    pub examples: Vec<Example>,
+    /// The explanation as markdown, as printed by `rustc --explain`: without code block
+    /// attributes and lines starting with `# `.
+    pub markdown: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExampleKind {
+    /// An example that fails to compile with the explained error (a `compile_fail` block).
+    Erroneous,
+    /// Any other example, usually showing how the error can be fixed.
+    Fixed,
+}
+
+/// A Rust code block of an explanation.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Example {
+    pub kind: ExampleKind,
+    /// The complete source of the example, including lines that are hidden when displayed.
+    pub code: String,
+    /// The source of the example as it is displayed to readers.
+    pub display: String,
+    /// The attributes of the code block, e.g. `["compile_fail", "E0308"]`.
+    pub attributes: Vec<String>,
+    /// The error code the example is expected to fail with, if any.
+    pub error_code: Option<String>,
+    /// The edition the example must be compiled with, if it isn't the default one.
+    pub edition: Option<String>,
+}
+
+impl Explanation {
+    pub fn parse(code: ErrCode, description: &str) -> Explanation {
+        let mut paragraphs = Vec::new();
+        let mut paragraph: Vec<&str> = Vec::new();
+        let mut examples = Vec::new();
+        let mut markdown = String::new();
+
+        let mut lines = description.lines();
+        while let Some(line) = lines.next() {
+            let dedented_line = line.trim_start();
+            if let Some(info) = dedented_line.strip_prefix("```") {
+                flush_paragraph(&mut paragraph, &mut paragraphs);
+                // The attributes of code blocks are only meaningful to rustdoc.
+                let indent_level = line.len() - dedented_line.len();
+                markdown.push_str(&line[..indent_level + 3]);
+                markdown.push('\n');
+
+                let mut body = Vec::new();
+                for line in lines.by_ref() {
+                    let dedented_line = line.trim_start();
+                    if dedented_line.starts_with("```") {
+                        markdown.push_str(&line[..line.len() - dedented_line.len() + 3]);
+                        markdown.push('\n');
+                        break;
+                    }
+                    // Unlike in `Example::display`, lone `#` lines are kept here, as
+                    // `rustc --explain` always has.
+                    if !dedented_line.starts_with("# ") {
+                        markdown.push_str(line);
+                        markdown.push('\n');
+                    }
+                    body.push(line.get(indent_level..).unwrap_or(dedented_line));
+                }
+                examples.extend(Example::parse(info, &body));
+            } else {
+                if dedented_line.trim_end().is_empty() {
+                    flush_paragraph(&mut paragraph, &mut paragraphs);
+                } else {
+                    paragraph.push(line.trim());
+                }
+                markdown.push_str(line);
+                markdown.push('\n');
+            }
+        }
+        flush_paragraph(&mut paragraph, &mut paragraphs);
+
+        // Headings (e.g. "#### Note: this error code is no longer emitted by the compiler.")
+        // are neither the title nor the summary. The paragraphs ending with a colon introduce
+        // examples.
+        let mut prose = paragraphs.into_iter().filter(|p| !p.starts_with('#'));
+        let title = prose.next().unwrap_or_default();
+        let summary = prose.find(|p| !p.ends_with(':'));
+
+        Explanation { code: code.to_string(), title, summary, examples, markdown }
+    }
+}
+
+fn flush_paragraph(paragraph: &mut Vec<&str>, paragraphs: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph.join(" "));
+        paragraph.clear();
+    }
+}
+
+/// Lines starting with `# ` are compiled but not displayed by rustdoc, see
+/// <https://doc.rust-lang.org/rustdoc/write-documentation/documentation-tests.html#hiding-portions-of-the-example>.
+fn is_hidden_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed == "#" || trimmed.starts_with("# ")
+}
+
+/// Removes the `#` marking a hidden line, or unescapes a `##` at the start of a line.
+fn unhide_line(line: &str) -> String {
+    let trimmed = line.trim();
+    if trimmed == "#" {
+        String::new()
+    } else if let Some(hidden) = trimmed.strip_prefix("# ") {
+        hidden.to_owned()
+    } else if trimmed.starts_with("##") {
+        line.replacen("##", "#", 1)
+    } else {
+        line.to_owned()
+    }
+}
+
+impl Example {
+    /// Parses a code block with the info string `info`, returning `None` if it isn't Rust code.
+    fn parse(info: &str, body: &[&str]) -> Option<Example> {
+        // Remove the explanations of attributes, like in "ignore (pseudo-Rust)".
+        let mut info = info.to_owned();
+        while let Some(start) = info.find('(')
+            && let Some(len) = info[start..].find(')')
+        {
+            info.replace_range(start..=start + len, "");
+        }
+        let attributes: Vec<String> = info
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|attr| !attr.is_empty())
+            .map(str::to_owned)
+            .collect();
+        if !attributes.iter().all(|attr| is_rust_attribute(attr)) {
+            return None;
+        }
+
+        let kind = if attributes.iter().any(|attr| attr == "compile_fail") {
+            ExampleKind::Erroneous
+        } else {
+            ExampleKind::Fixed
+        };
+        let error_code = attributes.iter().find(|attr| is_error_code(attr)).cloned();
+        let edition =
+            attributes.iter().find_map(|attr| attr.strip_prefix("edition")).map(str::to_owned);
+
+        let mut code = String::new();
+        let mut display = String::new();
+        for line in body {
+            code.push_str(&unhide_line(line));
+            code.push('\n');
+            if !is_hidden_line(line) {
+                display.push_str(&unhide_line(line));
+                display.push('\n');
+            }
+        }
+
+        Some(Example { kind, code, display, attributes, error_code, edition })
+    }
+}
+
+/// Whether `attr` is one of the code block attributes understood by rustdoc for Rust code.
+fn is_rust_attribute(attr: &str) -> bool {
+    matches!(
+        attr,
+        "rust"
+            | "compile_fail"
+            | "should_panic"
+            | "no_run"
+            | "ignore"
+            | "test_harness"
+            | "standalone"
+    ) || attr.starts_with("ignore-")
+        || attr.starts_with("edition")
+        || is_error_code(attr)
+}
+
+fn is_error_code(attr: &str) -> bool {
+    attr.len() == 5 && attr.starts_with('E') && attr[1..].bytes().all(|b| b.is_ascii_digit())
+}
@@ -0,0 +1,126 @@
+use super::*;
+
+use crate::E0308;
+
+#[test]
+fn title_summary_and_examples() {
+    let explanation = Explanation::parse(
+        E0308,
+        "Expected type did not match the received type.
+
+Erroneous code example:
+
+```compile_fail,E0308
+let x: i32 = \"I am not a number!\";
+```
+
+This error occurs when an expression was used in a place where the compiler
+expected an expression of a different type.
+
+```
+# fn main() {
+let x: i32 = 1;
+# }
+```
+",
+    );
+
+    assert_eq!(explanation.code, "E0308");
+    assert_eq!(explanation.title, "Expected type did not match the received type.");
+    assert_eq!(
+        explanation.summary.as_deref(),
+        Some(
+            "This error occurs when an expression was used in a place where the compiler \
+             expected an expression of a different type."
+        )
+    );
+
+    assert_eq!(
+        explanation.examples,
+        [
+            Example {
+                kind: ExampleKind::Erroneous,
+                code: "let x: i32 = \"I am not a number!\";\n".to_owned(),
+                display: "let x: i32 = \"I am not a number!\";\n".to_owned(),
+                attributes: vec!["compile_fail".to_owned(), "E0308".to_owned()],
+                error_code: Some("E0308".to_owned()),
+                edition: None,
+            },
+            Example {
+                kind: ExampleKind::Fixed,
+                code: "fn main() {\nlet x: i32 = 1;\n}\n".to_owned(),
+                display: "let x: i32 = 1;\n".to_owned(),
+                attributes: vec![],
+                error_code: None,
+                edition: None,
+            },
+        ]
+    );
+
+    // The markdown doesn't contain code block attributes or hidden lines.
+    assert!(explanation.markdown.contains("```\nlet x: i32 = \"I am not a number!\";\n```\n"));
+    assert!(explanation.markdown.contains("```\nlet x: i32 = 1;\n```\n"));
+}
+
+#[test]
+fn lone_hash_lines() {
+    let explanation = Explanation::parse(
+        E0308,
+        "Expected type did not match the received type.
+
+```
+#
+let x: i32 = 1;
+```
+",
+    );
+
+    // Lone `#` lines are hidden from the examples, but kept in the markdown.
+    assert_eq!(explanation.examples[0].code, "\nlet x: i32 = 1;\n");
+    assert_eq!(explanation.examples[0].display, "let x: i32 = 1;\n");
+    assert!(explanation.markdown.contains("```\n#\nlet x: i32 = 1;\n```\n"));
+}
+
+#[test]
+fn headings_are_not_the_title() {
+    let explanation = Explanation::parse(
+        E0308,
+        "#### Note: this error code is no longer emitted by the compiler.
+
+A long
+description.
+",
+    );
+    assert_eq!(explanation.title, "A long description.");
+    assert_eq!(explanation.summary, None);
+}
+
+#[test]
+fn code_block_attributes() {
+    let explanation = Explanation::parse(
+        E0308,
+        "Title.
+
+```ignore (pseudo-Rust)
+foo!();
+```
+
+```edition2018
+## not hidden
+```
+
+```text
+not Rust
+```
+",
+    );
+
+    let [ignored, edition] = &explanation.examples[..] else {
+        panic!("unexpected examples: {:?}", explanation.examples)
+    };
+    assert_eq!(ignored.kind, ExampleKind::Fixed);
+    assert_eq!(ignored.attributes, ["ignore"]);
+    assert_eq!(edition.edition.as_deref(), Some("2018"));
+    assert_eq!(edition.code, "# not hidden\n");
+    assert_eq!(edition.display, "# not hidden\n");
+}
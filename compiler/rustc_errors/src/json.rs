@@ -13,6 +13,7 @@
     should_show_source_code, ColorConfig, Destination, Emitter, HumanEmitter,
     HumanReadableErrorType,
 };
+use crate::explanation::Explanation;
 use crate::registry::Registry;
 use crate::translation::{to_fluent_args, Translate};
 use crate::{
@@ -57,6 +58,7 @@ pub struct JsonEmitter {
     macro_backtrace: bool,
     track_diagnostics: bool,
     terminal_url: TerminalUrl,
+    structured_explanations: bool,
 }
 
 impl JsonEmitter {
@@ -81,6 +83,7 @@ pub fn new(
             macro_backtrace: false,
             track_diagnostics: false,
             terminal_url: TerminalUrl::No,
+            structured_explanations: false,
         }
     }
 
@@ -244,6 +247,10 @@ struct DiagnosticCode {
     code: String,
     /// An explanation for the code.
     explanation: Option<&'static str>,
+    /// The explanation split into its title, summary and examples, with
+    /// `--json=explanations`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structured_explanation: Option<Explanation>,
 }
 
 #[derive(Serialize)]
@@ -326,12 +333,22 @@ fn reset(&mut self) -> io::Result<()> {
         let translated_message = je.translate_messages(&diag.messages, &args);
 
         let code = if let Some(code) = diag.code {
+            let registry = je.registry.as_ref().unwrap();
             Some(DiagnosticCode {
                 code: code.to_string(),
-                explanation: je.registry.as_ref().unwrap().try_find_description(code).ok(),
+                explanation: registry.try_find_description(code).ok(),
+                structured_explanation: if je.structured_explanations {
+                    registry.try_find_explanation(code).ok()
+                } else {
+                    None
+                },
             })
         } else if let Some(IsLint { name, .. }) = &diag.is_lint {
-            Some(DiagnosticCode { code: name.to_string(), explanation: None })
+            Some(DiagnosticCode {
+                code: name.to_string(),
+                explanation: None,
+                structured_explanation: None,
+            })
         } else {
             None
         };
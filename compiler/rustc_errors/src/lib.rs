@@ -51,7 +51,9 @@
 // See https://github.com/rust-lang/rust/pull/115393.
 pub use termcolor::{Color, ColorSpec, WriteColor};
 
+use apply_suggestions::RecordedSuggestions;
 use emitter::{is_case_difference, DynEmitter, Emitter};
+use lint_baseline::LintBaseline;
 use registry::Registry;
 use rustc_data_structures::fx::{FxHashSet, FxIndexMap, FxIndexSet};
 use rustc_data_structures::stable_hasher::{Hash128, StableHasher};
@@ -76,15 +78,19 @@
 use Level::*;
 
 pub mod annotate_snippet_emitter_writer;
+pub mod apply_suggestions;
 pub mod codes;
 mod diagnostic;
 mod diagnostic_impls;
 pub mod emitter;
 pub mod error;
+pub mod explanation;
 pub mod json;
+pub mod lint_baseline;
 mod lock;
 pub mod markdown;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 #[cfg(test)]
@@ -495,6 +501,16 @@ struct DiagCtxtInner {
     /// The file where the ICE information is stored. This allows delayed_span_bug backtraces to be
     /// stored along side the main panic backtrace.
     ice_file: Option<PathBuf>,
+
+    /// Lint hits recorded in the baseline passed with `-Zlint-baseline`. Lints matching an
+    /// entry are suppressed rather than emitted.
+    lint_baseline: Option<LintBaseline>,
+
+    /// Lint hits collected for `-Zwrite-lint-baseline`, if requested.
+    recorded_lint_baseline: Option<LintBaseline>,
+
+    /// Machine-applicable suggestions collected for `-Zapply-suggestions`, if requested.
+    recorded_suggestions: Option<RecordedSuggestions>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -603,6 +619,25 @@ pub fn with_ice_file(mut self, ice_file: PathBuf) -> Self {
         self
     }
 
+    /// Suppresses lints that match an entry in `baseline`.
+    pub fn with_lint_baseline(mut self, baseline: LintBaseline) -> Self {
+        self.inner.get_mut().lint_baseline = Some(baseline);
+        self
+    }
+
+    /// Records every emitted lint, see [`DiagCtxt::recorded_lint_baseline`].
+    pub fn with_lint_baseline_recording(mut self) -> Self {
+        self.inner.get_mut().recorded_lint_baseline = Some(LintBaseline::default());
+        self
+    }
+
+    /// Records the machine-applicable suggestions of every emitted diagnostic, see
+    /// [`DiagCtxt::take_recorded_suggestions`].
+    pub fn with_suggestion_recording(mut self) -> Self {
+        self.inner.get_mut().recorded_suggestions = Some(RecordedSuggestions::default());
+        self
+    }
+
     pub fn new(emitter: Box<DynEmitter>) -> Self {
         Self { inner: Lock::new(DiagCtxtInner::new(emitter)) }
     }
@@ -1016,6 +1051,16 @@ pub fn emit_future_breakage_report(&self) {
         }
     }
 
+    /// Returns the lints recorded since [`DiagCtxt::with_lint_baseline_recording`] was used.
+    pub fn take_recorded_lint_baseline(&self) -> Option<LintBaseline> {
+        self.inner.borrow_mut().recorded_lint_baseline.take()
+    }
+
+    /// Returns the suggestions recorded since [`DiagCtxt::with_suggestion_recording`] was used.
+    pub fn take_recorded_suggestions(&self) -> Option<RecordedSuggestions> {
+        self.inner.borrow_mut().recorded_suggestions.take()
+    }
+
     pub fn emit_unused_externs(
         &self,
         lint_level: rustc_lint_defs::Level,
@@ -1402,6 +1447,9 @@ fn new(emitter: Box<DynEmitter>) -> Self {
             unstable_expect_diagnostics: Vec::new(),
             fulfilled_expectations: Default::default(),
             ice_file: None,
+            lint_baseline: None,
+            recorded_lint_baseline: None,
+            recorded_suggestions: None,
         }
     }
 
@@ -1487,6 +1535,16 @@ fn emit_diagnostic(&mut self, mut diagnostic: DiagInner) -> Option<ErrorGuarante
                     }
                     return None;
                 }
+                if self.is_in_lint_baseline(&diagnostic) {
+                    // Lints recorded in the baseline are treated like allowed lints, but the
+                    // message may have used trimmed paths, like for `Expect`.
+                    self.suppressed_expected_diag = true;
+                    if diagnostic.has_future_breakage() {
+                        // The side-effect is at the top of this method.
+                        TRACK_DIAGNOSTIC(diagnostic, &mut |_| None);
+                    }
+                    return None;
+                }
             }
             Note | Help | FailureNote => {}
             OnceNote | OnceHelp => panic!("bad level: {:?}", diagnostic.level),
@@ -1566,6 +1624,12 @@ fn emit_diagnostic(&mut self, mut diagnostic: DiagInner) -> Option<ErrorGuarante
                 }
                 self.has_printed = true;
 
+                if let Some(recorded) = &mut self.recorded_suggestions
+                    && let Some(sm) = self.emitter.source_map()
+                {
+                    recorded.record(&diagnostic, sm);
+                }
+
                 self.emitter.emit_diagnostic(diagnostic);
             }
 
@@ -1597,6 +1661,25 @@ fn emit_diagnostic(&mut self, mut diagnostic: DiagInner) -> Option<ErrorGuarante
         })
     }
 
+    /// Records a lint warning for `-Zwrite-lint-baseline`, and returns whether it is in the
+    /// baseline of `-Zlint-baseline`, which then no longer matches another hit.
+    fn is_in_lint_baseline(&mut self, diagnostic: &DiagInner) -> bool {
+        if diagnostic.is_lint.is_none()
+            || (self.lint_baseline.is_none() && self.recorded_lint_baseline.is_none())
+        {
+            return false;
+        }
+        let Some(key) =
+            self.emitter.source_map().and_then(|sm| LintBaseline::key_for(diagnostic, sm))
+        else {
+            return false;
+        };
+        if let Some(recorded) = &mut self.recorded_lint_baseline {
+            recorded.record(key.clone());
+        }
+        self.lint_baseline.as_mut().is_some_and(|baseline| baseline.consume(&key))
+    }
+
     fn treat_err_as_bug(&self) -> bool {
         self.flags
             .treat_err_as_bug
@@ -0,0 +1,156 @@
+//! Lint baselines, used by `-Zlint-baseline` and `-Zwrite-lint-baseline`.
+//!
+//! A baseline records the lint hits that already exist in a crate so that they can be
+//! suppressed on later builds, letting only newly introduced warnings through. Each hit is
+//! identified by the lint name, the file it was reported in and a fingerprint of the source
+//! line(s) covered by its primary span. The fingerprint deliberately ignores line numbers and
+//! surrounding whitespace, so unrelated edits elsewhere in a file don't invalidate the baseline.
+//!
+//! The baseline is stored as JSON:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "entries": [
+//!     { "lint": "unused_variables", "file": "src/lib.rs", "fingerprint": "…", "count": 1 }
+//!   ]
+//! }
+//! ```
+
+use crate::DiagInner;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::stable_hasher::{Hash128, StableHasher};
+use rustc_span::source_map::SourceMap;
+use std::hash::Hash;
+use std::io;
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// The version of the on-disk baseline format.
+const LINT_BASELINE_VERSION: u32 = 1;
+
+/// Identifies a single lint hit in a baseline.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct BaselineKey {
+    lint: String,
+    file: String,
+    fingerprint: String,
+}
+
+/// A set of recorded lint hits, see the [module-level documentation](self).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LintBaseline {
+    /// How many times each hit may occur before it is reported again.
+    entries: FxIndexMap<BaselineKey, usize>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BaselineFile {
+    version: u32,
+    entries: Vec<BaselineEntry>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BaselineEntry {
+    lint: String,
+    file: String,
+    fingerprint: String,
+    count: usize,
+}
+
+impl LintBaseline {
+    /// Reads a baseline previously written by [`LintBaseline::write`].
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if file.version != LINT_BASELINE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "unsupported baseline version {} (expected {LINT_BASELINE_VERSION})",
+                    file.version
+                ),
+            ));
+        }
+
+        let mut baseline = LintBaseline::default();
+        for BaselineEntry { lint, file, fingerprint, count } in file.entries {
+            *baseline.entries.entry(BaselineKey { lint, file, fingerprint }).or_default() += count;
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline to `path`, with entries sorted so that the output is stable.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(BaselineKey { lint, file, fingerprint }, &count)| BaselineEntry {
+                lint: lint.clone(),
+                file: file.clone(),
+                fingerprint: fingerprint.clone(),
+                count,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (&a.file, &a.lint, &a.fingerprint).cmp(&(&b.file, &b.lint, &b.fingerprint))
+        });
+
+        let file = BaselineFile { version: LINT_BASELINE_VERSION, entries };
+        let mut contents = serde_json::to_string_pretty(&file)?;
+        contents.push('\n');
+        std::fs::write(path, contents)
+    }
+
+    /// The number of hits in this baseline.
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Computes the key identifying `diag`, if it is a lint with a primary span that points
+    /// into a source file.
+    pub(crate) fn key_for(diag: &DiagInner, sm: &SourceMap) -> Option<BaselineKey> {
+        let lint = diag.is_lint.as_ref()?;
+        let span = diag.span.primary_span()?.source_callsite();
+        if span.is_dummy() {
+            return None;
+        }
+
+        let source_file = sm.lookup_source_file(span.lo());
+        let file = sm.filename_for_diagnostics(&source_file.name).to_string();
+        let snippet = sm.span_to_snippet(sm.span_extend_to_line(span)).ok()?;
+
+        // Normalize whitespace so that reindenting or reflowing code doesn't change the
+        // fingerprint.
+        let mut hasher = StableHasher::new();
+        for word in snippet.split_whitespace() {
+            word.hash(&mut hasher);
+        }
+        let fingerprint = format!("{:032x}", hasher.finish::<Hash128>());
+
+        Some(BaselineKey { lint: lint.name.clone(), file, fingerprint })
+    }
+
+    /// Records one occurrence of `key`.
+    pub(crate) fn record(&mut self, key: BaselineKey) {
+        *self.entries.entry(key).or_default() += 1;
+    }
+
+    /// Consumes one occurrence of `key`, returning whether it was present in the baseline.
+    pub(crate) fn consume(&mut self, key: &BaselineKey) -> bool {
+        match self.entries.get_mut(key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+use crate::emitter::HumanReadableErrorType;
+use crate::json::JsonEmitter;
+use crate::{ColorConfig, DiagCtxt};
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+
+use std::io::Write;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits lints at the given `(lo, hi, lint)` positions in `code`, with `configure` applied to
+/// the `DiagCtxt`. Returns the messages of the diagnostics that were actually emitted, and the
+/// baseline recorded in the meantime, if any.
+fn emit_lints(
+    code: &str,
+    lints: &[(u32, u32, &str)],
+    configure: impl FnOnce(DiagCtxt) -> DiagCtxt,
+) -> (Vec<String>, Option<LintBaseline>) {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let je = JsonEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            sm,
+            fallback_bundle,
+            false, // pretty
+            HumanReadableErrorType::Short(ColorConfig::Never),
+        );
+
+        let dcx = configure(DiagCtxt::new(Box::new(je)));
+        for (i, &(lo, hi, lint)) in lints.iter().enumerate() {
+            let span = Span::with_root_ctxt(BytePos(lo), BytePos(hi));
+            let mut diag = dcx.struct_span_warn(span, format!("lint {i}"));
+            diag.is_lint(lint.to_owned(), false);
+            diag.emit();
+        }
+        let recorded = dcx.take_recorded_lint_baseline();
+
+        let bytes = output.lock().unwrap();
+        let messages = str::from_utf8(&bytes)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let diag: serde_json::Value = serde_json::from_str(line).unwrap();
+                diag["message"].as_str().unwrap().to_owned()
+            })
+            .collect();
+        (messages, recorded)
+    })
+}
+
+#[test]
+fn recorded_lints_are_suppressed() {
+    let code = "fn f() {\n    let x = 1;\n    let y = 2;\n}\n";
+    let (emitted, recorded) = emit_lints(
+        code,
+        &[(17, 18, "unused_variables"), (32, 33, "unused_variables")],
+        DiagCtxt::with_lint_baseline_recording,
+    );
+    assert_eq!(emitted, ["lint 0", "lint 1"]);
+    let baseline = recorded.unwrap();
+    assert_eq!(baseline.len(), 2);
+
+    // Moving and reindenting the existing hits doesn't affect the baseline, but a new hit is
+    // still reported.
+    let code =
+        "fn f() {\n    {\n        let x = 1;\n        let y = 2;\n    }\n    let z = 3;\n}\n";
+    let (emitted, recorded) = emit_lints(
+        code,
+        &[(27, 28, "unused_variables"), (46, 47, "unused_variables"), (67, 68, "unused_variables")],
+        |dcx| dcx.with_lint_baseline(baseline),
+    );
+    assert_eq!(emitted, ["lint 2"]);
+    assert!(recorded.is_none());
+}
+
+#[test]
+fn baseline_counts_are_consumed() {
+    let code = "fn f() { let (a, b) = (1, 2); }\n";
+    let (_, recorded) =
+        emit_lints(code, &[(14, 15, "unused_variables")], DiagCtxt::with_lint_baseline_recording);
+    let baseline = recorded.unwrap();
+
+    // Both hits are on the same line, but only one of them was recorded.
+    let (emitted, _) =
+        emit_lints(code, &[(14, 15, "unused_variables"), (17, 18, "unused_variables")], |dcx| {
+            dcx.with_lint_baseline(baseline)
+        });
+    assert_eq!(emitted, ["lint 1"]);
+}
+
+#[test]
+fn baseline_matches_lint_name() {
+    let code = "fn f() { let x = 1; }\n";
+    let (_, recorded) =
+        emit_lints(code, &[(13, 14, "unused_variables")], DiagCtxt::with_lint_baseline_recording);
+    let baseline = recorded.unwrap();
+
+    let (emitted, _) =
+        emit_lints(code, &[(13, 14, "unused_mut")], |dcx| dcx.with_lint_baseline(baseline));
+    assert_eq!(emitted, ["lint 0"]);
+}
+
+#[test]
+fn write_and_read() {
+    let code = "fn f() { let x = 1; }\nfn g() { let y = 2; }\n";
+    let (_, recorded) = emit_lints(
+        code,
+        &[(13, 14, "unused_variables"), (35, 36, "unused_variables"), (35, 36, "dead_code")],
+        DiagCtxt::with_lint_baseline_recording,
+    );
+    let baseline = recorded.unwrap();
+
+    let path = std::env::temp_dir().join(format!("lint-baseline-{}.json", std::process::id()));
+    baseline.write(&path).unwrap();
+    let read = LintBaseline::read(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read.unwrap(), baseline);
+}
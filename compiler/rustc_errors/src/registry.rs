@@ -1,3 +1,4 @@
+use crate::explanation::Explanation;
 use crate::ErrCode;
 use rustc_data_structures::fx::FxHashMap;
 
@@ -19,4 +20,10 @@ pub fn new(long_descriptions: &[(ErrCode, &'static str)]) -> Registry {
     pub fn try_find_description(&self, code: ErrCode) -> Result<&'static str, InvalidErrorCode> {
         self.long_descriptions.get(&code).copied().ok_or(InvalidErrorCode)
     }
+
+    /// Returns the structured form of the description of `code`, see
+    /// [`Registry::try_find_description`].
+    pub fn try_find_explanation(&self, code: ErrCode) -> Result<Explanation, InvalidErrorCode> {
+        self.try_find_description(code).map(|description| Explanation::parse(code, description))
+    }
 }
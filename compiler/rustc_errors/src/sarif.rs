@@ -0,0 +1,544 @@
+//! A SARIF emitter for errors.
+//!
+//! SARIF (Static Analysis Results Interchange Format) is the format consumed by most
+//! code-scanning services. Unlike the JSON emitter, which writes one object per diagnostic,
+//! a SARIF log describes a whole analysis run as a single document. Results are therefore
+//! buffered and the log is written out when the emitter is dropped.
+//!
+//! Only the subset of SARIF 2.1.0 needed to describe rustc diagnostics is produced: every
+//! diagnostic becomes a `result`, error codes and lint names become `rules`, labelled spans and
+//! sub-diagnostics become `relatedLocations`, and code suggestions become `fixes`. See
+//! <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html> for the specification.
+
+use crate::registry::Registry;
+use crate::translation::{to_fluent_args, Translate};
+use crate::{
+    diagnostic::IsLint, CodeSuggestion, DiagInner, DiagMessage, FluentBundle, LazyFallbackBundle,
+    Level, MultiSpan, Style, Subdiag,
+};
+use derive_setters::Setters;
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
+use rustc_error_messages::FluentArgs;
+use rustc_lint_defs::Applicability;
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+use serde::Serialize;
+use std::error::Report;
+use std::io::{self, Write};
+
+#[cfg(test)]
+mod tests;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Setters)]
+pub struct SarifEmitter {
+    #[setters(skip)]
+    dst: IntoDynSyncSend<Box<dyn Write + Send>>,
+    registry: Option<Registry>,
+    #[setters(skip)]
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    #[setters(skip)]
+    fallback_bundle: LazyFallbackBundle,
+    /// The name reported as the `tool.driver.name` of the run, e.g. "rustc" or "rustdoc".
+    #[setters(into)]
+    tool_name: String,
+    #[setters(skip)]
+    rules: FxIndexMap<String, ReportingDescriptor>,
+    #[setters(skip)]
+    results: Vec<SarifResult>,
+    /// Problems of the emitter itself, such as diagnostics that couldn't be translated.
+    #[setters(skip)]
+    notifications: Vec<Notification>,
+}
+
+impl SarifEmitter {
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        sm: Lrc<SourceMap>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst: IntoDynSyncSend(dst),
+            registry: None,
+            sm,
+            fluent_bundle: None,
+            fallback_bundle,
+            tool_name: "rustc".to_owned(),
+            rules: FxIndexMap::default(),
+            results: Vec::new(),
+            notifications: Vec::new(),
+        }
+    }
+
+    /// Writes the buffered results as a single SARIF log.
+    ///
+    /// Nothing is written if no diagnostic has been emitted, so that the short-lived
+    /// diagnostic contexts used during option parsing don't each produce an empty log.
+    fn flush_log(&mut self) -> io::Result<()> {
+        if self.results.is_empty() && self.notifications.is_empty() {
+            return Ok(());
+        }
+        let rules = std::mem::take(&mut self.rules).into_values().collect();
+        let results = std::mem::take(&mut self.results);
+        let notifications = std::mem::take(&mut self.notifications);
+        let invocations = if notifications.is_empty() {
+            vec![]
+        } else {
+            vec![Invocation {
+                execution_successful: false,
+                tool_execution_notifications: notifications,
+            }]
+        };
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: self.tool_name.clone(),
+                        information_uri: "https://www.rust-lang.org/",
+                        rules,
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                invocations,
+                results,
+            }],
+        };
+        serde_json::to_writer_pretty(&mut *self.dst, &log)?;
+        self.dst.write_all(b"\n")?;
+        self.dst.flush()
+    }
+
+    /// Returns the index of the rule describing `diag`, registering it if this is the first
+    /// result referring to it.
+    fn rule_index(&mut self, diag: &DiagInner) -> Option<(String, usize)> {
+        let (id, full_description, help_uri) = if let Some(code) = diag.code {
+            let explanation = self
+                .registry
+                .as_ref()
+                .and_then(|registry| registry.try_find_description(code).ok())
+                .map(|explanation| MultiformatMessage {
+                    text: explanation.to_owned(),
+                    markdown: Some(explanation.to_owned()),
+                });
+            let help_uri = format!("https://doc.rust-lang.org/error_codes/{code}.html");
+            (code.to_string(), explanation, Some(help_uri))
+        } else if let Some(IsLint { name, .. }) = &diag.is_lint {
+            (name.clone(), None, None)
+        } else {
+            return None;
+        };
+
+        let entry = self.rules.entry(id.clone());
+        let index = entry.index();
+        entry.or_insert_with(|| ReportingDescriptor { id: id.clone(), full_description, help_uri });
+        Some((id, index))
+    }
+
+    /// Converts `diag` into a result, or returns why one of its messages couldn't be translated.
+    fn result_from_diagnostic(&mut self, diag: &DiagInner) -> Result<SarifResult, String> {
+        let args = to_fluent_args(diag.args.iter());
+        let mut message = self.try_translate_messages(&diag.messages, &args)?;
+
+        let locations = self.locations_from_multispan(&diag.span, &args, true)?;
+        let mut related_locations = self.locations_from_multispan(&diag.span, &args, false)?;
+        for child in &diag.children {
+            self.add_sub_diagnostic(child, &args, &mut message, &mut related_locations)?;
+        }
+
+        let mut fixes = vec![];
+        for sugg in diag.suggestions.iter().flatten() {
+            fixes.extend(self.fixes_from_suggestion(sugg, &args)?);
+        }
+
+        // Only register the rule once the result is known to make it into the log.
+        let rule = self.rule_index(diag);
+        Ok(SarifResult {
+            rule_id: rule.as_ref().map(|(id, _)| id.clone()),
+            rule_index: rule.map(|(_, index)| index),
+            level: sarif_level(diag.level),
+            message: Message { text: message },
+            locations,
+            related_locations,
+            fixes,
+        })
+    }
+
+    /// Like [`Translate::translate_messages`], but returns translation failures instead of
+    /// panicking on them.
+    fn try_translate_messages(
+        &self,
+        messages: &[(DiagMessage, Style)],
+        args: &FluentArgs<'_>,
+    ) -> Result<String, String> {
+        messages.iter().map(|(m, _)| self.try_translate_message(m, args)).collect()
+    }
+
+    fn try_translate_message(
+        &self,
+        message: &DiagMessage,
+        args: &FluentArgs<'_>,
+    ) -> Result<String, String> {
+        self.translate_message(message, args)
+            .map(|m| m.into_owned())
+            .map_err(|err| Report::new(err).to_string())
+    }
+
+    /// Sub-diagnostics that point at code are reported as related locations; those that don't
+    /// are appended to the message of the result.
+    fn add_sub_diagnostic(
+        &self,
+        subdiag: &Subdiag,
+        args: &FluentArgs<'_>,
+        message: &mut String,
+        related_locations: &mut Vec<Location>,
+    ) -> Result<(), String> {
+        let child_message =
+            format!("{}: {}", subdiag.level, self.try_translate_messages(&subdiag.messages, args)?);
+        let primary_spans = subdiag.span.primary_spans();
+        if primary_spans.iter().all(|span| span.is_dummy()) {
+            message.push('\n');
+            message.push_str(&child_message);
+            return Ok(());
+        }
+        related_locations.extend(
+            primary_spans
+                .iter()
+                .filter_map(|&span| self.location_from_span(span, Some(child_message.clone()))),
+        );
+        Ok(())
+    }
+
+    /// Converts the labels of `msp` into locations, keeping either only the primary spans or only
+    /// the secondary ones.
+    fn locations_from_multispan(
+        &self,
+        msp: &MultiSpan,
+        args: &FluentArgs<'_>,
+        primary: bool,
+    ) -> Result<Vec<Location>, String> {
+        let mut locations = vec![];
+        for span_label in msp.span_labels() {
+            if span_label.is_primary != primary {
+                continue;
+            }
+            let label = span_label
+                .label
+                .as_ref()
+                .map(|m| self.try_translate_message(m, args))
+                .transpose()?;
+            locations.extend(self.location_from_span(span_label.span, label));
+        }
+        Ok(locations)
+    }
+
+    fn fixes_from_suggestion(
+        &self,
+        sugg: &CodeSuggestion,
+        args: &FluentArgs<'_>,
+    ) -> Result<Vec<Fix>, String> {
+        let description = self.try_translate_message(&sugg.msg, args)?;
+        let fixes = sugg
+            .substitutions
+            .iter()
+            .map(|substitution| {
+                let mut artifact_changes: FxIndexMap<String, Vec<Replacement>> =
+                    FxIndexMap::default();
+                for part in &substitution.parts {
+                    let Some((uri, region)) = self.region_from_span(part.span) else {
+                        continue;
+                    };
+                    artifact_changes.entry(uri).or_default().push(Replacement {
+                        deleted_region: region,
+                        inserted_content: ArtifactContent { text: part.snippet.clone() },
+                    });
+                }
+                Fix {
+                    description: Message { text: description.clone() },
+                    artifact_changes: artifact_changes
+                        .into_iter()
+                        .map(|(uri, replacements)| ArtifactChange {
+                            artifact_location: ArtifactLocation { uri },
+                            replacements,
+                        })
+                        .collect(),
+                    properties: FixProperties { applicability: sugg.applicability },
+                }
+            })
+            .collect();
+        Ok(fixes)
+    }
+
+    fn location_from_span(&self, span: Span, message: Option<String>) -> Option<Location> {
+        let (uri, region) = self.region_from_span(span)?;
+        Some(Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation { uri },
+                region,
+            },
+            message: message.map(|text| Message { text }),
+        })
+    }
+
+    fn region_from_span(&self, span: Span) -> Option<(String, Region)> {
+        if span.is_dummy() {
+            return None;
+        }
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        let uri = file_name_to_uri(&self.sm.filename_for_diagnostics(&start.file.name).to_string());
+        let region = Region {
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+            byte_offset: byte_start,
+            byte_length: byte_end - byte_start,
+        };
+        Some((uri, region))
+    }
+}
+
+impl Translate for SarifEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl crate::emitter::Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: DiagInner) {
+        // "aborting due to previous error" and friends only summarize the other diagnostics.
+        if diag.level == Level::FailureNote {
+            return;
+        }
+        match self.result_from_diagnostic(&diag) {
+            Ok(result) => self.results.push(result),
+            // Rather than losing the whole log, report the broken diagnostic as a problem of the
+            // emitter itself.
+            Err(err) => self.notifications.push(Notification {
+                level: "error",
+                message: Message { text: format!("failed to translate a diagnostic: {err}") },
+            }),
+        }
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        // The explanations are already part of the rules of the run.
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let result = self.flush_log();
+        // Don't turn an unwind (e.g. from a fatal error) into an abort.
+        if let Err(e) = result {
+            if !std::thread::panicking() {
+                panic!("failed to print diagnostics: {e:?}");
+            }
+        }
+    }
+}
+
+/// Maps a diagnostic level onto one of the SARIF result levels.
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::Fatal | Level::Error | Level::DelayedBug => "error",
+        Level::ForceWarning(_) | Level::Warning => "warning",
+        Level::Note | Level::OnceNote | Level::Help | Level::OnceHelp | Level::FailureNote => {
+            "note"
+        }
+        Level::Allow | Level::Expect(_) => "none",
+    }
+}
+
+/// Turns a file name as printed in diagnostics into a (relative) URI reference, percent-encoding
+/// anything that may not appear in one.
+fn file_name_to_uri(file_name: &str) -> String {
+    let mut uri = String::with_capacity(file_name.len());
+    for byte in file_name.bytes() {
+        match byte {
+            b'\\' => uri.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    /// How `startColumn` and `endColumn` are counted.
+    column_kind: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    invocations: Vec<Invocation>,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Invocation {
+    execution_successful: bool,
+    tool_execution_notifications: Vec<Notification>,
+}
+
+#[derive(Serialize)]
+struct Notification {
+    /// "error", "warning" or "note".
+    level: &'static str,
+    message: Message,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: String,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    /// The error code (e.g. "E1234") or the lint name.
+    id: String,
+    /// The long-form explanation of an error code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<MultiformatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning", "note" or "none".
+    level: &'static str,
+    message: Message,
+    /// The primary spans of the diagnostic.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    /// Secondary spans and sub-diagnostics pointing at code.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct MultiformatMessage {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    markdown: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    /// The label attached to this span, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    /// Exclusive.
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+    properties: FixProperties,
+}
+
+#[derive(Serialize)]
+struct FixProperties {
+    /// Whether the fix can be applied mechanically, see `Applicability`.
+    applicability: Applicability,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: ArtifactContent,
+}
+
+#[derive(Serialize)]
+struct ArtifactContent {
+    text: String,
+}
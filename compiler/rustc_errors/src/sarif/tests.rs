@@ -0,0 +1,163 @@
+use super::*;
+
+use crate::emitter::Emitter;
+use crate::{DiagCtxt, E0308};
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::BytePos;
+
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Runs `f` against a `DiagCtxt` backed by a `SarifEmitter` and returns the parsed log.
+fn emit_sarif(code: &str, f: impl FnOnce(&DiagCtxt)) -> serde_json::Value {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test dir/test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(Box::new(Shared { data: output.clone() }), sm, fallback_bundle)
+            .registry(Some(Registry::new(&[(
+                E0308,
+                "Expected type did not match the received type.",
+            )])));
+
+        let dcx = DiagCtxt::new(Box::new(se));
+        f(&dcx);
+        drop(dcx);
+
+        let bytes = output.lock().unwrap();
+        serde_json::from_str(str::from_utf8(&bytes).unwrap()).unwrap()
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn no_diagnostics_no_log() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(Box::new(Shared { data: output.clone() }), sm, fallback_bundle);
+        assert!(!se.should_show_explain());
+        drop(se);
+        assert!(output.lock().unwrap().is_empty());
+    })
+}
+
+#[test]
+fn error_with_code_and_fix() {
+    let log = emit_sarif("let x: u8 = 1u16;\n", |dcx| {
+        dcx.struct_span_err(span(12, 16), "mismatched types")
+            .with_code(E0308)
+            .with_span_label(span(7, 9), "expected due to this")
+            .with_note("a note without a span")
+            .with_span_suggestion(
+                span(13, 16),
+                "change the type of the numeric literal",
+                "u8",
+                Applicability::MachineApplicable,
+            )
+            .emit();
+    });
+
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rustc");
+
+    let rule = &run["tool"]["driver"]["rules"][0];
+    assert_eq!(rule["id"], "E0308");
+    assert_eq!(rule["helpUri"], "https://doc.rust-lang.org/error_codes/E0308.html");
+    assert_eq!(rule["fullDescription"]["text"], "Expected type did not match the received type.");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result["ruleId"], "E0308");
+    assert_eq!(result["ruleIndex"], 0);
+    assert_eq!(result["level"], "error");
+    assert_eq!(result["message"]["text"], "mismatched types\nnote: a note without a span");
+
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "test%20dir/test.rs");
+    assert_eq!(location["region"]["startLine"], 1);
+    assert_eq!(location["region"]["startColumn"], 13);
+    assert_eq!(location["region"]["endColumn"], 17);
+    assert_eq!(location["region"]["byteOffset"], 12);
+    assert_eq!(location["region"]["byteLength"], 4);
+
+    let related = &result["relatedLocations"][0];
+    assert_eq!(related["message"]["text"], "expected due to this");
+    assert_eq!(related["physicalLocation"]["region"]["startColumn"], 8);
+
+    let fix = &result["fixes"][0];
+    assert_eq!(fix["description"]["text"], "change the type of the numeric literal");
+    assert_eq!(fix["properties"]["applicability"], "MachineApplicable");
+    let replacement = &fix["artifactChanges"][0]["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"], "u8");
+    assert_eq!(replacement["deletedRegion"]["byteOffset"], 13);
+}
+
+#[test]
+fn rules_are_shared_between_results() {
+    let log = emit_sarif("fn f() {}\nfn g() {}\n", |dcx| {
+        dcx.struct_span_err(span(3, 4), "first").with_code(E0308).emit();
+        dcx.struct_span_warn(span(13, 14), "second").emit();
+        dcx.struct_span_err(span(13, 14), "third").with_code(E0308).emit();
+    });
+
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["ruleIndex"], 0);
+    assert_eq!(results[1]["level"], "warning");
+    assert!(results[1].get("ruleId").is_none());
+    assert_eq!(results[1]["locations"][0]["physicalLocation"]["region"]["startLine"], 2);
+    assert_eq!(results[2]["ruleIndex"], 0);
+}
+
+#[test]
+fn untranslatable_diagnostic_is_a_notification() {
+    let log = emit_sarif("fn f() {}\n", |dcx| {
+        dcx.struct_span_warn(
+            span(3, 4),
+            DiagMessage::FluentIdentifier("no_such_message".into(), None),
+        )
+        .emit();
+        dcx.struct_span_warn(span(3, 4), "still reported").emit();
+    });
+
+    let run = &log["runs"][0];
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["message"]["text"], "still reported");
+
+    let invocation = &run["invocations"][0];
+    assert_eq!(invocation["executionSuccessful"], false);
+    let notification = &invocation["toolExecutionNotifications"][0];
+    assert_eq!(notification["level"], "error");
+    let text = notification["message"]["text"].as_str().unwrap();
+    assert!(text.starts_with("failed to translate a diagnostic: "), "{text}");
+    assert!(text.contains("no_such_message"), "{text}");
+}
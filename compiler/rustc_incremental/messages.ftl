@@ -54,6 +54,9 @@ incremental_finalized_gc_failed =
 incremental_hard_link_failed =
     hard linking files in the incremental compilation cache failed. copying files instead. consider moving the cache directory to a file system which supports hard linking in session dir `{$path}`
 
+incremental_inspect_session_dir =
+    failed to inspect the incremental compilation session directory `{$path}`: {$err}
+
 incremental_invalid_gc_failed =
     failed to garbage collect invalid incremental compilation session directory `{$path}`: {$err}
 
@@ -85,6 +88,13 @@ incremental_repeated_depnode_label = dep-node label `{$label}` is repeated
 incremental_session_gc_failed =
     failed to garbage collect incremental compilation session directory `{$path}`: {$err}
 
+incremental_shared_cache_garbage_collect =
+    failed to garbage collect the shared cache at `{$path}`: {$err}
+
+incremental_shared_cache_open = failed to open the shared cache at `{$path}`: {$err}
+
+incremental_shared_cache_store = failed to add the shared cache entry `{$path}`: {$err}
+
 incremental_unchecked_clean = found unchecked `#[rustc_clean]` attribute
 
 incremental_undefined_clean_dirty_assertions =
@@ -102,3 +112,5 @@ incremental_unrecognized_depnode_label = dep-node label `{$label}` not recognize
 incremental_write_dep_graph = failed to write dependency graph to `{$path}`: {$err}
 
 incremental_write_new = failed to write {$name} to `{$path}`: {$err}
+
+incremental_write_report = failed to write the incremental report to `{$path}`: {$err}
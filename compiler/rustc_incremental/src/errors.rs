@@ -307,8 +307,43 @@ pub struct DeleteWorkProduct<'a> {
     pub err: std::io::Error,
 }
 
+#[derive(Diagnostic)]
+#[diag(incremental_shared_cache_open)]
+pub struct SharedCacheOpen<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(incremental_shared_cache_store)]
+pub struct SharedCacheStore<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(incremental_shared_cache_garbage_collect)]
+pub struct SharedCacheGarbageCollect<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(incremental_corrupt_file)]
 pub struct CorruptFile<'a> {
     pub path: &'a Path,
 }
+
+#[derive(Diagnostic)]
+#[diag(incremental_inspect_session_dir)]
+pub struct InspectSessionDir<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
+
+#[derive(Diagnostic)]
+#[diag(incremental_write_report)]
+pub struct WriteIncrementalReport<'a> {
+    pub path: &'a Path,
+    pub err: std::io::Error,
+}
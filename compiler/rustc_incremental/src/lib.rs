@@ -9,15 +9,18 @@
 mod assert_dep_graph;
 mod errors;
 mod persist;
+mod report;
 
 pub use persist::copy_cgu_workproduct_to_incr_comp_cache_dir;
 pub use persist::finalize_session_directory;
 pub use persist::in_incr_comp_dir;
 pub use persist::in_incr_comp_dir_sess;
+pub use persist::inspect_session_dir;
 pub use persist::load_query_result_cache;
 pub use persist::save_dep_graph;
 pub use persist::save_work_product_index;
 pub use persist::setup_dep_graph;
 pub use persist::LoadResult;
+pub use persist::SharedCache;
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
@@ -131,10 +131,10 @@
 mod tests;
 
 const LOCK_FILE_EXT: &str = ".lock";
-const DEP_GRAPH_FILENAME: &str = "dep-graph.bin";
+pub(crate) const DEP_GRAPH_FILENAME: &str = "dep-graph.bin";
 const STAGING_DEP_GRAPH_FILENAME: &str = "dep-graph.part.bin";
-const WORK_PRODUCTS_FILENAME: &str = "work-products.bin";
-const QUERY_CACHE_FILENAME: &str = "query-cache.bin";
+pub(crate) const WORK_PRODUCTS_FILENAME: &str = "work-products.bin";
+pub(crate) const QUERY_CACHE_FILENAME: &str = "query-cache.bin";
 
 // We encode integers using the following base, so they are shorter than decimal
 // or hexadecimal numbers (we want short file and directory names). Since these
@@ -882,7 +882,7 @@ fn all_except_most_recent(
 /// to circumvent this limitation, we canonicalize the path of the directory
 /// before passing it to std::fs::remove_dir_all(). This will convert the path
 /// into the '\\?\' format, which supports much longer paths.
-fn safe_remove_dir_all(p: &Path) -> io::Result<()> {
+pub(crate) fn safe_remove_dir_all(p: &Path) -> io::Result<()> {
     let canonicalized = match try_canonicalize(p) {
         Ok(canonicalized) => canonicalized,
         Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
@@ -0,0 +1,328 @@
+//! Inspection of incremental compilation session directories, used by `-Zinspect-incr-cache`.
+//!
+//! This reads the dep graph, query result cache and work product index saved by
+//! [`save_dep_graph`](super::save::save_dep_graph) and prints a summary of them. When an older
+//! session directory of the same crate is given with `-Zinspect-incr-cache-baseline`, the dep
+//! graphs of the two sessions are compared as well:
+//!
+//! - A node whose fingerprint differs between the two graphs was red in the newer session, i.e.
+//!   its query was re-executed and produced a different result.
+//! - A changed node that doesn't depend on any other changed node is a *root cause*: it is an
+//!   input of the compilation (source code, command-line arguments, upstream crates) or reads
+//!   state that is not tracked by the dep graph. All other changed nodes were invalidated by
+//!   one or more of these.
+//!
+//! Dep nodes are identified by their kind and the hash of their key, since mapping the hash back
+//! to the key requires the `TyCtxt` of the compilation session that created the node.
+
+use crate::errors;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
+use rustc_data_structures::memmap::Mmap;
+use rustc_middle::dep_graph::{
+    DepNode, DepsType, SerializedDepGraph, SerializedDepNodeIndex, DEP_KIND_NAMES,
+};
+use rustc_middle::query::on_disk_cache::{OnDiskCache, OnDiskCacheSummary};
+use rustc_serialize::opaque::MemDecoder;
+use rustc_serialize::Decodable;
+use rustc_session::Session;
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::data::SerializedWorkProduct;
+use super::file_format;
+use super::fs::{
+    in_incr_comp_dir, DEP_GRAPH_FILENAME, QUERY_CACHE_FILENAME, WORK_PRODUCTS_FILENAME,
+};
+
+/// The files of a session directory.
+struct SessionData {
+    commandline_args_hash: u64,
+    dep_graph_size: usize,
+    dep_graph: Arc<SerializedDepGraph>,
+    /// The size and contents of the query result cache, if there is one.
+    query_cache: Option<(usize, OnDiskCacheSummary)>,
+    work_products: Vec<SerializedWorkProduct>,
+}
+
+/// Whether a node of the newer session's dep graph is also in the baseline.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NodeStatus {
+    Unchanged,
+    Changed,
+    New,
+}
+
+/// Returns a description of the incremental compilation session directory `dir`, and of how its
+/// dep graph differs from the one in `baseline_dir`, if given.
+pub fn inspect_session_dir(sess: &Session, dir: &Path, baseline_dir: Option<&Path>) -> String {
+    let load = |dir: &Path| {
+        SessionData::load(sess, dir).unwrap_or_else(|err| {
+            sess.dcx().emit_fatal(errors::InspectSessionDir { path: dir, err })
+        })
+    };
+
+    let mut out = String::new();
+    let session = load(dir);
+    session.describe(dir, &mut out);
+    if let Some(baseline_dir) = baseline_dir {
+        let baseline = load(baseline_dir);
+        writeln!(out).unwrap();
+        describe_changes(&session, &baseline, baseline_dir, &mut out);
+    }
+    out
+}
+
+fn read_file(sess: &Session, dir: &Path, file_name: &str) -> io::Result<Option<(Mmap, usize)>> {
+    let path = in_incr_comp_dir(dir, file_name);
+    file_format::read_file(&path, false, sess.is_nightly_build(), sess.cfg_version)
+}
+
+fn missing_file(file_name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("`{file_name}` is missing or was written by a different version of rustc"),
+    )
+}
+
+fn corrupt_file(file_name: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("`{file_name}` is corrupt"))
+}
+
+impl SessionData {
+    fn load(sess: &Session, dir: &Path) -> io::Result<SessionData> {
+        let Some((bytes, start_pos)) = read_file(sess, dir, DEP_GRAPH_FILENAME)? else {
+            return Err(missing_file(DEP_GRAPH_FILENAME));
+        };
+        let Ok(mut decoder) = MemDecoder::new(&bytes, start_pos) else {
+            return Err(corrupt_file(DEP_GRAPH_FILENAME));
+        };
+        let commandline_args_hash = u64::decode(&mut decoder);
+        let dep_graph = SerializedDepGraph::decode::<DepsType>(&mut decoder);
+
+        let query_cache = match read_file(sess, dir, QUERY_CACHE_FILENAME)? {
+            Some((bytes, start_pos)) => {
+                let summary = OnDiskCache::summarize(&bytes, start_pos)
+                    .map_err(|()| corrupt_file(QUERY_CACHE_FILENAME))?;
+                Some((bytes.len(), summary))
+            }
+            None => None,
+        };
+
+        let work_products = match read_file(sess, dir, WORK_PRODUCTS_FILENAME)? {
+            Some((bytes, start_pos)) => {
+                let Ok(mut decoder) = MemDecoder::new(&bytes, start_pos) else {
+                    return Err(corrupt_file(WORK_PRODUCTS_FILENAME));
+                };
+                Vec::<SerializedWorkProduct>::decode(&mut decoder)
+            }
+            None => Vec::new(),
+        };
+
+        Ok(SessionData {
+            commandline_args_hash,
+            dep_graph_size: bytes.len(),
+            dep_graph,
+            query_cache,
+            work_products,
+        })
+    }
+
+    fn describe(&self, dir: &Path, out: &mut String) {
+        let graph = &self.dep_graph;
+        let edge_count: usize = indices(graph).map(|i| graph.edge_targets_from(i).count()).sum();
+
+        writeln!(out, "session directory: {}", dir.display()).unwrap();
+        writeln!(out, "command-line arguments hash: {:016x}", self.commandline_args_hash).unwrap();
+        writeln!(
+            out,
+            "dep graph: {} bytes, {} nodes, {} edges",
+            self.dep_graph_size,
+            graph.node_count(),
+            edge_count
+        )
+        .unwrap();
+        match &self.query_cache {
+            Some((size, summary)) => writeln!(
+                out,
+                "query cache: {size} bytes, {} query results, {} side effects, \
+                {} source files, {} allocations",
+                summary.query_results,
+                summary.side_effects,
+                summary.source_files,
+                summary.allocations
+            ),
+            None => writeln!(out, "query cache: none"),
+        }
+        .unwrap();
+
+        writeln!(out, "work products: {}", self.work_products.len()).unwrap();
+        let mut work_products: Vec<_> =
+            self.work_products.iter().map(|swp| &swp.work_product).collect();
+        work_products.sort_by(|a, b| a.cgu_name.cmp(&b.cgu_name));
+        for work_product in work_products {
+            let files: Vec<_> = work_product
+                .saved_files
+                .to_sorted_stable_ord()
+                .into_iter()
+                .map(|(_, path)| path.as_str())
+                .collect();
+            writeln!(out, "    {}: {}", work_product.cgu_name, files.join(", ")).unwrap();
+        }
+
+        writeln!(out, "nodes by kind:").unwrap();
+        write_kind_counts(out, indices(graph).map(|i| graph.index_to_node(i)));
+    }
+}
+
+/// Compares the dep graph of `session` to that of the older session `baseline`, see the
+/// [module-level documentation](self).
+fn describe_changes(
+    session: &SessionData,
+    baseline: &SessionData,
+    baseline_dir: &Path,
+    out: &mut String,
+) {
+    let graph = &session.dep_graph;
+    let prev_graph = &baseline.dep_graph;
+
+    writeln!(out, "baseline session directory: {}", baseline_dir.display()).unwrap();
+    if session.commandline_args_hash != baseline.commandline_args_hash {
+        writeln!(
+            out,
+            "the command-line arguments changed, so nothing of the baseline session was reused"
+        )
+        .unwrap();
+    }
+
+    let status: Vec<NodeStatus> = indices(graph)
+        .map(|i| match prev_graph.node_to_index_opt(&graph.index_to_node(i)) {
+            None => NodeStatus::New,
+            Some(prev_i)
+                if prev_graph.fingerprint_by_index(prev_i) != graph.fingerprint_by_index(i) =>
+            {
+                NodeStatus::Changed
+            }
+            Some(_) => NodeStatus::Unchanged,
+        })
+        .collect();
+    let is_changed = |i: SerializedDepNodeIndex| status[i.as_usize()] == NodeStatus::Changed;
+    let count = |s| status.iter().filter(|&&st| st == s).count();
+    let removed = indices(prev_graph)
+        .filter(|&i| graph.node_to_index_opt(&prev_graph.index_to_node(i)).is_none())
+        .count();
+    writeln!(
+        out,
+        "nodes: {} unchanged, {} changed, {} new, {} removed",
+        count(NodeStatus::Unchanged),
+        count(NodeStatus::Changed),
+        count(NodeStatus::New),
+        removed
+    )
+    .unwrap();
+
+    writeln!(out, "changed nodes by kind:").unwrap();
+    write_kind_counts(
+        out,
+        indices(graph).filter(|&i| is_changed(i)).map(|i| graph.index_to_node(i)),
+    );
+
+    // Find the changed nodes that don't depend on other changed nodes, and for each of them,
+    // how many changed nodes (transitively) depend on it.
+    let mut dependents: FxHashMap<SerializedDepNodeIndex, Vec<SerializedDepNodeIndex>> =
+        Default::default();
+    let mut root_causes = Vec::new();
+    for i in indices(graph).filter(|&i| is_changed(i)) {
+        let mut is_root_cause = true;
+        for dep in graph.edge_targets_from(i).filter(|&dep| is_changed(dep)) {
+            dependents.entry(dep).or_default().push(i);
+            is_root_cause = false;
+        }
+        if is_root_cause {
+            root_causes.push(i);
+        }
+    }
+    let mut root_causes: Vec<_> = root_causes
+        .into_iter()
+        .map(|root_cause| {
+            let mut invalidated = FxHashSet::default();
+            let mut stack = vec![root_cause];
+            while let Some(i) = stack.pop() {
+                for &dependent in dependents.get(&i).into_iter().flatten() {
+                    if invalidated.insert(dependent) {
+                        stack.push(dependent);
+                    }
+                }
+            }
+            (graph.index_to_node(root_cause), invalidated.len())
+        })
+        .collect();
+    root_causes.sort_by(|(a, a_count), (b, b_count)| {
+        b_count.cmp(a_count).then_with(|| describe_node(*a).cmp(&describe_node(*b)))
+    });
+
+    writeln!(out, "root causes (with the number of changed nodes that depend on them):").unwrap();
+    for (node, invalidated) in root_causes {
+        writeln!(out, "{invalidated:>10} {} ({})", describe_node(node), input_kind(node)).unwrap();
+    }
+
+    writeln!(out, "changed nodes:").unwrap();
+    let mut changed: Vec<_> = indices(graph)
+        .filter(|&i| is_changed(i))
+        .map(|i| describe_node(graph.index_to_node(i)))
+        .collect();
+    changed.sort();
+    for node in changed {
+        writeln!(out, "    {node}").unwrap();
+    }
+}
+
+fn indices(graph: &SerializedDepGraph) -> impl Iterator<Item = SerializedDepNodeIndex> {
+    (0..graph.node_count()).map(SerializedDepNodeIndex::from_usize)
+}
+
+fn kind_name(node: DepNode) -> &'static str {
+    DEP_KIND_NAMES.get(node.kind.as_usize()).copied().unwrap_or("<unknown>")
+}
+
+fn describe_node(node: DepNode) -> String {
+    format!("{}({})", kind_name(node), Fingerprint::from(node.hash).to_hex())
+}
+
+/// Writes the number of nodes of each kind, most common kinds first.
+fn write_kind_counts(out: &mut String, nodes: impl Iterator<Item = DepNode>) {
+    let mut counts: FxIndexMap<&str, usize> = Default::default();
+    for node in nodes {
+        *counts.entry(kind_name(node)).or_default() += 1;
+    }
+    counts.sort_by(|a, a_count, b, b_count| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    for (kind, count) in counts {
+        writeln!(out, "{count:>10} {kind}").unwrap();
+    }
+}
+
+/// Describes the input that a root cause of changes represents.
+fn input_kind(node: DepNode) -> Cow<'static, str> {
+    match kind_name(node) {
+        "crate_hash" | "crate_host_hash" | "crate_extern_paths" | "extra_filename"
+        | "used_crate_source" => {
+            // The keys of these queries are crate numbers, whose hash is the `DefPathHash` of
+            // the crate root. Its first half is the stable crate id.
+            let (stable_crate_id, _) = Fingerprint::from(node.hash).split();
+            format!("upstream crate {:016x}", stable_crate_id.as_u64()).into()
+        }
+        "crate_for_resolver"
+        | "resolver_for_lowering_raw"
+        | "resolutions"
+        | "hir_crate"
+        | "hir_crate_items"
+        | "opt_hir_owner_nodes"
+        | "hir_attrs"
+        | "source_span" => "source code".into(),
+        "output_filenames" | "global_backend_features" => "command-line arguments".into(),
+        _ => "untracked state".into(),
+    }
+}
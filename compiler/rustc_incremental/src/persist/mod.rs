@@ -6,16 +6,20 @@
 mod dirty_clean;
 mod file_format;
 mod fs;
+mod inspect;
 mod load;
 mod save;
+mod shared_cache;
 mod work_product;
 
 pub use fs::finalize_session_directory;
 pub use fs::in_incr_comp_dir;
 pub use fs::in_incr_comp_dir_sess;
+pub use inspect::inspect_session_dir;
 pub use load::load_query_result_cache;
 pub use load::setup_dep_graph;
 pub use load::LoadResult;
 pub use save::save_dep_graph;
 pub use save::save_work_product_index;
+pub use shared_cache::SharedCache;
 pub use work_product::copy_cgu_workproduct_to_incr_comp_cache_dir;
@@ -1,5 +1,6 @@
 use crate::assert_dep_graph::assert_dep_graph;
 use crate::errors;
+use crate::report;
 use rustc_data_structures::fx::FxIndexMap;
 use rustc_data_structures::sync::join;
 use rustc_middle::dep_graph::{
@@ -45,6 +46,10 @@ pub fn save_dep_graph(tcx: TyCtxt<'_>) {
         sess.time("assert_dep_graph", || assert_dep_graph(tcx));
         sess.time("check_dirty_clean", || dirty_clean::check_dirty_clean_annotations(tcx));
 
+        if sess.opts.unstable_opts.incremental_report.enabled() {
+            sess.time("incr_comp_write_report", || report::write_report(tcx));
+        }
+
         if sess.opts.unstable_opts.incremental_info {
             tcx.dep_graph.print_incremental_info()
         }
@@ -180,5 +185,6 @@ pub(crate) fn build_dep_graph(
         encoder,
         sess.opts.unstable_opts.query_dep_graph,
         sess.opts.unstable_opts.incremental_info,
+        sess.opts.unstable_opts.incremental_report.enabled(),
     ))
 }
@@ -0,0 +1,294 @@
+//! A content-addressed cache of codegen artifacts and query results that is
+//! shared between compilation sessions, enabled with `-Zshared-cache=<dir>`.
+//!
+//! Unlike the incremental compilation cache, which belongs to a single crate
+//! and is keyed by the names of its codegen units and by dep nodes, entries in
+//! the shared cache are keyed by a stable hash of everything that goes into a
+//! codegen unit (see `rustc_codegen_ssa::shared_cache`) or a query result (see
+//! `TyCtxt::with_shared_query_result_cache`). This means that any number of
+//! crates and rustc invocations, for example the crates of a workspace or its
+//! different target directories, can use the same cache directory, and that an
+//! entry can never be out of date: it is either found or not.
+//!
+//! The cache directory has the following layout:
+//!
+//! ```text
+//! {cache-dir}/cache.lock
+//! {cache-dir}/entries/{key}/last-used
+//! {cache-dir}/entries/{key}/module.{ext}
+//! {cache-dir}/entries/{key}/query-result
+//! {cache-dir}/tmp/{random}/...
+//! ```
+//!
+//! ## Synchronization
+//!
+//! Entries are never modified once they have been published. A new entry is
+//! first written to a private directory in `tmp`, which is then renamed to its
+//! final location. If another process published the same entry in the
+//! meantime, the rename fails and the private copy is simply discarded, since
+//! both copies have the same contents.
+//!
+//! Every compilation session that uses the cache opens it once, and holds a
+//! shared lock on `cache.lock` until it is done with it. The garbage
+//! collection, which deletes the least recently used entries once the cache
+//! grows beyond the size given with `-Zshared-cache-max-size`, needs an
+//! exclusive lock on that file. It is run at the end of a compilation session,
+//! once the session has released its own lock, and skipped if another session
+//! is using the cache at that time, in which case that session will take care
+//! of it later on.
+
+use crate::errors;
+use crate::persist::fs::safe_remove_dir_all;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::flock;
+use rustc_data_structures::memmap::Mmap;
+use rustc_data_structures::sync::Lrc;
+use rustc_data_structures::unord::UnordMap;
+use rustc_fs_util::link_or_copy;
+use rustc_middle::dep_graph::WorkProduct;
+use rustc_middle::query::on_disk_cache::SharedCacheStore;
+use rustc_serialize::opaque::{FileEncodeResult, FileEncoder};
+use rustc_session::Session;
+use std::fs as std_fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use rand::{thread_rng, RngCore};
+use tracing::debug;
+
+#[cfg(test)]
+mod tests;
+
+const LOCK_FILE_NAME: &str = "cache.lock";
+const ENTRIES_DIR_NAME: &str = "entries";
+const TMP_DIR_NAME: &str = "tmp";
+const LAST_USED_FILE_NAME: &str = "last-used";
+const QUERY_RESULT_FILE_NAME: &str = "query-result";
+
+/// Private directories in `tmp` that are older than this were left behind by
+/// crashed sessions and are deleted during garbage collection.
+const TMP_DIR_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// An open shared cache directory. While this exists, the entries in the cache
+/// are not garbage collected.
+///
+/// There must only be one of these per session. On some platforms, the locks
+/// on a file are per process, so dropping a second one would release the lock
+/// of the first, while on others, a second one would keep the session from
+/// ever garbage collecting the cache.
+pub struct SharedCache {
+    dir: PathBuf,
+    _lock: flock::Lock,
+}
+
+impl SharedCache {
+    /// Opens the cache directory given with `-Zshared-cache`, if any. Failing
+    /// to do so is not an error, compilation just continues without the cache.
+    pub fn open(sess: &Session) -> Option<Lrc<SharedCache>> {
+        let dir = sess.opts.unstable_opts.shared_cache.as_ref()?;
+        let _timer = sess.timer("shared_cache_open");
+
+        let open = || -> io::Result<SharedCache> {
+            std_fs::create_dir_all(dir.join(ENTRIES_DIR_NAME))?;
+            std_fs::create_dir_all(dir.join(TMP_DIR_NAME))?;
+            let lock = flock::Lock::new(
+                &dir.join(LOCK_FILE_NAME),
+                true,  // wait for a garbage collection to finish
+                true,  // create the lock file
+                false, // not exclusive
+            )?;
+            Ok(SharedCache { dir: dir.clone(), _lock: lock })
+        };
+
+        match open() {
+            Ok(cache) => Some(Lrc::new(cache)),
+            Err(err) => {
+                sess.dcx().emit_warn(errors::SharedCacheOpen { path: dir, err });
+                None
+            }
+        }
+    }
+
+    fn entry_dir(&self, key: Fingerprint) -> PathBuf {
+        self.dir.join(ENTRIES_DIR_NAME).join(key.to_hex())
+    }
+
+    /// Publishes the entry for `key`, whose files are written to a private
+    /// directory by `write_files` first.
+    fn publish(
+        &self,
+        sess: &Session,
+        key: Fingerprint,
+        write_files: impl FnOnce(&Path) -> io::Result<()>,
+    ) {
+        let entry_dir = self.entry_dir(key);
+        if entry_dir.exists() {
+            return;
+        }
+
+        let tmp_dir = self.dir.join(TMP_DIR_NAME).join(format!(
+            "{}-{:08x}",
+            key.to_hex(),
+            thread_rng().next_u32()
+        ));
+        let publish = || -> io::Result<()> {
+            std_fs::create_dir(&tmp_dir)?;
+            write_files(&tmp_dir)?;
+            std_fs::File::create(tmp_dir.join(LAST_USED_FILE_NAME))?;
+            match std_fs::rename(&tmp_dir, &entry_dir) {
+                // Another session published the same entry in the meantime.
+                Err(_) if entry_dir.exists() => safe_remove_dir_all(&tmp_dir),
+                result => result,
+            }
+        };
+
+        if let Err(err) = publish() {
+            sess.dcx().emit_warn(errors::SharedCacheStore { path: &entry_dir, err });
+            let _ = safe_remove_dir_all(&tmp_dir);
+        }
+    }
+
+    /// Releases the cache and garbage collects it, if no other session is using
+    /// it. This is called once at the end of the session, when `cache` is no
+    /// longer referenced by the global context or by codegen. If it still is,
+    /// the garbage collection is skipped.
+    pub fn close(cache: Lrc<SharedCache>, sess: &Session) {
+        let Some(SharedCache { dir, _lock }) = Lrc::into_inner(cache) else { return };
+        drop(_lock);
+
+        let _timer = sess.timer("shared_cache_garbage_collect");
+        if let Err(err) = garbage_collect(&dir, sess.opts.unstable_opts.shared_cache_max_size) {
+            sess.dcx().emit_warn(errors::SharedCacheGarbageCollect { path: &dir, err });
+        }
+    }
+}
+
+impl SharedCacheStore for SharedCache {
+    fn load_query_result(&self, key: Fingerprint) -> Option<Mmap> {
+        let entry_dir = self.entry_dir(key);
+        let file = std_fs::File::open(entry_dir.join(QUERY_RESULT_FILE_NAME)).ok()?;
+        // SAFETY: Entries are never modified once they have been published, and
+        // aren't deleted while `self` holds the lock on the cache.
+        let data = unsafe { Mmap::map(file) }.ok()?;
+        debug!(?key, "found shared cache query result");
+        mark_used(&entry_dir);
+        Some(data)
+    }
+
+    fn store_query_result(
+        &self,
+        sess: &Session,
+        key: Fingerprint,
+        encode: &mut dyn FnMut(FileEncoder) -> FileEncodeResult,
+    ) {
+        self.publish(sess, key, |tmp_dir| {
+            let encoder = FileEncoder::new(tmp_dir.join(QUERY_RESULT_FILE_NAME))?;
+            encode(encoder).map(|_| ()).map_err(|(_, err)| err)
+        });
+    }
+
+    fn load_work_product(&self, key: Fingerprint, cgu_name: &str) -> Option<WorkProduct> {
+        let entry_dir = self.entry_dir(key);
+        let mut saved_files = UnordMap::default();
+        for entry in entry_dir.read_dir().ok()? {
+            let path = entry.ok()?.path();
+            let Some(ext) =
+                path.file_name().and_then(|name| name.to_str()?.strip_prefix("module."))
+            else {
+                continue;
+            };
+            saved_files.insert(ext.to_string(), path.to_str()?.to_string());
+        }
+        debug!(?key, ?cgu_name, ?saved_files, "found shared cache entry");
+        mark_used(&entry_dir);
+        Some(WorkProduct { cgu_name: cgu_name.to_string(), saved_files })
+    }
+
+    fn store_work_product(
+        &self,
+        sess: &Session,
+        key: Fingerprint,
+        files: &[(&'static str, &Path)],
+    ) {
+        self.publish(sess, key, |tmp_dir| {
+            for (ext, path) in files {
+                link_or_copy(path, tmp_dir.join(format!("module.{ext}")))?;
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Keeps track of when the entry in `entry_dir` was last used, for the garbage
+/// collection. It doesn't matter if this fails, the entry may just be evicted
+/// earlier.
+fn mark_used(entry_dir: &Path) {
+    let _ = std_fs::File::options()
+        .write(true)
+        .create(true)
+        .open(entry_dir.join(LAST_USED_FILE_NAME))
+        .and_then(|file| file.set_modified(SystemTime::now()));
+}
+
+/// Deletes the least recently used entries of the cache in `dir` until it is
+/// no larger than `max_size` MiB.
+fn garbage_collect(dir: &Path, max_size: u64) -> io::Result<()> {
+    let Ok(_lock) = flock::Lock::new(
+        &dir.join(LOCK_FILE_NAME),
+        false, // don't wait
+        false, // don't create
+        true,  // exclusive
+    ) else {
+        debug!("shared cache is in use, skipping garbage collection");
+        return Ok(());
+    };
+
+    let now = SystemTime::now();
+    for tmp_dir in dir.join(TMP_DIR_NAME).read_dir()? {
+        let tmp_dir = tmp_dir?;
+        let modified = tmp_dir.metadata()?.modified()?;
+        if now.duration_since(modified).is_ok_and(|age| age > TMP_DIR_MAX_AGE) {
+            safe_remove_dir_all(&tmp_dir.path())?;
+        }
+    }
+
+    let mut entries = Vec::new();
+    for entry in dir.join(ENTRIES_DIR_NAME).read_dir()? {
+        let entry_dir = entry?.path();
+        let mut size = 0;
+        let mut last_used = SystemTime::UNIX_EPOCH;
+        for file in entry_dir.read_dir()? {
+            let file = file?;
+            let metadata = file.metadata()?;
+            size += metadata.len();
+            if file.file_name() == LAST_USED_FILE_NAME {
+                last_used = metadata.modified()?;
+            }
+        }
+        entries.push((last_used, size, entry_dir));
+    }
+
+    for entry_dir in entries_to_evict(entries, max_size.saturating_mul(1024 * 1024)) {
+        debug!("evicting shared cache entry `{}`", entry_dir.display());
+        safe_remove_dir_all(&entry_dir)?;
+    }
+    Ok(())
+}
+
+/// Given the last use, size and path of all entries, returns the least
+/// recently used entries that need to be removed to shrink the cache to
+/// `max_size` bytes.
+fn entries_to_evict(mut entries: Vec<(SystemTime, u64, PathBuf)>, max_size: u64) -> Vec<PathBuf> {
+    let mut size: u64 = entries.iter().map(|&(_, entry_size, _)| entry_size).sum();
+    entries.sort_unstable_by(|a, b| (a.0, &a.2).cmp(&(b.0, &b.2)));
+    entries
+        .into_iter()
+        .take_while(|&(_, entry_size, _)| {
+            let evict = size > max_size;
+            size -= entry_size;
+            evict
+        })
+        .map(|(_, _, path)| path)
+        .collect()
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+use std::time::UNIX_EPOCH;
+
+fn entry(last_used: u64, size: u64, name: &str) -> (SystemTime, u64, PathBuf) {
+    (UNIX_EPOCH + Duration::new(last_used, 0), size, PathBuf::from(name))
+}
+
+#[test]
+fn test_entries_to_evict() {
+    let entries = vec![entry(3, 10, "c"), entry(1, 10, "a"), entry(4, 10, "d"), entry(2, 10, "b")];
+
+    assert!(entries_to_evict(entries.clone(), 40).is_empty());
+    assert_eq!(entries_to_evict(entries.clone(), 35), vec![PathBuf::from("a")]);
+    assert_eq!(entries_to_evict(entries.clone(), 20), vec![PathBuf::from("a"), PathBuf::from("b")]);
+    assert_eq!(entries_to_evict(entries.clone(), 0).len(), 4);
+    assert!(entries_to_evict(Vec::new(), 0).is_empty());
+}
+
+#[test]
+fn test_entries_to_evict_large_entry() {
+    // The least recently used entries are evicted first, even if evicting a
+    // larger, more recently used one would be enough.
+    let entries = vec![entry(1, 1, "a"), entry(2, 100, "b"), entry(3, 1, "c")];
+    assert_eq!(entries_to_evict(entries, 50), vec![PathBuf::from("a"), PathBuf::from("b")]);
+}
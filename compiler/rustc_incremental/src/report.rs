@@ -0,0 +1,268 @@
+//! The report written by `-Zincremental-report`, explaining why queries were re-executed by an
+//! incremental compilation session.
+//!
+//! A query of the previous session is re-executed when one of its dependencies turned out to be
+//! red, i.e. was re-executed itself and produced a different result. Following the red
+//! dependencies backwards leads to the *root causes* of the re-execution, which are:
+//!
+//! - changed HIR nodes of items, which in turn point to the changed source files,
+//! - changed hashes of upstream crates, and
+//! - any other red node that doesn't have a red dependency, like the crate-wide HIR or queries
+//!   reading state that isn't tracked by the dep graph.
+//!
+//! For every root cause, the report counts the re-executed queries that (transitively) depend on
+//! it through red nodes other than root causes.
+
+use crate::errors;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
+use rustc_hir::def_id::{CrateNum, LocalDefId, StableCrateId, LOCAL_CRATE};
+use rustc_hir::definitions::DefPathHash;
+use rustc_middle::dep_graph::{
+    dep_kinds, DepContext, DepNode, FingerprintStyle, SerializedDepNodeIndex, DEP_KIND_NAMES,
+};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::SwitchWithOptPath;
+use serde::Serialize;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Serialize)]
+struct Report {
+    crate_name: String,
+    /// The number of nodes in the dep graph of the previous session. If this is zero, there was
+    /// no previous session, or its results could not be reused at all.
+    previous_dep_nodes: usize,
+    /// The number of queries of the previous session that were re-executed.
+    reexecuted_queries: usize,
+    changed_source_files: Vec<ChangedSourceFile>,
+    root_causes: Vec<RootCause>,
+}
+
+#[derive(Serialize)]
+struct ChangedSourceFile {
+    file: String,
+    /// The number of re-executed queries that depend on changed HIR nodes in this file.
+    reexecuted_queries: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RootCauseKind {
+    Hir,
+    UpstreamCrate,
+    Other,
+}
+
+#[derive(Serialize)]
+struct RootCause {
+    kind: RootCauseKind,
+    dep_node: String,
+    /// The source file of the changed item, for `Hir` root causes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    /// The name of the changed crate, for `UpstreamCrate` root causes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upstream_crate: Option<String>,
+    reexecuted_queries: usize,
+}
+
+/// Maps the hashes in dep nodes back to the items and crates they were computed from.
+struct KeyResolver<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    local_def_ids: FxHashMap<DefPathHash, LocalDefId>,
+    upstream_crates: FxHashMap<StableCrateId, CrateNum>,
+}
+
+impl<'tcx> KeyResolver<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>) -> Self {
+        // Dep nodes may refer to items that no longer exist, so the hashes can't be looked up
+        // with `def_path_hash_to_def_id`, which expects them to exist.
+        let local_def_ids = tcx
+            .iter_local_def_id()
+            .map(|def_id| (tcx.def_path_hash(def_id.to_def_id()), def_id))
+            .collect();
+        let upstream_crates =
+            tcx.crates(()).iter().map(|&cnum| (tcx.stable_crate_id(cnum), cnum)).collect();
+        KeyResolver { tcx, local_def_ids, upstream_crates }
+    }
+
+    fn def_path_hash(&self, node: DepNode) -> Option<DefPathHash> {
+        (self.tcx.fingerprint_style(node.kind) == FingerprintStyle::DefPathHash)
+            .then(|| DefPathHash(node.hash.into()))
+    }
+
+    fn local_def_id(&self, node: DepNode) -> Option<LocalDefId> {
+        self.local_def_ids.get(&self.def_path_hash(node)?).copied()
+    }
+
+    fn upstream_crate(&self, node: DepNode) -> Option<CrateNum> {
+        self.upstream_crates.get(&self.def_path_hash(node)?.stable_crate_id()).copied()
+    }
+
+    fn describe(&self, node: DepNode) -> String {
+        let kind = DEP_KIND_NAMES.get(node.kind.as_usize()).copied().unwrap_or("<unknown>");
+        let key = if self.tcx.fingerprint_style(node.kind) == FingerprintStyle::Unit {
+            String::new()
+        } else if let Some(def_id) = self.local_def_id(node) {
+            with_no_trimmed_paths!(self.tcx.def_path_str(def_id))
+        } else if let Some(cnum) = self.upstream_crate(node) {
+            let crate_name = self.tcx.crate_name(cnum);
+            if self.def_path_hash(node) == Some(self.tcx.def_path_hash(cnum.as_def_id())) {
+                crate_name.to_string()
+            } else {
+                format!("{crate_name}::{}", Fingerprint::from(node.hash).to_hex())
+            }
+        } else {
+            Fingerprint::from(node.hash).to_hex()
+        };
+        format!("{kind}({key})")
+    }
+
+    /// Returns the kind of root cause `node` is if it's red, regardless of its dependencies.
+    fn root_cause_kind(&self, node: DepNode) -> Option<RootCauseKind> {
+        match node.kind {
+            dep_kinds::crate_hash if self.upstream_crate(node).is_some() => {
+                Some(RootCauseKind::UpstreamCrate)
+            }
+            // The HIR of an item. The crate-wide HIR nodes, like `hir_crate`, change whenever
+            // any item does, so they are only root causes if there is no more specific one.
+            dep_kinds::opt_hir_owner_nodes
+            | dep_kinds::hir_attrs
+            | dep_kinds::hir_owner_parent
+            | dep_kinds::local_def_id_to_hir_id
+            | dep_kinds::source_span => Some(RootCauseKind::Hir),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the `-Zincremental-report` of the current session.
+pub(crate) fn write_report(tcx: TyCtxt<'_>) {
+    let SwitchWithOptPath::Enabled(ref directory) = tcx.sess.opts.unstable_opts.incremental_report
+    else {
+        return;
+    };
+    let directory = directory.as_deref().unwrap_or(Path::new("."));
+    let path = directory.join(format!("{}.incr_report.json", tcx.crate_name(LOCAL_CRATE)));
+
+    let report = build_report(tcx);
+    let write = || -> io::Result<()> {
+        fs::create_dir_all(directory)?;
+        let mut file = BufWriter::new(fs::File::create(&path)?);
+        serde_json::to_writer_pretty(&mut file, &report)?;
+        file.write_all(b"\n")?;
+        file.flush()
+    };
+    if let Err(err) = write() {
+        tcx.dcx().emit_warn(errors::WriteIncrementalReport { path: &path, err });
+    }
+}
+
+fn build_report(tcx: TyCtxt<'_>) -> Report {
+    let dep_graph = &tcx.dep_graph;
+    let crate_name = tcx.crate_name(LOCAL_CRATE).to_string();
+    let (Some(prev_graph), Some(reexecuted)) =
+        (dep_graph.previous_graph(), dep_graph.reexecuted_previous_nodes())
+    else {
+        return Report {
+            crate_name,
+            previous_dep_nodes: 0,
+            reexecuted_queries: 0,
+            changed_source_files: Vec::new(),
+            root_causes: Vec::new(),
+        };
+    };
+    let resolver = KeyResolver::new(tcx);
+    let is_red = |i| dep_graph.is_previous_node_red(i);
+
+    // The re-executed nodes that depend on each red node.
+    let mut dependents: FxHashMap<SerializedDepNodeIndex, Vec<SerializedDepNodeIndex>> =
+        Default::default();
+    for &i in &reexecuted {
+        for dep in prev_graph.edge_targets_from(i).filter(|&dep| is_red(dep)) {
+            dependents.entry(dep).or_default().push(i);
+        }
+    }
+
+    let mut root_causes: FxIndexMap<SerializedDepNodeIndex, RootCauseKind> = Default::default();
+    for i in (0..prev_graph.node_count()).map(SerializedDepNodeIndex::from_usize) {
+        if !is_red(i) {
+            continue;
+        }
+        let kind = resolver.root_cause_kind(prev_graph.index_to_node(i)).or_else(|| {
+            prev_graph.edge_targets_from(i).all(|dep| !is_red(dep)).then_some(RootCauseKind::Other)
+        });
+        if let Some(kind) = kind {
+            root_causes.insert(i, kind);
+        }
+    }
+
+    // Find the re-executed nodes caused by each root cause, without passing through other
+    // root causes.
+    let mut files: FxIndexMap<String, FxHashSet<SerializedDepNodeIndex>> = Default::default();
+    let mut report_root_causes = Vec::new();
+    for (&root_cause, &kind) in &root_causes {
+        let mut invalidated = FxHashSet::default();
+        let mut stack = vec![root_cause];
+        while let Some(i) = stack.pop() {
+            for &dependent in dependents.get(&i).into_iter().flatten() {
+                if !root_causes.contains_key(&dependent)
+                    && invalidated.insert(dependent)
+                    && is_red(dependent)
+                {
+                    stack.push(dependent);
+                }
+            }
+        }
+
+        let node = prev_graph.index_to_node(root_cause);
+        let file = match kind {
+            RootCauseKind::Hir => resolver.local_def_id(node).map(|def_id| {
+                let span = tcx.source_span_untracked(def_id);
+                tcx.sess.source_map().span_to_filename(span).prefer_local().to_string()
+            }),
+            _ => None,
+        };
+        let upstream_crate = match kind {
+            RootCauseKind::UpstreamCrate => {
+                resolver.upstream_crate(node).map(|cnum| tcx.crate_name(cnum).to_string())
+            }
+            _ => None,
+        };
+        if let Some(file) = &file {
+            files.entry(file.clone()).or_default().extend(invalidated.iter().copied());
+        }
+        report_root_causes.push(RootCause {
+            kind,
+            dep_node: resolver.describe(node),
+            file,
+            upstream_crate,
+            reexecuted_queries: invalidated.len(),
+        });
+    }
+    report_root_causes.sort_by(|a, b| {
+        b.reexecuted_queries.cmp(&a.reexecuted_queries).then_with(|| a.dep_node.cmp(&b.dep_node))
+    });
+
+    let mut changed_source_files: Vec<_> = files
+        .into_iter()
+        .map(|(file, invalidated)| ChangedSourceFile {
+            file,
+            reexecuted_queries: invalidated.len(),
+        })
+        .collect();
+    changed_source_files.sort_by(|a, b| {
+        b.reexecuted_queries.cmp(&a.reexecuted_queries).then_with(|| a.file.cmp(&b.file))
+    });
+
+    Report {
+        crate_name,
+        previous_dep_nodes: prev_graph.node_count(),
+        reexecuted_queries: reexecuted.len(),
+        changed_source_files,
+        root_causes: report_root_causes,
+    }
+}
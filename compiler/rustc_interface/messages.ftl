@@ -1,6 +1,12 @@
 interface_cant_emit_mir =
     could not emit MIR: {$error}
 
+interface_cant_load_lint_plugin =
+    couldn't load lint plugin {$error}
+
+interface_cant_read_lint_config =
+    couldn't read lint configuration file `{$path}`: {$error}
+
 interface_emoji_identifier =
     identifiers cannot contain emoji: `{$ident}`
 
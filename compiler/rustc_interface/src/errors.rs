@@ -63,6 +63,19 @@ pub struct CantEmitMIR {
     pub error: io::Error,
 }
 
+#[derive(Diagnostic)]
+#[diag(interface_cant_load_lint_plugin)]
+pub struct CantLoadLintPlugin {
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(interface_cant_read_lint_config)]
+pub struct CantReadLintConfig<'a> {
+    pub path: &'a Path,
+    pub error: io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(interface_rustc_error_fatal)]
 pub struct RustcErrorFatal {
@@ -1,3 +1,4 @@
+use crate::errors;
 use crate::util;
 
 use rustc_ast::token;
@@ -7,10 +8,11 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::jobserver;
 use rustc_data_structures::stable_hasher::StableHasher;
-use rustc_data_structures::sync::Lrc;
+use rustc_data_structures::sync::{Lrc, OnceLock};
 use rustc_errors::registry::Registry;
 use rustc_errors::{DiagCtxt, ErrorGuaranteed};
-use rustc_lint::LintStore;
+use rustc_incremental::SharedCache;
+use rustc_lint::{LintConfig, LintStore};
 use rustc_middle::ty;
 use rustc_middle::ty::CurrentGcx;
 use rustc_middle::util::Providers;
@@ -22,9 +24,10 @@
 use rustc_session::parse::ParseSess;
 use rustc_session::{lint, CompilerIO, EarlyDiagCtxt, Session};
 use rustc_span::source_map::{FileLoader, RealFileLoader, SourceMapInputs};
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{sym, Symbol};
 use rustc_span::FileName;
-use std::path::PathBuf;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
 use std::result;
 use std::sync::Arc;
 use tracing::trace;
@@ -43,6 +46,9 @@ pub struct Compiler {
     pub codegen_backend: Box<dyn CodegenBackend>,
     pub(crate) override_queries: Option<fn(&Session, &mut Providers)>,
     pub(crate) current_gcx: CurrentGcx,
+    /// The cache given with `-Zshared-cache`, which is opened along with the
+    /// global context and garbage collected at the end of the session.
+    pub(crate) shared_cache: OnceLock<Option<Lrc<SharedCache>>>,
 }
 
 /// Converts strings provided as `--cfg [cfgspec]` into a `Cfg`.
@@ -478,13 +484,55 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                 register_lints(&sess, &mut lint_store);
                 sess.registered_lints = true;
             }
+            for path in &sess.opts.unstable_opts.lint_plugin {
+                let path = Path::new(path);
+                // The contents of the plugin are part of the untracked state, so that
+                // rebuilding it invalidates the incremental cache, like changing its path does.
+                let contents = std::fs::read(path).unwrap_or_else(|err| {
+                    sess.dcx().emit_fatal(errors::CantLoadLintPlugin {
+                        error: format!("{}: {err}", path.display()),
+                    })
+                });
+                let mut hasher = StableHasher::new();
+                sess.opts.untracked_state_hash.hash(&mut hasher);
+                contents.hash(&mut hasher);
+                sess.opts.untracked_state_hash = hasher.finish();
+                sess.psess
+                    .file_depinfo
+                    .borrow_mut()
+                    .insert(Symbol::intern(&path.to_string_lossy()));
+                match util::load_lint_plugin(path) {
+                    Ok(register_plugin_lints) => register_plugin_lints(&sess, &mut lint_store),
+                    Err(error) => sess.dcx().emit_fatal(errors::CantLoadLintPlugin { error }),
+                }
+            }
+            if !sess.opts.unstable_opts.lint_plugin.is_empty() {
+                sess.registered_lints = true;
+            }
+            if let Some(path) = &sess.opts.unstable_opts.lint_config {
+                let lint_config = LintConfig::read(path).unwrap_or_else(|error| {
+                    sess.dcx().emit_fatal(errors::CantReadLintConfig { path, error })
+                });
+                // The levels are part of the untracked state, so that editing the file
+                // invalidates the incremental cache like changing the lint flags does.
+                let mut hasher = StableHasher::new();
+                sess.opts.untracked_state_hash.hash(&mut hasher);
+                lint_config.hash(&mut hasher);
+                sess.opts.untracked_state_hash = hasher.finish();
+                sess.psess
+                    .file_depinfo
+                    .borrow_mut()
+                    .insert(Symbol::intern(&path.to_string_lossy()));
+                lint_store.set_lint_config(lint_config);
+            }
             sess.lint_store = Some(Lrc::new(lint_store));
 
-            let compiler = Compiler {
+            let mut compiler = Compiler {
                 sess,
                 codegen_backend,
                 override_queries: config.override_queries,
                 current_gcx,
+                shared_cache: OnceLock::new(),
             };
 
             // There are two paths out of `f`.
@@ -502,6 +550,12 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
 
                 let res = f(&compiler);
 
+                // Both the global context and codegen are done with the shared
+                // cache by now, so it can be garbage collected.
+                if let Some(shared_cache) = compiler.shared_cache.take().flatten() {
+                    SharedCache::close(shared_cache, &compiler.sess);
+                }
+
                 // If `f` doesn't panic, `finish_diagnostics` will run
                 // normally when `sess_abort_guard` is dropped.
                 drop(sess_abort_guard);
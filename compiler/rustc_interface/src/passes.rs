@@ -662,6 +662,12 @@ pub fn create_global_ctxt<'tcx>(
     }
 
     let incremental = dep_graph.is_fully_enabled();
+    // With incremental compilation, query results are cached by the dep graph instead.
+    let shared_cache = if incremental {
+        None
+    } else {
+        compiler.shared_cache.get_or_init(|| rustc_incremental::SharedCache::open(sess)).clone()
+    };
 
     sess.time("setup_global_ctxt", || {
         gcx_cell.get_or_init(move || {
@@ -678,6 +684,7 @@ pub fn create_global_ctxt<'tcx>(
                     providers.queries,
                     providers.extern_queries,
                     query_result_on_disk_cache,
+                    shared_cache.map(|cache| cache as Lrc<_>),
                     incremental,
                 ),
                 providers.hooks,
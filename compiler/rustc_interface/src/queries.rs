@@ -15,6 +15,7 @@
 use rustc_middle::arena::Arena;
 use rustc_middle::dep_graph::DepGraph;
 use rustc_middle::ty::{GlobalCtxt, TyCtxt};
+use rustc_monomorphize::ItemTimeReport;
 use rustc_serialize::opaque::FileEncodeResult;
 use rustc_session::config::{self, CrateType, OutputFilenames, OutputType};
 use rustc_session::cstore::Untracked;
@@ -233,6 +234,7 @@ pub fn codegen_and_build_linker(&'tcx self) -> Result<Linker> {
             Self::check_for_rustc_errors_attr(tcx);
 
             let ongoing_codegen = passes::start_codegen(&*self.compiler.codegen_backend, tcx);
+            let item_time_report = rustc_monomorphize::item_time_report(tcx);
 
             Ok(Linker {
                 dep_graph: tcx.dep_graph.clone(),
@@ -243,6 +245,7 @@ pub fn codegen_and_build_linker(&'tcx self) -> Result<Linker> {
                     None
                 },
                 ongoing_codegen,
+                item_time_report,
             })
         })
     }
@@ -254,6 +257,7 @@ pub struct Linker {
     // Only present when incr. comp. is enabled.
     crate_hash: Option<Svh>,
     ongoing_codegen: Box<dyn Any>,
+    item_time_report: Option<ItemTimeReport>,
 }
 
 impl Linker {
@@ -265,6 +269,10 @@ pub fn link(self, sess: &Session, codegen_backend: &dyn CodegenBackend) -> Resul
             return Err(guar);
         }
 
+        if let Some(item_time_report) = self.item_time_report {
+            sess.time("write_item_time_report", || item_time_report.write(sess));
+        }
+
         sess.time("serialize_work_products", || {
             rustc_incremental::save_work_product_index(sess, &self.dep_graph, work_products)
         });
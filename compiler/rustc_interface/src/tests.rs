@@ -677,6 +677,8 @@ macro_rules! untracked {
 
     // Make sure that changing an [UNTRACKED] option leaves the hash unchanged.
     // tidy-alphabetical-start
+    untracked!(apply_suggestions, Some(PathBuf::from("fixed")));
+    untracked!(apply_suggestions_diff, true);
     untracked!(assert_incr_state, Some(String::from("loaded")));
     untracked!(deduplicate_diagnostics, false);
     untracked!(dump_dep_graph, true);
@@ -685,6 +687,7 @@ macro_rules! untracked {
     untracked!(dump_mir_dir, String::from("abc"));
     untracked!(dump_mir_exclude_pass_number, true);
     untracked!(dump_mir_graphviz, true);
+    untracked!(dump_mono_bloat, SwitchWithOptPath::Enabled(Some("mono-bloat-dir/".into())));
     untracked!(dump_mono_stats, SwitchWithOptPath::Enabled(Some("mono-items-dir/".into())));
     untracked!(dump_mono_stats_format, DumpMonoStatsFormat::Json);
     untracked!(dylib_lto, true);
@@ -695,7 +698,9 @@ macro_rules! untracked {
     untracked!(incremental_info, true);
     untracked!(incremental_verify_ich, true);
     untracked!(input_stats, true);
+    untracked!(item_time_report, SwitchWithOptPath::Enabled(None));
     untracked!(link_native_libraries, false);
+    untracked!(lint_baseline, Some(PathBuf::from("abc")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, vec!["all".to_owned()]);
     untracked!(macro_backtrace, true);
@@ -717,7 +722,11 @@ macro_rules! untracked {
     untracked!(profile_closures, true);
     untracked!(query_dep_graph, true);
     untracked!(self_profile, SwitchWithOptPath::Enabled(None));
+    untracked!(self_profile_chrome_trace, true);
     untracked!(self_profile_events, Some(vec![String::new()]));
+    untracked!(self_profile_summary, true);
+    untracked!(shared_cache, Some(PathBuf::from("abc")));
+    untracked!(shared_cache_max_size, 1);
     untracked!(shell_argfiles, true);
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
@@ -733,6 +742,7 @@ macro_rules! untracked {
     untracked!(unpretty, Some("expanded".to_string()));
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
+    untracked!(write_lint_baseline, Some(PathBuf::from("abc")));
     untracked!(write_long_types_to_disk, false);
     // tidy-alphabetical-end
 
@@ -778,6 +788,7 @@ macro_rules! tracked {
     tracked!(fuel, Some(("abc".to_string(), 99)));
     tracked!(function_return, FunctionReturn::ThunkExtern);
     tracked!(function_sections, Some(false));
+    tracked!(future_size_limit, Some(4096));
     tracked!(human_readable_cgu_names, true);
     tracked!(incremental_ignore_spans, true);
     tracked!(inline_in_all_cgus, Some(true));
@@ -865,6 +876,8 @@ macro_rules! tracked_no_crate_hash {
             assert_non_crate_hash_different(&reference, &opts);
         };
     }
+    tracked_no_crate_hash!(lint_config, Some(PathBuf::from("lints.json")));
+    tracked_no_crate_hash!(lint_plugin, vec![String::from("abc"), String::from("def")]);
     tracked_no_crate_hash!(no_codegen, true);
     tracked_no_crate_hash!(verbose_internals, true);
 }
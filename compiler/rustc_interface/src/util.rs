@@ -3,6 +3,7 @@
 use rustc_codegen_ssa::traits::CodegenBackend;
 #[cfg(parallel_compiler)]
 use rustc_data_structures::sync;
+use rustc_lint::LintStore;
 use rustc_metadata::{load_symbol_from_dylib, DylibError};
 use rustc_middle::ty::CurrentGcx;
 use rustc_parse::validate_attr;
@@ -27,6 +28,9 @@
 /// Function pointer type that constructs a new CodegenBackend.
 pub type MakeBackendFn = fn() -> Box<dyn CodegenBackend>;
 
+/// Function pointer type that registers the lints of a lint plugin, see [`load_lint_plugin`].
+pub type LintPluginFn = fn(&Session, &mut LintStore);
+
 /// Adds `target_feature = "..."` cfgs for a variety of platform
 /// specific features (SSE, NEON etc.).
 ///
@@ -235,6 +239,33 @@ fn load_backend_from_dylib(early_dcx: &EarlyDiagCtxt, path: &Path) -> MakeBacken
     }
 }
 
+/// Loads a lint plugin, i.e. a dynamic library built against this compiler that exports a
+/// [`LintPluginFn`] named `__rustc_lint_plugin`, which registers the lints of the plugin:
+///
+/// ```ignore (illustrative)
+/// #[no_mangle]
+/// pub fn __rustc_lint_plugin(_sess: &Session, lint_store: &mut LintStore) {
+///     lint_store.register_lint_tool(Symbol::intern("my_tool"));
+///     lint_store.register_lints(&[MY_LINT]);
+///     lint_store.register_early_pass(|| Box::new(MyLintPass));
+/// }
+/// ```
+///
+/// The plugins passed with `-Z lint-plugin` are loaded by [`run_compiler`], and drivers can
+/// load the plugins they find themselves in [`Config::register_lints`].
+///
+/// [`run_compiler`]: crate::run_compiler
+/// [`Config::register_lints`]: crate::Config::register_lints
+pub fn load_lint_plugin(path: &Path) -> Result<LintPluginFn, String> {
+    match unsafe { load_symbol_from_dylib::<LintPluginFn>(path, "__rustc_lint_plugin") } {
+        Ok(plugin_sym) => Ok(plugin_sym),
+        Err(DylibError::DlOpen(path, err)) => Err(format!("{path}{err}")),
+        Err(DylibError::DlSym(path, err)) => {
+            Err(format!("{path}: `__rustc_lint_plugin` symbol lookup failed{err}"))
+        }
+    }
+}
+
 /// Get the codegen backend based on the name and specified sysroot.
 ///
 /// A name of `None` indicates that the default backend should be used.
@@ -434,9 +434,22 @@ lint_invalid_reference_casting_note_book = for more information, visit <https://
 
 lint_invalid_reference_casting_note_ty_has_interior_mutability = even for types with interior mutability, the only legal way to obtain a mutable pointer from a shared reference is through `UnsafeCell::get`
 
+lint_large_future = this future is {$size} bytes, more than the limit of {$limit} bytes
+    .help = move large values to the heap, e.g. with `Box`, or drop them before the `.await`, and box large awaited futures with `Box::pin`
+
+lint_large_future_await_point = held across this `.await`
+
+lint_large_future_held_awaitee = this awaited future takes {$size} bytes
+
+lint_large_future_held_local = `{$name}` takes {$size} bytes
+
+lint_large_future_held_temporary = this temporary value takes {$size} bytes
+
 lint_legacy_derive_helpers = derive helper attribute is used before it is introduced
     .label = the attribute is introduced here
 
+lint_lint_config_source = `forbid` lint level was set in the lint configuration file
+
 lint_lintpass_by_hand = implementing `LintPass` by hand
     .help = try using `declare_lint_pass!` or `impl_lint_pass!` instead
 
@@ -685,6 +698,7 @@ lint_renamed_lint = lint `{$name}` has been renamed to `{$replace}`
     .help = use the new name `{$replace}`
 
 lint_requested_level = requested on the command line with `{$level} {$lint_name}`
+lint_requested_level_in_lint_config = requested in the lint configuration file with `"{$lint_name}": "{$level}"`
 
 lint_reserved_prefix = prefix `{$prefix}` is unknown
     .label = unknown prefix
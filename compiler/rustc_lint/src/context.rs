@@ -17,10 +17,11 @@
 use self::TargetLint::*;
 
 use crate::levels::LintLevelsBuilder;
+use crate::lint_config::LintConfig;
 use crate::passes::{EarlyLintPassObject, LateLintPassObject};
 use rustc_data_structures::fx::FxIndexMap;
 use rustc_data_structures::sync;
-use rustc_data_structures::unord::UnordMap;
+use rustc_data_structures::unord::{UnordMap, UnordSet};
 use rustc_errors::{Diag, DiagMessage, LintDiagnostic, MultiSpan};
 use rustc_feature::Features;
 use rustc_hir as hir;
@@ -75,6 +76,13 @@ pub struct LintStore {
 
     /// Map of registered lint groups to what lints they expand to.
     lint_groups: FxIndexMap<&'static str, LintGroup>,
+
+    /// Tools whose lints can be used in lint attributes, e.g. `#[allow(tool::lint)]`, in
+    /// addition to the tools registered with `#![register_tool]`.
+    lint_tools: UnordSet<Symbol>,
+
+    /// Lint levels from the file passed to `-Zlint-config`.
+    lint_config: Option<LintConfig>,
 }
 
 impl LintStoreMarker for LintStore {}
@@ -144,6 +152,8 @@ pub fn new() -> LintStore {
             late_module_passes: vec![],
             by_name: Default::default(),
             lint_groups: Default::default(),
+            lint_tools: Default::default(),
+            lint_config: None,
         }
     }
 
@@ -305,6 +315,22 @@ pub fn register_removed(&mut self, name: &str, reason: &str) {
         self.by_name.insert(name.into(), Removed(reason.into()));
     }
 
+    /// Allows the lints of `tool`, declared with `declare_tool_lint!`, to be used in lint
+    /// attributes and on the command line without `#![register_tool(tool)]`.
+    pub fn register_lint_tool(&mut self, tool: Symbol) {
+        self.lint_tools.insert(tool);
+    }
+
+    /// Sets the lint levels of a configuration file, which apply in addition to the command
+    /// line and attributes.
+    pub fn set_lint_config(&mut self, config: LintConfig) {
+        self.lint_config = Some(config);
+    }
+
+    pub(crate) fn lint_config(&self) -> Option<&LintConfig> {
+        self.lint_config.as_ref()
+    }
+
     pub fn find_lints(&self, mut lint_name: &str) -> Result<Vec<LintId>, FindLintError> {
         match self.by_name.get(lint_name) {
             Some(&Id(lint_id)) => Ok(vec![lint_id]),
@@ -358,6 +384,7 @@ pub fn check_lint_name(
             if tool_name != sym::rustc
                 && tool_name != sym::rustdoc
                 && !registered_tools.contains(&Ident::with_dummy_span(tool_name))
+                && !self.lint_tools.contains(&tool_name)
             {
                 return CheckLintNameResult::NoTool;
             }
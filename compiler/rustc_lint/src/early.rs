@@ -61,10 +61,25 @@ fn check_id(&mut self, id: ast::NodeId) {
     fn with_lint_attrs<F>(&mut self, id: ast::NodeId, attrs: &'a [ast::Attribute], f: F)
     where
         F: FnOnce(&mut Self),
+    {
+        self.with_module_lint_attrs(id, attrs, None, f)
+    }
+
+    /// Like `with_lint_attrs`, but also applies the levels of the lint configuration file for
+    /// the source file of the crate root or of an out-of-line module, whose contents are at
+    /// `inner_span`.
+    fn with_module_lint_attrs<F>(
+        &mut self,
+        id: ast::NodeId,
+        attrs: &'a [ast::Attribute],
+        inner_span: Option<Span>,
+        f: F,
+    ) where
+        F: FnOnce(&mut Self),
     {
         let is_crate_node = id == ast::CRATE_NODE_ID;
         debug!(?id);
-        let push = self.context.builder.push(attrs, is_crate_node, None);
+        let push = self.context.builder.push(attrs, is_crate_node, None, inner_span);
 
         self.inlined_check_id(id);
         debug!("early context: enter_attrs({:?})", attrs);
@@ -85,7 +100,13 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
-        self.with_lint_attrs(it.id, &it.attrs, |cx| {
+        let inner_span = match &it.kind {
+            ast::ItemKind::Mod(_, ast::ModKind::Loaded(_, ast::Inline::No, spans)) => {
+                Some(spans.inner_span)
+            }
+            _ => None,
+        };
+        self.with_module_lint_attrs(it.id, &it.attrs, inner_span, |cx| {
             lint_callback!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             lint_callback!(cx, check_item_post, it);
@@ -334,6 +355,9 @@ pub trait EarlyCheckNode<'a>: Copy {
     fn attrs<'b>(self) -> &'b [ast::Attribute]
     where
         'a: 'b;
+    /// The span of the contents of the node, if the levels of its source file in the lint
+    /// configuration file apply to it.
+    fn inner_span(self) -> Option<Span>;
     fn check<'b, T: EarlyLintPass>(self, cx: &mut EarlyContextAndPass<'b, T>)
     where
         'a: 'b;
@@ -349,6 +373,9 @@ fn attrs<'b>(self) -> &'b [ast::Attribute]
     {
         self.1
     }
+    fn inner_span(self) -> Option<Span> {
+        Some(self.0.spans.inner_span)
+    }
     fn check<'b, T: EarlyLintPass>(self, cx: &mut EarlyContextAndPass<'b, T>)
     where
         'a: 'b,
@@ -369,6 +396,9 @@ fn attrs<'b>(self) -> &'b [ast::Attribute]
     {
         self.1
     }
+    fn inner_span(self) -> Option<Span> {
+        None
+    }
     fn check<'b, T: EarlyLintPass>(self, cx: &mut EarlyContextAndPass<'b, T>)
     where
         'a: 'b,
@@ -420,7 +450,9 @@ pub fn check_ast_node_inner<'a, T: EarlyLintPass>(
 ) {
     let mut cx = EarlyContextAndPass { context, pass };
 
-    cx.with_lint_attrs(check_node.id(), check_node.attrs(), |cx| check_node.check(cx));
+    cx.with_module_lint_attrs(check_node.id(), check_node.attrs(), check_node.inner_span(), |cx| {
+        check_node.check(cx)
+    });
 
     // All of the buffered lints should have been emitted at this point.
     // If not, that means that we somehow buffered a lint for a node id
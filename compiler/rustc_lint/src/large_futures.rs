@@ -0,0 +1,158 @@
+use crate::lints::{LargeFuture, LargeFutureHeldValue};
+use crate::{fluent_generated as fluent, LateContext, LateLintPass, LintContext};
+use rustc_errors::MultiSpan;
+use rustc_hir as hir;
+use rustc_middle::ty::{self, EarlyBinder};
+use rustc_session::lint::Level;
+use rustc_session::{declare_lint, declare_lint_pass};
+use rustc_span::symbol::sym;
+
+declare_lint! {
+    /// The `large_futures` lint detects `async` blocks and functions whose
+    /// future is larger than a size limit.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,edition2021
+    /// #![warn(large_futures)]
+    ///
+    /// async fn yield_now() {}
+    ///
+    /// async fn process() {
+    ///     let buffer = [0u8; 32 * 1024];
+    ///     yield_now().await;
+    ///     println!("{}", buffer[0]);
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// The future of an `async` block or function stores every value that
+    /// is alive across an `.await`, and futures are often moved or stored
+    /// on the stack, e.g. while they are polled by an executor. Large futures
+    /// can therefore cause stack overflows, which are hard to track down
+    /// because the size of a future isn't visible in the source.
+    ///
+    /// The lint reports futures larger than 16384 bytes, a limit that can be
+    /// changed with `-Z future-size-limit=<bytes>`, and points out the values
+    /// that contribute most to the size, together with the `.await` points
+    /// they are held across. To make a future smaller, move the large values
+    /// to the heap, e.g. with `Box`, or drop them before the `.await`. Large
+    /// futures that are awaited can be boxed with `Box::pin`.
+    pub LARGE_FUTURES,
+    Allow,
+    "detects futures larger than a size limit"
+}
+
+declare_lint_pass!(LargeFutures => [LARGE_FUTURES]);
+
+/// The default of `-Zfuture-size-limit`.
+const DEFAULT_FUTURE_SIZE_LIMIT: u64 = 16 * 1024;
+
+/// The number of held values that are reported for a large future.
+const REPORTED_HELD_VALUES: usize = 3;
+
+/// Held values that take less than this fraction of a future aren't reported.
+const REPORTED_HELD_VALUE_FRACTION: u64 = 10;
+
+impl<'tcx> LateLintPass<'tcx> for LargeFutures {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        let hir::ExprKind::Closure(&hir::Closure {
+            def_id,
+            kind:
+                hir::ClosureKind::Coroutine(hir::CoroutineKind::Desugared(
+                    hir::CoroutineDesugaring::Async,
+                    source,
+                )),
+            ..
+        }) = expr.kind
+        else {
+            return;
+        };
+        // The lint is allowed by default, so avoid computing the layout of every future.
+        let (level, _) = cx.tcx.lint_level_at_node(LARGE_FUTURES, expr.hir_id);
+        if level == Level::Allow {
+            return;
+        }
+        if cx.typeck_results().tainted_by_errors.is_some() {
+            return;
+        }
+        let ty = cx.tcx.erase_regions(cx.typeck_results().expr_ty(expr));
+        let ty::Coroutine(coroutine_def_id, args) = *ty.kind() else { return };
+
+        // The size of generic futures depends on their parameters.
+        let Ok(layout) = cx.layout_of(ty) else { return };
+        let future_size = layout.size.bytes();
+        let limit = cx
+            .sess()
+            .opts
+            .unstable_opts
+            .future_size_limit
+            .map_or(DEFAULT_FUTURE_SIZE_LIMIT, |limit| limit as u64);
+        if future_size <= limit {
+            return;
+        }
+
+        let Some(coroutine_layout) =
+            cx.tcx.coroutine_layout(coroutine_def_id, args.as_coroutine().kind_ty())
+        else {
+            return;
+        };
+        let mut held_values: Vec<_> = coroutine_layout
+            .field_tys
+            .iter_enumerated()
+            .filter_map(|(local, saved)| {
+                let field_ty = EarlyBinder::bind(saved.ty).instantiate(cx.tcx, args);
+                let size = cx.layout_of(cx.tcx.erase_regions(field_ty)).ok()?.size.bytes();
+                let await_spans: Vec<_> = coroutine_layout
+                    .variant_fields
+                    .iter_enumerated()
+                    .filter(|(_, fields)| fields.iter().any(|&field| field == local))
+                    .map(|(variant, _)| coroutine_layout.variant_source_info[variant].span)
+                    .collect();
+                let reported = size * REPORTED_HELD_VALUE_FRACTION >= future_size;
+                (reported && !await_spans.is_empty()).then_some((
+                    size,
+                    saved.source_info.span,
+                    coroutine_layout.field_names[local],
+                    await_spans,
+                ))
+            })
+            .collect();
+        // The largest values first, in source order if they have the same size.
+        held_values.sort_by_key(|&(size, span, ..)| (std::cmp::Reverse(size), span));
+
+        let held_values = held_values
+            .into_iter()
+            .take(REPORTED_HELD_VALUES)
+            .map(|(size, span, name, await_spans)| {
+                let mut spans = MultiSpan::from_span(span);
+                // An awaited future is held across its own `.await`, which is already in its span.
+                for await_span in await_spans.into_iter().filter(|&s| !span.contains(s)) {
+                    spans.push_span_label(await_span, fluent::lint_large_future_await_point);
+                }
+                match name {
+                    Some(sym::__awaitee) => LargeFutureHeldValue::Awaitee { spans, size },
+                    Some(name) => LargeFutureHeldValue::Local { spans, name, size },
+                    None => LargeFutureHeldValue::Temporary { spans, size },
+                }
+            })
+            .collect();
+
+        let span = match source {
+            // Point at the signature rather than the whole body of `async fn`s.
+            hir::CoroutineSource::Fn => cx.tcx.def_span(cx.tcx.local_parent(def_id)),
+            hir::CoroutineSource::Block | hir::CoroutineSource::Closure => {
+                cx.sess().source_map().guess_head_span(expr.span)
+            }
+        };
+        cx.emit_span_lint(
+            LARGE_FUTURES,
+            span,
+            LargeFuture { size: future_size, limit, held_values },
+        );
+    }
+}
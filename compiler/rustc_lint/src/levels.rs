@@ -175,6 +175,7 @@ fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> ShallowLintLe
     if owner == hir::CRATE_OWNER_ID {
         levels.add_command_line();
     }
+    levels.add_owner_file_config(owner);
 
     match attrs.map.range(..) {
         // There is only something to do if there are attributes at all.
@@ -297,6 +298,31 @@ fn push_expectation(&mut self, id: LintExpectationId, expectation: LintExpectati
 }
 
 impl<'tcx> LintLevelsBuilder<'_, LintLevelQueryMap<'tcx>> {
+    /// Applies the levels of the lint configuration file to `owner` if it is the crate root or
+    /// a module with its own source file.
+    fn add_owner_file_config(&mut self, owner: hir::OwnerId) {
+        if self.store.lint_config().is_none() {
+            return;
+        }
+        let tcx = self.provider.tcx;
+        let inner_span = match tcx.hir_owner_node(owner) {
+            hir::OwnerNode::Crate(mod_) => mod_.spans.inner_span,
+            hir::OwnerNode::Item(&hir::Item { kind: hir::ItemKind::Mod(mod_), span, .. }) => {
+                let sm = tcx.sess.source_map();
+                let inner_span = mod_.spans.inner_span;
+                if sm.lookup_source_file_idx(inner_span.lo())
+                    == sm.lookup_source_file_idx(span.lo())
+                {
+                    // Inline modules share the levels of their parent module.
+                    return;
+                }
+                inner_span
+            }
+            _ => return,
+        };
+        self.add_file_config(inner_span);
+    }
+
     fn add_id(&mut self, hir_id: HirId) {
         self.provider.cur = hir_id;
         self.add(
@@ -507,17 +533,25 @@ fn process_command_line(&mut self) {
     /// * Lint attributes are validated, e.g., a `#[forbid]` can't be switched to
     ///   `#[allow]`
     ///
+    /// For the crate root and out-of-line modules, `inner_span` is the span of their contents,
+    /// and the levels of the lint configuration file for their source file are applied before
+    /// the attributes.
+    ///
     /// Don't forget to call `pop`!
     pub(crate) fn push(
         &mut self,
         attrs: &[ast::Attribute],
         is_crate_node: bool,
         source_hir_id: Option<HirId>,
+        inner_span: Option<Span>,
     ) -> BuilderPush {
         let prev = self.provider.cur;
         self.provider.cur =
             self.provider.sets.list.push(LintSet { specs: FxIndexMap::default(), parent: prev });
 
+        if let Some(inner_span) = inner_span {
+            self.add_file_config(inner_span);
+        }
         self.add(attrs, is_crate_node, source_hir_id);
 
         if self.provider.current_specs().is_empty() {
@@ -560,9 +594,12 @@ fn insert(&mut self, id: LintId, lvl: LevelAndSource) {
     }
 
     fn add_command_line(&mut self) {
+        if let Some(config) = self.store.lint_config() {
+            self.add_config_levels(config.crate_levels());
+        }
+
         for &(ref lint_name, level) in &self.sess.opts.lint_opts {
-            // Checks the validity of lint names derived from the command line.
-            let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
+            let (_, lint_name_only) = parse_lint_and_tool_name(lint_name);
             if lint_name_only == crate::WARNINGS.name_lower()
                 && matches!(level, Level::ForceWarn(_))
             {
@@ -570,51 +607,13 @@ fn add_command_line(&mut self) {
                     .dcx()
                     .emit_err(UnsupportedGroup { lint_group: crate::WARNINGS.name_lower() });
             }
-            match self.store.check_lint_name(lint_name_only, tool_name, self.registered_tools) {
-                CheckLintNameResult::Renamed(ref replace) => {
-                    let name = lint_name.as_str();
-                    let suggestion = RenamedLintSuggestion::WithoutSpan { replace };
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = RenamedLintFromCommandLine { name, suggestion, requested_level };
-                    self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
-                }
-                CheckLintNameResult::Removed(ref reason) => {
-                    let name = lint_name.as_str();
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = RemovedLintFromCommandLine { name, reason, requested_level };
-                    self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
-                }
-                CheckLintNameResult::NoLint(suggestion) => {
-                    let name = lint_name.clone();
-                    let suggestion = suggestion.map(|(replace, from_rustc)| {
-                        UnknownLintSuggestion::WithoutSpan { replace, from_rustc }
-                    });
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = UnknownLintFromCommandLine { name, suggestion, requested_level };
-                    self.emit_lint(UNKNOWN_LINTS, lint);
-                }
-                CheckLintNameResult::Tool(Err((Some(_), ref replace))) => {
-                    let name = lint_name.clone();
-                    let requested_level = RequestedLevel { level, lint_name };
-                    let lint = DeprecatedLintNameFromCommandLine { name, replace, requested_level };
-                    self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
-                }
-                CheckLintNameResult::NoTool => {
-                    self.sess.dcx().emit_err(CheckNameUnknownTool {
-                        tool_name: tool_name.unwrap(),
-                        sub: RequestedLevel { level, lint_name },
-                    });
-                }
-                _ => {}
-            };
 
-            let orig_level = level;
-            let lint_flag_val = Symbol::intern(lint_name);
-
-            let Ok(ids) = self.store.find_lints(lint_name) else {
-                // errors already handled above
+            let requested_level = RequestedLevel::CommandLine { level, lint_name };
+            let Some(ids) = self.check_requested_lint_name(lint_name, requested_level) else {
+                // errors already emitted by `check_requested_lint_name`
                 continue;
             };
+            let src = LintLevelSource::CommandLine(Symbol::intern(lint_name), level);
             for id in ids {
                 // ForceWarn and Forbid cannot be overridden
                 if let Some((Level::ForceWarn(_) | Level::Forbid, _)) =
@@ -624,13 +623,95 @@ fn add_command_line(&mut self) {
                 }
 
                 if self.check_gated_lint(id, DUMMY_SP, true) {
-                    let src = LintLevelSource::CommandLine(lint_flag_val, orig_level);
                     self.insert(id, (level, src));
                 }
             }
         }
     }
 
+    /// Applies levels from the lint configuration file. Lint groups are applied before single
+    /// lints, so that the level of a lint takes precedence over the level of its groups.
+    fn add_config_levels(&mut self, levels: &'s [(String, Level)]) {
+        let (groups, lints): (Vec<_>, Vec<_>) = levels
+            .iter()
+            .partition(|(lint_name, _)| self.store.is_lint_group(Symbol::intern(lint_name)));
+        for &(ref lint_name, level) in groups.into_iter().chain(lints) {
+            let requested_level = RequestedLevel::LintConfig { level: level.as_str(), lint_name };
+            let Some(ids) = self.check_requested_lint_name(lint_name, requested_level) else {
+                continue;
+            };
+            let src = LintLevelSource::Config(Symbol::intern(lint_name), level);
+            for id in ids {
+                // Like on the command line, ForceWarn and Forbid cannot be overridden
+                if let (Level::ForceWarn(_) | Level::Forbid, _) =
+                    self.provider.get_lint_level(id.lint, self.sess)
+                {
+                    continue;
+                }
+
+                if self.check_gated_lint(id, DUMMY_SP, true) {
+                    self.insert(id, (level, src));
+                }
+            }
+        }
+    }
+
+    /// Applies the levels of the lint configuration file for the source file containing
+    /// `inner_span`, the span of the contents of the crate root or of an out-of-line module.
+    fn add_file_config(&mut self, inner_span: Span) {
+        let Some(config) = self.store.lint_config() else { return };
+        let sm = self.sess.source_map();
+        let source_file = sm.lookup_source_file(inner_span.lo());
+        let file = sm.filename_for_diagnostics(&source_file.name).to_string();
+        for levels in config.file_levels(&file) {
+            self.add_config_levels(levels);
+        }
+    }
+
+    /// Checks the validity of a lint name that doesn't come from an attribute, and returns
+    /// the lints it refers to.
+    fn check_requested_lint_name(
+        &self,
+        lint_name: &str,
+        requested_level: RequestedLevel<'_>,
+    ) -> Option<Vec<LintId>> {
+        let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
+        match self.store.check_lint_name(lint_name_only, tool_name, self.registered_tools) {
+            CheckLintNameResult::Renamed(ref replace) => {
+                let suggestion = RenamedLintSuggestion::WithoutSpan { replace };
+                let lint =
+                    RenamedLintFromCommandLine { name: lint_name, suggestion, requested_level };
+                self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
+            }
+            CheckLintNameResult::Removed(ref reason) => {
+                let lint = RemovedLintFromCommandLine { name: lint_name, reason, requested_level };
+                self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
+            }
+            CheckLintNameResult::NoLint(suggestion) => {
+                let name = lint_name.to_owned();
+                let suggestion = suggestion.map(|(replace, from_rustc)| {
+                    UnknownLintSuggestion::WithoutSpan { replace, from_rustc }
+                });
+                let lint = UnknownLintFromCommandLine { name, suggestion, requested_level };
+                self.emit_lint(UNKNOWN_LINTS, lint);
+            }
+            CheckLintNameResult::Tool(Err((Some(_), ref replace))) => {
+                let name = lint_name.to_owned();
+                let lint = DeprecatedLintNameFromCommandLine { name, replace, requested_level };
+                self.emit_lint(RENAMED_AND_REMOVED_LINTS, lint);
+            }
+            CheckLintNameResult::NoTool => {
+                self.sess.dcx().emit_err(CheckNameUnknownTool {
+                    tool_name: tool_name.unwrap(),
+                    sub: requested_level,
+                });
+            }
+            _ => {}
+        };
+
+        self.store.find_lints(lint_name).ok()
+    }
+
     /// Attempts to insert the `id` to `level_src` map entry. If unsuccessful
     /// (e.g. if a forbid was already inserted on the same scope), then emits a
     /// diagnostic with no change to `specs`.
@@ -659,6 +740,7 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                 LintLevelSource::Default => false,
                 LintLevelSource::Node { name, .. } => self.store.is_lint_group(name),
                 LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                LintLevelSource::Config(symbol, _) => self.store.is_lint_group(symbol),
             };
             debug!(
                 "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -675,6 +757,7 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                     OverruledAttributeSub::NodeSource { span, reason }
                 }
                 LintLevelSource::CommandLine(_, _) => OverruledAttributeSub::CommandLineSource,
+                LintLevelSource::Config(_, _) => OverruledAttributeSub::LintConfigSource,
             };
             if !fcw_warning {
                 self.sess.dcx().emit_err(OverruledAttribute {
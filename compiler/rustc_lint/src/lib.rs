@@ -54,9 +54,11 @@
 mod impl_trait_overcaptures;
 mod internal;
 mod invalid_from_utf8;
+mod large_futures;
 mod late;
 mod let_underscore;
 mod levels;
+mod lint_config;
 mod lints;
 mod map_unit_fn;
 mod methods;
@@ -94,6 +96,7 @@
 use impl_trait_overcaptures::ImplTraitOvercaptures;
 use internal::*;
 use invalid_from_utf8::*;
+use large_futures::*;
 use let_underscore::*;
 use map_unit_fn::*;
 use methods::*;
@@ -120,6 +123,7 @@
 pub use context::{EarlyContext, LateContext, LintContext};
 pub use early::{check_ast_node, EarlyCheckNode};
 pub use late::{check_crate, late_lint_mod, unerased_lint_store};
+pub use lint_config::LintConfig;
 pub use passes::{EarlyLintPass, LateLintPass};
 pub use rustc_session::lint::Level::{self, *};
 pub use rustc_session::lint::{BufferedEarlyLint, FutureIncompatibleInfo, Lint, LintId};
@@ -228,6 +232,7 @@ fn lint_mod(tcx: TyCtxt<'_>, module_def_id: LocalModDefId) {
             AsyncFnInTrait: AsyncFnInTrait,
             NonLocalDefinitions: NonLocalDefinitions::default(),
             ImplTraitOvercaptures: ImplTraitOvercaptures,
+            LargeFutures: LargeFutures,
         ]
     ]
 );
@@ -0,0 +1,171 @@
+//! Lint configuration files, used by `-Zlint-config`.
+//!
+//! A configuration file sets lint levels for a whole crate, and for the source files matching
+//! a list of path globs, without editing the sources. It is stored as JSON:
+//!
+//! ```json
+//! {
+//!   "lints": { "unused_variables": "deny", "clippy::pedantic": "warn" },
+//!   "overrides": [
+//!     { "paths": ["src/generated/**"], "lints": { "missing_docs": "allow" } }
+//!   ]
+//! }
+//! ```
+//!
+//! The levels of `lints` apply to the whole crate, as if they had been passed on the command
+//! line before any `-A`/`-W`/`-D`/`-F` flag. The levels of an override apply to the modules
+//! whose source files match one of its paths, as if they were attributes at the top of these
+//! modules. Like attributes, they are inherited by the modules declared within these modules,
+//! and attributes in the sources take precedence over them.
+
+use rustc_session::lint::Level;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// The lint levels of a configuration file, see the [module-level documentation](self).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LintConfig {
+    /// The levels of the whole crate.
+    lints: Vec<(String, Level)>,
+    overrides: Vec<PathOverride>,
+}
+
+/// Lint levels for the source files matching some path globs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct PathOverride {
+    paths: Vec<String>,
+    lints: Vec<(String, Level)>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    lints: BTreeMap<String, String>,
+    #[serde(default)]
+    overrides: Vec<OverrideEntry>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OverrideEntry {
+    paths: Vec<String>,
+    #[serde(default)]
+    lints: BTreeMap<String, String>,
+}
+
+impl LintConfig {
+    /// Reads the configuration file at `path`.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::parse(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub(crate) fn parse(contents: &str) -> Result<Self, String> {
+        let file: ConfigFile = serde_json::from_str(contents).map_err(|err| err.to_string())?;
+        let overrides = file
+            .overrides
+            .into_iter()
+            .map(|OverrideEntry { paths, lints }| {
+                if paths.is_empty() {
+                    return Err("an override must have at least one path".to_owned());
+                }
+                Ok(PathOverride { paths, lints: parse_levels(lints)? })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(LintConfig { lints: parse_levels(file.lints)?, overrides })
+    }
+
+    /// The levels that apply to the whole crate.
+    pub(crate) fn crate_levels(&self) -> &[(String, Level)] {
+        &self.lints
+    }
+
+    /// The levels of the overrides that apply to `file`, in the order in which they have to be
+    /// applied.
+    pub(crate) fn file_levels<'a>(
+        &'a self,
+        file: &str,
+    ) -> impl Iterator<Item = &'a [(String, Level)]> + 'a {
+        let file = normalize_path(file);
+        self.overrides
+            .iter()
+            .filter(move |o| o.paths.iter().any(|pattern| glob_matches(pattern, &file)))
+            .map(|o| &o.lints[..])
+    }
+}
+
+/// Parses the levels of a `lints` table. Lint names may be written with dashes, as on the
+/// command line.
+fn parse_levels(lints: BTreeMap<String, String>) -> Result<Vec<(String, Level)>, String> {
+    lints
+        .into_iter()
+        .map(|(lint_name, level)| match Level::from_str(&level) {
+            Some(level) => Ok((lint_name.replace('-', "_"), level)),
+            None => Err(format!(
+                "invalid level `{level}` for lint `{lint_name}`, \
+                expected one of `allow`, `warn`, `deny` or `forbid`"
+            )),
+        })
+        .collect()
+}
+
+fn normalize_path(path: &str) -> String {
+    let path = if cfg!(windows) { path.replace('\\', "/") } else { path.to_owned() };
+    match path.strip_prefix("./") {
+        Some(path) => path.to_owned(),
+        None => path,
+    }
+}
+
+/// Checks whether `path` matches the glob `pattern`. In a pattern, `*` matches any sequence of
+/// characters but `/`, `?` matches any character but `/`, and a `**` component matches any
+/// number of path components.
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = normalize_path(pattern);
+    let pattern: Vec<_> = pattern.split('/').collect();
+    let path: Vec<_> = path.split('/').collect();
+    components_match(&pattern, &path)
+}
+
+fn components_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", pattern)) => {
+            (0..=path.len()).any(|skip| components_match(pattern, &path[skip..]))
+        }
+        Some((first, pattern)) => match path.split_first() {
+            Some((component, path)) => {
+                component_matches(first, component) && components_match(pattern, path)
+            }
+            None => false,
+        },
+    }
+}
+
+fn component_matches(pattern: &str, name: &str) -> bool {
+    let mut pattern_chars = pattern.chars();
+    match pattern_chars.next() {
+        None => name.is_empty(),
+        Some('*') => {
+            let pattern = pattern_chars.as_str();
+            name.char_indices()
+                .map(|(i, _)| i)
+                .chain([name.len()])
+                .any(|skip| component_matches(pattern, &name[skip..]))
+        }
+        Some(c) => {
+            let mut name_chars = name.chars();
+            match name_chars.next() {
+                Some(n) if c == '?' || c == n => {
+                    component_matches(pattern_chars.as_str(), name_chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+#[test]
+fn parse_config() {
+    let config = LintConfig::parse(
+        r#"{
+            "lints": { "unused-variables": "deny", "clippy::pedantic": "warn" },
+            "overrides": [
+                { "paths": ["src/generated/**", "build.rs"], "lints": { "missing_docs": "allow" } }
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.crate_levels(),
+        [
+            ("clippy::pedantic".to_owned(), Level::Warn),
+            ("unused_variables".to_owned(), Level::Deny)
+        ]
+    );
+    let missing_docs = [("missing_docs".to_owned(), Level::Allow)];
+    assert_eq!(config.file_levels("src/generated/mod.rs").collect::<Vec<_>>(), [&missing_docs]);
+    assert_eq!(config.file_levels("./build.rs").collect::<Vec<_>>(), [&missing_docs]);
+    assert_eq!(config.file_levels("src/lib.rs").count(), 0);
+}
+
+#[test]
+fn parse_empty_config() {
+    assert_eq!(LintConfig::parse("{}").unwrap(), LintConfig::default());
+}
+
+#[test]
+fn parse_invalid_config() {
+    assert!(LintConfig::parse(r#"{ "lints": { "unused": "expect" } }"#).is_err());
+    assert!(LintConfig::parse(r#"{ "lint": { "unused": "allow" } }"#).is_err());
+    assert!(LintConfig::parse(r#"{ "overrides": [{ "paths": [] }] }"#).is_err());
+}
+
+#[test]
+fn glob_literal() {
+    assert!(glob_matches("src/lib.rs", "src/lib.rs"));
+    assert!(!glob_matches("src/lib.rs", "src/main.rs"));
+    assert!(!glob_matches("src", "src/lib.rs"));
+    assert!(!glob_matches("lib.rs", "src/lib.rs"));
+}
+
+#[test]
+fn glob_wildcards() {
+    assert!(glob_matches("src/*.rs", "src/lib.rs"));
+    assert!(!glob_matches("src/*.rs", "src/generated/mod.rs"));
+    assert!(glob_matches("src/*_generated.rs", "src/api_generated.rs"));
+    assert!(glob_matches("src/mod?.rs", "src/mod1.rs"));
+    assert!(!glob_matches("src/mod?.rs", "src/mod.rs"));
+    assert!(glob_matches("crates/*/src/lib.rs", "crates/föö/src/lib.rs"));
+}
+
+#[test]
+fn glob_recursive() {
+    assert!(glob_matches("src/generated/**", "src/generated/mod.rs"));
+    assert!(glob_matches("src/generated/**", "src/generated/a/b/c.rs"));
+    assert!(!glob_matches("src/generated/**", "src/lib.rs"));
+    assert!(glob_matches("**/tests/*.rs", "tests/ui.rs"));
+    assert!(glob_matches("**/tests/*.rs", "crates/foo/tests/ui.rs"));
+    assert!(glob_matches("src/**/mod.rs", "src/mod.rs"));
+    assert!(glob_matches("./src/**", "src/lib.rs"));
+}
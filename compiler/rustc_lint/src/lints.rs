@@ -6,7 +6,7 @@
 use crate::fluent_generated as fluent;
 use rustc_errors::{
     codes::*, Applicability, Diag, DiagArgValue, DiagMessage, DiagStyledString,
-    ElidedLifetimeInPathSubdiag, EmissionGuarantee, LintDiagnostic, SubdiagMessageOp,
+    ElidedLifetimeInPathSubdiag, EmissionGuarantee, LintDiagnostic, MultiSpan, SubdiagMessageOp,
     Subdiagnostic, SuggestionStyle,
 };
 use rustc_hir::{def::Namespace, def_id::DefId};
@@ -937,6 +937,40 @@ pub struct BadOptAccessDiag<'a> {
     pub msg: &'a str,
 }
 
+// large_futures.rs
+#[derive(LintDiagnostic)]
+#[diag(lint_large_future)]
+#[help]
+pub struct LargeFuture {
+    pub size: u64,
+    pub limit: u64,
+    #[subdiagnostic]
+    pub held_values: Vec<LargeFutureHeldValue>,
+}
+
+#[derive(Subdiagnostic)]
+pub enum LargeFutureHeldValue {
+    #[note(lint_large_future_held_local)]
+    Local {
+        #[primary_span]
+        spans: MultiSpan,
+        name: Symbol,
+        size: u64,
+    },
+    #[note(lint_large_future_held_awaitee)]
+    Awaitee {
+        #[primary_span]
+        spans: MultiSpan,
+        size: u64,
+    },
+    #[note(lint_large_future_held_temporary)]
+    Temporary {
+        #[primary_span]
+        spans: MultiSpan,
+        size: u64,
+    },
+}
+
 // let_underscore.rs
 #[derive(LintDiagnostic)]
 pub enum NonBindingLet {
@@ -363,6 +363,27 @@ extern "C" void LLVMRustPrintTargetCPUs(LLVMTargetMachineRef TM,
   Print(Out, BufString.data(), BufString.size());
 }
 
+extern "C" size_t LLVMRustGetTargetCPUsCount(LLVMTargetMachineRef TM) {
+  const TargetMachine *Target = unwrap(TM);
+  const MCSubtargetInfo *MCInfo = Target->getMCSubtargetInfo();
+  return MCInfo->getAllProcessorDescriptions().size();
+}
+
+extern "C" const char* LLVMRustGetTargetCPU(LLVMTargetMachineRef TM, size_t Index) {
+  const TargetMachine *Target = unwrap(TM);
+  const MCSubtargetInfo *MCInfo = Target->getMCSubtargetInfo();
+  const ArrayRef<SubtargetSubTypeKV> CPUTable = MCInfo->getAllProcessorDescriptions();
+  return CPUTable[Index].Key;
+}
+
+// Whether the target machine is for the architecture of the host, i.e. whether
+// "native" is a meaningful CPU for it.
+extern "C" bool LLVMRustIsHostArch(LLVMTargetMachineRef TM) {
+  const TargetMachine *Target = unwrap(TM);
+  const Triple::ArchType HostArch = Triple(sys::getDefaultTargetTriple()).getArch();
+  return HostArch == Target->getTargetTriple().getArch();
+}
+
 extern "C" size_t LLVMRustGetTargetFeaturesCount(LLVMTargetMachineRef TM) {
 #if LLVM_VERSION_GE(18, 0)
   const TargetMachine *Target = unwrap(TM);
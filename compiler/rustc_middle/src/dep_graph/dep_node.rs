@@ -121,6 +121,10 @@ pub(super) fn dep_kind_from_label_string(label: &str) -> Result<DepKind, ()> {
             }
         }
 
+        /// The names of all dep kinds, indexed by `DepKind::as_usize`. Unlike the `Debug`
+        /// implementation of `DepKind`, this can be used without a `TyCtxt`.
+        pub const DEP_KIND_NAMES: &[&str] = &[$(stringify!($variant),)*];
+
         /// Contains variant => str representations for constructing
         /// DepNode groups for tests.
         #[allow(dead_code, non_upper_case_globals)]
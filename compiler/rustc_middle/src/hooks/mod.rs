@@ -103,6 +103,11 @@ fn clone(&self) -> Self { *self }
 
     /// Create a list-like THIR representation for debugging.
     hook thir_flat(key: LocalDefId) -> String;
+
+    /// Collects the mono items of the current crate and how they use each other, with the
+    /// strategy chosen by `-Zprint-mono-items` or `-Clink-dead-code`. The result isn't
+    /// cached, since it is large, so every call does the whole collection again.
+    hook collect_mono_item_graph() -> mir::mono::MonoItemGraph<'tcx>;
 }
 
 #[cold]
@@ -34,6 +34,10 @@ pub enum LintLevelSource {
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by the lint configuration file passed to `-Zlint-config`.
+    /// The provided `Level` is the level specified in the file.
+    Config(Symbol, Level),
 }
 
 impl LintLevelSource {
@@ -42,6 +46,7 @@ pub fn name(&self) -> Symbol {
             LintLevelSource::Default => symbol::kw::Default,
             LintLevelSource::Node { name, .. } => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::Config(name, _) => name,
         }
     }
 
@@ -50,6 +55,7 @@ pub fn span(&self) -> Span {
             LintLevelSource::Default => DUMMY_SP,
             LintLevelSource::Node { span, .. } => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::Config(_, _) => DUMMY_SP,
         }
     }
 }
@@ -235,6 +241,22 @@ pub fn explain_lint_level_source(
                 ));
             }
         }
+        LintLevelSource::Config(lint_config_val, orig_level) => {
+            let level_str = orig_level.as_str();
+            if lint_config_val.as_str() == name {
+                err.note_once(format!(
+                    "requested in the lint configuration file with `\"{name}\": \"{level_str}\"`"
+                ));
+            } else {
+                err.note_once(format!(
+                    "`\"{name}\": \"{level_str}\"` implied by `\"{lint_config_val}\": \"{level_str}\"` \
+                    in the lint configuration file"
+                ));
+                err.help_once(format!(
+                    "to override `\"{lint_config_val}\": \"{level_str}\"` add `#[allow({name})]`"
+                ));
+            }
+        }
         LintLevelSource::Node { name: lint_attr_name, span, reason, .. } => {
             if let Some(rationale) = reason {
                 err.note(rationale.to_string());
@@ -6,6 +6,7 @@
 use rustc_data_structures::base_n::CASE_INSENSITIVE;
 use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::fx::FxIndexMap;
 use rustc_data_structures::stable_hasher::{Hash128, HashStable, StableHasher};
 use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
@@ -72,7 +73,7 @@ pub fn size_estimate(&self, tcx: TyCtxt<'tcx>) -> usize {
                     InstanceDef::Item(..)
                     | InstanceDef::DropGlue(..)
                     | InstanceDef::AsyncDropGlueCtorShim(..) => {
-                        let mir = tcx.instance_mir(instance.def);
+                        let mir = tcx.codegen_instance_mir(instance);
                         mir.basic_blocks.iter().map(|bb| bb.statements.len() + 1).sum()
                     }
                     // Other compiler-generated shims size estimate: 1
@@ -241,6 +242,90 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// Why a mono item is used by another mono item.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UsageKind {
+    /// The item is called directly, including the calls to panic functions that are implicit in
+    /// assertions and unwinding.
+    Call,
+    /// The item is the drop glue of a value that is dropped, or that is stored in a vtable.
+    DropGlue,
+    /// The item is a method of a vtable that is created by an unsizing cast.
+    VtableMethod,
+    /// The item is a function whose address is taken, e.g. by reifying it into a function
+    /// pointer or by referring to it from a constant.
+    FnPointer,
+    /// The item is a static that is referred to, or the thread-local shim of a static.
+    Static,
+    /// The item is a root of the collection, like `main` or an exported function. Roots are not
+    /// used by another item, so this never appears in a [`UsageMap`].
+    Root,
+}
+
+#[derive(Debug)]
+pub struct UsageMap<'tcx> {
+    // Maps every mono item to the mono items used by it, and why they are used.
+    used_map: FxHashMap<MonoItem<'tcx>, Vec<(MonoItem<'tcx>, UsageKind)>>,
+
+    // Maps every mono item to the mono items that use it.
+    user_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
+}
+
+impl<'tcx> UsageMap<'tcx> {
+    pub fn new() -> UsageMap<'tcx> {
+        UsageMap { used_map: FxHashMap::default(), user_map: FxHashMap::default() }
+    }
+
+    pub fn record_used(
+        &mut self,
+        user_item: MonoItem<'tcx>,
+        used_items: Vec<(MonoItem<'tcx>, UsageKind)>,
+    ) {
+        for &(used_item, kind) in used_items.iter() {
+            debug_assert_ne!(kind, UsageKind::Root);
+            self.user_map.entry(used_item).or_default().push(user_item);
+        }
+
+        assert!(self.used_map.insert(user_item, used_items).is_none());
+    }
+
+    pub fn get_user_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
+        self.user_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
+    }
+
+    /// The mono items used by `item`, along with why they are used. An item that is used
+    /// several times appears once per use.
+    pub fn get_used_items(&self, item: MonoItem<'tcx>) -> &[(MonoItem<'tcx>, UsageKind)] {
+        self.used_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
+    }
+
+    /// Internally iterate over all inlined items used by `item`.
+    pub fn for_each_inlined_used_item<F>(&self, tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>, mut f: F)
+    where
+        F: FnMut(MonoItem<'tcx>),
+    {
+        let used_items = self.used_map.get(&item).unwrap();
+        for (used_item, _) in used_items.iter() {
+            let is_inlined = used_item.instantiation_mode(tcx) == InstantiationMode::LocalCopy;
+            if is_inlined {
+                f(*used_item);
+            }
+        }
+    }
+}
+
+/// The mono items of the current crate, and how they use each other, as computed by the
+/// `collect_mono_item_graph` hook.
+#[derive(Debug)]
+pub struct MonoItemGraph<'tcx> {
+    /// The items that the collection started from, like `main` or the exported functions of a
+    /// library. A root can also be used by other items.
+    pub roots: Vec<MonoItem<'tcx>>,
+    /// All the collected items, including the roots.
+    pub items: FxHashSet<MonoItem<'tcx>>,
+    pub usage_map: UsageMap<'tcx>,
+}
+
 #[derive(Debug)]
 pub struct CodegenUnit<'tcx> {
     /// A name for this CGU. Incremental compilation requires that
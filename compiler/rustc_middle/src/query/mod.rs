@@ -1139,6 +1139,14 @@
         desc { |tcx| "generating MIR shim for `{}`", tcx.def_path_str(key.def_id()) }
     }
 
+    /// The MIR body that is collected and codegened for an instance.
+    ///
+    /// This is the `instance_mir` of the instance, unless a driver overrides this query to
+    /// transform the body, in which case the returned body may already be monomorphic.
+    query codegen_instance_mir(key: ty::Instance<'tcx>) -> &'tcx mir::Body<'tcx> {
+        desc { "getting the MIR body to codegen for `{}`", key }
+    }
+
     /// The `symbol_name` query provides the symbol name for calling a
     /// given instance from the local crate. In particular, it will also
     /// look up the correct symbol name of instances from upstream crates.
@@ -1,13 +1,14 @@
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::{FxHashMap, FxIndexSet};
 use rustc_data_structures::memmap::Mmap;
-use rustc_data_structures::sync::{HashMapExt, Lock, Lrc, RwLock};
+use rustc_data_structures::sync::{DynSend, DynSync, HashMapExt, Lock, Lrc, RwLock};
 use rustc_data_structures::unhash::UnhashMap;
 use rustc_data_structures::unord::{UnordMap, UnordSet};
 use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LocalDefId, StableCrateId, LOCAL_CRATE};
 use rustc_hir::definitions::DefPathHash;
 use rustc_index::{Idx, IndexVec};
 use rustc_macros::{Decodable, Encodable};
-use rustc_middle::dep_graph::{DepNodeIndex, SerializedDepNodeIndex};
+use rustc_middle::dep_graph::{DepNodeIndex, SerializedDepNodeIndex, WorkProduct};
 use rustc_middle::mir::interpret::{AllocDecodingSession, AllocDecodingState};
 use rustc_middle::mir::{self, interpret};
 use rustc_middle::ty::codec::{RefDecodable, TyDecoder, TyEncoder};
@@ -29,9 +30,13 @@
 use rustc_span::{CachingSourceMapView, Symbol};
 use std::collections::hash_map::Entry;
 use std::mem;
+use std::path::Path;
 
 const TAG_FILE_FOOTER: u128 = 0xC0FFEE_C0FFEE_C0FFEE_C0FFEE_C0FFEE;
 
+// The index of the result in the data written by `OnDiskCache::serialize_single_result`.
+const SINGLE_RESULT_INDEX: SerializedDepNodeIndex = SerializedDepNodeIndex::from_u32(0);
+
 // A normal span encoded with both location information and a `SyntaxContext`
 const TAG_FULL_SPAN: u8 = 0;
 // A partial span with no location information, encoded only with a `SyntaxContext`
@@ -116,6 +121,19 @@ struct Footer {
     foreign_expn_data: UnhashMap<ExpnHash, u32>,
 }
 
+/// The contents of a serialized query result cache, see [`OnDiskCache::summarize`].
+#[derive(Debug, Default)]
+pub struct OnDiskCacheSummary {
+    /// The number of dep nodes with cached query results.
+    pub query_results: usize,
+    /// The number of dep nodes with cached side effects (e.g. diagnostics).
+    pub side_effects: usize,
+    /// The number of source files referenced by the cached results.
+    pub source_files: usize,
+    /// The number of interpreter allocations referenced by the cached results.
+    pub allocations: usize,
+}
+
 pub type EncodedDepNodeIndex = Vec<(SerializedDepNodeIndex, AbsoluteBytePos)>;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Encodable, Decodable)]
@@ -159,18 +177,11 @@ impl<'sess> OnDiskCache<'sess> {
     /// on-disk data is corrupt, an error is returned.
     pub fn new(sess: &'sess Session, data: Mmap, start_pos: usize) -> Result<Self, ()> {
         assert!(sess.opts.incremental.is_some());
+        Self::from_data(sess, data, start_pos)
+    }
 
-        let mut decoder = MemDecoder::new(&data, start_pos)?;
-
-        // Decode the *position* of the footer, which can be found in the
-        // last 8 bytes of the file.
-        let footer_pos = decoder
-            .with_position(decoder.len() - IntEncodedWithFixedSize::ENCODED_SIZE, |decoder| {
-                IntEncodedWithFixedSize::decode(decoder).0 as usize
-            });
-        // Decode the file footer, which contains all the lookup tables, etc.
-        let footer: Footer =
-            decoder.with_position(footer_pos, |decoder| decode_tagged(decoder, TAG_FILE_FOOTER));
+    fn from_data(sess: &'sess Session, data: Mmap, start_pos: usize) -> Result<Self, ()> {
+        let footer = Self::decode_footer(&data, start_pos)?;
 
         Ok(Self {
             serialized_data: RwLock::new(Some(data)),
@@ -188,6 +199,31 @@ pub fn new(sess: &'sess Session, data: Mmap, start_pos: usize) -> Result<Self, (
         })
     }
 
+    fn decode_footer(data: &[u8], start_pos: usize) -> Result<Footer, ()> {
+        let mut decoder = MemDecoder::new(data, start_pos)?;
+
+        // Decode the *position* of the footer, which can be found in the
+        // last 8 bytes of the file.
+        let footer_pos = decoder
+            .with_position(decoder.len() - IntEncodedWithFixedSize::ENCODED_SIZE, |decoder| {
+                IntEncodedWithFixedSize::decode(decoder).0 as usize
+            });
+        // Decode the file footer, which contains all the lookup tables, etc.
+        Ok(decoder.with_position(footer_pos, |decoder| decode_tagged(decoder, TAG_FILE_FOOTER)))
+    }
+
+    /// Summarizes the contents of the serialized cache in `data` without setting up a cache
+    /// to load query results from. This is used by `-Zinspect-incr-cache`.
+    pub fn summarize(data: &[u8], start_pos: usize) -> Result<OnDiskCacheSummary, ()> {
+        let footer = Self::decode_footer(data, start_pos)?;
+        Ok(OnDiskCacheSummary {
+            query_results: footer.query_result_index.len(),
+            side_effects: footer.side_effects_index.len(),
+            source_files: footer.file_index_to_stable_id.len(),
+            allocations: footer.interpret_alloc_index.len(),
+        })
+    }
+
     pub fn new_empty(source_map: &'sess SourceMap) -> Self {
         Self {
             serialized_data: RwLock::new(None),
@@ -223,6 +259,70 @@ pub fn drop_serialized_data(&self, tcx: TyCtxt<'_>) {
     }
 
     pub fn serialize(&self, tcx: TyCtxt<'_>, encoder: FileEncoder) -> FileEncodeResult {
+        Self::serialize_with(tcx, encoder, |encoder| {
+            // Encode query results.
+            let mut query_result_index = EncodedDepNodeIndex::new();
+
+            tcx.sess.time("encode_query_results", || {
+                let qri = &mut query_result_index;
+                (tcx.query_system.fns.encode_query_results)(tcx, encoder, qri);
+            });
+
+            // Encode side effects.
+            let side_effects_index: EncodedDepNodeIndex = self
+                .current_side_effects
+                .borrow()
+                .iter()
+                .map(|(dep_node_index, side_effects)| {
+                    let pos = AbsoluteBytePos::new(encoder.position());
+                    let dep_node_index = SerializedDepNodeIndex::new(dep_node_index.index());
+                    encoder.encode_tagged(dep_node_index, side_effects);
+
+                    (dep_node_index, pos)
+                })
+                .collect();
+
+            (query_result_index, side_effects_index)
+        })
+    }
+
+    /// Serializes `value` on its own, for the cache given with `-Zshared-cache`.
+    /// The result can be loaded with [`OnDiskCache::load_single_result`], in any
+    /// session that has the crates it refers to.
+    pub fn serialize_single_result<'tcx, T>(
+        tcx: TyCtxt<'tcx>,
+        value: &T,
+        encoder: FileEncoder,
+    ) -> FileEncodeResult
+    where
+        T: for<'a> Encodable<CacheEncoder<'a, 'tcx>>,
+    {
+        Self::serialize_with(tcx, encoder, |encoder| {
+            let pos = AbsoluteBytePos::new(encoder.position());
+            encoder.encode_tagged(SINGLE_RESULT_INDEX, value);
+            (vec![(SINGLE_RESULT_INDEX, pos)], EncodedDepNodeIndex::new())
+        })
+    }
+
+    /// Loads a value serialized with [`OnDiskCache::serialize_single_result`].
+    pub fn load_single_result<'tcx, T>(tcx: TyCtxt<'tcx>, data: Mmap) -> Option<T>
+    where
+        T: for<'a> Decodable<CacheDecoder<'a, 'tcx>>,
+    {
+        let cache = Self::from_data(tcx.sess, data, 0).ok()?;
+        cache.try_load_query_result(tcx, SINGLE_RESULT_INDEX)
+    }
+
+    /// Serializes the results encoded by `encode_results`, which returns the
+    /// index of the query results and of the side effects, followed by the data
+    /// they refer to and the footer.
+    fn serialize_with<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        encoder: FileEncoder,
+        encode_results: impl FnOnce(
+            &mut CacheEncoder<'_, 'tcx>,
+        ) -> (EncodedDepNodeIndex, EncodedDepNodeIndex),
+    ) -> FileEncodeResult {
         // Serializing the `DepGraph` should not modify it.
         tcx.dep_graph.with_ignore(|| {
             // Allocate `SourceFileIndex`es.
@@ -258,28 +358,7 @@ pub fn serialize(&self, tcx: TyCtxt<'_>, encoder: FileEncoder) -> FileEncodeResu
                 symbol_table: Default::default(),
             };
 
-            // Encode query results.
-            let mut query_result_index = EncodedDepNodeIndex::new();
-
-            tcx.sess.time("encode_query_results", || {
-                let enc = &mut encoder;
-                let qri = &mut query_result_index;
-                (tcx.query_system.fns.encode_query_results)(tcx, enc, qri);
-            });
-
-            // Encode side effects.
-            let side_effects_index: EncodedDepNodeIndex = self
-                .current_side_effects
-                .borrow()
-                .iter()
-                .map(|(dep_node_index, side_effects)| {
-                    let pos = AbsoluteBytePos::new(encoder.position());
-                    let dep_node_index = SerializedDepNodeIndex::new(dep_node_index.index());
-                    encoder.encode_tagged(dep_node_index, side_effects);
-
-                    (dep_node_index, pos)
-                })
-                .collect();
+            let (query_result_index, side_effects_index) = encode_results(&mut encoder);
 
             let interpret_alloc_index = {
                 let mut interpret_alloc_index = Vec::new();
@@ -453,6 +532,35 @@ fn with_decoder<'a, 'tcx, T, F: for<'s> FnOnce(&mut CacheDecoder<'s, 'tcx>) -> T
     }
 }
 
+/// The cache of query results and codegen units that is shared between
+/// compilation sessions and crates, see `rustc_incremental::SharedCache`.
+/// Unlike the [`OnDiskCache`], which holds the results of the previous session
+/// of the same crate, entries are looked up by a fingerprint of everything they
+/// depend on. Query results are stored with
+/// [`OnDiskCache::serialize_single_result`].
+pub trait SharedCacheStore: DynSync + DynSend {
+    /// Returns the data stored for `key`, if any.
+    fn load_query_result(&self, key: Fingerprint) -> Option<Mmap>;
+
+    /// Stores the data written by `encode` for `key`, unless there already is
+    /// some. Failing to do so only emits a warning.
+    fn store_query_result(
+        &self,
+        sess: &Session,
+        key: Fingerprint,
+        encode: &mut dyn FnMut(FileEncoder) -> FileEncodeResult,
+    );
+
+    /// Returns the files stored for the codegen unit with `key`, if any. The
+    /// paths of the returned work product point into the cache, and stay
+    /// valid for as long as the cache is open.
+    fn load_work_product(&self, key: Fingerprint, cgu_name: &str) -> Option<WorkProduct>;
+
+    /// Stores the files of a freshly compiled codegen unit, given by extension,
+    /// for `key`. Failing to do so only emits a warning.
+    fn store_work_product(&self, sess: &Session, key: Fingerprint, files: &[(&'static str, &Path)]);
+}
+
 //- DECODING -------------------------------------------------------------------
 
 /// A decoder that can read from the incremental compilation cache. It is similar to the one
@@ -1,14 +1,18 @@
 use crate::dep_graph;
 use crate::dep_graph::DepKind;
+use crate::query::on_disk_cache::CacheDecoder;
 use crate::query::on_disk_cache::CacheEncoder;
 use crate::query::on_disk_cache::EncodedDepNodeIndex;
 use crate::query::on_disk_cache::OnDiskCache;
+use crate::query::on_disk_cache::SharedCacheStore;
 use crate::query::{
     DynamicQueries, ExternProviders, Providers, QueryArenas, QueryCaches, QueryEngine, QueryStates,
 };
 use crate::ty::TyCtxt;
 use field_offset::FieldOffset;
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::sync::AtomicU64;
+use rustc_data_structures::sync::Lrc;
 use rustc_data_structures::sync::WorkerLocal;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::hir_id::OwnerId;
@@ -18,6 +22,7 @@
 pub(crate) use rustc_query_system::query::QueryJobId;
 use rustc_query_system::query::*;
 use rustc_query_system::HandleCycleError;
+use rustc_serialize::{Decodable, Encodable};
 use rustc_span::{ErrorGuaranteed, Span, DUMMY_SP};
 use std::ops::Deref;
 
@@ -70,6 +75,11 @@ pub struct QuerySystem<'tcx> {
     /// This is `None` if we are not incremental compilation mode
     pub on_disk_cache: Option<OnDiskCache<'tcx>>,
 
+    /// The cache given with `-Zshared-cache`, for the few query results that are
+    /// stored there with [`TyCtxt::with_shared_query_result_cache`] and for
+    /// codegen units. This is `None` with incremental compilation.
+    pub shared_cache: Option<Lrc<dyn SharedCacheStore>>,
+
     pub fns: QuerySystemFns<'tcx>,
 
     pub jobs: AtomicU64,
@@ -126,6 +136,40 @@ pub fn at(self, span: Span) -> TyCtxtAt<'tcx> {
     pub fn try_mark_green(self, dep_node: &dep_graph::DepNode) -> bool {
         (self.query_system.fns.try_mark_green)(self, dep_node)
     }
+
+    /// Computes a query result with `compute`, or loads it from the cache given
+    /// with `-Zshared-cache` if another session stored a result for `key` there.
+    ///
+    /// The caller has to make sure that `key` covers everything the result
+    /// depends on, and that the crates the result refers to are loaded in any
+    /// session that computes the same key. Results are only stored if `compute`
+    /// didn't emit an error.
+    pub fn with_shared_query_result_cache<T>(
+        self,
+        key: Fingerprint,
+        compute: impl FnOnce() -> T,
+    ) -> T
+    where
+        T: for<'a> Encodable<CacheEncoder<'a, 'tcx>> + for<'a> Decodable<CacheDecoder<'a, 'tcx>>,
+    {
+        let Some(shared_cache) = &self.query_system.shared_cache else {
+            return compute();
+        };
+        if let Some(data) = shared_cache.load_query_result(key)
+            && let Some(value) = OnDiskCache::load_single_result(self, data)
+        {
+            return value;
+        }
+
+        let error_count = self.dcx().err_count();
+        let value = compute();
+        if self.dcx().err_count() == error_count {
+            shared_cache.store_query_result(self.sess, key, &mut |encoder| {
+                OnDiskCache::serialize_single_result(self, &value, encoder)
+            });
+        }
+        value
+    }
 }
 
 #[inline]
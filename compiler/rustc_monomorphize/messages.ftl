@@ -1,9 +1,15 @@
 monomorphize_consider_type_length_limit =
     consider adding a `#![type_length_limit="{$type_length}"]` attribute to your crate
 
+monomorphize_couldnt_dump_mono_bloat =
+    unexpected error occurred while dumping the monomorphization bloat report: {$error}
+
 monomorphize_couldnt_dump_mono_stats =
     unexpected error occurred while dumping monomorphization stats: {$error}
 
+monomorphize_couldnt_write_item_time_report =
+    unexpected error occurred while writing the item time report: {$error}
+
 monomorphize_encountered_error_while_instantiating =
     the above error was encountered while instantiating `{$formatted_item}`
 
@@ -205,6 +205,7 @@
 //! this is not implemented however: a mono item will be produced
 //! regardless of whether it is actually needed or not.
 
+mod bloat;
 mod move_check;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
@@ -215,7 +216,7 @@
 use rustc_hir::lang_items::LangItem;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::interpret::{AllocId, ErrorHandled, GlobalAlloc, Scalar};
-use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
+use rustc_middle::mir::mono::{InstantiationMode, MonoItem, MonoItemGraph, UsageKind, UsageMap};
 use rustc_middle::mir::traversal;
 use rustc_middle::mir::visit::Visitor as MirVisitor;
 use rustc_middle::mir::{self, Location, MentionedItem};
@@ -228,6 +229,7 @@
     TypeVisitableExt, VtblEntry,
 };
 use rustc_middle::ty::{GenericArgKind, GenericArgs};
+use rustc_middle::util::Providers;
 use rustc_middle::{bug, span_bug};
 use rustc_session::config::EntryFnType;
 use rustc_session::Limit;
@@ -240,24 +242,44 @@
 
 use crate::errors::{
     self, EncounteredErrorWhileInstantiating, NoOptimizedMir, RecursionLimit, TypeLengthLimit,
+    UnknownCguCollectionMode,
 };
 use move_check::MoveCheckState;
 
+pub(crate) use bloat::dump_mono_bloat;
+
 #[derive(PartialEq)]
 pub enum MonoItemCollectionStrategy {
     Eager,
     Lazy,
 }
 
-pub struct UsageMap<'tcx> {
-    // Maps every mono item to the mono items used by it.
-    used_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
-
-    // Maps every mono item to the mono items that use it.
-    user_map: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>>,
+/// The mono items used by an item, along with why they are used.
+struct MonoItems<'tcx> {
+    items: Vec<(Spanned<MonoItem<'tcx>>, UsageKind)>,
 }
 
-type MonoItems<'tcx> = Vec<Spanned<MonoItem<'tcx>>>;
+impl<'tcx> MonoItems<'tcx> {
+    fn new() -> Self {
+        MonoItems { items: Vec::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn push(&mut self, item: Spanned<MonoItem<'tcx>>, kind: UsageKind) {
+        self.items.push((item, kind));
+    }
+
+    fn extend<I: IntoIterator<Item = Spanned<MonoItem<'tcx>>>>(
+        &mut self,
+        items: I,
+        kind: UsageKind,
+    ) {
+        self.items.extend(items.into_iter().map(|item| (item, kind)));
+    }
+}
 
 /// The state that is shared across the concurrent threads that are doing collection.
 struct SharedState<'tcx> {
@@ -288,45 +310,6 @@ enum CollectionMode {
     MentionedItems,
 }
 
-impl<'tcx> UsageMap<'tcx> {
-    fn new() -> UsageMap<'tcx> {
-        UsageMap { used_map: FxHashMap::default(), user_map: FxHashMap::default() }
-    }
-
-    fn record_used<'a>(
-        &mut self,
-        user_item: MonoItem<'tcx>,
-        used_items: &'a [Spanned<MonoItem<'tcx>>],
-    ) where
-        'tcx: 'a,
-    {
-        let used_items: Vec<_> = used_items.iter().map(|item| item.node).collect();
-        for &used_item in used_items.iter() {
-            self.user_map.entry(used_item).or_default().push(user_item);
-        }
-
-        assert!(self.used_map.insert(user_item, used_items).is_none());
-    }
-
-    pub fn get_user_items(&self, item: MonoItem<'tcx>) -> &[MonoItem<'tcx>] {
-        self.user_map.get(&item).map(|items| items.as_slice()).unwrap_or(&[])
-    }
-
-    /// Internally iterate over all inlined items used by `item`.
-    pub fn for_each_inlined_used_item<F>(&self, tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>, mut f: F)
-    where
-        F: FnMut(MonoItem<'tcx>),
-    {
-        let used_items = self.used_map.get(&item).unwrap();
-        for used_item in used_items.iter() {
-            let is_inlined = used_item.instantiation_mode(tcx) == InstantiationMode::LocalCopy;
-            if is_inlined {
-                f(*used_item);
-            }
-        }
-    }
-}
-
 /// Collect all monomorphized items reachable from `starting_point`, and emit a note diagnostic if a
 /// post-monomorphization error is encountered during a collection step.
 ///
@@ -417,13 +400,16 @@ fn collect_items_rec<'tcx>(
                 }
 
                 if tcx.needs_thread_local_shim(def_id) {
-                    used_items.push(respan(
-                        starting_item.span,
-                        MonoItem::Fn(Instance {
-                            def: InstanceDef::ThreadLocalShim(def_id),
-                            args: GenericArgs::empty(),
-                        }),
-                    ));
+                    used_items.push(
+                        respan(
+                            starting_item.span,
+                            MonoItem::Fn(Instance {
+                                def: InstanceDef::ThreadLocalShim(def_id),
+                                args: GenericArgs::empty(),
+                            }),
+                        ),
+                        UsageKind::Static,
+                    );
                 }
             }
 
@@ -480,7 +466,10 @@ fn collect_items_rec<'tcx>(
                             let instance = Instance::mono(tcx, *def_id);
                             if should_codegen_locally(tcx, instance) {
                                 trace!("collecting static {:?}", def_id);
-                                used_items.push(dummy_spanned(MonoItem::Static(*def_id)));
+                                used_items.push(
+                                    dummy_spanned(MonoItem::Static(*def_id)),
+                                    UsageKind::Static,
+                                );
                             }
                         }
                         hir::InlineAsmOperand::In { .. }
@@ -518,13 +507,14 @@ fn collect_items_rec<'tcx>(
     // This is part of the output of collection and hence only relevant for "used" items.
     // ("Mentioned" items are only considered internally during collection.)
     if mode == CollectionMode::UsedItems {
-        state.usage_map.lock_mut().record_used(starting_item.node, &used_items);
+        let used_items = used_items.items.iter().map(|&(item, kind)| (item.node, kind)).collect();
+        state.usage_map.lock_mut().record_used(starting_item.node, used_items);
     }
 
     if mode == CollectionMode::MentionedItems {
         assert!(used_items.is_empty(), "'mentioned' collection should never encounter used items");
     } else {
-        for used_item in used_items {
+        for (used_item, _) in used_items.items {
             collect_items_rec(
                 tcx,
                 used_item,
@@ -538,7 +528,7 @@ fn collect_items_rec<'tcx>(
 
     // Walk over mentioned items *after* used items, so that if an item is both mentioned and used then
     // the loop above has fully collected it, so this loop will skip it.
-    for mentioned_item in mentioned_items {
+    for (mentioned_item, _) in mentioned_items.items {
         collect_items_rec(
             tcx,
             mentioned_item,
@@ -776,7 +766,10 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                     let instance =
                         Instance::resolve_closure(self.tcx, def_id, args, ty::ClosureKind::FnOnce);
                     if should_codegen_locally(self.tcx, instance) {
-                        self.used_items.push(create_fn_mono_item(self.tcx, instance, span));
+                        self.used_items.push(
+                            create_fn_mono_item(self.tcx, instance, span),
+                            UsageKind::FnPointer,
+                        );
                     }
                 } else {
                     bug!()
@@ -787,7 +780,7 @@ fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
                 let instance = Instance::mono(self.tcx, def_id);
                 if should_codegen_locally(self.tcx, instance) {
                     trace!("collecting thread-local static {:?}", def_id);
-                    self.used_items.push(respan(span, MonoItem::Static(def_id)));
+                    self.used_items.push(respan(span, MonoItem::Static(def_id)), UsageKind::Static);
                 }
             }
             _ => { /* not interesting */ }
@@ -813,7 +806,7 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
         let push_mono_lang_item = |this: &mut Self, lang_item: LangItem| {
             let instance = Instance::mono(tcx, tcx.require_lang_item(lang_item, Some(source)));
             if should_codegen_locally(tcx, instance) {
-                this.used_items.push(create_fn_mono_item(tcx, instance, source));
+                this.used_items.push(create_fn_mono_item(tcx, instance, source), UsageKind::Call);
             }
         };
 
@@ -847,7 +840,10 @@ fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Loc
                             let instance = Instance::mono(self.tcx, def_id);
                             if should_codegen_locally(self.tcx, instance) {
                                 trace!("collecting asm sym static {:?}", def_id);
-                                self.used_items.push(respan(source, MonoItem::Static(def_id)));
+                                self.used_items.push(
+                                    respan(source, MonoItem::Static(def_id)),
+                                    UsageKind::Static,
+                                );
                             }
                         }
                         _ => {}
@@ -902,7 +898,7 @@ fn visit_drop_use<'tcx>(
     output: &mut MonoItems<'tcx>,
 ) {
     let instance = Instance::resolve_drop_in_place(tcx, ty);
-    visit_instance_use(tcx, instance, is_direct_call, source, output);
+    visit_instance_use(tcx, instance, is_direct_call, UsageKind::DropGlue, source, output);
 }
 
 /// For every call of this function in the visitor, make sure there is a matching call in the
@@ -923,7 +919,8 @@ fn visit_fn_use<'tcx>(
                 _ => bug!("failed to resolve instance for {ty}"),
             }
         };
-        visit_instance_use(tcx, instance, is_direct_call, source, output);
+        let kind = if is_direct_call { UsageKind::Call } else { UsageKind::FnPointer };
+        visit_instance_use(tcx, instance, is_direct_call, kind, source, output);
     }
 }
 
@@ -931,6 +928,7 @@ fn visit_instance_use<'tcx>(
     tcx: TyCtxt<'tcx>,
     instance: ty::Instance<'tcx>,
     is_direct_call: bool,
+    kind: UsageKind,
     source: Span,
     output: &mut MonoItems<'tcx>,
 ) {
@@ -948,13 +946,13 @@ fn visit_instance_use<'tcx>(
             let def_id = tcx.lang_items().get(LangItem::PanicNounwind).unwrap();
             let panic_instance = Instance::mono(tcx, def_id);
             if should_codegen_locally(tcx, panic_instance) {
-                output.push(create_fn_mono_item(tcx, panic_instance, source));
+                output.push(create_fn_mono_item(tcx, panic_instance, source), UsageKind::Call);
             }
         } else if tcx.has_attr(def_id, sym::rustc_intrinsic) {
             // Codegen the fallback body of intrinsics with fallback bodies
             let instance = ty::Instance::new(def_id, instance.args);
             if should_codegen_locally(tcx, instance) {
-                output.push(create_fn_mono_item(tcx, instance, source));
+                output.push(create_fn_mono_item(tcx, instance, source), kind);
             }
         }
     }
@@ -971,7 +969,7 @@ fn visit_instance_use<'tcx>(
         ty::InstanceDef::DropGlue(_, None) | ty::InstanceDef::AsyncDropGlueCtorShim(_, None) => {
             // Don't need to emit noop drop glue if we are calling directly.
             if !is_direct_call {
-                output.push(create_fn_mono_item(tcx, instance, source));
+                output.push(create_fn_mono_item(tcx, instance, source), kind);
             }
         }
         ty::InstanceDef::DropGlue(_, Some(_))
@@ -985,7 +983,7 @@ fn visit_instance_use<'tcx>(
         | ty::InstanceDef::FnPtrShim(..)
         | ty::InstanceDef::CloneShim(..)
         | ty::InstanceDef::FnPtrAddrShim(..) => {
-            output.push(create_fn_mono_item(tcx, instance, source));
+            output.push(create_fn_mono_item(tcx, instance, source), kind);
         }
     }
 }
@@ -1193,7 +1191,7 @@ fn create_mono_items_for_vtable_methods<'tcx>(
                 }
             })
             .map(|item| create_fn_mono_item(tcx, item, source));
-        output.extend(methods);
+        output.extend(methods, UsageKind::VtableMethod);
     }
 
     // Also add the destructor.
@@ -1208,7 +1206,7 @@ fn collect_alloc<'tcx>(tcx: TyCtxt<'tcx>, alloc_id: AllocId, output: &mut MonoIt
             let instance = Instance::mono(tcx, def_id);
             if should_codegen_locally(tcx, instance) {
                 trace!("collecting static {:?}", def_id);
-                output.push(dummy_spanned(MonoItem::Static(def_id)));
+                output.push(dummy_spanned(MonoItem::Static(def_id)), UsageKind::Static);
             }
         }
         GlobalAlloc::Memory(alloc) => {
@@ -1226,7 +1224,7 @@ fn collect_alloc<'tcx>(tcx: TyCtxt<'tcx>, alloc_id: AllocId, output: &mut MonoIt
         GlobalAlloc::Function(fn_instance) => {
             if should_codegen_locally(tcx, fn_instance) {
                 trace!("collecting {:?} with {:#?}", alloc_id, fn_instance);
-                output.push(create_fn_mono_item(tcx, fn_instance, DUMMY_SP));
+                output.push(create_fn_mono_item(tcx, fn_instance, DUMMY_SP), UsageKind::FnPointer);
             }
         }
         GlobalAlloc::VTable(ty, trait_ref) => {
@@ -1261,7 +1259,7 @@ fn collect_items_of_instance<'tcx>(
     mentioned_items: &mut MonoItems<'tcx>,
     mode: CollectionMode,
 ) {
-    let body = tcx.instance_mir(instance.def);
+    let body = tcx.codegen_instance_mir(instance);
     // Naively, in "used" collection mode, all functions get added to *both* `used_items` and
     // `mentioned_items`. Mentioned items processing will then notice that they have already been
     // visited, but at that point each mentioned item has been monomorphized, added to the
@@ -1324,7 +1322,14 @@ fn visit_mentioned_item<'tcx>(
                 // for "mentioned" item collection.
                 // We can set `is_direct_call`; that just means we'll skip a bunch of shims that anyway
                 // can't have their own failing constants.
-                visit_instance_use(tcx, instance, /*is_direct_call*/ true, span, output);
+                visit_instance_use(
+                    tcx,
+                    instance,
+                    /*is_direct_call*/ true,
+                    UsageKind::Call,
+                    span,
+                    output,
+                );
             }
         }
         MentionedItem::Drop(ty) => {
@@ -1347,7 +1352,7 @@ fn visit_mentioned_item<'tcx>(
                 let instance =
                     Instance::resolve_closure(tcx, def_id, args, ty::ClosureKind::FnOnce);
                 if should_codegen_locally(tcx, instance) {
-                    output.push(create_fn_mono_item(tcx, instance, span));
+                    output.push(create_fn_mono_item(tcx, instance, span), UsageKind::FnPointer);
                 }
             } else {
                 bug!()
@@ -1385,7 +1390,7 @@ fn collect_const_value<'tcx>(
 #[instrument(skip(tcx, mode), level = "debug")]
 fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionStrategy) -> Vec<MonoItem<'_>> {
     debug!("collecting roots");
-    let mut roots = Vec::new();
+    let mut roots = MonoItems::new();
 
     {
         let entry_fn = tcx.entry_fn(());
@@ -1411,8 +1416,9 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionStrategy) -> Vec<MonoI
     // whose predicates hold. Luckily, items that aren't instantiable
     // can't actually be used, so we can just skip codegenning them.
     roots
+        .items
         .into_iter()
-        .filter_map(|Spanned { node: mono_item, .. }| {
+        .filter_map(|(Spanned { node: mono_item, .. }, _)| {
             mono_item.is_instantiable(tcx).then_some(mono_item)
         })
         .collect()
@@ -1421,6 +1427,9 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionStrategy) -> Vec<MonoI
 struct RootCollector<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     strategy: MonoItemCollectionStrategy,
+    /// Roots are not used by another item. The roots themselves are marked with
+    /// [`UsageKind::Root`], and the kinds of the items that are roots because of a drop or a
+    /// constant are ignored.
     output: &'a mut MonoItems<'tcx>,
     entry_fn: Option<(DefId, EntryFnType)>,
 }
@@ -1443,12 +1452,12 @@ fn process_item(&mut self, id: hir::ItemId) {
                     "RootCollector: ItemKind::GlobalAsm({})",
                     self.tcx.def_path_str(id.owner_id)
                 );
-                self.output.push(dummy_spanned(MonoItem::GlobalAsm(id)));
+                self.output.push(dummy_spanned(MonoItem::GlobalAsm(id)), UsageKind::Root);
             }
             DefKind::Static { .. } => {
                 let def_id = id.owner_id.to_def_id();
                 debug!("RootCollector: ItemKind::Static({})", self.tcx.def_path_str(def_id));
-                self.output.push(dummy_spanned(MonoItem::Static(def_id)));
+                self.output.push(dummy_spanned(MonoItem::Static(def_id)), UsageKind::Root);
             }
             DefKind::Const => {
                 // const items only generate mono items if they are
@@ -1501,7 +1510,7 @@ fn push_if_root(&mut self, def_id: LocalDefId) {
             debug!("found root");
 
             let instance = Instance::mono(self.tcx, def_id.to_def_id());
-            self.output.push(create_fn_mono_item(self.tcx, instance, DUMMY_SP));
+            self.output.push(create_fn_mono_item(self.tcx, instance, DUMMY_SP), UsageKind::Root);
         }
     }
 
@@ -1537,7 +1546,7 @@ fn push_extra_entry_roots(&mut self) {
             self.tcx.mk_args(&[main_ret_ty.into()]),
         );
 
-        self.output.push(create_fn_mono_item(self.tcx, start_instance, DUMMY_SP));
+        self.output.push(create_fn_mono_item(self.tcx, start_instance, DUMMY_SP), UsageKind::Root);
     }
 }
 
@@ -1609,7 +1618,7 @@ fn create_mono_items_for_default_impls<'tcx>(
 
         let mono_item = create_fn_mono_item(tcx, instance, DUMMY_SP);
         if mono_item.node.is_instantiable(tcx) && should_codegen_locally(tcx, instance) {
-            output.push(mono_item);
+            output.push(mono_item, UsageKind::Root);
         }
     }
 }
@@ -1618,11 +1627,42 @@ fn create_mono_items_for_default_impls<'tcx>(
 // Top-level entry point, tying it all together
 //=-----------------------------------------------------------------------------
 
+fn collection_strategy(tcx: TyCtxt<'_>) -> MonoItemCollectionStrategy {
+    match tcx.sess.opts.unstable_opts.print_mono_items {
+        Some(ref s) => {
+            let mode = s.to_lowercase();
+            let mode = mode.trim();
+            if mode == "eager" {
+                MonoItemCollectionStrategy::Eager
+            } else {
+                if mode != "lazy" {
+                    tcx.dcx().emit_warn(UnknownCguCollectionMode { mode });
+                }
+
+                MonoItemCollectionStrategy::Lazy
+            }
+        }
+        None => {
+            if tcx.sess.link_dead_code() {
+                MonoItemCollectionStrategy::Eager
+            } else {
+                MonoItemCollectionStrategy::Lazy
+            }
+        }
+    }
+}
+
+fn collect_mono_item_graph(tcx: TyCtxtAt<'_>) -> MonoItemGraph<'_> {
+    let strategy = collection_strategy(tcx.tcx);
+    let (roots, items, usage_map) = collect_crate_mono_items(tcx.tcx, strategy);
+    MonoItemGraph { roots, items, usage_map }
+}
+
 #[instrument(skip(tcx, strategy), level = "debug")]
-pub fn collect_crate_mono_items(
+fn collect_crate_mono_items(
     tcx: TyCtxt<'_>,
     strategy: MonoItemCollectionStrategy,
-) -> (FxHashSet<MonoItem<'_>>, UsageMap<'_>) {
+) -> (Vec<MonoItem<'_>>, FxHashSet<MonoItem<'_>>, UsageMap<'_>) {
     let _prof_timer = tcx.prof.generic_activity("monomorphization_collector");
 
     let roots = tcx
@@ -1642,7 +1682,7 @@ pub fn collect_crate_mono_items(
         let state: LRef<'_, _> = &mut state;
 
         tcx.sess.time("monomorphization_collector_graph_walk", || {
-            par_for_each_in(roots, |root| {
+            par_for_each_in(roots.clone(), |root| {
                 let mut recursion_depths = DefIdMap::default();
                 collect_items_rec(
                     tcx,
@@ -1656,5 +1696,9 @@ pub fn collect_crate_mono_items(
         });
     }
 
-    (state.visited.into_inner(), state.usage_map.into_inner())
+    (roots, state.visited.into_inner(), state.usage_map.into_inner())
+}
+
+pub fn provide(providers: &mut Providers) {
+    providers.hooks.collect_mono_item_graph = collect_mono_item_graph;
 }
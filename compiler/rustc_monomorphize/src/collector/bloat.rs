@@ -0,0 +1,140 @@
+//! The monomorphization bloat report of `-Z dump-mono-bloat`.
+//!
+//! The collected mono items are grouped by the definition they are instances of, like all the
+//! instances of a generic function or all the drop glue. Each group lists its instances with
+//! their size estimates, along with the shortest chain of mono items that caused each instance
+//! to be collected, starting from one of the roots of the collection, like `main` or the public
+//! functions of a library.
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_middle::mir::mono::{MonoItem, UsageMap};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::{DefId, LOCAL_CRATE};
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(serde::Serialize)]
+struct Report<'a> {
+    crate_name: String,
+    instances: usize,
+    size_estimate: usize,
+    definitions: Vec<Definition<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct Definition<'a> {
+    name: String,
+    span: String,
+    /// The number of instances of the definition.
+    instantiations: usize,
+    /// The sum of the size estimates of the instances.
+    size_estimate: usize,
+    /// The instances, from the largest to the smallest.
+    instances: Vec<Instance<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct Instance<'a> {
+    name: &'a str,
+    size_estimate: usize,
+    /// The mono items that caused this instance to be collected, starting from a root and
+    /// ending with the item that directly uses this instance. Empty for roots.
+    used_by: Vec<&'a str>,
+}
+
+/// Writes the bloat report of the mono items collected for the current crate to
+/// `<crate>.mono_bloat.json` in the given output directory.
+pub(crate) fn dump_mono_bloat<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    roots: &[MonoItem<'tcx>],
+    items: &FxHashSet<MonoItem<'tcx>>,
+    usage_map: &UsageMap<'tcx>,
+    output_directory: &Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output_directory = if let Some(ref directory) = output_directory {
+        fs::create_dir_all(directory)?;
+        directory
+    } else {
+        Path::new(".")
+    };
+
+    // Each name is printed once, as an item can be part of the chains of many others.
+    let names: FxHashMap<MonoItem<'tcx>, String> =
+        items.iter().map(|&item| (item, with_no_trimmed_paths!(item.to_string()))).collect();
+    let name = |item: &MonoItem<'tcx>| &names[item][..];
+
+    // Find the chain of each item on a shortest path from the roots, with a breadth-first search
+    // from the roots. The chain of an item extends the chain of its first user, which is found
+    // first. The roots are sorted by name to make the chains deterministic.
+    let mut roots: Vec<_> = roots.iter().filter(|item| items.contains(item)).copied().collect();
+    roots.sort_by_key(|item| name(item));
+    // Items that the usage map doesn't connect to a root, if any, are searched from afterwards
+    // in the same order, so that every item has a chain even if it's part of a cycle.
+    let mut remaining: Vec<_> = items.iter().copied().collect();
+    remaining.sort_by_key(|item| std::cmp::Reverse(name(item)));
+    let mut used_by: FxHashMap<MonoItem<'tcx>, Vec<&str>> = Default::default();
+    let mut queue = VecDeque::new();
+    for root in roots.into_iter().chain(std::iter::from_fn(|| remaining.pop())) {
+        if used_by.contains_key(&root) {
+            continue;
+        }
+        used_by.insert(root, Vec::new());
+        queue.push_back(root);
+        while let Some(user) = queue.pop_front() {
+            for &(used, _) in usage_map.used_map.get(&user).into_iter().flatten() {
+                if !used_by.contains_key(&used) {
+                    let mut chain = used_by[&user].clone();
+                    chain.push(name(&user));
+                    used_by.insert(used, chain);
+                    queue.push_back(used);
+                }
+            }
+        }
+    }
+
+    let mut instances_per_def_id: FxHashMap<DefId, Vec<Instance<'_>>> = Default::default();
+    for item in items {
+        instances_per_def_id.entry(item.def_id()).or_default().push(Instance {
+            name: name(item),
+            size_estimate: item.size_estimate(tcx),
+            used_by: used_by.remove(item).unwrap_or_default(),
+        });
+    }
+
+    let mut definitions: Vec<_> = instances_per_def_id
+        .into_iter()
+        .map(|(def_id, mut instances)| {
+            instances.sort_by(|a, b| {
+                b.size_estimate.cmp(&a.size_estimate).then_with(|| a.name.cmp(&b.name))
+            });
+            Definition {
+                name: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+                span: tcx.sess.source_map().span_to_embeddable_string(tcx.def_span(def_id)),
+                instantiations: instances.len(),
+                size_estimate: instances.iter().map(|instance| instance.size_estimate).sum(),
+                instances,
+            }
+        })
+        .collect();
+    // Output the definitions sorted by total instantiated size, from heaviest to lightest.
+    definitions
+        .sort_by(|a, b| b.size_estimate.cmp(&a.size_estimate).then_with(|| a.name.cmp(&b.name)));
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    let report = Report {
+        crate_name: crate_name.to_string(),
+        instances: items.len(),
+        size_estimate: definitions.iter().map(|definition| definition.size_estimate).sum(),
+        definitions,
+    };
+
+    let output_path = output_directory.join(format!("{crate_name}.mono_bloat.json"));
+    let mut file = BufWriter::new(File::create(output_path)?);
+    serde_json::to_writer_pretty(&mut file, &report)?;
+    writeln!(file)?;
+    file.flush()?;
+    Ok(())
+}
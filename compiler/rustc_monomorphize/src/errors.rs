@@ -80,12 +80,24 @@ pub struct SymbolAlreadyDefined {
     pub symbol: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(monomorphize_couldnt_dump_mono_bloat)]
+pub struct CouldntDumpMonoBloat {
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(monomorphize_couldnt_dump_mono_stats)]
 pub struct CouldntDumpMonoStats {
     pub error: String,
 }
 
+#[derive(Diagnostic)]
+#[diag(monomorphize_couldnt_write_item_time_report)]
+pub struct CouldntWriteItemTimeReport {
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(monomorphize_encountered_error_while_instantiating)]
 pub struct EncounteredErrorWhileInstantiating {
@@ -0,0 +1,235 @@
+//! The report of `-Z item-time-report`, which ranks items by the compile time spent on them.
+//!
+//! The time of queries keyed by an item (type checking, borrow checking, MIR building and
+//! optimizations, ...) and the time spent generating backend code for each instance of an item
+//! are recorded by `CodeStats::time_item`. This module combines them with the instances of each
+//! item found by partitioning, so that the cost of generic items instantiated many times shows
+//! up next to their instantiation counts.
+//!
+//! The time the codegen backend spends optimizing each codegen unit is only known once codegen
+//! has finished, after the type context is gone, so it is added to the report just before it is
+//! written.
+
+use crate::errors::CouldntWriteItemTimeReport;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::SwitchWithOptPath;
+use rustc_session::Session;
+use rustc_span::def_id::{DefId, LOCAL_CRATE};
+use std::cmp;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(serde::Serialize)]
+struct Report {
+    crate_name: String,
+    /// The time attributed to items, in seconds.
+    total_time: f64,
+    /// The time attributed to generating backend code for items, in seconds.
+    total_codegen_time: f64,
+    /// The time the codegen backend spent optimizing codegen units, in seconds.
+    total_optimization_time: f64,
+    items: Vec<Item>,
+    codegen_units: Vec<CodegenUnit>,
+}
+
+#[derive(serde::Serialize)]
+struct Item {
+    name: String,
+    span: String,
+    /// The time spent on the item by all activities, in seconds.
+    time: f64,
+    /// The percentage of `Report::total_time` spent on the item.
+    percent_of_total: f64,
+    /// The time spent generating backend code for the instances of the item, in seconds.
+    codegen_time: f64,
+    /// The percentage of `Report::total_codegen_time` spent on the item.
+    percent_of_codegen: f64,
+    /// The number of distinct instances of the item codegened in this crate.
+    instantiations: usize,
+    /// The sum of the size estimates of the instances of the item, in all codegen units.
+    size_estimate: usize,
+    activities: Vec<Activity>,
+}
+
+#[derive(serde::Serialize)]
+struct CodegenUnit {
+    name: String,
+    /// The time the codegen backend spent optimizing the codegen unit, in seconds.
+    optimization_time: f64,
+    /// The percentage of `Report::total_optimization_time` spent on the codegen unit.
+    percent_of_optimization: f64,
+    /// The number of items in the codegen unit.
+    items: usize,
+    size_estimate: usize,
+}
+
+#[derive(serde::Serialize)]
+struct Activity {
+    /// The name of the query, or `codegen`.
+    name: &'static str,
+    time: f64,
+    count: usize,
+}
+
+/// The `-Z item-time-report` of the current crate, which is written once codegen has finished.
+pub struct ItemTimeReport {
+    report: Report,
+    /// The number of items and the size estimate of each codegen unit.
+    codegen_units: FxHashMap<String, (usize, usize)>,
+    output_directory: PathBuf,
+}
+
+/// Collects the `-Z item-time-report` of the current crate. This must run after the backend code
+/// of all items has been generated, so that their codegen time is recorded.
+pub fn item_time_report(tcx: TyCtxt<'_>) -> Option<ItemTimeReport> {
+    let SwitchWithOptPath::Enabled(ref directory) = tcx.sess.opts.unstable_opts.item_time_report
+    else {
+        return None;
+    };
+    let mut report = ItemTimeReport {
+        report: collect_report(tcx),
+        codegen_units: Default::default(),
+        output_directory: directory.clone().unwrap_or_else(|| PathBuf::from(".")),
+    };
+    if tcx.sess.opts.output_types.should_codegen() {
+        for cgu in tcx.collect_and_partition_mono_items(()).1 {
+            report
+                .codegen_units
+                .insert(cgu.name().to_string(), (cgu.items().len(), cgu.size_estimate()));
+        }
+    }
+    Some(report)
+}
+
+impl ItemTimeReport {
+    /// Adds the optimization time of each codegen unit to the report, and writes it.
+    pub fn write(mut self, sess: &Session) {
+        let optimization_times = sess.code_stats.optimization_times();
+        let total_optimization_time: Duration = optimization_times.values().sum();
+        let mut codegen_units: Vec<_> = optimization_times
+            .into_iter()
+            .map(|(name, time)| {
+                let (items, size_estimate) =
+                    self.codegen_units.get(&name).copied().unwrap_or_default();
+                CodegenUnit {
+                    name,
+                    optimization_time: time.as_secs_f64(),
+                    percent_of_optimization: percent(time, total_optimization_time),
+                    items,
+                    size_estimate,
+                }
+            })
+            .collect();
+        codegen_units.sort_by(|a, b| {
+            b.optimization_time.total_cmp(&a.optimization_time).then_with(|| a.name.cmp(&b.name))
+        });
+        self.report.total_optimization_time = total_optimization_time.as_secs_f64();
+        self.report.codegen_units = codegen_units;
+
+        if let Err(err) = self.write_to_file() {
+            sess.dcx().emit_fatal(CouldntWriteItemTimeReport { error: err.to_string() });
+        }
+    }
+
+    fn write_to_file(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(&self.output_directory)?;
+        let output_path =
+            self.output_directory.join(format!("{}.item_times.json", self.report.crate_name));
+        let mut file = BufWriter::new(File::create(output_path)?);
+        serde_json::to_writer_pretty(&mut file, &self.report)?;
+        writeln!(file)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+fn percent(time: Duration, total: Duration) -> f64 {
+    if total.is_zero() { 0.0 } else { time.as_secs_f64() * 100.0 / total.as_secs_f64() }
+}
+
+fn collect_report(tcx: TyCtxt<'_>) -> Report {
+    let item_times = tcx.sess.code_stats.item_times();
+
+    // The instances of each item, if codegen ran. Partitioning has already happened by now, so
+    // this doesn't monomorphize anything.
+    let mut instances: FxHashMap<DefId, (FxHashSet<MonoItem<'_>>, usize)> = Default::default();
+    if tcx.sess.opts.output_types.should_codegen() {
+        for cgu in tcx.collect_and_partition_mono_items(()).1 {
+            for mono_item in cgu.items().keys().filter(|mono_item| mono_item.is_user_defined()) {
+                let (items, size_estimate) = instances.entry(mono_item.def_id()).or_default();
+                items.insert(*mono_item);
+                *size_estimate += mono_item.size_estimate(tcx);
+            }
+        }
+    }
+
+    let mut activities_per_item: FxHashMap<DefId, Vec<(&'static str, Duration, usize)>> =
+        Default::default();
+    for (&(def_id, activity), item_time) in &item_times {
+        activities_per_item.entry(def_id).or_default().push((
+            activity,
+            item_time.self_time,
+            item_time.count,
+        ));
+    }
+    // Items without recorded time, like upstream generic items whose instances were only
+    // created and not codegened here, are still included with their instances.
+    for &def_id in instances.keys() {
+        activities_per_item.entry(def_id).or_default();
+    }
+
+    let total_time: Duration = item_times.values().map(|item_time| item_time.self_time).sum();
+    let total_codegen_time: Duration = item_times
+        .iter()
+        .filter(|((_, activity), _)| *activity == "codegen")
+        .map(|(_, item_time)| item_time.self_time)
+        .sum();
+    let mut items: Vec<_> = activities_per_item
+        .into_iter()
+        .map(|(def_id, mut activities)| {
+            activities.sort_by_key(|&(name, time, _)| (cmp::Reverse(time), name));
+            let time: Duration = activities.iter().map(|&(_, time, _)| time).sum();
+            let codegen_time: Duration = activities
+                .iter()
+                .filter(|&&(name, _, _)| name == "codegen")
+                .map(|&(_, time, _)| time)
+                .sum();
+            let (instantiations, size_estimate) = instances
+                .get(&def_id)
+                .map_or((0, 0), |(items, size_estimate)| (items.len(), *size_estimate));
+            let span = tcx.def_span(def_id);
+            Item {
+                name: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+                span: tcx.sess.source_map().span_to_embeddable_string(span),
+                time: time.as_secs_f64(),
+                percent_of_total: percent(time, total_time),
+                codegen_time: codegen_time.as_secs_f64(),
+                percent_of_codegen: percent(codegen_time, total_codegen_time),
+                instantiations,
+                size_estimate,
+                activities: activities
+                    .into_iter()
+                    .map(|(name, time, count)| Activity { name, time: time.as_secs_f64(), count })
+                    .collect(),
+            }
+        })
+        .collect();
+    // Rank the items from the most to the least expensive.
+    items.sort_by(|a, b| {
+        b.time.total_cmp(&a.time).then_with(|| b.size_estimate.cmp(&a.size_estimate))
+    });
+
+    Report {
+        crate_name: tcx.crate_name(LOCAL_CRATE).to_string(),
+        total_time: total_time.as_secs_f64(),
+        total_codegen_time: total_codegen_time.as_secs_f64(),
+        total_optimization_time: 0.0,
+        items,
+        codegen_units: Vec::new(),
+    }
+}
@@ -4,23 +4,26 @@
 
 use rustc_hir::lang_items::LangItem;
 use rustc_middle::bug;
-use rustc_middle::query::{Providers, TyCtxtAt};
+use rustc_middle::query::TyCtxtAt;
 use rustc_middle::traits;
 use rustc_middle::ty::adjustment::CustomCoerceUnsized;
 use rustc_middle::ty::Instance;
 use rustc_middle::ty::TyCtxt;
 use rustc_middle::ty::{self, Ty};
+use rustc_middle::util::Providers;
 use rustc_span::def_id::DefId;
 use rustc_span::def_id::LOCAL_CRATE;
 use rustc_span::ErrorGuaranteed;
 
 mod collector;
 mod errors;
+mod item_times;
 mod partitioning;
 mod polymorphize;
 mod util;
 
 use collector::should_codegen_locally;
+pub use item_times::{item_time_report, ItemTimeReport};
 
 rustc_fluent_macro::fluent_messages! { "../messages.ftl" }
 
@@ -75,6 +78,8 @@ fn is_llvm_intrinsic(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
 }
 
 pub fn provide(providers: &mut Providers) {
+    collector::provide(providers);
     partitioning::provide(providers);
     polymorphize::provide(providers);
+    providers.codegen_instance_mir = |tcx, instance| tcx.instance_mir(instance.def);
 }
@@ -12,12 +12,14 @@
 use crate::profiling_support::QueryKeyStringCache;
 use field_offset::offset_of;
 use rustc_data_structures::stable_hasher::HashStable;
-use rustc_data_structures::sync::AtomicU64;
+use rustc_data_structures::sync::{AtomicU64, Lrc};
 use rustc_middle::arena::Arena;
 use rustc_middle::dep_graph::DepNodeIndex;
 use rustc_middle::dep_graph::{self, DepKind, DepKindStruct};
 use rustc_middle::query::erase::{erase, restore, Erase};
-use rustc_middle::query::on_disk_cache::{CacheEncoder, EncodedDepNodeIndex, OnDiskCache};
+use rustc_middle::query::on_disk_cache::{
+    CacheEncoder, EncodedDepNodeIndex, OnDiskCache, SharedCacheStore,
+};
 use rustc_middle::query::plumbing::{DynamicQuery, QuerySystem, QuerySystemFns};
 use rustc_middle::query::AsLocalKey;
 use rustc_middle::query::{
@@ -200,6 +202,7 @@ pub fn query_system<'tcx>(
     local_providers: Providers,
     extern_providers: ExternProviders,
     on_disk_cache: Option<OnDiskCache<'tcx>>,
+    shared_cache: Option<Lrc<dyn SharedCacheStore>>,
     incremental: bool,
 ) -> QuerySystem<'tcx> {
     QuerySystem {
@@ -208,6 +211,7 @@ pub fn query_system<'tcx>(
         caches: Default::default(),
         dynamic_queries: dynamic_queries(),
         on_disk_cache,
+        shared_cache,
         fns: QuerySystemFns {
             engine: engine(incremental),
             local_providers,
@@ -548,6 +548,23 @@ pub fn __rust_begin_short_backtrace<F, T>(f: F) -> T
     result
 }
 
+/// Computes the result of a query with `compute`. With `-Z item-time-report`, the time it took
+/// is recorded for the item the query is keyed by, if any.
+#[inline(always)]
+pub(crate) fn compute_and_time_item<'tcx, K: Key, R>(
+    tcx: TyCtxt<'tcx>,
+    query_name: &'static str,
+    key: K,
+    compute: impl FnOnce(K) -> R,
+) -> R {
+    if tcx.sess.prof.time_items() {
+        let def_id = key.key_as_def_id();
+        tcx.sess.code_stats.time_item(def_id, query_name, || compute(key))
+    } else {
+        compute(key)
+    }
+}
+
 // NOTE: `$V` isn't used here, but we still need to match on it so it can be passed to other macros
 // invoked by `rustc_query_append`.
 macro_rules! define_queries {
@@ -615,16 +632,18 @@ pub fn dynamic_query<'tcx>() -> DynamicQuery<'tcx, queries::$name::Storage<'tcx>
                     compute: |tcx, key| {
                         #[cfg(debug_assertions)]
                         let _guard = tracing::span!(tracing::Level::TRACE, stringify!($name), ?key).entered();
-                        __rust_begin_short_backtrace(||
-                            queries::$name::provided_to_erased(
-                                tcx,
-                                {
-                                    let ret = call_provider!([$($modifiers)*][tcx, $name, key]);
-                                    tracing::trace!(?ret);
-                                    ret
-                                }
+                        $crate::plumbing::compute_and_time_item(tcx, stringify!($name), key, |key| {
+                            __rust_begin_short_backtrace(||
+                                queries::$name::provided_to_erased(
+                                    tcx,
+                                    {
+                                        let ret = call_provider!([$($modifiers)*][tcx, $name, key]);
+                                        tracing::trace!(?ret);
+                                        ret
+                                    }
+                                )
                             )
-                        )
+                        })
                     },
                     can_load_from_disk: should_ever_cache_on_disk!([$($modifiers)*] true false),
                     try_load_from_disk: should_ever_cache_on_disk!([$($modifiers)*] {
@@ -211,6 +211,7 @@ pub(crate) fn alloc_self_profile_query_strings_for_query_cache<'tcx, C>(
                 // Create the string version of the query-key
                 let query_key = query_key.to_self_profile_string(&mut query_string_builder);
                 let event_id = event_id_builder.from_label_and_arg(query_name, query_key);
+                profiler.map_event_id_to_label(event_id, query_name);
 
                 // Doing this in bulk might be a good idea:
                 profiler.map_query_invocation_id_to_string(
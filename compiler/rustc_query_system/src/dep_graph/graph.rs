@@ -107,6 +107,11 @@ pub(crate) struct DepGraphData<D: Deps> {
     /// a particular query result was decoded from disk
     /// (not just marked green)
     debug_loaded_from_disk: Lock<FxHashSet<DepNode>>,
+
+    /// The nodes of the previous dep-graph whose queries were re-executed in
+    /// this session, whether or not their result changed. This is only
+    /// recorded for `-Zincremental-report`.
+    reexecuted: Option<Lock<Vec<SerializedDepNodeIndex>>>,
 }
 
 pub fn hash_result<R>(hcx: &mut StableHashingContext<'_>, result: &R) -> Fingerprint
@@ -126,6 +131,7 @@ pub fn new(
         encoder: FileEncoder,
         record_graph: bool,
         record_stats: bool,
+        record_reexecuted: bool,
     ) -> DepGraph<D> {
         let prev_graph_node_count = prev_graph.node_count();
 
@@ -180,6 +186,7 @@ pub fn new(
                 previous: prev_graph,
                 colors,
                 debug_loaded_from_disk: Default::default(),
+                reexecuted: record_reexecuted.then(Default::default),
             })),
             virtual_dep_node_index: Lrc::new(AtomicU32::new(0)),
         }
@@ -391,6 +398,10 @@ pub(crate) fn with_task<Ctxt: HasDepContext<Deps = D>, A: Debug, R>(
             );
 
             self.colors.insert(prev_index, color);
+
+            if let Some(reexecuted) = &self.reexecuted {
+                reexecuted.lock().push(prev_index);
+            }
         }
 
         (result, dep_node_index)
@@ -934,6 +945,26 @@ pub fn is_green(&self, dep_node: &DepNode) -> bool {
         self.node_color(dep_node).is_some_and(|c| c.is_green())
     }
 
+    /// Returns the dep-graph of the previous compilation session, if any.
+    pub fn previous_graph(&self) -> Option<&SerializedDepGraph> {
+        self.data.as_ref().map(|data| &*data.previous)
+    }
+
+    /// Returns true if the node of the previous dep-graph with the given index
+    /// has been marked as red during the current compilation session.
+    pub fn is_previous_node_red(&self, prev_index: SerializedDepNodeIndex) -> bool {
+        self.data
+            .as_ref()
+            .is_some_and(|data| data.colors.get(prev_index) == Some(DepNodeColor::Red))
+    }
+
+    /// Returns the nodes of the previous dep-graph whose queries were
+    /// re-executed during the current compilation session. This is only
+    /// available if re-executions were recorded when creating the dep-graph.
+    pub fn reexecuted_previous_nodes(&self) -> Option<Vec<SerializedDepNodeIndex>> {
+        Some(self.data.as_ref()?.reexecuted.as_ref()?.lock().clone())
+    }
+
     /// This method loads all on-disk cacheable query results into memory, so
     /// they can be written out to the new cache file again. Most query results
     /// will already be in memory but in the case where we marked something as
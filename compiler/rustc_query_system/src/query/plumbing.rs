@@ -274,8 +274,15 @@ fn wait_for_query<Q, Qcx>(
     let query_blocked_prof_timer = qcx.dep_context().profiler().query_blocked();
 
     // With parallel queries we might just have to wait on some other
-    // thread.
-    let result = latch.wait_on(current, span);
+    // thread. The query is computed on that thread, so the time spent
+    // waiting isn't spent on the item of the current query.
+    let result = if qcx.dep_context().profiler().time_items() {
+        rustc_session::code_stats::CodeStats::exclude_from_item_time(|| {
+            latch.wait_on(current, span)
+        })
+    } else {
+        latch.wait_on(current, span)
+    };
 
     match result {
         Ok(()) => {
@@ -1,3 +1,27 @@
+session_applied_suggestions =
+    applied {$applied} machine-applicable {$applied ->
+        [one] suggestion
+        *[other] suggestions
+    } to {$files} {$files ->
+        [one] file
+        *[other] files
+    }, written to `{$path}`
+
+session_applied_suggestions_overlapping =
+    skipped {$overlapping} {$overlapping ->
+        [one] suggestion that overlaps
+        *[other] suggestions that overlap
+    } with an applied suggestion
+
+session_applied_suggestions_unappliable =
+    skipped {$unappliable} {$unappliable ->
+        [one] suggestion that has
+        *[other] suggestions that have
+    } several alternatives or {$unappliable ->
+        [one] changes
+        *[other] change
+    } code outside the local source files
+
 session_binary_float_literal_not_supported = binary float literal is not supported
 session_branch_protection_requires_aarch64 = `-Zbranch-protection` is only supported on aarch64
 
@@ -18,6 +42,8 @@ session_expr_parentheses_needed = parentheses are required to parse this as an e
 
 session_failed_to_create_profiler = failed to create profiler: {$err}
 
+session_failed_to_write_self_profile_output = failed to write self-profile output `{$path}`: {$err}
+
 session_feature_diagnostic_for_issue =
     see issue #{$n} <https://github.com/rust-lang/rust/issues/{$n}> for more information
 
@@ -43,6 +69,8 @@ session_hexadecimal_float_literal_not_supported = hexadecimal float literal is n
 session_incompatible_linker_flavor = linker flavor `{$flavor}` is incompatible with the current target
     .note = compatible flavors are: {$compatible_list}
 
+session_incremental_report_requires_incremental = `-Zincremental-report` requires `-Cincremental`
+
 session_instrumentation_not_supported = {$us} instrumentation is not supported for this target
 
 session_int_literal_too_large = integer literal is too large
@@ -3,7 +3,9 @@
 use rustc_span::def_id::DefId;
 use rustc_span::Symbol;
 use rustc_target::abi::{Align, Size};
+use std::cell::Cell;
 use std::cmp;
+use std::time::{Duration, Instant};
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantInfo {
@@ -89,10 +91,28 @@ pub struct VTableSizeInfo {
     pub upcasting_cost_percent: f64,
 }
 
+/// The time spent on an item by one activity (a query or codegen), for `-Z item-time-report`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ItemTime {
+    /// The time spent in the activity, excluding the time spent in the timed activities it
+    /// invoked, on this or other items.
+    pub self_time: Duration,
+    /// The number of times the activity was run for this item.
+    pub count: usize,
+}
+
+thread_local! {
+    /// The total time of the timed activities that were invoked by the activity currently
+    /// timed by `CodeStats::time_item` on this thread.
+    static NESTED_ITEM_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
     vtable_sizes: Lock<FxHashMap<DefId, VTableSizeInfo>>,
+    item_times: Lock<FxHashMap<(DefId, &'static str), ItemTime>>,
+    optimization_times: Lock<FxHashMap<String, Duration>>,
 }
 
 impl CodeStats {
@@ -134,6 +154,56 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
+    /// Runs `f`, the activity `activity` on the item `def_id`, and records its self time.
+    ///
+    /// Activities without an item are timed too, so that their time isn't attributed to the
+    /// item of the activity invoking them.
+    pub fn time_item<R>(
+        &self,
+        def_id: Option<DefId>,
+        activity: &'static str,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        let outer_nested_time = NESTED_ITEM_TIME.replace(Duration::ZERO);
+        let start = Instant::now();
+        let result = f();
+        let time = start.elapsed();
+        let nested_time = NESTED_ITEM_TIME.replace(outer_nested_time + time);
+
+        if let Some(def_id) = def_id {
+            let mut item_times = self.item_times.lock();
+            let item_time = item_times.entry((def_id, activity)).or_default();
+            item_time.self_time += time.saturating_sub(nested_time);
+            item_time.count += 1;
+        }
+        result
+    }
+
+    /// Runs `f` without counting the time it takes in the self time of the activity timed by
+    /// `time_item` on this thread, e.g. because the thread is blocked waiting for another one.
+    pub fn exclude_from_item_time<R>(f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        NESTED_ITEM_TIME.set(NESTED_ITEM_TIME.get() + start.elapsed());
+        result
+    }
+
+    /// Returns the times recorded by `time_item` so far.
+    pub fn item_times(&self) -> FxHashMap<(DefId, &'static str), ItemTime> {
+        self.item_times.lock().clone()
+    }
+
+    /// Records the time the codegen backend spent optimizing the codegen unit `cgu_name`, for
+    /// `-Z item-time-report`.
+    pub fn record_optimization_time(&self, cgu_name: String, time: Duration) {
+        *self.optimization_times.lock().entry(cgu_name).or_default() += time;
+    }
+
+    /// Returns the times recorded by `record_optimization_time` so far.
+    pub fn optimization_times(&self) -> FxHashMap<String, Duration> {
+        self.optimization_times.lock().clone()
+    }
+
     pub fn print_type_sizes(&self) {
         let type_sizes = self.type_sizes.borrow();
         // We will soon sort, so the initial order does not matter.
@@ -19,6 +19,7 @@
 use rustc_span::edition::{Edition, DEFAULT_EDITION, EDITION_NAME_LIST, LATEST_STABLE_EDITION};
 use rustc_span::source_map::FilePathMapping;
 use rustc_span::{FileName, FileNameDisplayPreference, RealFileName, SourceFileHashAlgorithm};
+use rustc_target::json::{Json, ToJson};
 use rustc_target::spec::{LinkSelfContainedComponents, LinkerFeatures};
 use rustc_target::spec::{SplitDebuginfo, Target, TargetTriple};
 use std::collections::btree_map::{
@@ -473,6 +474,7 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    StableMir,
 }
 
 // Safety: Trivial C-Style enums have a stable sort order across compilation sessions.
@@ -497,7 +499,8 @@ fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
             | OutputType::Assembly
             | OutputType::LlvmAssembly
             | OutputType::Mir
-            | OutputType::Object => false,
+            | OutputType::Object
+            | OutputType::StableMir => false,
         }
     }
 
@@ -512,6 +515,7 @@ pub fn shorthand(&self) -> &'static str {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::StableMir => "stable-mir",
         }
     }
 
@@ -526,13 +530,14 @@ fn from_shorthand(shorthand: &str) -> Option<Self> {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "stable-mir" => OutputType::StableMir,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::ThinLinkBitcode.shorthand(),
             OutputType::Assembly.shorthand(),
@@ -542,6 +547,7 @@ fn shorthands_display() -> String {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::StableMir.shorthand(),
         )
     }
 
@@ -556,6 +562,7 @@ pub fn extension(&self) -> &'static str {
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
             OutputType::Exe => "",
+            OutputType::StableMir => "smir.json",
         }
     }
 
@@ -564,7 +571,8 @@ pub fn is_text_output(&self) -> bool {
             OutputType::Assembly
             | OutputType::LlvmAssembly
             | OutputType::Mir
-            | OutputType::DepInfo => true,
+            | OutputType::DepInfo
+            | OutputType::StableMir => true,
             OutputType::Bitcode
             | OutputType::ThinLinkBitcode
             | OutputType::Object
@@ -587,6 +595,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// A single SARIF log, consumed by code-scanning services.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -658,7 +668,7 @@ pub fn should_codegen(&self) -> bool {
             | OutputType::Mir
             | OutputType::Object
             | OutputType::Exe => true,
-            OutputType::Metadata | OutputType::DepInfo => false,
+            OutputType::Metadata | OutputType::DepInfo | OutputType::StableMir => false,
         })
     }
 
@@ -672,7 +682,8 @@ pub fn should_link(&self) -> bool {
             | OutputType::Mir
             | OutputType::Metadata
             | OutputType::Object
-            | OutputType::DepInfo => false,
+            | OutputType::DepInfo
+            | OutputType::StableMir => false,
             OutputType::Exe => true,
         })
     }
@@ -763,9 +774,38 @@ pub fn files(&self) -> Option<impl Iterator<Item = &CanonicalizedPath>> {
 #[derive(Clone, PartialEq, Debug)]
 pub struct PrintRequest {
     pub kind: PrintKind,
+    pub format: PrintFormat,
     pub out: OutFileName,
 }
 
+/// Version of the documents written with `--print-format=json`. This must be bumped whenever
+/// the `data` of an existing print kind changes shape.
+pub const PRINT_JSON_FORMAT_VERSION: u32 = 1;
+
+impl PrintRequest {
+    /// Writes `data` wrapped in the envelope shared by all JSON print requests, i.e.
+    /// `{"format_version": 1, "kind": "cfg", "data": ...}`.
+    pub fn write_json(&self, data: Json, sess: &Session) {
+        let document: Json = [
+            ("format_version", PRINT_JSON_FORMAT_VERSION.to_json()),
+            ("kind", self.kind.name().to_json()),
+            ("data", data),
+        ]
+        .into_iter()
+        .collect();
+        self.out.overwrite(&format!("{document:#}\n"), sess);
+    }
+}
+
+/// How the output of a `--print` request is formatted.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PrintFormat {
+    /// The ad hoc, human-readable format of each print kind.
+    Text,
+    /// A versioned JSON document, see `PrintRequest::write_json`.
+    Json,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum PrintKind {
     FileNames,
@@ -789,6 +829,37 @@ pub enum PrintKind {
     DeploymentTarget,
 }
 
+const PRINT_KINDS: &[(&str, PrintKind)] = &[
+    // tidy-alphabetical-start
+    ("all-target-specs-json", PrintKind::AllTargetSpecs),
+    ("calling-conventions", PrintKind::CallingConventions),
+    ("cfg", PrintKind::Cfg),
+    ("code-models", PrintKind::CodeModels),
+    ("crate-name", PrintKind::CrateName),
+    ("deployment-target", PrintKind::DeploymentTarget),
+    ("file-names", PrintKind::FileNames),
+    ("link-args", PrintKind::LinkArgs),
+    ("native-static-libs", PrintKind::NativeStaticLibs),
+    ("relocation-models", PrintKind::RelocationModels),
+    ("split-debuginfo", PrintKind::SplitDebuginfo),
+    ("stack-protector-strategies", PrintKind::StackProtectorStrategies),
+    ("sysroot", PrintKind::Sysroot),
+    ("target-cpus", PrintKind::TargetCPUs),
+    ("target-features", PrintKind::TargetFeatures),
+    ("target-libdir", PrintKind::TargetLibdir),
+    ("target-list", PrintKind::TargetList),
+    ("target-spec-json", PrintKind::TargetSpec),
+    ("tls-models", PrintKind::TlsModels),
+    // tidy-alphabetical-end
+];
+
+impl PrintKind {
+    /// The name of this print kind as accepted by `--print`.
+    pub fn name(self) -> &'static str {
+        PRINT_KINDS.iter().find(|&&(_, kind)| kind == self).unwrap().0
+    }
+}
+
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub struct NextSolverConfig {
     /// Whether the new trait solver should be enabled in coherence.
@@ -1139,6 +1210,7 @@ fn default() -> Options {
             json_artifact_notifications: false,
             json_unused_externs: JsonUnusedExterns::No,
             json_future_incompat: false,
+            json_explanations: false,
             pretty: None,
             working_dir: RealFileName::LocalPath(std::env::current_dir().unwrap()),
             color: ColorConfig::Auto,
@@ -1515,7 +1587,7 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "",
             "error-format",
             "How errors and other messages are produced",
-            "human|json|short",
+            "human|json|short|sarif",
         ),
         opt::multi_s("", "json", "Configure the JSON output of the compiler", "CONFIG"),
         opt::opt_s(
@@ -1540,6 +1612,8 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "FROM=TO",
         ),
         opt::multi("", "env-set", "Inject an environment variable", "VAR=VALUE"),
+        opt::opt("", "print-format", "Format of the output of `--print` requests", "text|json"),
+        opt::opt("", "explain-format", "Format of the output of `--explain`", "text|json"),
     ]);
     opts
 }
@@ -1598,6 +1672,7 @@ pub struct JsonConfig {
     json_artifact_notifications: bool,
     pub json_unused_externs: JsonUnusedExterns,
     json_future_incompat: bool,
+    json_explanations: bool,
 }
 
 /// Report unused externs in event stream
@@ -1638,6 +1713,7 @@ pub fn parse_json(early_dcx: &EarlyDiagCtxt, matches: &getopts::Matches) -> Json
     let mut json_artifact_notifications = false;
     let mut json_unused_externs = JsonUnusedExterns::No;
     let mut json_future_incompat = false;
+    let mut json_explanations = false;
     for option in matches.opt_strs("json") {
         // For now conservatively forbid `--color` with `--json` since `--json`
         // won't actually be emitting any colors and anything colorized is
@@ -1654,6 +1730,7 @@ pub fn parse_json(early_dcx: &EarlyDiagCtxt, matches: &getopts::Matches) -> Json
                 "unused-externs" => json_unused_externs = JsonUnusedExterns::Loud,
                 "unused-externs-silent" => json_unused_externs = JsonUnusedExterns::Silent,
                 "future-incompat" => json_future_incompat = true,
+                "explanations" => json_explanations = true,
                 s => early_dcx.early_fatal(format!("unknown `--json` option `{s}`")),
             }
         }
@@ -1664,6 +1741,7 @@ pub fn parse_json(early_dcx: &EarlyDiagCtxt, matches: &getopts::Matches) -> Json
         json_artifact_notifications,
         json_unused_externs,
         json_future_incompat,
+        json_explanations,
     }
 }
 
@@ -1688,6 +1766,7 @@ pub fn parse_error_format(
             }
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
+            Some("sarif") => ErrorOutputType::Sarif,
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
 
             Some(arg) => {
@@ -1695,8 +1774,8 @@ pub fn parse_error_format(
                     HumanReadableErrorType::Default(color),
                 ));
                 early_dcx.early_fatal(format!(
-                    "argument for `--error-format` must be `human`, `json` or \
-                     `short` (instead was `{arg}`)"
+                    "argument for `--error-format` must be `human`, `json`, \
+                     `short` or `sarif` (instead was `{arg}`)"
                 ))
             }
         }
@@ -1755,6 +1834,9 @@ fn check_error_format_stability(
         if let ErrorOutputType::Json { pretty: true, .. } = error_format {
             early_dcx.early_fatal("`--error-format=pretty-json` is unstable");
         }
+        if let ErrorOutputType::Sarif = error_format {
+            early_dcx.early_fatal("`--error-format=sarif` is unstable");
+        }
         if let ErrorOutputType::HumanReadable(HumanReadableErrorType::AnnotateSnippet(_)) =
             error_format
         {
@@ -1779,10 +1861,12 @@ fn parse_output_types(
                         display = OutputType::shorthands_display(),
                     ))
                 });
-                if output_type == OutputType::ThinLinkBitcode && !unstable_opts.unstable_options {
+                if matches!(output_type, OutputType::ThinLinkBitcode | OutputType::StableMir)
+                    && !unstable_opts.unstable_options
+                {
                     early_dcx.early_fatal(format!(
                         "{} requested but -Zunstable-options not specified",
-                        OutputType::ThinLinkBitcode.shorthand()
+                        output_type.shorthand()
                     ));
                 }
                 output_types.insert(output_type, path);
@@ -1854,40 +1938,28 @@ fn collect_print_requests(
     unstable_opts: &UnstableOptions,
     matches: &getopts::Matches,
 ) -> Vec<PrintRequest> {
+    let format = match matches.opt_str("print-format").as_deref() {
+        None | Some("text") => PrintFormat::Text,
+        Some("json") => PrintFormat::Json,
+        Some(arg) => early_dcx.early_fatal(format!(
+            "argument for `--print-format` must be `text` or `json` (instead was `{arg}`)"
+        )),
+    };
+
     let mut prints = Vec::<PrintRequest>::new();
     if cg.target_cpu.as_ref().is_some_and(|s| s == "help") {
-        prints.push(PrintRequest { kind: PrintKind::TargetCPUs, out: OutFileName::Stdout });
+        prints.push(PrintRequest { kind: PrintKind::TargetCPUs, format, out: OutFileName::Stdout });
         cg.target_cpu = None;
     };
     if cg.target_feature == "help" {
-        prints.push(PrintRequest { kind: PrintKind::TargetFeatures, out: OutFileName::Stdout });
+        prints.push(PrintRequest {
+            kind: PrintKind::TargetFeatures,
+            format,
+            out: OutFileName::Stdout,
+        });
         cg.target_feature = String::new();
     }
 
-    const PRINT_KINDS: &[(&str, PrintKind)] = &[
-        // tidy-alphabetical-start
-        ("all-target-specs-json", PrintKind::AllTargetSpecs),
-        ("calling-conventions", PrintKind::CallingConventions),
-        ("cfg", PrintKind::Cfg),
-        ("code-models", PrintKind::CodeModels),
-        ("crate-name", PrintKind::CrateName),
-        ("deployment-target", PrintKind::DeploymentTarget),
-        ("file-names", PrintKind::FileNames),
-        ("link-args", PrintKind::LinkArgs),
-        ("native-static-libs", PrintKind::NativeStaticLibs),
-        ("relocation-models", PrintKind::RelocationModels),
-        ("split-debuginfo", PrintKind::SplitDebuginfo),
-        ("stack-protector-strategies", PrintKind::StackProtectorStrategies),
-        ("sysroot", PrintKind::Sysroot),
-        ("target-cpus", PrintKind::TargetCPUs),
-        ("target-features", PrintKind::TargetFeatures),
-        ("target-libdir", PrintKind::TargetLibdir),
-        ("target-list", PrintKind::TargetList),
-        ("target-spec-json", PrintKind::TargetSpec),
-        ("tls-models", PrintKind::TlsModels),
-        // tidy-alphabetical-end
-    ];
-
     // We disallow reusing the same path in multiple prints, such as `--print
     // cfg=output.txt --print link-args=output.txt`, because outputs are printed
     // by disparate pieces of the compiler, and keeping track of which files
@@ -1942,7 +2014,7 @@ fn collect_print_requests(
             }
         }
 
-        PrintRequest { kind, out }
+        PrintRequest { kind, format, out }
     }));
 
     prints
@@ -2348,6 +2420,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         json_artifact_notifications,
         json_unused_externs,
         json_future_incompat,
+        json_explanations,
     } = parse_json(early_dcx, matches);
 
     let error_format = parse_error_format(early_dcx, matches, color, json_rendered);
@@ -2367,6 +2440,10 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
 
     check_error_format_stability(early_dcx, &unstable_opts, error_format);
 
+    if json_explanations && !unstable_opts.unstable_options {
+        early_dcx.early_fatal("`--json=explanations` is unstable");
+    }
+
     let output_types = parse_output_types(early_dcx, &unstable_opts, matches);
 
     let mut cg = CodegenOptions::build(early_dcx, matches);
@@ -2641,6 +2718,7 @@ pub fn build_session_options(early_dcx: &mut EarlyDiagCtxt, matches: &getopts::M
         json_artifact_notifications,
         json_unused_externs,
         json_future_incompat,
+        json_explanations,
         pretty,
         working_dir,
         color,
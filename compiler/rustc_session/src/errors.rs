@@ -153,6 +153,10 @@ pub(crate) struct CannotMixAndMatchSanitizers {
 #[diag(session_split_lto_unit_requires_lto)]
 pub(crate) struct SplitLtoUnitRequiresLto;
 
+#[derive(Diagnostic)]
+#[diag(session_incremental_report_requires_incremental)]
+pub(crate) struct IncrementalReportRequiresIncremental;
+
 #[derive(Diagnostic)]
 #[diag(session_unstable_virtual_function_elimination)]
 pub(crate) struct UnstableVirtualFunctionElimination;
@@ -186,6 +190,20 @@ pub(crate) struct FileIsNotWriteable<'a> {
     pub(crate) file: &'a std::path::Path,
 }
 
+#[derive(Diagnostic)]
+#[diag(session_applied_suggestions)]
+pub(crate) struct AppliedSuggestions<'a> {
+    pub(crate) applied: usize,
+    pub(crate) files: usize,
+    pub(crate) path: &'a std::path::Path,
+    pub(crate) overlapping: usize,
+    #[note(session_applied_suggestions_overlapping)]
+    pub(crate) has_overlapping: Option<()>,
+    pub(crate) unappliable: usize,
+    #[note(session_applied_suggestions_unappliable)]
+    pub(crate) has_unappliable: Option<()>,
+}
+
 #[derive(Diagnostic)]
 #[diag(session_file_write_fail)]
 pub(crate) struct FileWriteFail<'a> {
@@ -472,3 +490,10 @@ pub(crate) struct IncompatibleLinkerFlavor {
 pub(crate) struct FailedToCreateProfiler {
     pub(crate) err: String,
 }
+
+#[derive(Diagnostic)]
+#[diag(session_failed_to_write_self_profile_output)]
+pub(crate) struct FailedToWriteSelfProfileOutput<'a> {
+    pub(crate) path: &'a std::path::Path,
+    pub(crate) err: String,
+}
@@ -216,6 +216,9 @@ pub struct Options {
         /// `true` if we're emitting a JSON job containing a future-incompat report for lints
         json_future_incompat: bool [TRACKED],
 
+        /// `true` if JSON diagnostics include the structured explanation of their error code
+        json_explanations: bool [UNTRACKED],
+
         pretty: Option<PpMode> [UNTRACKED],
 
         /// The (potentially remapped) working directory
@@ -1605,6 +1608,12 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "only allow the listed language features to be enabled in code (comma separated)"),
     always_encode_mir: bool = (false, parse_bool, [TRACKED],
         "encode MIR of all functions into the crate metadata (default: no)"),
+    apply_suggestions: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "apply the machine-applicable suggestions of the emitted diagnostics and write the \
+        patched source files to the given directory"),
+    apply_suggestions_diff: bool = (false, parse_bool, [UNTRACKED],
+        "write the changes of `-Z apply-suggestions` as a unified diff `suggestions.diff` \
+        instead of patched source files (default: no)"),
     asm_comments: bool = (false, parse_bool, [TRACKED],
         "generate comments into the assembly (may change behavior) (default: no)"),
     assert_incr_state: Option<String> = (None, parse_opt_string, [UNTRACKED],
@@ -1668,6 +1677,10 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "exclude the pass number when dumping MIR (used in tests) (default: no)"),
     dump_mir_graphviz: bool = (false, parse_bool, [UNTRACKED],
         "in addition to `.mir` files, create graphviz `.dot` files (default: no)"),
+    dump_mono_bloat: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "output a JSON report of the instances of each generic definition, with their size \
+        estimates and the chains of items that caused their monomorphization"),
     dump_mono_stats: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [UNTRACKED],
         "output statistics about monomorphization collection"),
@@ -1709,6 +1722,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "whether each function should go in its own section"),
     future_incompat_test: bool = (false, parse_bool, [UNTRACKED],
         "forces all lints to be future incompatible, used for internal testing (default: no)"),
+    future_size_limit: Option<usize> = (None, parse_opt_number, [TRACKED],
+        "the size in bytes above which the `large_futures` lint reports futures \
+        (default: 16384)"),
     graphviz_dark_mode: bool = (false, parse_bool, [UNTRACKED],
         "use dark-themed colors in graphviz output (default: no)"),
     graphviz_font: String = ("Courier, monospace".to_string(), parse_string, [UNTRACKED],
@@ -1729,6 +1745,10 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
     incremental_info: bool = (false, parse_bool, [UNTRACKED],
         "print high-level information about incremental reuse (or the lack thereof) \
         (default: no)"),
+    incremental_report: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write a JSON report of the root causes of queries re-executed by an incremental \
+        session to `<crate>.incr_report.json` in the given directory (default: `.`)"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
         "verify extended properties for incr. comp. (default: no):
         - hashes of green query instances
@@ -1748,6 +1768,11 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "a default MIR inlining threshold (default: 50)"),
     input_stats: bool = (false, parse_bool, [UNTRACKED],
         "gather statistics about the input (default: no)"),
+    inspect_incr_cache: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "print the contents of an incremental compilation session directory and exit"),
+    inspect_incr_cache_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "an older incremental compilation session directory to compare the one given with \
+        `-Zinspect-incr-cache` against"),
     instrument_mcount: bool = (false, parse_bool, [TRACKED],
         "insert function instrument code for mcount-based tracing (default: no)"),
     instrument_xray: Option<InstrumentXRay> = (None, parse_instrument_xray, [TRACKED],
@@ -1760,6 +1785,10 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
          `=skip-entry`
          `=skip-exit`
          Multiple options can be combined with commas."),
+    item_time_report: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "attribute the time spent in queries and codegen to the items they work on, and write \
+        a ranked report to `<crate>.item_times.json` in the given directory (default: `.`)"),
     layout_seed: Option<u64> = (None, parse_opt_number, [TRACKED],
         "seed layout randomization"),
     link_directives: bool = (true, parse_bool, [TRACKED],
@@ -1770,8 +1799,17 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
     linker_features: LinkerFeaturesCli = (LinkerFeaturesCli::default(), parse_linker_features, [UNTRACKED],
         "a comma-separated list of linker features to enable (+) or disable (-): `lld`"),
+    lint_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "suppress lint warnings recorded in the given baseline file \
+        (see `-Z write-lint-baseline`)"),
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED_NO_CRATE_HASH],
+        "set lint levels for the crate and for source files matching path globs from the given \
+        JSON file"),
     lint_mir: bool = (false, parse_bool, [UNTRACKED],
         "lint MIR before and after each transformation"),
+    lint_plugin: Vec<String> = (Vec::new(), parse_string_push, [TRACKED_NO_CRATE_HASH],
+        "load lints from the given dynamic library, which must export a `__rustc_lint_plugin` \
+        function (can be used multiple times)"),
     llvm_module_flag: Vec<(String, u32, String)> = (Vec::new(), parse_llvm_module_flag, [TRACKED],
         "a list of module flags to pass to LLVM (space separated)"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
@@ -1939,6 +1977,9 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
     self_profile: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [UNTRACKED],
         "run the self profiler and output the raw event data"),
+    self_profile_chrome_trace: bool = (false, parse_bool, [UNTRACKED],
+        "with `-Z self-profile`, also write the recorded events as a Chrome trace \
+        (`<crate>-<pid>.chrome_trace.json`) to the profile directory"),
     self_profile_counter: String = ("wall-time".to_string(), parse_string, [UNTRACKED],
         "counter used by the self profiler (default: `wall-time`), one of:
         `wall-time` (monotonic clock, i.e. `std::time::Instant`)
@@ -1951,8 +1992,18 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         for example: `-Z self-profile-events=default,query-keys`
         all options: none, all, default, generic-activity, query-provider, query-cache-hit
                      query-blocked, incr-cache-load, incr-result-hashing, query-keys, function-args, args, llvm, artifact-sizes"),
+    self_profile_summary: bool = (false, parse_bool, [UNTRACKED],
+        "with `-Z self-profile`, also write a summary of the time spent in each query and \
+        activity (`<crate>-<pid>.summary.txt`) to the profile directory"),
     share_generics: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make the current crate share its generic instantiations"),
+    shared_cache: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "reuse codegen artifacts and trait selection results from, and add them to, a cache in \
+        the given directory that can be shared between crates and compilation sessions \
+        (ignored with `-C incremental`)"),
+    shared_cache_max_size: u64 = (10240, parse_number, [UNTRACKED],
+        "the size in MiB the cache given with `-Z shared-cache` is allowed to grow to \
+        (default: 10240)"),
     shell_argfiles: bool = (false, parse_bool, [UNTRACKED],
         "allow argument files to be specified with POSIX \"shell-style\" argument quoting"),
     show_span: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -2089,6 +2140,8 @@ pub(crate) fn parse_wasm_c_abi(slot: &mut WasmCAbi, v: Option<&str>) -> bool {
         "whether to build a wasi command or reactor"),
     wasm_c_abi: WasmCAbi = (WasmCAbi::Legacy, parse_wasm_c_abi, [TRACKED],
         "use spec-compliant C ABI for `wasm32-unknown-unknown` (default: legacy)"),
+    write_lint_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "record all emitted lint warnings in a baseline file for use with `-Z lint-baseline`"),
     write_long_types_to_disk: bool = (true, parse_bool, [UNTRACKED],
         "whether long type names should be written to files instead of being printed in errors"),
     // tidy-alphabetical-end
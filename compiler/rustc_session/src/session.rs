@@ -18,9 +18,12 @@
     AtomicU64, DynSend, DynSync, Lock, Lrc, MappedReadGuard, ReadGuard, RwLock,
 };
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitter;
+use rustc_errors::apply_suggestions::AppliedSuggestions;
 use rustc_errors::emitter::{stderr_destination, DynEmitter, HumanEmitter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
+use rustc_errors::lint_baseline::LintBaseline;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     codes::*, fallback_fluent_bundle, Diag, DiagCtxt, DiagMessage, Diagnostic, ErrorGuaranteed,
     FatalAbort, FluentBundle, LazyFallbackBundle, TerminalUrl,
@@ -292,9 +295,57 @@ pub fn finish_diagnostics(&self, registry: &Registry) -> Option<ErrorGuaranteed>
         if self.opts.json_future_incompat {
             self.dcx().emit_future_breakage_report();
         }
+        if let Some(path) = &self.opts.unstable_opts.write_lint_baseline
+            && let Some(baseline) = self.dcx().take_recorded_lint_baseline()
+            && let Err(err) = baseline.write(path)
+        {
+            self.dcx().emit_err(errors::FileWriteFail { path, err: err.to_string() });
+        }
+        for (path, err) in self.prof.write_recorded_outputs() {
+            self.dcx().emit_warn(errors::FailedToWriteSelfProfileOutput {
+                path: &path,
+                err: err.to_string(),
+            });
+        }
         guar
     }
 
+    /// Applies the suggestions recorded for `-Zapply-suggestions`, and writes the patched
+    /// files, or their diff with `-Zapply-suggestions-diff`, to the requested directory.
+    ///
+    /// Returns `None` if `-Zapply-suggestions` isn't set or the suggestions were already applied.
+    pub fn apply_recorded_suggestions(
+        &self,
+    ) -> Option<Result<AppliedSuggestions, ErrorGuaranteed>> {
+        let dir = self.opts.unstable_opts.apply_suggestions.as_deref()?;
+        let applied = self.dcx().take_recorded_suggestions()?.apply();
+
+        let (path, result) = if self.opts.unstable_opts.apply_suggestions_diff {
+            let path = dir.join("suggestions.diff");
+            let result = std::fs::create_dir_all(dir)
+                .and_then(|()| std::fs::write(&path, applied.unified_diff()));
+            (path, result)
+        } else {
+            (dir.to_owned(), applied.write_files(dir))
+        };
+        if let Err(err) = result {
+            let guar =
+                self.dcx().emit_err(errors::FileWriteFail { path: &path, err: err.to_string() });
+            return Some(Err(guar));
+        }
+
+        self.dcx().emit_note(errors::AppliedSuggestions {
+            applied: applied.applied,
+            files: applied.files.len(),
+            path: &path,
+            overlapping: applied.overlapping,
+            has_overlapping: (applied.overlapping > 0).then_some(()),
+            unappliable: applied.unappliable,
+            has_unappliable: (applied.unappliable > 0).then_some(()),
+        });
+        Some(Ok(applied))
+    }
+
     /// Returns true if the crate is a testing one.
     pub fn is_test_crate(&self) -> bool {
         self.opts.test
@@ -987,7 +1038,17 @@ fn default_emitter(
             .diagnostic_width(sopts.diagnostic_width)
             .macro_backtrace(macro_backtrace)
             .track_diagnostics(track_diagnostics)
-            .terminal_url(terminal_url),
+            .terminal_url(terminal_url)
+            .structured_explanations(sopts.json_explanations),
+        ),
+        config::ErrorOutputType::Sarif => Box::new(
+            SarifEmitter::new(
+                Box::new(io::BufWriter::new(io::stderr())),
+                source_map,
+                fallback_bundle,
+            )
+            .registry(Some(registry))
+            .fluent_bundle(bundle),
         ),
     }
 }
@@ -1041,6 +1102,18 @@ pub fn build_session(
     if let Some(ice_file) = ice_file {
         dcx = dcx.with_ice_file(ice_file);
     }
+    if let Some(path) = &sopts.unstable_opts.lint_baseline {
+        let baseline = LintBaseline::read(path).unwrap_or_else(|e| {
+            early_dcx.early_fatal(format!("failed to read lint baseline `{}`: {e}", path.display()))
+        });
+        dcx = dcx.with_lint_baseline(baseline);
+    }
+    if sopts.unstable_opts.write_lint_baseline.is_some() {
+        dcx = dcx.with_lint_baseline_recording();
+    }
+    if sopts.unstable_opts.apply_suggestions.is_some() {
+        dcx = dcx.with_suggestion_recording();
+    }
 
     // Now that the proper handler has been constructed, drop early_dcx to
     // prevent accidental use.
@@ -1056,6 +1129,8 @@ pub fn build_session(
             sopts.crate_name.as_deref(),
             sopts.unstable_opts.self_profile_events.as_deref(),
             &sopts.unstable_opts.self_profile_counter,
+            sopts.unstable_opts.self_profile_summary,
+            sopts.unstable_opts.self_profile_chrome_trace,
         );
         match profiler {
             Ok(profiler) => Some(Arc::new(profiler)),
@@ -1091,6 +1166,7 @@ pub fn build_session(
     let prof = SelfProfilerRef::new(
         self_profiler,
         sopts.unstable_opts.time_passes.then(|| sopts.unstable_opts.time_passes_format),
+        sopts.unstable_opts.item_time_report.enabled(),
     );
 
     let ctfe_backtrace = Lock::new(match env::var("RUSTC_CTFE_BACKTRACE") {
@@ -1259,6 +1335,11 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         sess.dcx().emit_err(errors::SplitLtoUnitRequiresLto);
     }
 
+    // The incremental report explains the reuse of the previous incremental session.
+    if sess.opts.unstable_opts.incremental_report.enabled() && sess.opts.incremental.is_none() {
+        sess.dcx().emit_err(errors::IncrementalReportRequiresIncremental);
+    }
+
     // VFE requires LTO.
     if sess.lto() != config::Lto::Fat {
         if sess.opts.unstable_opts.virtual_function_elimination {
@@ -1431,6 +1512,11 @@ fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
             pretty,
             json_rendered,
         )),
+        config::ErrorOutputType::Sarif => Box::new(SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            Lrc::new(SourceMap::new(FilePathMapping::empty())),
+            fallback_bundle,
+        )),
     };
     emitter
 }
@@ -1,21 +1,30 @@
 //! Module containing the translation from stable mir constructs to the rustc counterpart.
 //!
 //! This module will only include a few constructs to allow users to invoke internal rustc APIs
-//! due to incomplete stable coverage.
+//! due to incomplete stable coverage, and the MIR constructs that can be converted back without
+//! loss, which are used to hand transformed bodies back to the compiler.
 
 // Prefer importing stable_mir over internal rustc constructs to make this file more readable.
 use crate::rustc_smir::Tables;
-use rustc_middle::ty::{self as rustc_ty, Ty as InternalTy, TyCtxt};
+use rustc_middle::traits::Reveal;
+use rustc_middle::ty::{self as rustc_ty, Ty as InternalTy, TyCtxt, Upcast};
 use rustc_span::Symbol;
 use stable_mir::abi::Layout;
 use stable_mir::mir::alloc::AllocId;
 use stable_mir::mir::mono::{Instance, MonoItem, StaticDef};
-use stable_mir::mir::{BinOp, Mutability, Place, ProjectionElem, Safety};
+use stable_mir::mir::{
+    AggregateKind, AssertMessage, BinOp, BorrowKind, CastKind, ConstOperand, Constant,
+    CopyNonOverlapping, CoroutineDesugaring, CoroutineKind, CoroutineSource, FakeBorrowKind,
+    LocalDecl, MutBorrowKind, Mutability, NonDivergingIntrinsic, NullOp, Operand, Place,
+    PointerCoercion, ProjectionElem, RetagKind, Rvalue, Safety, SourceInfo, SwitchTargets, UnOp,
+    UnwindAction, VarDebugInfo, VarDebugInfoContents,
+};
 use stable_mir::ty::{
-    Abi, AdtDef, Binder, BoundRegionKind, BoundTyKind, BoundVariableKind, ClosureKind, Const,
-    DynKind, ExistentialPredicate, ExistentialProjection, ExistentialTraitRef, FloatTy, FnSig,
-    GenericArgKind, GenericArgs, IndexedVal, IntTy, Movability, Pattern, Region, RigidTy, Span,
-    TermKind, TraitRef, Ty, UintTy, VariantDef, VariantIdx,
+    Abi, AdtDef, AliasTerm, Binder, BoundRegionKind, BoundTyKind, BoundVariableKind, ClauseKind,
+    ClosureKind, Const, DynKind, ExistentialPredicate, ExistentialProjection, ExistentialTraitRef,
+    FloatTy, FnSig, GenericArgKind, GenericArgs, IndexedVal, IntTy, Movability, OutlivesPredicate,
+    ParamEnv, Pattern, PredicatePolarity, ProjectionPredicate, Region, RigidTy, Span, TermKind,
+    TraitPredicate, TraitRef, Ty, UintTy, VariantDef, VariantIdx,
 };
 use stable_mir::{CrateItem, CrateNum, DefId};
 
@@ -420,6 +429,108 @@ fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<
     }
 }
 
+impl RustcInternal for AliasTerm {
+    type T<'tcx> = rustc_ty::AliasTerm<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_ty::AliasTerm::new(
+            tcx,
+            self.def_id.0.internal(tables, tcx),
+            self.args.internal(tables, tcx),
+        )
+    }
+}
+
+impl RustcInternal for PredicatePolarity {
+    type T<'tcx> = rustc_ty::PredicatePolarity;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            PredicatePolarity::Positive => rustc_ty::PredicatePolarity::Positive,
+            PredicatePolarity::Negative => rustc_ty::PredicatePolarity::Negative,
+        }
+    }
+}
+
+impl RustcInternal for TraitPredicate {
+    type T<'tcx> = rustc_ty::TraitPredicate<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_ty::TraitPredicate {
+            trait_ref: self.trait_ref.internal(tables, tcx),
+            polarity: self.polarity.internal(tables, tcx),
+        }
+    }
+}
+
+impl RustcInternal for ProjectionPredicate {
+    type T<'tcx> = rustc_ty::ProjectionPredicate<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_ty::ProjectionPredicate {
+            projection_term: self.projection_term.internal(tables, tcx),
+            term: self.term.internal(tables, tcx),
+        }
+    }
+}
+
+impl RustcInternal for ClauseKind {
+    type T<'tcx> = rustc_ty::ClauseKind<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            ClauseKind::Trait(trait_predicate) => {
+                rustc_ty::ClauseKind::Trait(trait_predicate.internal(tables, tcx))
+            }
+            ClauseKind::RegionOutlives(OutlivesPredicate(a, b)) => {
+                rustc_ty::ClauseKind::RegionOutlives(rustc_ty::OutlivesPredicate(
+                    a.internal(tables, tcx),
+                    b.internal(tables, tcx),
+                ))
+            }
+            ClauseKind::TypeOutlives(OutlivesPredicate(a, b)) => {
+                rustc_ty::ClauseKind::TypeOutlives(rustc_ty::OutlivesPredicate(
+                    a.internal(tables, tcx),
+                    b.internal(tables, tcx),
+                ))
+            }
+            ClauseKind::Projection(projection_predicate) => {
+                rustc_ty::ClauseKind::Projection(projection_predicate.internal(tables, tcx))
+            }
+            ClauseKind::ConstArgHasType(const_, ty) => rustc_ty::ClauseKind::ConstArgHasType(
+                ty_const(const_, tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            ClauseKind::WellFormed(arg) => {
+                rustc_ty::ClauseKind::WellFormed(arg.internal(tables, tcx))
+            }
+            ClauseKind::ConstEvaluatable(const_) => {
+                rustc_ty::ClauseKind::ConstEvaluatable(ty_const(const_, tables, tcx))
+            }
+        }
+    }
+}
+
+impl RustcInternal for ParamEnv {
+    type T<'tcx> = rustc_ty::ParamEnv<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            ParamEnv::RevealAll => rustc_ty::ParamEnv::reveal_all(),
+            ParamEnv::Item(def_id) => {
+                tcx.param_env_reveal_all_normalized(def_id.internal(tables, tcx))
+            }
+            ParamEnv::Predicates(clauses) => {
+                let clauses: Vec<rustc_ty::Clause<'tcx>> =
+                    clauses.iter().map(|clause| clause.internal(tables, tcx).upcast(tcx)).collect();
+                // Like the environments of items, include the predicates implied by supertraits.
+                let clauses = rustc_trait_selection::traits::elaborate(tcx, clauses);
+                rustc_ty::ParamEnv::new(tcx.mk_clauses_from_iter(clauses), Reveal::All)
+            }
+        }
+    }
+}
+
 impl RustcInternal for AllocId {
     type T<'tcx> = rustc_middle::mir::interpret::AllocId;
     fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
@@ -582,6 +693,440 @@ fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::
     }
 }
 
+impl RustcInternal for Operand {
+    type T<'tcx> = rustc_middle::mir::Operand<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            Operand::Copy(place) => rustc_middle::mir::Operand::Copy(place.internal(tables, tcx)),
+            Operand::Move(place) => rustc_middle::mir::Operand::Move(place.internal(tables, tcx)),
+            Operand::Constant(constant) => {
+                rustc_middle::mir::Operand::Constant(Box::new(constant.internal(tables, tcx)))
+            }
+        }
+    }
+}
+
+impl RustcInternal for Constant {
+    type T<'tcx> = rustc_middle::mir::ConstOperand<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::ConstOperand {
+            span: self.span.internal(tables, tcx),
+            user_ty: self.user_ty.map(rustc_ty::UserTypeAnnotationIndex::from_usize),
+            const_: self.literal.internal(tables, tcx),
+        }
+    }
+}
+
+impl RustcInternal for ConstOperand {
+    type T<'tcx> = rustc_middle::mir::ConstOperand<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::ConstOperand {
+            span: self.span.internal(tables, tcx),
+            user_ty: self.user_ty.map(rustc_ty::UserTypeAnnotationIndex::from_usize),
+            const_: self.const_.internal(tables, tcx),
+        }
+    }
+}
+
+impl RustcInternal for Rvalue {
+    type T<'tcx> = rustc_middle::mir::Rvalue<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            Rvalue::AddressOf(mutability, place) => rustc_middle::mir::Rvalue::AddressOf(
+                mutability.internal(tables, tcx),
+                place.internal(tables, tcx),
+            ),
+            Rvalue::Aggregate(kind, operands) => rustc_middle::mir::Rvalue::Aggregate(
+                Box::new(kind.internal(tables, tcx)),
+                operands.iter().map(|operand| operand.internal(tables, tcx)).collect(),
+            ),
+            Rvalue::BinaryOp(bin_op, lhs, rhs) => rustc_middle::mir::Rvalue::BinaryOp(
+                bin_op.internal(tables, tcx),
+                Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+            ),
+            Rvalue::Cast(cast_kind, operand, ty) => rustc_middle::mir::Rvalue::Cast(
+                cast_kind.internal(tables, tcx),
+                operand.internal(tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            Rvalue::CheckedBinaryOp(bin_op, lhs, rhs) => {
+                // Checked operations are represented by their overflowing variant in MIR.
+                let Some(overflowing_op) = bin_op.internal(tables, tcx).wrapping_to_overflowing()
+                else {
+                    panic!("Trying to convert `{bin_op:?}` to a checked operation")
+                };
+                rustc_middle::mir::Rvalue::BinaryOp(
+                    overflowing_op,
+                    Box::new((lhs.internal(tables, tcx), rhs.internal(tables, tcx))),
+                )
+            }
+            Rvalue::CopyForDeref(place) => {
+                rustc_middle::mir::Rvalue::CopyForDeref(place.internal(tables, tcx))
+            }
+            Rvalue::Discriminant(place) => {
+                rustc_middle::mir::Rvalue::Discriminant(place.internal(tables, tcx))
+            }
+            Rvalue::Len(place) => rustc_middle::mir::Rvalue::Len(place.internal(tables, tcx)),
+            Rvalue::Ref(region, kind, place) => rustc_middle::mir::Rvalue::Ref(
+                region.internal(tables, tcx),
+                kind.internal(tables, tcx),
+                place.internal(tables, tcx),
+            ),
+            Rvalue::Repeat(operand, count) => rustc_middle::mir::Rvalue::Repeat(
+                operand.internal(tables, tcx),
+                ty_const(count, tables, tcx),
+            ),
+            Rvalue::ShallowInitBox(operand, ty) => rustc_middle::mir::Rvalue::ShallowInitBox(
+                operand.internal(tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            Rvalue::ThreadLocalRef(item) => {
+                rustc_middle::mir::Rvalue::ThreadLocalRef(item.internal(tables, tcx))
+            }
+            Rvalue::NullaryOp(null_op, ty) => rustc_middle::mir::Rvalue::NullaryOp(
+                null_op.internal(tables, tcx),
+                ty.internal(tables, tcx),
+            ),
+            Rvalue::UnaryOp(un_op, operand) => rustc_middle::mir::Rvalue::UnaryOp(
+                un_op.internal(tables, tcx),
+                operand.internal(tables, tcx),
+            ),
+            Rvalue::Use(operand) => rustc_middle::mir::Rvalue::Use(operand.internal(tables, tcx)),
+        }
+    }
+}
+
+impl RustcInternal for AggregateKind {
+    type T<'tcx> = rustc_middle::mir::AggregateKind<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            AggregateKind::Array(ty) => {
+                rustc_middle::mir::AggregateKind::Array(ty.internal(tables, tcx))
+            }
+            AggregateKind::Tuple => rustc_middle::mir::AggregateKind::Tuple,
+            AggregateKind::Adt(def, variant_idx, args, user_ty, field_idx) => {
+                rustc_middle::mir::AggregateKind::Adt(
+                    def.0.internal(tables, tcx),
+                    variant_idx.internal(tables, tcx),
+                    args.internal(tables, tcx),
+                    user_ty.map(rustc_ty::UserTypeAnnotationIndex::from_usize),
+                    field_idx.map(rustc_target::abi::FieldIdx::from_usize),
+                )
+            }
+            AggregateKind::Closure(def, args) => rustc_middle::mir::AggregateKind::Closure(
+                def.0.internal(tables, tcx),
+                args.internal(tables, tcx),
+            ),
+            AggregateKind::Coroutine(def, args, _movability) => {
+                rustc_middle::mir::AggregateKind::Coroutine(
+                    def.0.internal(tables, tcx),
+                    args.internal(tables, tcx),
+                )
+            }
+            AggregateKind::RawPtr(ty, mutability) => rustc_middle::mir::AggregateKind::RawPtr(
+                ty.internal(tables, tcx),
+                mutability.internal(tables, tcx),
+            ),
+        }
+    }
+}
+
+impl RustcInternal for CastKind {
+    type T<'tcx> = rustc_middle::mir::CastKind;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            CastKind::PointerExposeAddress => rustc_middle::mir::CastKind::PointerExposeProvenance,
+            CastKind::PointerWithExposedProvenance => {
+                rustc_middle::mir::CastKind::PointerWithExposedProvenance
+            }
+            CastKind::PointerCoercion(coercion) => {
+                rustc_middle::mir::CastKind::PointerCoercion(coercion.internal(tables, tcx))
+            }
+            CastKind::DynStar => rustc_middle::mir::CastKind::DynStar,
+            CastKind::IntToInt => rustc_middle::mir::CastKind::IntToInt,
+            CastKind::FloatToInt => rustc_middle::mir::CastKind::FloatToInt,
+            CastKind::FloatToFloat => rustc_middle::mir::CastKind::FloatToFloat,
+            CastKind::IntToFloat => rustc_middle::mir::CastKind::IntToFloat,
+            CastKind::PtrToPtr => rustc_middle::mir::CastKind::PtrToPtr,
+            CastKind::FnPtrToPtr => rustc_middle::mir::CastKind::FnPtrToPtr,
+            CastKind::Transmute => rustc_middle::mir::CastKind::Transmute,
+        }
+    }
+}
+
+impl RustcInternal for PointerCoercion {
+    type T<'tcx> = rustc_ty::adjustment::PointerCoercion;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        use rustc_ty::adjustment::PointerCoercion as InternalCoercion;
+        match self {
+            PointerCoercion::ReifyFnPointer => InternalCoercion::ReifyFnPointer,
+            PointerCoercion::UnsafeFnPointer => InternalCoercion::UnsafeFnPointer,
+            PointerCoercion::ClosureFnPointer(safety) => {
+                InternalCoercion::ClosureFnPointer(safety.internal(tables, tcx))
+            }
+            PointerCoercion::MutToConstPointer => InternalCoercion::MutToConstPointer,
+            PointerCoercion::ArrayToPointer => InternalCoercion::ArrayToPointer,
+            PointerCoercion::Unsize => InternalCoercion::Unsize,
+        }
+    }
+}
+
+impl RustcInternal for NullOp {
+    type T<'tcx> = rustc_middle::mir::NullOp<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            NullOp::SizeOf => rustc_middle::mir::NullOp::SizeOf,
+            NullOp::AlignOf => rustc_middle::mir::NullOp::AlignOf,
+            NullOp::OffsetOf(indices) => rustc_middle::mir::NullOp::OffsetOf(
+                tcx.mk_offset_of_from_iter(indices.iter().map(|(variant_idx, field_idx)| {
+                    (
+                        variant_idx.internal(tables, tcx),
+                        rustc_target::abi::FieldIdx::from_usize(*field_idx),
+                    )
+                })),
+            ),
+            NullOp::UbChecks => rustc_middle::mir::NullOp::UbChecks,
+        }
+    }
+}
+
+impl RustcInternal for UnOp {
+    type T<'tcx> = rustc_middle::mir::UnOp;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            UnOp::Not => rustc_middle::mir::UnOp::Not,
+            UnOp::Neg => rustc_middle::mir::UnOp::Neg,
+        }
+    }
+}
+
+impl RustcInternal for BorrowKind {
+    type T<'tcx> = rustc_middle::mir::BorrowKind;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            BorrowKind::Shared => rustc_middle::mir::BorrowKind::Shared,
+            BorrowKind::Fake(kind) => {
+                rustc_middle::mir::BorrowKind::Fake(kind.internal(tables, tcx))
+            }
+            BorrowKind::Mut { kind } => {
+                rustc_middle::mir::BorrowKind::Mut { kind: kind.internal(tables, tcx) }
+            }
+        }
+    }
+}
+
+impl RustcInternal for MutBorrowKind {
+    type T<'tcx> = rustc_middle::mir::MutBorrowKind;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            MutBorrowKind::Default => rustc_middle::mir::MutBorrowKind::Default,
+            MutBorrowKind::TwoPhaseBorrow => rustc_middle::mir::MutBorrowKind::TwoPhaseBorrow,
+            MutBorrowKind::ClosureCapture => rustc_middle::mir::MutBorrowKind::ClosureCapture,
+        }
+    }
+}
+
+impl RustcInternal for FakeBorrowKind {
+    type T<'tcx> = rustc_middle::mir::FakeBorrowKind;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            FakeBorrowKind::Deep => rustc_middle::mir::FakeBorrowKind::Deep,
+            FakeBorrowKind::Shallow => rustc_middle::mir::FakeBorrowKind::Shallow,
+        }
+    }
+}
+
+impl RustcInternal for NonDivergingIntrinsic {
+    type T<'tcx> = rustc_middle::mir::NonDivergingIntrinsic<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            NonDivergingIntrinsic::Assume(operand) => {
+                rustc_middle::mir::NonDivergingIntrinsic::Assume(operand.internal(tables, tcx))
+            }
+            NonDivergingIntrinsic::CopyNonOverlapping(CopyNonOverlapping { src, dst, count }) => {
+                rustc_middle::mir::NonDivergingIntrinsic::CopyNonOverlapping(
+                    rustc_middle::mir::CopyNonOverlapping {
+                        src: src.internal(tables, tcx),
+                        dst: dst.internal(tables, tcx),
+                        count: count.internal(tables, tcx),
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl RustcInternal for RetagKind {
+    type T<'tcx> = rustc_middle::mir::RetagKind;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            RetagKind::FnEntry => rustc_middle::mir::RetagKind::FnEntry,
+            RetagKind::TwoPhase => rustc_middle::mir::RetagKind::TwoPhase,
+            RetagKind::Raw => rustc_middle::mir::RetagKind::Raw,
+            RetagKind::Default => rustc_middle::mir::RetagKind::Default,
+        }
+    }
+}
+
+impl RustcInternal for AssertMessage {
+    type T<'tcx> = rustc_middle::mir::AssertMessage<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        use rustc_middle::mir::AssertKind;
+        match self {
+            AssertMessage::BoundsCheck { len, index } => AssertKind::BoundsCheck {
+                len: len.internal(tables, tcx),
+                index: index.internal(tables, tcx),
+            },
+            AssertMessage::Overflow(bin_op, lhs, rhs) => AssertKind::Overflow(
+                bin_op.internal(tables, tcx),
+                lhs.internal(tables, tcx),
+                rhs.internal(tables, tcx),
+            ),
+            AssertMessage::OverflowNeg(operand) => {
+                AssertKind::OverflowNeg(operand.internal(tables, tcx))
+            }
+            AssertMessage::DivisionByZero(operand) => {
+                AssertKind::DivisionByZero(operand.internal(tables, tcx))
+            }
+            AssertMessage::RemainderByZero(operand) => {
+                AssertKind::RemainderByZero(operand.internal(tables, tcx))
+            }
+            AssertMessage::ResumedAfterReturn(coroutine) => {
+                AssertKind::ResumedAfterReturn(coroutine.internal(tables, tcx))
+            }
+            AssertMessage::ResumedAfterPanic(coroutine) => {
+                AssertKind::ResumedAfterPanic(coroutine.internal(tables, tcx))
+            }
+            AssertMessage::MisalignedPointerDereference { required, found } => {
+                AssertKind::MisalignedPointerDereference {
+                    required: required.internal(tables, tcx),
+                    found: found.internal(tables, tcx),
+                }
+            }
+        }
+    }
+}
+
+impl RustcInternal for CoroutineKind {
+    type T<'tcx> = rustc_hir::CoroutineKind;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            CoroutineKind::Desugared(desugaring, source) => rustc_hir::CoroutineKind::Desugared(
+                match desugaring {
+                    CoroutineDesugaring::Async => rustc_hir::CoroutineDesugaring::Async,
+                    CoroutineDesugaring::Gen => rustc_hir::CoroutineDesugaring::Gen,
+                    CoroutineDesugaring::AsyncGen => rustc_hir::CoroutineDesugaring::AsyncGen,
+                },
+                match source {
+                    CoroutineSource::Block => rustc_hir::CoroutineSource::Block,
+                    CoroutineSource::Closure => rustc_hir::CoroutineSource::Closure,
+                    CoroutineSource::Fn => rustc_hir::CoroutineSource::Fn,
+                },
+            ),
+            CoroutineKind::Coroutine(movability) => {
+                rustc_hir::CoroutineKind::Coroutine(movability.internal(tables, tcx))
+            }
+        }
+    }
+}
+
+impl RustcInternal for UnwindAction {
+    type T<'tcx> = rustc_middle::mir::UnwindAction;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            UnwindAction::Continue => rustc_middle::mir::UnwindAction::Continue,
+            UnwindAction::Unreachable => rustc_middle::mir::UnwindAction::Unreachable,
+            // The reason for terminating isn't kept in stable MIR.
+            UnwindAction::Terminate => rustc_middle::mir::UnwindAction::Terminate(
+                rustc_middle::mir::UnwindTerminateReason::Abi,
+            ),
+            UnwindAction::Cleanup(bb) => rustc_middle::mir::UnwindAction::Cleanup(
+                rustc_middle::mir::BasicBlock::from_usize(*bb),
+            ),
+        }
+    }
+}
+
+impl RustcInternal for SwitchTargets {
+    type T<'tcx> = rustc_middle::mir::SwitchTargets;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::SwitchTargets::new(
+            self.branches()
+                .map(|(value, target)| (value, rustc_middle::mir::BasicBlock::from_usize(target))),
+            rustc_middle::mir::BasicBlock::from_usize(self.otherwise()),
+        )
+    }
+}
+
+impl RustcInternal for LocalDecl {
+    type T<'tcx> = rustc_middle::mir::LocalDecl<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::LocalDecl {
+            mutability: self.mutability.internal(tables, tcx),
+            ..rustc_middle::mir::LocalDecl::new(
+                self.ty.internal(tables, tcx),
+                self.span.internal(tables, tcx),
+            )
+        }
+    }
+}
+
+impl RustcInternal for SourceInfo {
+    type T<'tcx> = rustc_middle::mir::SourceInfo;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::SourceInfo {
+            span: self.span.internal(tables, tcx),
+            scope: rustc_middle::mir::SourceScope::from_u32(self.scope),
+        }
+    }
+}
+
+impl RustcInternal for VarDebugInfo {
+    type T<'tcx> = rustc_middle::mir::VarDebugInfo<'tcx>;
+
+    fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        rustc_middle::mir::VarDebugInfo {
+            name: Symbol::intern(&self.name),
+            source_info: self.source_info.internal(tables, tcx),
+            composite: self.composite.as_ref().map(|composite| {
+                Box::new(rustc_middle::mir::VarDebugInfoFragment {
+                    ty: composite.ty.internal(tables, tcx),
+                    projection: composite.projection.internal(tables, tcx),
+                })
+            }),
+            value: match &self.value {
+                VarDebugInfoContents::Place(place) => {
+                    rustc_middle::mir::VarDebugInfoContents::Place(place.internal(tables, tcx))
+                }
+                VarDebugInfoContents::Const(constant) => {
+                    rustc_middle::mir::VarDebugInfoContents::Const(constant.internal(tables, tcx))
+                }
+            },
+            argument_index: self.argument_index,
+        }
+    }
+}
+
 impl<T> RustcInternal for &T
 where
     T: RustcInternal,
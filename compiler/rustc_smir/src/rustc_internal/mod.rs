@@ -23,6 +23,12 @@
 
 mod internal;
 pub mod pretty;
+pub mod snapshot;
+mod transform;
+
+pub use transform::{
+    install_override_queries, override_queries, register_mir_transform, MirTransform, MirTransforms,
+};
 
 /// Convert an internal Rust compiler item into its stable counterpart, if one exists.
 ///
@@ -85,6 +91,10 @@ pub fn adt_def(&mut self, did: DefId) -> stable_mir::ty::AdtDef {
         stable_mir::ty::AdtDef(self.create_def_id(did))
     }
 
+    pub fn module_def(&mut self, did: DefId) -> stable_mir::ty::ModuleDef {
+        stable_mir::ty::ModuleDef(self.create_def_id(did))
+    }
+
     pub fn foreign_module_def(&mut self, did: DefId) -> stable_mir::ty::ForeignModuleDef {
         stable_mir::ty::ForeignModuleDef(self.create_def_id(did))
     }
@@ -206,6 +216,14 @@ pub(crate) fn with_tables<R>(f: impl for<'tcx> FnOnce(&mut Tables<'tcx>) -> R) -
 pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
 where
     F: FnOnce() -> T,
+{
+    run_with_context(tcx, |_| f())
+}
+
+/// Like [`run`], but also passes the Stable MIR context to `f`, so it can query it directly.
+pub(crate) fn run_with_context<'tcx, F, T>(tcx: TyCtxt<'tcx>, f: F) -> Result<T, Error>
+where
+    F: FnOnce(&TablesWrapper<'tcx>) -> T,
 {
     let tables = TablesWrapper(RefCell::new(Tables {
         tcx,
@@ -217,7 +235,7 @@ pub fn run<F, T>(tcx: TyCtxt<'_>, f: F) -> Result<T, Error>
         constants: IndexMap::default(),
         layouts: IndexMap::default(),
     }));
-    stable_mir::compiler_interface::run(&tables, || init(&tables, f))
+    stable_mir::compiler_interface::run(&tables, || init(&tables, || f(&tables)))
 }
 
 /// Instantiate and run the compiler with the provided arguments and callback.
@@ -323,6 +341,7 @@ pub struct StableMir<B = (), C = (), F = fn($(optional!($with_tcx TyCtxt))?) ->
             args: Vec<String>,
             callback: Option<F>,
             result: Option<ControlFlow<B, C>>,
+            mir_transforms: Option<rustc_internal::MirTransforms>,
         }
 
         impl<B, C, F> StableMir<B, C, F>
@@ -333,7 +352,7 @@ impl<B, C, F> StableMir<B, C, F>
         {
             /// Creates a new `StableMir` instance, with given test_function and arguments.
             pub fn new(args: Vec<String>, callback: F) -> Self {
-                StableMir { args, callback: Some(callback), result: None }
+                StableMir { args, callback: Some(callback), result: None, mir_transforms: None }
             }
 
             /// Runs the compiler against given target and tests it with `test_function`
@@ -341,6 +360,8 @@ pub fn run(&mut self) -> Result<C, CompilerError<B>> {
                 let compiler_result = rustc_driver::catch_fatal_errors(|| {
                     RunCompiler::new(&self.args.clone(), self).run()
                 });
+                // The session is over, so drop the MIR transformations registered for it.
+                self.mir_transforms = None;
                 match (compiler_result, self.result.take()) {
                     (Ok(Ok(())), Some(ControlFlow::Continue(value))) => Ok(value),
                     (Ok(Ok(())), Some(ControlFlow::Break(value))) => {
@@ -365,6 +386,11 @@ impl<B, C, F> Callbacks for StableMir<B, C, F>
             C: Send,
             F: FnOnce($(optional!($with_tcx TyCtxt))?) -> ControlFlow<B, C> + Send,
         {
+            /// Install the queries that apply the registered MIR transformations.
+            fn config(&mut self, config: &mut interface::Config) {
+                rustc_internal::install_override_queries(&mut config.override_queries);
+            }
+
             /// Called after analysis. Return value instructs the compiler whether to
             /// continue the compilation afterwards (defaults to `Compilation::Continue`)
             fn after_analysis<'tcx>(
@@ -374,6 +400,7 @@ fn after_analysis<'tcx>(
             ) -> Compilation {
                 queries.global_ctxt().unwrap().enter(|tcx| {
                     if let Some(callback) = self.callback.take() {
+                        self.mir_transforms = Some(rustc_internal::MirTransforms::new(tcx.sess));
                         rustc_internal::run(tcx, || {
                             self.result = Some(callback($(optional!($with_tcx tcx))?));
                         })
@@ -412,6 +439,11 @@ pub fn create_or_fetch(&mut self, key: K) -> V {
         let v = self.index_map.entry(key).or_insert(V::to_val(len));
         *v
     }
+
+    /// The number of values created so far, whose indices are `0..len`.
+    pub(crate) fn len(&self) -> usize {
+        self.index_map.len()
+    }
 }
 
 impl<K: PartialEq + Hash + Eq, V: Copy + Debug + PartialEq + IndexedVal> Index<V>
@@ -0,0 +1,321 @@
+//! Write the Stable MIR of a crate to a [`Snapshot`].
+
+use std::io;
+
+use rustc_hir::def::DefKind;
+use rustc_hir::LangItem;
+use rustc_middle::ty::{TyCtxt, TypeVisitableExt};
+use stable_mir::abi::Layout;
+use stable_mir::compiler_interface::Context;
+use stable_mir::mir::alloc::AllocId;
+use stable_mir::mir::mono::InstanceDef;
+use stable_mir::snapshot::{
+    AdtInfo, AllocInfo, DefDetails, DefInfo, GenericsInfo, InstanceInfo, IntrinsicInfo, ItemInfo,
+    Snapshot, SpanInfo, TyInfo, VariantInfo, FORMAT_VERSION,
+};
+use stable_mir::ty::{
+    AdtDef, ConstId, ForeignDef, ForeignModuleDef, ImplDef, IndexedVal, ModuleDef, RigidTy, Span,
+    TraitDef, Ty, TyKind, VariantDef, VariantIdx,
+};
+use stable_mir::{DefId, Error};
+
+use super::run_with_context;
+use crate::rustc_smir::context::{has_attrs, TablesWrapper};
+
+/// Write a snapshot of the Stable MIR of the local crate, in JSON.
+pub fn write_smir_snapshot<W: io::Write>(tcx: TyCtxt<'_>, w: W) -> io::Result<()> {
+    let snapshot =
+        run_with_context(tcx, record_snapshot).map_err(|err| io::Error::other(err.to_string()))?;
+    snapshot.write(w)
+}
+
+/// Record the items of the local crate, and then the definitions, types, spans, layouts,
+/// allocations, instances and constants they refer to.
+///
+/// Recording an entry may refer to new entries, e.g. the kind of a type refers to other types,
+/// so this records the entries in the order they were created until there is no new entry.
+fn record_snapshot(cx: &TablesWrapper<'_>) -> Snapshot {
+    let tcx = cx.0.borrow().tcx;
+    let items = cx
+        .all_local_items()
+        .into_iter()
+        .map(|item| {
+            let requires_monomorphization = cx.requires_monomorphization(item.0);
+            ItemInfo {
+                item,
+                kind: cx.item_kind(item),
+                requires_monomorphization,
+                instance: (!requires_monomorphization).then(|| cx.mono_instance(item.0)),
+                body: cx.has_body(item.0).then(|| cx.mir_body(item.0)),
+            }
+        })
+        .collect();
+    let local_crate = cx.local_crate();
+    let external_crates = cx.external_crates();
+    let root_modules = std::iter::once(&local_crate)
+        .chain(&external_crates)
+        .map(|krate| cx.root_module(krate.id))
+        .collect();
+    let (bool_ty, ordering_ty) = {
+        let mut tables = cx.0.borrow_mut();
+        let ordering_ty = tcx
+            .lang_items()
+            .get(LangItem::OrderingEnum)
+            .is_some()
+            .then(|| tables.intern_ty(tcx.ty_ordering_enum(None)));
+        (tables.intern_ty(tcx.types.bool), ordering_ty)
+    };
+    let mut snapshot = Snapshot {
+        format_version: FORMAT_VERSION,
+        trait_decls: cx.trait_decls(local_crate.id),
+        trait_impls: cx.trait_impls(local_crate.id),
+        foreign_modules: cx.foreign_modules(local_crate.id),
+        local_crate,
+        external_crates,
+        root_modules,
+        target: cx.target_info(),
+        entry_fn: cx.entry_fn(),
+        items,
+        bool_ty,
+        ordering_ty,
+        defs: Vec::new(),
+        types: Vec::new(),
+        spans: Vec::new(),
+        layouts: Vec::new(),
+        allocs: Vec::new(),
+        instances: Vec::new(),
+        consts: Vec::new(),
+    };
+
+    loop {
+        let counts = {
+            let tables = cx.0.borrow();
+            [
+                tables.def_ids.len(),
+                tables.types.len(),
+                tables.spans.len(),
+                tables.layouts.len(),
+                tables.alloc_ids.len(),
+                tables.instances.len(),
+                tables.constants.len(),
+            ]
+        };
+        let recorded = [
+            snapshot.defs.len(),
+            snapshot.types.len(),
+            snapshot.spans.len(),
+            snapshot.layouts.len(),
+            snapshot.allocs.len(),
+            snapshot.instances.len(),
+            snapshot.consts.len(),
+        ];
+        if counts == recorded {
+            return snapshot;
+        }
+        let [num_defs, num_types, num_spans, num_layouts, num_allocs, num_instances, num_consts] =
+            counts;
+
+        for index in snapshot.defs.len()..num_defs {
+            snapshot.defs.push(record_def(cx, DefId::to_val(index)));
+        }
+        for index in snapshot.types.len()..num_types {
+            snapshot.types.push(record_ty(cx, Ty::to_val(index)));
+        }
+        for index in snapshot.spans.len()..num_spans {
+            let span = Span::to_val(index);
+            snapshot.spans.push(SpanInfo {
+                repr: cx.span_to_string(span),
+                filename: cx.get_filename(&span),
+                lines: cx.get_lines(&span),
+            });
+        }
+        for index in snapshot.layouts.len()..num_layouts {
+            snapshot.layouts.push(cx.layout_shape(Layout::to_val(index)));
+        }
+        for index in snapshot.allocs.len()..num_allocs {
+            let alloc = cx.global_alloc(AllocId::to_val(index));
+            snapshot.allocs.push(AllocInfo { vtable: cx.vtable_allocation(&alloc), alloc });
+        }
+        for index in snapshot.instances.len()..num_instances {
+            snapshot.instances.push(record_instance(cx, InstanceDef::to_val(index)));
+        }
+        for index in snapshot.consts.len()..num_consts {
+            snapshot.consts.push(cx.0.borrow().constants[ConstId::to_val(index)].to_string());
+        }
+    }
+}
+
+/// Record a definition, with the information that is available for its kind of definition.
+fn record_def(cx: &TablesWrapper<'_>, def_id: DefId) -> DefInfo {
+    let (tcx, did) = {
+        let tables = cx.0.borrow();
+        (tables.tcx, tables[def_id])
+    };
+    let kind = tcx.def_kind(did);
+    let has_attrs = has_attrs(tcx, did);
+    let has_ty = matches!(
+        kind,
+        DefKind::Struct
+            | DefKind::Union
+            | DefKind::Enum
+            | DefKind::TyAlias
+            | DefKind::ForeignTy
+            | DefKind::Fn
+            | DefKind::Const
+            | DefKind::Static { .. }
+            | DefKind::AssocFn
+            | DefKind::AssocConst
+            | DefKind::Field
+            | DefKind::Ctor(..)
+            | DefKind::Closure
+    ) || (did.is_local() && matches!(kind, DefKind::AnonConst | DefKind::InlineConst));
+    let has_generics = matches!(
+        kind,
+        DefKind::Struct
+            | DefKind::Union
+            | DefKind::Enum
+            | DefKind::Trait
+            | DefKind::TraitAlias
+            | DefKind::TyAlias
+            | DefKind::AssocTy
+            | DefKind::Fn
+            | DefKind::Const
+            | DefKind::Static { .. }
+            | DefKind::AssocFn
+            | DefKind::AssocConst
+            | DefKind::Closure
+            | DefKind::Impl { .. }
+    );
+    let details = match kind {
+        DefKind::Struct | DefKind::Union | DefKind::Enum => {
+            let adt = AdtDef(def_id);
+            let variants = (0..cx.adt_variants_len(adt))
+                .map(|idx| {
+                    let variant = VariantDef { idx: VariantIdx::to_val(idx), adt_def: adt };
+                    VariantInfo {
+                        name: cx.variant_name(variant),
+                        fields: cx.variant_fields(variant),
+                    }
+                })
+                .collect();
+            DefDetails::Adt(AdtInfo {
+                kind: cx.adt_kind(adt),
+                is_box: cx.adt_is_box(adt),
+                is_simd: cx.adt_is_simd(adt),
+                is_cstr: cx.adt_is_cstr(adt),
+                variants,
+            })
+        }
+        DefKind::Fn | DefKind::AssocFn => DefDetails::Fn {
+            intrinsic: cx.intrinsic(def_id).map(|intrinsic| IntrinsicInfo {
+                name: cx.intrinsic_name(intrinsic),
+                must_be_overridden: cx.intrinsic_must_be_overridden(intrinsic),
+            }),
+        },
+        DefKind::Trait => DefDetails::Trait(cx.trait_decl(&TraitDef(def_id))),
+        DefKind::Impl { of_trait: true } => DefDetails::TraitImpl(cx.trait_impl(&ImplDef(def_id))),
+        // Recording the modules of other crates would record most of their definitions.
+        DefKind::Mod if did.is_local() => {
+            let module = ModuleDef(def_id);
+            DefDetails::Module {
+                children: cx.module_children(module),
+                submodules: cx.submodules(module),
+            }
+        }
+        DefKind::ForeignMod => {
+            let module = ForeignModuleDef(def_id);
+            DefDetails::ForeignModule {
+                module: cx.foreign_module(module),
+                items: cx.foreign_items(module),
+            }
+        }
+        _ => DefDetails::Other,
+    };
+    DefInfo {
+        name: cx.def_name(def_id, false),
+        trimmed_name: cx.def_name(def_id, true),
+        krate: cx.krate(def_id).id,
+        span: cx.span_of_an_item(def_id),
+        visibility: cx.visibility(def_id),
+        parent_module: cx.parent_module(def_id),
+        foreign_item_kind: cx
+            .is_foreign_item(def_id)
+            .then(|| cx.foreign_item_kind(ForeignDef(def_id))),
+        attrs: has_attrs.then(|| cx.all_attrs(def_id)),
+        doc_comment: if has_attrs { cx.doc_comment(def_id) } else { None },
+        ty: has_ty.then(|| cx.def_ty(def_id)),
+        generics: has_generics.then(|| GenericsInfo {
+            generics: cx.generics_of(def_id),
+            predicates: cx.predicates_of(def_id),
+            explicit_predicates: cx.explicit_predicates_of(def_id),
+        }),
+        details,
+    }
+}
+
+fn record_ty(cx: &TablesWrapper<'_>, ty: Ty) -> TyInfo {
+    let (has_escaping_bound_vars, is_generic) = {
+        let tables = cx.0.borrow();
+        let internal = tables.types[ty];
+        let has_escaping_bound_vars = internal.has_escaping_bound_vars();
+        (
+            has_escaping_bound_vars,
+            has_escaping_bound_vars || internal.has_param() || internal.has_aliases(),
+        )
+    };
+    // Types under binders, e.g. in function signatures, can't be laid out.
+    let layout = if has_escaping_bound_vars {
+        Err(Error::from("type has escaping bound variables"))
+    } else {
+        cx.ty_layout(ty)
+    };
+    let kind = cx.ty_kind(ty);
+    let fn_sig = match &kind {
+        TyKind::RigidTy(RigidTy::FnDef(def, args)) => Some(cx.fn_sig(*def, args)),
+        TyKind::RigidTy(RigidTy::Closure(_, args)) => Some(cx.closure_sig(args)),
+        _ => None,
+    };
+    let discriminant_ty = match &kind {
+        TyKind::RigidTy(rigid) => Some(cx.rigid_ty_discriminant_ty(rigid)),
+        _ => None,
+    };
+    // Only the types that don't depend on generic parameters can be resolved to instances.
+    let (instance, fn_ptr_instance) = match &kind {
+        TyKind::RigidTy(RigidTy::FnDef(def, args)) if !is_generic => {
+            (cx.resolve_instance(*def, args), cx.resolve_for_fn_ptr(*def, args))
+        }
+        _ => (None, None),
+    };
+    TyInfo {
+        pretty: cx.ty_pretty(ty),
+        layout,
+        fn_sig,
+        discriminant_ty,
+        instance,
+        fn_ptr_instance,
+        drop_in_place: (!is_generic).then(|| cx.resolve_drop_in_place(ty)),
+        kind,
+    }
+}
+
+fn record_instance(cx: &TablesWrapper<'_>, def: InstanceDef) -> InstanceInfo {
+    let is_local = cx.0.borrow().instances[def].def_id().is_local();
+    let ty = cx.instance_ty(def);
+    // Only functions have an ABI, e.g. the instances of statics don't.
+    let abi = match cx.ty_kind(ty) {
+        TyKind::RigidTy(RigidTy::FnDef(..) | RigidTy::Closure(..)) => cx.instance_abi(def),
+        _ => Err(Error::from("instance is not a function")),
+    };
+    InstanceInfo {
+        def_id: cx.instance_def_id(def),
+        ty,
+        args: cx.instance_args(def),
+        name: cx.instance_name(def, false),
+        trimmed_name: cx.instance_name(def, true),
+        mangled_name: cx.instance_mangled_name(def),
+        is_empty_drop_shim: cx.is_empty_drop_shim(def),
+        is_empty_async_drop_ctor_shim: cx.is_empty_async_drop_ctor_shim(def),
+        abi,
+        body: if is_local { cx.instance_body(def) } else { None },
+    }
+}
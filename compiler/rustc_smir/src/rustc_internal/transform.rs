@@ -0,0 +1,461 @@
+//! Transformations of the MIR codegened for each instance, written against Stable MIR.
+//!
+//! Drivers register transformations with [`register_mir_transform`], and the compiler applies them
+//! to the monomorphic body of each instance they ask for before collecting the items used by the
+//! instance and generating its code. The transformed body is converted back to MIR by reusing the
+//! statements and terminators of the original body that the transformations kept, since these may
+//! contain information that isn't exposed by Stable MIR, and by converting the others.
+
+use super::{run, with_tables, RustcInternal, TLV};
+use crate::rustc_smir::builder::BodyBuilder;
+use crate::rustc_smir::{Stable, Tables};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_middle::util::Providers;
+use rustc_session::Session;
+use rustc_span::source_map::Spanned;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{Body, Statement, StatementKind, Terminator, TerminatorKind, UnwindAction};
+use stable_mir::ty::IndexedVal;
+use stable_mir::Error;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// A transformation of the bodies codegened in a compilation session, see
+/// [`register_mir_transform`].
+pub trait MirTransform: Send + Sync {
+    /// Whether to transform the body of `instance`. Only the bodies of the instances that some
+    /// transformation applies to are converted to Stable MIR, so this should be cheap.
+    fn applies_to(&self, instance: Instance) -> bool;
+
+    /// Transform the monomorphic body of `instance`.
+    fn transform(&self, instance: Instance, body: &mut Body);
+}
+
+/// The transformations registered for a compilation session.
+#[derive(Default)]
+struct SessionTransforms {
+    transforms: Vec<Arc<dyn MirTransform>>,
+    /// Whether `codegen_instance_mir` was computed for an instance already. Its result is cached,
+    /// so transformations registered after that wouldn't apply consistently.
+    bodies_computed: bool,
+}
+
+/// The transformations registered for each session that enabled them with [`MirTransforms`],
+/// keyed by the address of the session.
+///
+/// This can't be stored in the session itself, since the queries that apply the transformations
+/// are plain function pointers.
+static SESSION_TRANSFORMS: Mutex<Vec<(usize, SessionTransforms)>> = Mutex::new(Vec::new());
+
+/// The `override_queries` that [`install_override_queries`] replaced.
+static PREVIOUS_OVERRIDE_QUERIES: Mutex<Option<fn(&Session, &mut Providers)>> = Mutex::new(None);
+
+fn session_key(sess: &Session) -> usize {
+    sess as *const Session as usize
+}
+
+/// Enables MIR transformations for a compilation session, for as long as it is alive.
+///
+/// Dropping it drops the transformations registered for the session, which drivers must do once
+/// the session is over.
+pub struct MirTransforms {
+    session: usize,
+}
+
+impl MirTransforms {
+    /// Enable MIR transformations for `sess`, which must have been configured with
+    /// [`install_override_queries`].
+    pub fn new(sess: &Session) -> MirTransforms {
+        let session = session_key(sess);
+        SESSION_TRANSFORMS.lock().unwrap().push((session, SessionTransforms::default()));
+        MirTransforms { session }
+    }
+}
+
+impl Drop for MirTransforms {
+    fn drop(&mut self) {
+        let mut sessions = SESSION_TRANSFORMS.lock().unwrap();
+        if let Some(index) = sessions.iter().position(|(session, _)| *session == self.session) {
+            sessions.remove(index);
+        }
+    }
+}
+
+/// Register a transformation of the body of the instances codegened by the current compilation
+/// session.
+///
+/// The transformation is invoked with the monomorphic body of every instance it applies to, before
+/// the items the instance uses are collected, and the compiler then generates the code of the body
+/// as transformed. Transformations run in the order they were registered. The items they are
+/// given are only valid for the duration of the call.
+///
+/// Transformations can add statements, terminators, basic blocks and locals, and modify the
+/// existing ones, but not the arguments of the body. Constructs that Stable MIR doesn't fully
+/// describe, like inline assembly, can be kept or moved, but not modified or created. `Abort` can
+/// only terminate cleanup blocks, i.e. blocks that are reachable from the unwind action of a
+/// terminator.
+///
+/// Transformations must be registered from the callback of [`run!`](crate::run) or
+/// [`run_with_tcx!`](crate::run_with_tcx), which codegen after their callback returns
+/// `ControlFlow::Continue`, or of other drivers that enable them with [`MirTransforms`] and
+/// [`install_override_queries`]. They aren't supported with incremental compilation, since the
+/// incremental cache doesn't know about them.
+///
+/// The transformed bodies are cached for the rest of the session, so transformations can't be
+/// registered anymore once the body of an instance has been computed for codegen or for the
+/// collection of its mono items, e.g. by [`CallGraph::collect`].
+///
+/// [`CallGraph::collect`]: stable_mir::mir::call_graph::CallGraph::collect
+pub fn register_mir_transform(transform: impl MirTransform + 'static) -> Result<(), Error> {
+    if !TLV.is_set() {
+        return Err(Error::new(
+            "MIR transformations can only be registered while the compiler is running".to_string(),
+        ));
+    }
+    let (session, incremental) = with_tables(|tables| {
+        (session_key(tables.tcx.sess), tables.tcx.sess.opts.incremental.is_some())
+    });
+    if incremental {
+        return Err(Error::new(
+            "MIR transformations are not supported with incremental compilation".to_string(),
+        ));
+    }
+    let mut sessions = SESSION_TRANSFORMS.lock().unwrap();
+    let Some((_, transforms)) = sessions.iter_mut().find(|(key, _)| *key == session) else {
+        return Err(Error::new(
+            "MIR transformations are not enabled for this compilation session".to_string(),
+        ));
+    };
+    if transforms.bodies_computed {
+        return Err(Error::new(
+            "MIR transformations must be registered before any body is codegened or collected"
+                .to_string(),
+        ));
+    }
+    transforms.transforms.push(Arc::new(transform));
+    Ok(())
+}
+
+/// Set the `override_queries` of the driver's compiler configuration to [`override_queries`],
+/// which then calls the `override_queries` it replaced, if any.
+pub fn install_override_queries(override_queries: &mut Option<fn(&Session, &mut Providers)>) {
+    let previous = override_queries.replace(self::override_queries);
+    // Don't chain to ourselves if the configuration is reused.
+    if previous.map(|previous| previous as usize) != Some(self::override_queries as usize) {
+        *PREVIOUS_OVERRIDE_QUERIES.lock().unwrap() = previous;
+    }
+}
+
+/// Override the queries that the registered MIR transformations need to apply to codegen.
+pub fn override_queries(sess: &Session, providers: &mut Providers) {
+    let previous = *PREVIOUS_OVERRIDE_QUERIES.lock().unwrap();
+    if let Some(previous) = previous {
+        previous(sess, providers);
+    }
+    providers.codegen_instance_mir = codegen_instance_mir;
+}
+
+fn codegen_instance_mir<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: ty::Instance<'tcx>,
+) -> &'tcx mir::Body<'tcx> {
+    // Clone the transformations, so that the lock isn't held while they run.
+    let session = session_key(tcx.sess);
+    let transforms = SESSION_TRANSFORMS
+        .lock()
+        .unwrap()
+        .iter_mut()
+        .find(|(key, _)| *key == session)
+        .map(|(_, transforms)| {
+            transforms.bodies_computed = true;
+            transforms.transforms.clone()
+        })
+        .unwrap_or_default();
+    if transforms.is_empty() {
+        return tcx.instance_mir(instance.def);
+    }
+
+    let transform = || {
+        let stable_instance = with_tables(|tables| instance.stable(tables));
+        let transforms: Vec<_> =
+            transforms.iter().filter(|transform| transform.applies_to(stable_instance)).collect();
+        if transforms.is_empty() {
+            return Ok(None);
+        }
+        let original = BodyBuilder::new(tcx, instance).build_internal();
+        let original_stable = with_tables(|tables| original.stable(tables));
+        let mut body = original_stable.clone();
+        for transform in transforms {
+            transform.transform(stable_instance, &mut body);
+        }
+        if body == original_stable {
+            return Ok(Some(original));
+        }
+        with_tables(|tables| lower_body(tables, tcx, original.clone(), &original_stable, &body))
+            .map(Some)
+    };
+    // Bodies are usually requested during codegen, after the Stable MIR context of the driver's
+    // callback is gone, but they can also be requested from the callback.
+    let result = if TLV.is_set() { transform() } else { run(tcx, transform).and_then(|res| res) };
+    match result {
+        Ok(Some(body)) => tcx.arena.alloc(body),
+        Ok(None) => tcx.instance_mir(instance.def),
+        Err(err) => {
+            tcx.dcx().fatal(format!("failed to apply MIR transformations to `{instance}`: {err}"))
+        }
+    }
+}
+
+/// Convert the transformed stable `body` back into MIR. The statements and terminators that are
+/// also in `original_stable` are taken from `original`.
+fn lower_body<'tcx>(
+    tables: &mut Tables<'_>,
+    tcx: TyCtxt<'tcx>,
+    original: mir::Body<'tcx>,
+    original_stable: &Body,
+    body: &Body,
+) -> Result<mir::Body<'tcx>, Error> {
+    if body.arg_locals() != original_stable.arg_locals()
+        || body.ret_local() != original_stable.ret_local()
+        || body.spread_arg() != original_stable.spread_arg()
+    {
+        return Err(Error::new("the signature of the body was modified".to_string()));
+    }
+
+    // Look up the original statements and terminators by span, so that they are found even if
+    // the transformations moved them to other blocks.
+    let mut original_statements: FxHashMap<usize, Vec<(&Statement, &mir::Statement<'tcx>)>> =
+        FxHashMap::default();
+    let mut original_terminators: FxHashMap<usize, Vec<(&Terminator, &mir::Terminator<'tcx>)>> =
+        FxHashMap::default();
+    let mut original_scopes: FxHashMap<usize, mir::SourceScope> = FxHashMap::default();
+    for (block, data) in original_stable.blocks.iter().zip(original.basic_blocks.iter()) {
+        for (statement, internal) in block.statements.iter().zip(&data.statements) {
+            original_statements
+                .entry(statement.span.to_index())
+                .or_default()
+                .push((statement, internal));
+            original_scopes.entry(statement.span.to_index()).or_insert(internal.source_info.scope);
+        }
+        let terminator = &block.terminator;
+        original_terminators
+            .entry(terminator.span.to_index())
+            .or_default()
+            .push((terminator, data.terminator()));
+        original_scopes
+            .entry(terminator.span.to_index())
+            .or_insert(data.terminator().source_info.scope);
+    }
+
+    // Stable MIR doesn't distinguish cleanup blocks, which are the blocks reachable from the
+    // cleanup of unwind actions.
+    let mut is_cleanup = vec![false; body.blocks.len()];
+    let mut stack: Vec<_> = body
+        .blocks
+        .iter()
+        .filter_map(|block| match block.terminator.kind.unwind() {
+            Some(UnwindAction::Cleanup(bb)) => Some(*bb),
+            _ => None,
+        })
+        .collect();
+    while let Some(bb) = stack.pop() {
+        let Some(block) = body.blocks.get(bb) else {
+            return Err(Error::new(format!("unknown basic block `{bb}`")));
+        };
+        if !mem::replace(&mut is_cleanup[bb], true) {
+            stack.extend(block.terminator.successors());
+        }
+    }
+
+    // The statements and terminators added by the transformations take the source scope of an
+    // original one with the same span, or else of the closest original one before them in their
+    // block, so that they stay in the scope of the code they were added to.
+    let mut blocks = Vec::with_capacity(body.blocks.len());
+    for (block, is_cleanup) in body.blocks.iter().zip(is_cleanup) {
+        let originals: Vec<_> = block
+            .statements
+            .iter()
+            .map(|statement| {
+                original_statements
+                    .get(&statement.span.to_index())
+                    .and_then(|originals| originals.iter().find(|(stable, _)| *stable == statement))
+                    .map(|(_, internal)| *internal)
+            })
+            .collect();
+        let terminator = &block.terminator;
+        let original_terminator = original_terminators
+            .get(&terminator.span.to_index())
+            .and_then(|originals| originals.iter().find(|(stable, _)| *stable == terminator))
+            .map(|(_, internal)| *internal);
+        let mut scope = originals
+            .iter()
+            .flatten()
+            .map(|internal| internal.source_info.scope)
+            .chain(original_terminator.map(|internal| internal.source_info.scope))
+            .next()
+            .unwrap_or(mir::OUTERMOST_SOURCE_SCOPE);
+
+        let mut statements = Vec::with_capacity(block.statements.len());
+        for (statement, original) in block.statements.iter().zip(originals) {
+            let statement = match original {
+                Some(internal) => {
+                    scope = internal.source_info.scope;
+                    internal.clone()
+                }
+                None => {
+                    let scope =
+                        original_scopes.get(&statement.span.to_index()).copied().unwrap_or(scope);
+                    lower_statement(tables, tcx, statement, scope)?
+                }
+            };
+            statements.push(statement);
+        }
+        let terminator = match original_terminator {
+            Some(internal) => internal.clone(),
+            None => {
+                let scope =
+                    original_scopes.get(&terminator.span.to_index()).copied().unwrap_or(scope);
+                lower_terminator(tables, tcx, terminator, scope, is_cleanup)?
+            }
+        };
+        blocks.push(mir::BasicBlockData { statements, terminator: Some(terminator), is_cleanup });
+    }
+
+    let local_decls: Vec<_> = body
+        .local_decls()
+        .map(|(local, decl)| match original_stable.local_decl(local) {
+            Some(original_decl) if original_decl == decl => {
+                original.local_decls[mir::Local::from_usize(local)].clone()
+            }
+            _ => decl.internal(tables, tcx),
+        })
+        .collect();
+    let var_debug_info = if body.var_debug_info == original_stable.var_debug_info {
+        None
+    } else {
+        Some(body.var_debug_info.internal(tables, tcx))
+    };
+
+    let mut transformed = original;
+    *transformed.basic_blocks_mut() = blocks.into_iter().collect();
+    transformed.local_decls = local_decls.into_iter().collect();
+    if let Some(var_debug_info) = var_debug_info {
+        transformed.var_debug_info = var_debug_info;
+    }
+    Ok(transformed)
+}
+
+fn lower_statement<'tcx>(
+    tables: &mut Tables<'_>,
+    tcx: TyCtxt<'tcx>,
+    statement: &Statement,
+    scope: mir::SourceScope,
+) -> Result<mir::Statement<'tcx>, Error> {
+    let kind = match &statement.kind {
+        StatementKind::Assign(place, rvalue) => mir::StatementKind::Assign(Box::new((
+            place.internal(tables, tcx),
+            rvalue.internal(tables, tcx),
+        ))),
+        StatementKind::SetDiscriminant { place, variant_index } => {
+            mir::StatementKind::SetDiscriminant {
+                place: Box::new(place.internal(tables, tcx)),
+                variant_index: variant_index.internal(tables, tcx),
+            }
+        }
+        StatementKind::Deinit(place) => {
+            mir::StatementKind::Deinit(Box::new(place.internal(tables, tcx)))
+        }
+        StatementKind::StorageLive(local) => {
+            mir::StatementKind::StorageLive(mir::Local::from_usize(*local))
+        }
+        StatementKind::StorageDead(local) => {
+            mir::StatementKind::StorageDead(mir::Local::from_usize(*local))
+        }
+        StatementKind::Retag(kind, place) => mir::StatementKind::Retag(
+            kind.internal(tables, tcx),
+            Box::new(place.internal(tables, tcx)),
+        ),
+        StatementKind::PlaceMention(place) => {
+            mir::StatementKind::PlaceMention(Box::new(place.internal(tables, tcx)))
+        }
+        StatementKind::Intrinsic(intrinsic) => {
+            mir::StatementKind::Intrinsic(Box::new(intrinsic.internal(tables, tcx)))
+        }
+        StatementKind::ConstEvalCounter => mir::StatementKind::ConstEvalCounter,
+        StatementKind::Nop => mir::StatementKind::Nop,
+        StatementKind::FakeRead(..)
+        | StatementKind::AscribeUserType { .. }
+        | StatementKind::Coverage(_) => {
+            return Err(Error::new(format!("cannot add or modify statement `{statement:?}`")));
+        }
+    };
+    Ok(mir::Statement {
+        source_info: mir::SourceInfo { span: statement.span.internal(tables, tcx), scope },
+        kind,
+    })
+}
+
+fn lower_terminator<'tcx>(
+    tables: &mut Tables<'_>,
+    tcx: TyCtxt<'tcx>,
+    terminator: &Terminator,
+    scope: mir::SourceScope,
+    is_cleanup: bool,
+) -> Result<mir::Terminator<'tcx>, Error> {
+    let span = terminator.span.internal(tables, tcx);
+    let kind = match &terminator.kind {
+        TerminatorKind::Goto { target } => {
+            mir::TerminatorKind::Goto { target: mir::BasicBlock::from_usize(*target) }
+        }
+        TerminatorKind::SwitchInt { discr, targets } => mir::TerminatorKind::SwitchInt {
+            discr: discr.internal(tables, tcx),
+            targets: targets.internal(tables, tcx),
+        },
+        TerminatorKind::Resume => mir::TerminatorKind::UnwindResume,
+        // `Abort` is Stable MIR's `UnwindTerminate`, which can only terminate cleanup blocks. An
+        // added one terminates unwinding like a panic in a destructor during cleanup does.
+        TerminatorKind::Abort if is_cleanup => {
+            mir::TerminatorKind::UnwindTerminate(mir::UnwindTerminateReason::InCleanup)
+        }
+        TerminatorKind::Abort => {
+            return Err(Error::new("`Abort` can only terminate cleanup blocks".to_string()));
+        }
+        TerminatorKind::Return => mir::TerminatorKind::Return,
+        TerminatorKind::Unreachable => mir::TerminatorKind::Unreachable,
+        TerminatorKind::Drop { place, target, unwind } => mir::TerminatorKind::Drop {
+            place: place.internal(tables, tcx),
+            target: mir::BasicBlock::from_usize(*target),
+            unwind: unwind.internal(tables, tcx),
+            replace: false,
+        },
+        TerminatorKind::Call { func, args, destination, target, unwind } => {
+            mir::TerminatorKind::Call {
+                func: func.internal(tables, tcx),
+                args: args
+                    .iter()
+                    .map(|arg| Spanned { node: arg.internal(tables, tcx), span })
+                    .collect(),
+                destination: destination.internal(tables, tcx),
+                target: target.map(mir::BasicBlock::from_usize),
+                unwind: unwind.internal(tables, tcx),
+                call_source: mir::CallSource::Normal,
+                fn_span: span,
+            }
+        }
+        TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
+            mir::TerminatorKind::Assert {
+                cond: cond.internal(tables, tcx),
+                expected: *expected,
+                msg: Box::new(msg.internal(tables, tcx)),
+                target: mir::BasicBlock::from_usize(*target),
+                unwind: unwind.internal(tables, tcx),
+            }
+        }
+        TerminatorKind::InlineAsm { .. } => {
+            return Err(Error::new(format!("cannot add or modify terminator `{terminator:?}`")));
+        }
+    };
+    Ok(mir::Terminator { source_info: mir::SourceInfo { span, scope }, kind })
+}
@@ -28,16 +28,23 @@ pub fn new(tcx: TyCtxt<'tcx>, instance: ty::Instance<'tcx>) -> Self {
     /// Build a stable monomorphic body for a given instance based on the MIR body.
     ///
     /// All constants are also evaluated.
-    pub fn build(mut self, tables: &mut Tables<'tcx>) -> stable_mir::mir::Body {
-        let body = tables.tcx.instance_mir(self.instance.def).clone();
-        let mono_body = if !self.instance.args.is_empty()
+    pub fn build(self, tables: &mut Tables<'tcx>) -> stable_mir::mir::Body {
+        self.build_internal().stable(tables)
+    }
+
+    /// Build the monomorphic body for a given instance, using the internal representation.
+    ///
+    /// All constants are also evaluated.
+    pub fn build_internal(mut self) -> mir::Body<'tcx> {
+        let body = self.tcx.instance_mir(self.instance.def).clone();
+        if !self.instance.args.is_empty()
             // Without the `generic_const_exprs` feature gate, anon consts in signatures do not
             // get generic parameters. Which is wrong, but also not a problem without
             // generic_const_exprs
             || self.tcx.def_kind(self.instance.def_id()) != DefKind::AnonConst
         {
             let mut mono_body = self.instance.instantiate_mir_and_normalize_erasing_regions(
-                tables.tcx,
+                self.tcx,
                 ty::ParamEnv::reveal_all(),
                 ty::EarlyBinder::bind(body),
             );
@@ -46,8 +53,7 @@ pub fn build(mut self, tables: &mut Tables<'tcx>) -> stable_mir::mir::Body {
         } else {
             // Already monomorphic.
             body
-        };
-        mono_body.stable(tables)
+        }
     }
 }
 
@@ -6,6 +6,10 @@
 #![allow(rustc::usage_of_qualified_ty)]
 
 use rustc_abi::HasDataLayout;
+use rustc_ast::util::comments::beautify_doc_string;
+use rustc_data_structures::fx::FxIndexSet;
+use rustc_hir::def::{DefKind, Res};
+use rustc_middle::metadata::ModChild;
 use rustc_middle::ty;
 use rustc_middle::ty::layout::{
     FnAbiOf, FnAbiOfHelpers, HasParamEnv, HasTyCtxt, LayoutOf, LayoutOfHelpers,
@@ -15,18 +19,22 @@
     GenericPredicates, Instance, List, ParamEnv, ScalarInt, TyCtxt, TypeVisitableExt, ValTree,
 };
 use rustc_span::def_id::LOCAL_CRATE;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
 use stable_mir::abi::{FnAbi, Layout, LayoutShape};
 use stable_mir::compiler_interface::Context;
 use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::call_graph::{CallGraph, Edge};
 use stable_mir::mir::mono::{InstanceDef, StaticDef};
 use stable_mir::mir::{BinOp, Body, Place};
 use stable_mir::target::{MachineInfo, MachineSize};
 use stable_mir::ty::{
     AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, Const, FieldDef, FnDef, ForeignDef,
-    ForeignItemKind, GenericArgs, IntrinsicDef, LineInfo, PolyFnSig, RigidTy, Span, Ty, TyKind,
-    UintTy, VariantDef,
+    ForeignItemKind, GenericArgs, IntrinsicDef, LineInfo, ModuleChild, ModuleDef, PolyFnSig,
+    RigidTy, Span, TraitRef, Ty, TyKind, UintTy, VariantDef, Visibility,
+};
+use stable_mir::{
+    Attribute, Crate, CrateDef, CrateItem, CrateNum, DefId, Error, Filename, ItemKind, Symbol,
 };
-use stable_mir::{Crate, CrateDef, CrateItem, CrateNum, DefId, Error, Filename, ItemKind, Symbol};
 use std::cell::RefCell;
 use std::iter;
 
@@ -700,6 +708,276 @@ fn binop_ty(&self, bin_op: BinOp, rhs: Ty, lhs: Ty) -> Ty {
         let ty = bin_op.internal(&mut *tables, tcx).ty(tcx, rhs_internal, lhs_internal);
         ty.stable(&mut *tables)
     }
+
+    fn attrs_by_path(&self, def_id: stable_mir::DefId, attr: &[Symbol]) -> Vec<Attribute> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables[def_id];
+        if !has_attrs(tcx, did) {
+            return Vec::new();
+        }
+        let attr_name: Vec<_> = attr.iter().map(|seg| rustc_span::Symbol::intern(seg)).collect();
+        tcx.get_attrs_by_path(did, &attr_name)
+            .map(|attribute| {
+                let value = rustc_ast_pretty::pprust::attribute_to_string(attribute);
+                Attribute::new(value, attribute.span.stable(&mut *tables))
+            })
+            .collect()
+    }
+
+    fn all_attrs(&self, def_id: stable_mir::DefId) -> Vec<Attribute> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables[def_id];
+        if !has_attrs(tcx, did) {
+            return Vec::new();
+        }
+        tcx.get_attrs_unchecked(did)
+            .iter()
+            .map(|attribute| {
+                let value = rustc_ast_pretty::pprust::attribute_to_string(attribute);
+                Attribute::new(value, attribute.span.stable(&mut *tables))
+            })
+            .collect()
+    }
+
+    fn doc_comment(&self, def_id: stable_mir::DefId) -> Option<String> {
+        let tables = self.0.borrow();
+        let (tcx, did) = (tables.tcx, tables[def_id]);
+        if !has_attrs(tcx, did) {
+            return None;
+        }
+        let lines: Vec<String> = tcx
+            .get_attrs_unchecked(did)
+            .iter()
+            .filter_map(|attribute| attribute.doc_str_and_comment_kind())
+            .flat_map(|(doc, kind)| {
+                let doc = beautify_doc_string(doc, kind);
+                doc.as_str().lines().map(str::to_string).collect::<Vec<_>>()
+            })
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+        // Remove the indentation that is common to all lines, like the space after `///`.
+        let indent = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let lines: Vec<_> = lines.iter().map(|line| line.get(indent..).unwrap_or("")).collect();
+        Some(lines.join("\n"))
+    }
+
+    fn visibility(&self, def_id: stable_mir::DefId) -> Result<Visibility, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables[def_id];
+        let visibility = match tcx.def_kind(did) {
+            DefKind::TyParam
+            | DefKind::ConstParam
+            | DefKind::LifetimeParam
+            | DefKind::Closure
+            | DefKind::AnonConst
+            | DefKind::InlineConst
+            | DefKind::OpaqueTy
+            | DefKind::Impl { .. }
+            | DefKind::ForeignMod
+            | DefKind::GlobalAsm => {
+                // These can't be named, so they don't have a visibility of their own.
+                let Some(module) = parent_module(tcx, did) else {
+                    return Err(Error::new(format!(
+                        "Item `{}` has no parent module",
+                        tcx.def_path_str(did)
+                    )));
+                };
+                ty::Visibility::Restricted(module)
+            }
+            _ => tcx.visibility(did),
+        };
+        Ok(visibility.stable(&mut *tables))
+    }
+
+    fn parent_module(&self, def_id: stable_mir::DefId) -> Option<ModuleDef> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables[def_id];
+        parent_module(tcx, did).map(|module| tables.module_def(module))
+    }
+
+    fn root_module(&self, crate_num: CrateNum) -> ModuleDef {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let krate = crate_num.internal(&mut *tables, tcx);
+        tables.module_def(krate.as_def_id())
+    }
+
+    fn module_children(&self, def: ModuleDef) -> Vec<ModuleChild> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        module_children(tcx, tables[def.0])
+            .iter()
+            .filter_map(|child| {
+                let Res::Def(_, did) = child.res else { return None };
+                Some(ModuleChild {
+                    name: child.ident.to_string(),
+                    def_id: tables.create_def_id(did),
+                    vis: child.vis.stable(&mut *tables),
+                    is_import: !child.reexport_chain.is_empty(),
+                })
+            })
+            .collect()
+    }
+
+    fn submodules(&self, def: ModuleDef) -> Vec<ModuleDef> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        module_children(tcx, tables[def.0])
+            .iter()
+            .filter_map(|child| match child.res {
+                Res::Def(DefKind::Mod, did) if child.reexport_chain.is_empty() => {
+                    Some(tables.module_def(did))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn trait_ref_holds(&self, trait_ref: &TraitRef, env: &stable_mir::ty::ParamEnv) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let trait_ref = trait_ref.internal(&mut *tables, tcx);
+        let param_env = env.internal(&mut *tables, tcx);
+        tcx.infer_ctxt()
+            .build()
+            .type_implements_trait(trait_ref.def_id, trait_ref.args, param_env)
+            .must_apply_modulo_regions()
+    }
+
+    fn normalize_ty(&self, ty: Ty, env: &stable_mir::ty::ParamEnv) -> Result<Ty, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        let param_env = env.internal(&mut *tables, tcx);
+        match tcx.try_normalize_erasing_regions(param_env, ty) {
+            Ok(normalized) => Ok(normalized.stable(&mut *tables)),
+            Err(_) => Err(Error::new(format!("Failed to normalize `{ty}`"))),
+        }
+    }
+
+    fn ty_is_copy(&self, ty: Ty, env: &stable_mir::ty::ParamEnv) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        let param_env = env.internal(&mut *tables, tcx);
+        tcx.infer_ctxt().build().type_is_copy_modulo_regions(param_env, ty)
+    }
+
+    fn ty_is_sized(&self, ty: Ty, env: &stable_mir::ty::ParamEnv) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        let param_env = env.internal(&mut *tables, tcx);
+        tcx.infer_ctxt().build().type_is_sized_modulo_regions(param_env, ty)
+    }
+
+    fn ty_needs_drop(&self, ty: Ty, env: &stable_mir::ty::ParamEnv) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        let param_env = env.internal(&mut *tables, tcx);
+        ty.needs_drop(tcx, param_env)
+    }
+
+    fn call_graph(&self) -> Result<CallGraph, Error> {
+        // The collection may need the tables, e.g. to run the MIR transforms of a `StableMir`
+        // driver, so don't hold on to them while it runs.
+        let tcx = self.0.borrow().tcx;
+        let graph = tcx.collect_mono_item_graph();
+        // If there was an error, e.g. because a constant failed to evaluate, the graph may be
+        // missing items.
+        if tcx.dcx().has_errors().is_some() {
+            return Err(Error::new("Failed to collect the mono items".to_string()));
+        }
+
+        let mut tables = self.0.borrow_mut();
+        // Sort the items so that the graph doesn't depend on the order of the collection.
+        let mut items: Vec<_> = graph.items.iter().copied().collect();
+        items.sort_by_cached_key(|item| with_no_trimmed_paths!(item.to_string()));
+        let mut edges = Vec::new();
+        for &user in &items {
+            // An item can use another one in the same way several times, e.g. if it has several
+            // calls to the same function, but the graph only has one edge for them.
+            let uses: FxIndexSet<_> =
+                graph.usage_map.get_used_items(user).iter().copied().collect();
+            for (used, kind) in uses {
+                edges.push(Edge {
+                    user: user.stable(&mut *tables),
+                    used: used.stable(&mut *tables),
+                    kind: kind.stable(&mut *tables),
+                });
+            }
+        }
+        let mut roots = graph.roots.clone();
+        roots.sort_by_cached_key(|item| with_no_trimmed_paths!(item.to_string()));
+        roots.dedup();
+        Ok(CallGraph::new(
+            roots.iter().map(|item| item.stable(&mut *tables)).collect(),
+            items.iter().map(|item| item.stable(&mut *tables)).collect(),
+            edges,
+        ))
+    }
+}
+
+/// Whether the attributes of the given definition are available. The crate metadata only has
+/// the attributes of some kinds of definitions, see `should_encode_attrs` in `rustc_metadata`.
+pub(crate) fn has_attrs(tcx: TyCtxt<'_>, def_id: rustc_span::def_id::DefId) -> bool {
+    def_id.is_local()
+        || matches!(
+            tcx.def_kind(def_id),
+            DefKind::Mod
+                | DefKind::Struct
+                | DefKind::Union
+                | DefKind::Enum
+                | DefKind::Variant
+                | DefKind::Trait
+                | DefKind::TraitAlias
+                | DefKind::TyAlias
+                | DefKind::ForeignTy
+                | DefKind::AssocTy
+                | DefKind::Fn
+                | DefKind::Const
+                | DefKind::Static { nested: false, .. }
+                | DefKind::AssocFn
+                | DefKind::AssocConst
+                | DefKind::Macro(_)
+                | DefKind::Field
+                | DefKind::Impl { .. }
+                | DefKind::Closure
+                // The attributes of constructors are the ones of their struct or variant.
+                | DefKind::Ctor(..)
+        )
+}
+
+/// Get the closest module that contains the given definition.
+fn parent_module(
+    tcx: TyCtxt<'_>,
+    def_id: rustc_span::def_id::DefId,
+) -> Option<rustc_span::def_id::DefId> {
+    let mut parent = tcx.opt_parent(def_id)?;
+    while tcx.def_kind(parent) != DefKind::Mod {
+        parent = tcx.opt_parent(parent)?;
+    }
+    Some(parent)
+}
+
+fn module_children(tcx: TyCtxt<'_>, def_id: rustc_span::def_id::DefId) -> &[ModChild] {
+    if let Some(local_def_id) = def_id.as_local() {
+        tcx.module_children_local(local_def_id)
+    } else {
+        tcx.module_children(def_id)
+    }
 }
 
 pub struct TablesWrapper<'tcx>(pub RefCell<Tables<'tcx>>);
@@ -3,7 +3,7 @@
 use rustc_middle::bug;
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::alloc_range;
-use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::mir::mono::{MonoItem, UsageKind};
 use stable_mir::mir::alloc::GlobalAlloc;
 use stable_mir::mir::{ConstOperand, Statement, UserTypeProjection, VarDebugInfoFragment};
 use stable_mir::ty::{Allocation, Const, ConstantKind};
@@ -776,3 +776,19 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         }
     }
 }
+
+impl<'tcx> Stable<'tcx> for UsageKind {
+    type T = stable_mir::mir::call_graph::EdgeKind;
+
+    fn stable(&self, _: &mut Tables<'_>) -> Self::T {
+        use stable_mir::mir::call_graph::EdgeKind;
+        match self {
+            UsageKind::Call => EdgeKind::Call,
+            UsageKind::DropGlue => EdgeKind::DropGlue,
+            UsageKind::VtableMethod => EdgeKind::VtableMethod,
+            UsageKind::FnPointer => EdgeKind::FnPointer,
+            UsageKind::Static => EdgeKind::Static,
+            UsageKind::Root => unreachable!("roots are not used by other items"),
+        }
+    }
+}
@@ -893,3 +893,16 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         }
     }
 }
+
+impl<'tcx> Stable<'tcx> for ty::Visibility<rustc_span::def_id::DefId> {
+    type T = stable_mir::ty::Visibility;
+
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        match *self {
+            ty::Visibility::Public => stable_mir::ty::Visibility::Public,
+            ty::Visibility::Restricted(module) => {
+                stable_mir::ty::Visibility::Restricted(tables.module_def(module))
+            }
+        }
+    }
+}
@@ -22,7 +22,7 @@
 use crate::rustc_internal::IndexMap;
 
 mod alloc;
-mod builder;
+pub(crate) mod builder;
 pub(crate) mod context;
 mod convert;
 
@@ -3,11 +3,13 @@
 // seems likely that they should eventually be merged into more
 // general routines.
 
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_infer::traits::FulfillmentErrorCode;
 use rustc_middle::bug;
 use rustc_middle::traits::CodegenObligationError;
-use rustc_middle::ty::{self, TyCtxt, TypeVisitableExt};
+use rustc_middle::ty::{self, GenericArgKind, TyCtxt, TypeVisitableExt};
 use rustc_trait_selection::traits::error_reporting::TypeErrCtxtExt;
 use rustc_trait_selection::traits::{
     ImplSource, Obligation, ObligationCause, ObligationCtxt, SelectionContext, Unimplemented,
@@ -22,6 +24,86 @@
 ///
 /// This also expects that `trait_ref` is fully normalized.
 pub fn codegen_select_candidate<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: (ty::ParamEnv<'tcx>, ty::TraitRef<'tcx>),
+) -> Result<&'tcx ImplSource<'tcx, ()>, CodegenObligationError> {
+    match shared_cache_key(tcx, key) {
+        Some(shared_cache_key) => {
+            tcx.with_shared_query_result_cache(shared_cache_key, || select_candidate(tcx, key))
+        }
+        None => select_candidate(tcx, key),
+    }
+}
+
+/// The key of the result of `codegen_select_candidate` in the cache given with
+/// `-Zshared-cache`, if it can be stored there.
+///
+/// Results are only shared if there are no where clauses and the trait
+/// reference doesn't refer to the local crate. The coherence rules then ensure
+/// that the selected impl comes from the crate that defines the trait or from
+/// one of the crates that define the types in the trait reference, and the
+/// builtin impls of auto traits only depend on those crates and the crates
+/// they depend on. All of these are covered by the hashes of the former crates.
+fn shared_cache_key<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    (param_env, trait_ref): (ty::ParamEnv<'tcx>, ty::TraitRef<'tcx>),
+) -> Option<Fingerprint> {
+    if tcx.query_system.shared_cache.is_none() || !param_env.caller_bounds().is_empty() {
+        return None;
+    }
+
+    let mut def_ids = vec![trait_ref.def_id];
+    for arg in trait_ref.args.walk() {
+        match arg.unpack() {
+            GenericArgKind::Type(ty) => match *ty.kind() {
+                ty::Adt(adt_def, _) => def_ids.push(adt_def.did()),
+                ty::Foreign(def_id)
+                | ty::FnDef(def_id, _)
+                | ty::Closure(def_id, _)
+                | ty::CoroutineClosure(def_id, _)
+                | ty::Coroutine(def_id, _)
+                | ty::CoroutineWitness(def_id, _) => def_ids.push(def_id),
+                ty::Alias(_, alias) => def_ids.push(alias.def_id),
+                ty::Dynamic(predicates, ..) => {
+                    def_ids.extend(predicates.iter().map(
+                        |predicate| match predicate.skip_binder() {
+                            ty::ExistentialPredicate::Trait(trait_ref) => trait_ref.def_id,
+                            ty::ExistentialPredicate::Projection(projection) => projection.def_id,
+                            ty::ExistentialPredicate::AutoTrait(def_id) => def_id,
+                        },
+                    ))
+                }
+                _ => {}
+            },
+            GenericArgKind::Const(ct) => {
+                if let ty::ConstKind::Unevaluated(uv) = ct.kind() {
+                    def_ids.push(uv.def);
+                }
+            }
+            GenericArgKind::Lifetime(_) => {}
+        }
+    }
+    if def_ids.iter().any(|def_id| def_id.is_local()) {
+        return None;
+    }
+    let mut crate_hashes: Vec<_> =
+        def_ids.iter().map(|def_id| tcx.crate_hash(def_id.krate).as_u128()).collect();
+    crate_hashes.sort_unstable();
+    crate_hashes.dedup();
+
+    Some(tcx.with_stable_hashing_context(|mut hcx| {
+        let mut hasher = StableHasher::new();
+        "codegen_select_candidate".hash_stable(&mut hcx, &mut hasher);
+        tcx.sess.cfg_version.hash_stable(&mut hcx, &mut hasher);
+        tcx.sess.opts.target_triple.triple().hash_stable(&mut hcx, &mut hasher);
+        tcx.next_trait_solver_globally().hash_stable(&mut hcx, &mut hasher);
+        (param_env, trait_ref).hash_stable(&mut hcx, &mut hasher);
+        crate_hashes.hash_stable(&mut hcx, &mut hasher);
+        hasher.finish()
+    }))
+}
+
+fn select_candidate<'tcx>(
     tcx: TyCtxt<'tcx>,
     (param_env, trait_ref): (ty::ParamEnv<'tcx>, ty::TraitRef<'tcx>),
 ) -> Result<&'tcx ImplSource<'tcx, ()>, CodegenObligationError> {
@@ -7,18 +7,19 @@
 
 use crate::abi::{FnAbi, Layout, LayoutShape};
 use crate::mir::alloc::{AllocId, GlobalAlloc};
+use crate::mir::call_graph::CallGraph;
 use crate::mir::mono::{Instance, InstanceDef, StaticDef};
 use crate::mir::{BinOp, Body, Place};
 use crate::target::MachineInfo;
 use crate::ty::{
     AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, Const, FieldDef, FnDef, ForeignDef,
     ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgs, GenericPredicates, Generics,
-    ImplDef, ImplTrait, IntrinsicDef, LineInfo, PolyFnSig, RigidTy, Span, TraitDecl, TraitDef, Ty,
-    TyKind, UintTy, VariantDef,
+    ImplDef, ImplTrait, IntrinsicDef, LineInfo, ModuleChild, ModuleDef, ParamEnv, PolyFnSig,
+    RigidTy, Span, TraitDecl, TraitDef, TraitRef, Ty, TyKind, UintTy, VariantDef, Visibility,
 };
 use crate::{
-    mir, Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename, ImplTraitDecls, ItemKind,
-    Symbol, TraitDecls,
+    mir, Attribute, Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename, ImplTraitDecls,
+    ItemKind, Symbol, TraitDecls,
 };
 
 /// This trait defines the interface between stable_mir and the Rust compiler.
@@ -226,6 +227,48 @@ fn resolve_closure(
 
     /// Get the resulting type of binary operation.
     fn binop_ty(&self, bin_op: BinOp, rhs: Ty, lhs: Ty) -> Ty;
+
+    /// Get the attributes of a definition with the given path.
+    fn attrs_by_path(&self, def_id: DefId, attr: &[Symbol]) -> Vec<Attribute>;
+
+    /// Get all the attributes of a definition.
+    fn all_attrs(&self, def_id: DefId) -> Vec<Attribute>;
+
+    /// Get the documentation of a definition.
+    fn doc_comment(&self, def_id: DefId) -> Option<String>;
+
+    /// Get the visibility of a definition.
+    fn visibility(&self, def_id: DefId) -> Result<Visibility, Error>;
+
+    /// Get the module a definition is declared in.
+    fn parent_module(&self, def_id: DefId) -> Option<ModuleDef>;
+
+    /// Get the root module of a crate.
+    fn root_module(&self, crate_num: CrateNum) -> ModuleDef;
+
+    /// Get the items declared or imported in a module.
+    fn module_children(&self, def: ModuleDef) -> Vec<ModuleChild>;
+
+    /// Get the modules declared in a module.
+    fn submodules(&self, def: ModuleDef) -> Vec<ModuleDef>;
+
+    /// Check whether a trait reference holds in the given environment.
+    fn trait_ref_holds(&self, trait_ref: &TraitRef, env: &ParamEnv) -> bool;
+
+    /// Normalize a type in the given environment.
+    fn normalize_ty(&self, ty: Ty, env: &ParamEnv) -> Result<Ty, Error>;
+
+    /// Check whether a type implements `Copy` in the given environment.
+    fn ty_is_copy(&self, ty: Ty, env: &ParamEnv) -> bool;
+
+    /// Check whether a type implements `Sized` in the given environment.
+    fn ty_is_sized(&self, ty: Ty, env: &ParamEnv) -> bool;
+
+    /// Check whether a type needs to be dropped in the given environment.
+    fn ty_needs_drop(&self, ty: Ty, env: &ParamEnv) -> bool;
+
+    /// Collect the mono items of the local crate and how they use each other.
+    fn call_graph(&self) -> Result<CallGraph, Error>;
 }
 
 // A thread local variable that stores a pointer to the tables mapping between TyCtxt
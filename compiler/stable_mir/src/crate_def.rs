@@ -1,11 +1,12 @@
 //! Module that define a common trait for things that represent a crate definition,
 //! such as, a function, a trait, an enum, and any other definitions.
 
-use crate::ty::Span;
-use crate::{with, Crate, Symbol};
+use crate::ty::{ModuleDef, Span, Visibility};
+use crate::{with, Crate, Error, Symbol};
+use serde::{Deserialize, Serialize};
 
 /// A unique identification number for each item accessible for the current compilation unit.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DefId(pub(crate) usize);
 
 /// A trait for retrieving information about a particular definition.
@@ -50,6 +51,81 @@ fn span(&self) -> Span {
         let def_id = self.def_id();
         with(|cx| cx.span_of_an_item(def_id))
     }
+
+    /// Return the attributes of this definition whose path is `attr`.
+    ///
+    /// For example, `&["inline".to_string()]` returns the `#[inline]` attributes, and
+    /// `&["rustfmt".to_string(), "skip".to_string()]` returns the `#[rustfmt::skip]` tool
+    /// attributes.
+    fn attrs_by_path(&self, attr: &[Symbol]) -> Vec<Attribute> {
+        let def_id = self.def_id();
+        with(|cx| cx.attrs_by_path(def_id, attr))
+    }
+
+    /// Return all the attributes of this definition, including its doc comments.
+    ///
+    /// For definitions of other crates, this only returns the attributes that are recorded in
+    /// the crate metadata.
+    fn all_attrs(&self) -> Vec<Attribute> {
+        let def_id = self.def_id();
+        with(|cx| cx.all_attrs(def_id))
+    }
+
+    /// Return the documentation of this definition, if it has any.
+    ///
+    /// This is the text of its doc comments and `#[doc = "..."]` attributes, with the comment
+    /// markers and common indentation removed, one attribute per line.
+    fn doc_comment(&self) -> Option<String> {
+        let def_id = self.def_id();
+        with(|cx| cx.doc_comment(def_id))
+    }
+
+    /// Return the visibility of this definition.
+    ///
+    /// Definitions that can't be named, like closures or impls, are visible in their parent
+    /// module. Returns an error if such a definition has no parent module.
+    fn visibility(&self) -> Result<Visibility, Error> {
+        let def_id = self.def_id();
+        with(|cx| cx.visibility(def_id))
+    }
+
+    /// Return the module this definition is declared in, or `None` for the root module of a
+    /// crate.
+    fn parent_module(&self) -> Option<ModuleDef> {
+        let def_id = self.def_id();
+        with(|cx| cx.parent_module(def_id))
+    }
+}
+
+impl CrateDef for DefId {
+    fn def_id(&self) -> DefId {
+        *self
+    }
+}
+
+/// An attribute of a definition, such as `#[inline]`, a doc comment, or a tool attribute like
+/// `#[rustfmt::skip]`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attribute {
+    value: String,
+    span: Span,
+}
+
+impl Attribute {
+    pub fn new(value: String, span: Span) -> Attribute {
+        Attribute { value, span }
+    }
+
+    /// Get the attribute as it is written in the source code, e.g. `#[inline(always)]` or
+    /// `/// Some documentation.`.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Get the span of the attribute.
+    pub fn span(&self) -> Span {
+        self.span
+    }
 }
 
 macro_rules! crate_def {
@@ -57,7 +133,7 @@ macro_rules! crate_def {
       $vis:vis $name:ident $(;)?
     ) => {
         $(#[$attr])*
-        #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+        #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
         $vis struct $name(pub DefId);
 
         impl CrateDef for $name {
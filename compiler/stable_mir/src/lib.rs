@@ -17,17 +17,19 @@
 //! The goal is to eventually be published on
 //! [crates.io](https://crates.io).
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Debug;
 use std::io;
 
 use crate::compiler_interface::with;
+pub use crate::crate_def::Attribute;
 pub use crate::crate_def::CrateDef;
 pub use crate::crate_def::DefId;
 pub use crate::error::*;
 use crate::mir::Body;
 use crate::mir::Mutability;
-use crate::ty::{ForeignModuleDef, ImplDef, IndexedVal, Span, TraitDef, Ty};
+use crate::ty::{ForeignModuleDef, ImplDef, IndexedVal, ModuleDef, Span, TraitDef, Ty};
 
 pub mod abi;
 #[macro_use]
@@ -36,6 +38,7 @@
 #[macro_use]
 pub mod error;
 pub mod mir;
+pub mod snapshot;
 pub mod target;
 pub mod ty;
 pub mod visitor;
@@ -75,7 +78,7 @@ fn to_index(&self) -> usize {
 pub type ImplTraitDecls = Vec<ImplDef>;
 
 /// Holds information about a crate.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Crate {
     pub id: CrateNum,
     pub name: Symbol,
@@ -97,9 +100,14 @@ pub fn trait_decls(&self) -> TraitDecls {
     pub fn trait_impls(&self) -> ImplTraitDecls {
         with(|cx| cx.trait_impls(self.id))
     }
+
+    /// The root module of this crate.
+    pub fn root_module(&self) -> ModuleDef {
+        with(|cx| cx.root_module(self.id))
+    }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum ItemKind {
     Fn,
     Static,
@@ -107,7 +115,7 @@ pub enum ItemKind {
     Ctor(CtorKind),
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum CtorKind {
     Const,
     Fn,
@@ -186,7 +194,7 @@ pub fn all_trait_impls() -> ImplTraitDecls {
 }
 
 /// A type that provides internal information but that can still be used for debug purpose.
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Opaque(String);
 
 impl std::fmt::Display for Opaque {
@@ -0,0 +1,124 @@
+//! The call graph of the monomorphized items of the local crate.
+//!
+//! The graph is built by the compiler's mono item collector, so it contains exactly the items
+//! that would be code generated for the local crate, and records why each of them is needed.
+//!
+//! ```ignore(illustrative)
+//! let graph = CallGraph::collect()?;
+//! for item in graph.items() {
+//!     for reason in graph.collection_reasons(item) {
+//!         println!("{item:?}: {reason:?}");
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::mir::mono::MonoItem;
+use crate::{with, Error};
+use serde::{Deserialize, Serialize};
+
+/// The mono items of the local crate and how they use each other.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallGraph {
+    roots: Vec<MonoItem>,
+    items: Vec<MonoItem>,
+    edges: Vec<Edge>,
+    /// The indices in `edges` of the uses of other items by each item.
+    uses: HashMap<MonoItem, Vec<usize>>,
+    /// The indices in `edges` of the uses of each item by other items.
+    users: HashMap<MonoItem, Vec<usize>>,
+}
+
+/// A use of a mono item by another mono item.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Edge {
+    pub user: MonoItem,
+    pub used: MonoItem,
+    pub kind: EdgeKind,
+}
+
+/// Why a mono item is used by another mono item.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EdgeKind {
+    /// The item is called directly, including the calls to panic functions that are implicit in
+    /// assertions and unwinding.
+    Call,
+    /// The item is the drop glue of a value that is dropped, or that is stored in a vtable.
+    DropGlue,
+    /// The item is a method of a vtable that is created by an unsizing cast.
+    VtableMethod,
+    /// The item is a function whose address is taken, e.g. by reifying it into a function
+    /// pointer or by referring to it from a constant.
+    FnPointer,
+    /// The item is a static that is referred to, or the thread-local shim of a static.
+    Static,
+}
+
+/// Why a mono item was collected.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollectionReason<'a> {
+    /// The item is a root of the collection.
+    Root,
+    /// The item is used by another collected item.
+    Used(&'a Edge),
+}
+
+impl CallGraph {
+    /// Collect the mono items of the local crate with the same strategy as code generation.
+    /// Every call runs the whole collection again.
+    ///
+    /// Returns an error if an error has been emitted, e.g. because a constant failed to
+    /// evaluate during the collection.
+    pub fn collect() -> Result<CallGraph, Error> {
+        with(|cx| cx.call_graph())
+    }
+
+    /// Build a call graph from its roots, items and edges.
+    pub fn new(roots: Vec<MonoItem>, items: Vec<MonoItem>, edges: Vec<Edge>) -> CallGraph {
+        let mut uses: HashMap<_, Vec<_>> = HashMap::new();
+        let mut users: HashMap<_, Vec<_>> = HashMap::new();
+        for (idx, edge) in edges.iter().enumerate() {
+            uses.entry(edge.user.clone()).or_default().push(idx);
+            users.entry(edge.used.clone()).or_default().push(idx);
+        }
+        CallGraph { roots, items, edges, uses, users }
+    }
+
+    /// The items that the collection started from, like `main` or the exported functions of a
+    /// library.
+    pub fn roots(&self) -> &[MonoItem] {
+        &self.roots
+    }
+
+    /// All the collected items, including the roots, sorted by name.
+    pub fn items(&self) -> &[MonoItem] {
+        &self.items
+    }
+
+    /// The ways the items use each other. An item that uses another one in several ways, e.g. by
+    /// calling it and taking its address, has an edge for each of them.
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// The uses of other items by `item`.
+    pub fn uses(&self, item: &MonoItem) -> impl Iterator<Item = &Edge> {
+        self.edges_at(self.uses.get(item))
+    }
+
+    /// The uses of `item` by other items.
+    pub fn users(&self, item: &MonoItem) -> impl Iterator<Item = &Edge> {
+        self.edges_at(self.users.get(item))
+    }
+
+    fn edges_at<'a>(&'a self, indices: Option<&'a Vec<usize>>) -> impl Iterator<Item = &'a Edge> {
+        indices.into_iter().flatten().map(|&idx| &self.edges[idx])
+    }
+
+    /// All the reasons why `item` was collected. This is empty if `item` wasn't collected.
+    pub fn collection_reasons<'a>(&'a self, item: &'a MonoItem) -> Vec<CollectionReason<'a>> {
+        let root = self.roots.contains(item).then_some(CollectionReason::Root);
+        root.into_iter().chain(self.users(item).map(CollectionReason::Used)).collect()
+    }
+}
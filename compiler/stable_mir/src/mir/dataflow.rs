@@ -0,0 +1,338 @@
+//! # Dataflow analyses of Stable MIR bodies
+//!
+//! A dataflow analysis computes a state, which is an element of a [`JoinSemiLattice`], at every
+//! point of the control-flow graph of a [`Body`]. The structure is similar to the dataflow
+//! framework internal to `rustc`:
+//!
+//! - An [`Analysis`] defines the domain of the state, the direction in which the state flows
+//!   through the body ([`Forward`] or [`Backward`]), the state at the boundary of the body, and
+//!   how each statement and terminator changes the state (its *transfer function*).
+//! - [`Analysis::iterate_to_fixpoint`] propagates the states until they no longer change, and
+//!   returns the [`Results`] of the analysis, i.e. the state at the entry of each basic block.
+//! - A [`ResultsCursor`] recomputes the state before or after any statement or terminator from
+//!   the results.
+//!
+//! The states flowing into a basic block from multiple blocks are combined with
+//! [`JoinSemiLattice::join`]. The effect of a terminator is propagated to all of its successors,
+//! including its unwind target. Only the effect of a call or of inline assembly returning, e.g.
+//! writing the destination of a call, is applied to the edge to its return target alone, with
+//! [`Analysis::apply_call_return_effect`].
+//!
+//! Some common analyses of the locals of a body are provided: [`MaybeLiveLocals`],
+//! [`MaybeInitializedLocals`] and [`MaybeBorrowedLocals`].
+
+use std::collections::VecDeque;
+
+use crate::mir::{BasicBlockIdx, Body, Statement, Terminator, TerminatorKind};
+
+mod impls;
+mod lattice;
+
+pub use impls::{MaybeBorrowedLocals, MaybeInitializedLocals, MaybeLiveLocals};
+pub use lattice::{BitSet, GenKill, JoinSemiLattice};
+
+/// A statement of a basic block, or its terminator if `statement_index` is the number of
+/// statements of the block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProgramPoint {
+    pub block: BasicBlockIdx,
+    pub statement_index: usize,
+}
+
+impl ProgramPoint {
+    /// The first statement of `block`, or its terminator if it has no statement.
+    pub fn start_of(block: BasicBlockIdx) -> ProgramPoint {
+        ProgramPoint { block, statement_index: 0 }
+    }
+
+    /// The terminator of `block`.
+    pub fn terminator_of(body: &Body, block: BasicBlockIdx) -> ProgramPoint {
+        ProgramPoint { block, statement_index: body.blocks[block].statements.len() }
+    }
+}
+
+/// The direction in which the state of an analysis flows through a body.
+pub trait Direction {
+    /// Whether the state flows from the start of the body to its exits.
+    const IS_FORWARD: bool;
+}
+
+/// The state flows from the start of the body to its exits, and from each block to its
+/// successors.
+pub enum Forward {}
+
+impl Direction for Forward {
+    const IS_FORWARD: bool = true;
+}
+
+/// The state flows from the exits of the body to its start, and from each block to its
+/// predecessors.
+pub enum Backward {}
+
+impl Direction for Backward {
+    const IS_FORWARD: bool = false;
+}
+
+/// A dataflow analysis.
+pub trait Analysis {
+    /// The type of the state of the analysis at each point of the body.
+    type Domain: Clone + JoinSemiLattice;
+
+    /// The direction of the analysis, [`Forward`] or [`Backward`].
+    type Direction: Direction;
+
+    /// The initial state of every basic block, which must be the bottom value of the lattice.
+    fn bottom_value(&self, body: &Body) -> Self::Domain;
+
+    /// Mutates the bottom value into the state at the boundary of the body, which is its start
+    /// for a forward analysis, and the exit of the blocks without successors for a backward one.
+    fn initialize_boundary_state(&self, body: &Body, state: &mut Self::Domain);
+
+    /// Updates the state with the effect of a statement.
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        point: ProgramPoint,
+    );
+
+    /// Updates the state with the effect of a terminator, on all of its successors.
+    fn apply_terminator_effect(
+        &mut self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        point: ProgramPoint,
+    );
+
+    /// Updates the state with the effect of a `Call` or `InlineAsm` terminator returning, which
+    /// only applies to the edge to its return target, and not to its unwind target.
+    ///
+    /// The cursors of the [`Results`] don't apply this effect, since it isn't part of a block:
+    /// the state after a terminator is the state before it returns.
+    fn apply_call_return_effect(&mut self, _state: &mut Self::Domain, _terminator: &Terminator) {}
+
+    /// Computes the state at the entry of each basic block, which is its start for a forward
+    /// analysis, and its end for a backward one.
+    fn iterate_to_fixpoint(mut self, body: &Body) -> Results<Self>
+    where
+        Self: Sized,
+    {
+        let num_blocks = body.blocks.len();
+        let bottom = self.bottom_value(body);
+        let mut entry_sets = vec![bottom; num_blocks];
+        // The blocks the state of each block flows into, and whether the edge goes from a call to
+        // its return target.
+        let mut next_blocks = vec![Vec::new(); num_blocks];
+        for (block, data) in body.blocks.iter().enumerate() {
+            let return_target = return_target(&data.terminator);
+            for successor in data.terminator.successors() {
+                let is_return = return_target == Some(successor);
+                if Self::Direction::IS_FORWARD {
+                    next_blocks[block].push((successor, is_return));
+                } else {
+                    next_blocks[successor].push((block, is_return));
+                }
+            }
+        }
+
+        // Visit the blocks in an order that usually reaches the fixpoint quickly: in the order
+        // of their indices for a forward analysis, and in the reverse order otherwise.
+        let mut worklist: VecDeque<_> = if Self::Direction::IS_FORWARD {
+            if num_blocks > 0 {
+                self.initialize_boundary_state(body, &mut entry_sets[0]);
+            }
+            (0..num_blocks).collect()
+        } else {
+            for (block, data) in body.blocks.iter().enumerate() {
+                if data.terminator.successors().is_empty() {
+                    self.initialize_boundary_state(body, &mut entry_sets[block]);
+                }
+            }
+            (0..num_blocks).rev().collect()
+        };
+        let mut in_worklist = vec![true; num_blocks];
+
+        while let Some(block) = worklist.pop_front() {
+            in_worklist[block] = false;
+            let mut state = entry_sets[block].clone();
+            apply_block_effects(&mut self, body, block, &mut state, None);
+            for &(next, is_return) in &next_blocks[block] {
+                let changed = if is_return {
+                    // The call is the terminator of the block the state flows from in a forward
+                    // analysis, and of the block it flows into otherwise.
+                    let call = if Self::Direction::IS_FORWARD { block } else { next };
+                    let mut return_state = state.clone();
+                    self.apply_call_return_effect(&mut return_state, &body.blocks[call].terminator);
+                    entry_sets[next].join(&return_state)
+                } else {
+                    entry_sets[next].join(&state)
+                };
+                if changed && !in_worklist[next] {
+                    in_worklist[next] = true;
+                    worklist.push_back(next);
+                }
+            }
+        }
+
+        Results { analysis: self, entry_sets }
+    }
+}
+
+/// The block that `terminator` continues with when it returns, if it is a `Call` or an `InlineAsm`.
+fn return_target(terminator: &Terminator) -> Option<BasicBlockIdx> {
+    match terminator.kind {
+        TerminatorKind::Call { target, .. } => target,
+        TerminatorKind::InlineAsm { destination, .. } => destination,
+        _ => None,
+    }
+}
+
+/// Applies the effects of a basic block to the state at its entry, in the direction of the
+/// analysis, stopping at `target` if there is one.
+///
+/// The target is a statement index and whether to apply the effect of that statement, in the
+/// direction of the analysis.
+fn apply_block_effects<A: Analysis>(
+    analysis: &mut A,
+    body: &Body,
+    block: BasicBlockIdx,
+    state: &mut A::Domain,
+    target: Option<(usize, bool)>,
+) {
+    let data = &body.blocks[block];
+    let terminator_index = data.statements.len();
+    let reached = |index: usize| target.is_some_and(|(target, _)| target == index);
+    let apply_target = target.map_or(true, |(_, apply)| apply);
+
+    if A::Direction::IS_FORWARD {
+        for (statement_index, statement) in data.statements.iter().enumerate() {
+            let point = ProgramPoint { block, statement_index };
+            if reached(statement_index) {
+                if apply_target {
+                    analysis.apply_statement_effect(state, statement, point);
+                }
+                return;
+            }
+            analysis.apply_statement_effect(state, statement, point);
+        }
+        if !reached(terminator_index) || apply_target {
+            let point = ProgramPoint { block, statement_index: terminator_index };
+            analysis.apply_terminator_effect(state, &data.terminator, point);
+        }
+    } else {
+        let point = ProgramPoint { block, statement_index: terminator_index };
+        if reached(terminator_index) {
+            if apply_target {
+                analysis.apply_terminator_effect(state, &data.terminator, point);
+            }
+            return;
+        }
+        analysis.apply_terminator_effect(state, &data.terminator, point);
+        for (statement_index, statement) in data.statements.iter().enumerate().rev() {
+            let point = ProgramPoint { block, statement_index };
+            if reached(statement_index) {
+                if apply_target {
+                    analysis.apply_statement_effect(state, statement, point);
+                }
+                return;
+            }
+            analysis.apply_statement_effect(state, statement, point);
+        }
+    }
+}
+
+/// The results of a dataflow analysis: the state at the entry of each basic block.
+pub struct Results<A: Analysis> {
+    analysis: A,
+    entry_sets: Vec<A::Domain>,
+}
+
+impl<A: Analysis> Results<A> {
+    /// The analysis that computed the results.
+    pub fn analysis(&self) -> &A {
+        &self.analysis
+    }
+
+    /// The state at the entry of `block`, which is its start for a forward analysis, and its end
+    /// for a backward one.
+    pub fn entry_set_for_block(&self, block: BasicBlockIdx) -> &A::Domain {
+        &self.entry_sets[block]
+    }
+
+    /// Creates a cursor to inspect the state at any point of `body`, which must be the body the
+    /// analysis ran on.
+    pub fn into_results_cursor(self, body: &Body) -> ResultsCursor<'_, A> {
+        ResultsCursor::new(body, self)
+    }
+}
+
+/// Computes the state of an analysis at any point of a body, from the results of the analysis.
+///
+/// "Before" and "after" refer to the order in which statements are executed, regardless of the
+/// direction of the analysis.
+pub struct ResultsCursor<'body, A: Analysis> {
+    body: &'body Body,
+    results: Results<A>,
+    state: A::Domain,
+}
+
+impl<'body, A: Analysis> ResultsCursor<'body, A> {
+    /// Creates a cursor to inspect the results of an analysis of `body`.
+    pub fn new(body: &'body Body, results: Results<A>) -> Self {
+        let state = results.analysis.bottom_value(body);
+        ResultsCursor { body, results, state }
+    }
+
+    /// The body the results are for.
+    pub fn body(&self) -> &'body Body {
+        self.body
+    }
+
+    /// The results of the analysis.
+    pub fn results(&self) -> &Results<A> {
+        &self.results
+    }
+
+    /// Returns the results of the analysis, consuming the cursor.
+    pub fn into_results(self) -> Results<A> {
+        self.results
+    }
+
+    /// The state at the last point the cursor was moved to.
+    pub fn get(&self) -> &A::Domain {
+        &self.state
+    }
+
+    /// Moves the cursor to the start of `block`, before its first statement.
+    pub fn seek_to_block_start(&mut self, block: BasicBlockIdx) {
+        if A::Direction::IS_FORWARD {
+            self.state.clone_from(&self.results.entry_sets[block]);
+        } else {
+            self.seek(block, None);
+        }
+    }
+
+    /// Moves the cursor to the end of `block`, after its terminator.
+    pub fn seek_to_block_end(&mut self, block: BasicBlockIdx) {
+        if A::Direction::IS_FORWARD {
+            self.seek(block, None);
+        } else {
+            self.state.clone_from(&self.results.entry_sets[block]);
+        }
+    }
+
+    /// Moves the cursor to just before the effect of the statement or terminator at `point`.
+    pub fn seek_before(&mut self, point: ProgramPoint) {
+        self.seek(point.block, Some((point.statement_index, !A::Direction::IS_FORWARD)));
+    }
+
+    /// Moves the cursor to just after the effect of the statement or terminator at `point`.
+    pub fn seek_after(&mut self, point: ProgramPoint) {
+        self.seek(point.block, Some((point.statement_index, A::Direction::IS_FORWARD)));
+    }
+
+    fn seek(&mut self, block: BasicBlockIdx, target: Option<(usize, bool)>) {
+        self.state.clone_from(&self.results.entry_sets[block]);
+        apply_block_effects(&mut self.results.analysis, self.body, block, &mut self.state, target);
+    }
+}
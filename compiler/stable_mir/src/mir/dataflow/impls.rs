@@ -0,0 +1,316 @@
+//! Dataflow analyses of the locals of a body.
+//!
+//! These analyses work on whole locals: reading, writing or moving a part of a local, like one of
+//! its fields, is considered as an access to the local, but doesn't change whether the local as a
+//! whole is initialized.
+
+use super::{Analysis, Backward, BitSet, Forward, GenKill, ProgramPoint};
+use crate::mir::visit::{Location, PlaceContext};
+use crate::mir::{
+    Body, MirVisitor, Operand, Place, ProjectionElem, Rvalue, Statement, StatementKind, Terminator,
+    TerminatorKind, RETURN_LOCAL,
+};
+
+/// Computes the locals that may be live, i.e. whose current value may be read later.
+///
+/// A local is live before a statement if the statement reads it, or if it is live after the
+/// statement and the statement doesn't overwrite it as a whole. Borrowing a local counts as
+/// reading it, so a local that is only accessed through a reference after it is borrowed is not
+/// live anymore. Combine this analysis with [`MaybeBorrowedLocals`] to find the locals that can
+/// be accessed.
+///
+/// This is a backward analysis.
+pub struct MaybeLiveLocals;
+
+impl Analysis for MaybeLiveLocals {
+    type Domain = BitSet;
+    type Direction = Backward;
+
+    fn bottom_value(&self, body: &Body) -> BitSet {
+        // Nothing is live after the body returns.
+        BitSet::new_empty(body.locals().len())
+    }
+
+    fn initialize_boundary_state(&self, _body: &Body, _state: &mut BitSet) {}
+
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut BitSet,
+        statement: &Statement,
+        _point: ProgramPoint,
+    ) {
+        let location = Location::new(statement.span);
+        match &statement.kind {
+            StatementKind::Assign(place, rvalue) => {
+                write_liveness(state, place, true);
+                UseCollector { state }.visit_rvalue(rvalue, location);
+            }
+            StatementKind::Deinit(place) => write_liveness(state, place, true),
+            // Setting the discriminant doesn't read the local, but doesn't overwrite it either.
+            StatementKind::SetDiscriminant { place, .. } => write_liveness(state, place, false),
+            StatementKind::FakeRead(_, place) | StatementKind::Retag(_, place) => {
+                gen_place_uses(state, place)
+            }
+            StatementKind::Intrinsic(_) => {
+                UseCollector { state }.visit_statement(statement, location);
+            }
+            StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_)
+            | StatementKind::PlaceMention(_)
+            | StatementKind::AscribeUserType { .. }
+            | StatementKind::Coverage(_)
+            | StatementKind::ConstEvalCounter
+            | StatementKind::Nop => {}
+        }
+    }
+
+    fn apply_terminator_effect(
+        &mut self,
+        state: &mut BitSet,
+        terminator: &Terminator,
+        _point: ProgramPoint,
+    ) {
+        let location = Location::new(terminator.span);
+        match &terminator.kind {
+            // The destination and the outputs are only written on return, see
+            // `apply_call_return_effect`.
+            TerminatorKind::Call { func, args, .. } => {
+                let mut uses = UseCollector { state };
+                uses.visit_operand(func, location);
+                for arg in args {
+                    uses.visit_operand(arg, location);
+                }
+            }
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for in_value in operands.iter().filter_map(|operand| operand.in_value.as_ref()) {
+                    UseCollector { state }.visit_operand(in_value, location);
+                }
+            }
+            TerminatorKind::Return => state.gen(RETURN_LOCAL),
+            TerminatorKind::Drop { place, .. } => gen_place_uses(state, place),
+            TerminatorKind::SwitchInt { .. } | TerminatorKind::Assert { .. } => {
+                UseCollector { state }.visit_terminator(terminator, location);
+            }
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Unreachable => {}
+        }
+    }
+
+    fn apply_call_return_effect(&mut self, state: &mut BitSet, terminator: &Terminator) {
+        match &terminator.kind {
+            TerminatorKind::Call { destination, .. } => write_liveness(state, destination, true),
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for out_place in operands.iter().filter_map(|operand| operand.out_place.as_ref()) {
+                    write_liveness(state, out_place, true);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Updates the live locals with a write to `place`, which overwrites the local if `overwrites`
+/// and the place is the whole local.
+fn write_liveness(state: &mut BitSet, place: &Place, overwrites: bool) {
+    if place.projection.is_empty() {
+        if overwrites {
+            state.kill(place.local);
+        }
+        return;
+    }
+    if is_indirect(place) {
+        // Writing through a pointer reads the pointer.
+        state.gen(place.local);
+    }
+    gen_index_uses(state, place);
+}
+
+/// Adds the locals read to access `place`.
+fn gen_place_uses(state: &mut BitSet, place: &Place) {
+    state.gen(place.local);
+    gen_index_uses(state, place);
+}
+
+fn gen_index_uses(state: &mut BitSet, place: &Place) {
+    for elem in &place.projection {
+        if let ProjectionElem::Index(local) = elem {
+            state.gen(*local);
+        }
+    }
+}
+
+/// Whether `place` is accessed through a pointer stored in its local, instead of being stored in
+/// the local itself.
+fn is_indirect(place: &Place) -> bool {
+    place.projection.iter().any(|elem| matches!(elem, ProjectionElem::Deref))
+}
+
+/// Adds all the locals the visited items mention to the state.
+struct UseCollector<'a> {
+    state: &'a mut BitSet,
+}
+
+impl MirVisitor for UseCollector<'_> {
+    fn visit_place(&mut self, place: &Place, _ptx: PlaceContext, _location: Location) {
+        gen_place_uses(self.state, place);
+    }
+}
+
+/// Computes the locals that may be initialized, i.e. that may hold a value that wasn't moved out.
+///
+/// The arguments are initialized at the start of the body. A local becomes initialized when it
+/// is assigned as a whole, and uninitialized when it is moved out of or dropped as a whole, or
+/// when its storage starts or ends.
+///
+/// This is a forward analysis.
+pub struct MaybeInitializedLocals;
+
+impl Analysis for MaybeInitializedLocals {
+    type Domain = BitSet;
+    type Direction = Forward;
+
+    fn bottom_value(&self, body: &Body) -> BitSet {
+        BitSet::new_empty(body.locals().len())
+    }
+
+    fn initialize_boundary_state(&self, body: &Body, state: &mut BitSet) {
+        state.gen_all(1..=body.arg_locals().len());
+    }
+
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut BitSet,
+        statement: &Statement,
+        _point: ProgramPoint,
+    ) {
+        match &statement.kind {
+            StatementKind::Assign(place, rvalue) => {
+                MoveCollector { state }.visit_rvalue(rvalue, Location::new(statement.span));
+                if place.projection.is_empty() {
+                    state.gen(place.local);
+                }
+            }
+            StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                state.kill(*local)
+            }
+            StatementKind::Deinit(place) if place.projection.is_empty() => state.kill(place.local),
+            StatementKind::Intrinsic(_) => {
+                MoveCollector { state }.visit_statement(statement, Location::new(statement.span));
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_terminator_effect(
+        &mut self,
+        state: &mut BitSet,
+        terminator: &Terminator,
+        _point: ProgramPoint,
+    ) {
+        let location = Location::new(terminator.span);
+        match &terminator.kind {
+            // The destination and the outputs are only initialized on return, see
+            // `apply_call_return_effect`.
+            TerminatorKind::Call { func, args, .. } => {
+                let mut moves = MoveCollector { state };
+                moves.visit_operand(func, location);
+                for arg in args {
+                    moves.visit_operand(arg, location);
+                }
+            }
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for in_value in operands.iter().filter_map(|operand| operand.in_value.as_ref()) {
+                    MoveCollector { state }.visit_operand(in_value, location);
+                }
+            }
+            TerminatorKind::Drop { place, .. } if place.projection.is_empty() => {
+                state.kill(place.local)
+            }
+            TerminatorKind::SwitchInt { .. } | TerminatorKind::Assert { .. } => {
+                MoveCollector { state }.visit_terminator(terminator, location);
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_call_return_effect(&mut self, state: &mut BitSet, terminator: &Terminator) {
+        match &terminator.kind {
+            TerminatorKind::Call { destination, .. } if destination.projection.is_empty() => {
+                state.gen(destination.local)
+            }
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for out_place in operands.iter().filter_map(|operand| operand.out_place.as_ref()) {
+                    if out_place.projection.is_empty() {
+                        state.gen(out_place.local);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Removes the locals that the visited operands move out of as a whole from the state.
+struct MoveCollector<'a> {
+    state: &'a mut BitSet,
+}
+
+impl MirVisitor for MoveCollector<'_> {
+    fn visit_operand(&mut self, operand: &Operand, _location: Location) {
+        match operand {
+            Operand::Move(place) if place.projection.is_empty() => self.state.kill(place.local),
+            _ => {}
+        }
+    }
+}
+
+/// Computes the locals that may be borrowed, i.e. whose address may have been taken by a
+/// reference or a raw pointer.
+///
+/// A local stays borrowed until its storage ends, even if the reference or pointer is no longer
+/// used. Dropping a local borrows it, since the drop glue takes a mutable reference to it.
+///
+/// This is a forward analysis.
+pub struct MaybeBorrowedLocals;
+
+impl Analysis for MaybeBorrowedLocals {
+    type Domain = BitSet;
+    type Direction = Forward;
+
+    fn bottom_value(&self, body: &Body) -> BitSet {
+        BitSet::new_empty(body.locals().len())
+    }
+
+    fn initialize_boundary_state(&self, _body: &Body, _state: &mut BitSet) {}
+
+    fn apply_statement_effect(
+        &mut self,
+        state: &mut BitSet,
+        statement: &Statement,
+        _point: ProgramPoint,
+    ) {
+        match &statement.kind {
+            StatementKind::Assign(_, Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place))
+                if !is_indirect(place) =>
+            {
+                state.gen(place.local)
+            }
+            StatementKind::StorageDead(local) => state.kill(*local),
+            _ => {}
+        }
+    }
+
+    fn apply_terminator_effect(
+        &mut self,
+        state: &mut BitSet,
+        terminator: &Terminator,
+        _point: ProgramPoint,
+    ) {
+        match &terminator.kind {
+            TerminatorKind::Drop { place, .. } if !is_indirect(place) => state.gen(place.local),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! The domains of dataflow analyses.
+//!
+//! The state of an analysis at each point of a body must form a join-semilattice, so that the
+//! states flowing into a basic block from its predecessors can be combined, and so that the
+//! fixpoint iteration terminates when the lattice has a finite height.
+
+use std::fmt::{self, Debug, Formatter};
+
+/// A set with a partial order and a least upper bound (join) for every pair of elements.
+pub trait JoinSemiLattice: Eq {
+    /// Computes the least upper bound of `self` and `other`, and stores it in `self`.
+    ///
+    /// Returns whether `self` changed.
+    fn join(&mut self, other: &Self) -> bool;
+}
+
+/// `false` is the bottom value, and `true` the top value.
+impl JoinSemiLattice for bool {
+    fn join(&mut self, other: &Self) -> bool {
+        if *self || !*other {
+            return false;
+        }
+        *self = true;
+        true
+    }
+}
+
+/// A domain that analyses can update by adding elements to (`gen`) or removing them from (`kill`)
+/// the state.
+pub trait GenKill<T> {
+    /// Inserts `elem` into the state.
+    fn gen(&mut self, elem: T);
+
+    /// Removes `elem` from the state.
+    fn kill(&mut self, elem: T);
+
+    /// Calls `gen` for each element in `elems`.
+    fn gen_all(&mut self, elems: impl IntoIterator<Item = T>) {
+        for elem in elems {
+            self.gen(elem);
+        }
+    }
+
+    /// Calls `kill` for each element in `elems`.
+    fn kill_all(&mut self, elems: impl IntoIterator<Item = T>) {
+        for elem in elems {
+            self.kill(elem);
+        }
+    }
+}
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed-size set of the indices `0..domain_size`, e.g. of the locals of a body.
+///
+/// As a lattice, the empty set is the bottom value and the join is the union.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    domain_size: usize,
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    /// Creates a set that contains none of the indices in `0..domain_size`.
+    pub fn new_empty(domain_size: usize) -> BitSet {
+        BitSet { domain_size, words: vec![0; domain_size.div_ceil(WORD_BITS)] }
+    }
+
+    /// Creates a set that contains all the indices in `0..domain_size`.
+    pub fn new_filled(domain_size: usize) -> BitSet {
+        let mut set = BitSet::new_empty(domain_size);
+        set.insert_all();
+        set
+    }
+
+    /// The number of indices the set can contain.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns whether the set contains `elem`.
+    pub fn contains(&self, elem: usize) -> bool {
+        let (word, mask) = self.word_and_mask(elem);
+        self.words[word] & mask != 0
+    }
+
+    /// Inserts `elem`, and returns whether the set changed.
+    pub fn insert(&mut self, elem: usize) -> bool {
+        let (word, mask) = self.word_and_mask(elem);
+        let changed = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        changed
+    }
+
+    /// Removes `elem`, and returns whether the set changed.
+    pub fn remove(&mut self, elem: usize) -> bool {
+        let (word, mask) = self.word_and_mask(elem);
+        let changed = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        changed
+    }
+
+    /// Inserts all the indices of the domain.
+    pub fn insert_all(&mut self) {
+        self.words.fill(!0);
+        if self.domain_size % WORD_BITS != 0 {
+            // Clear the bits past the end of the domain, so that equal sets have equal words.
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << (self.domain_size % WORD_BITS)) - 1;
+        }
+    }
+
+    /// Removes all the indices.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns whether the set contains no index.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// The number of indices in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Iterates over the indices in the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            (0..WORD_BITS)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| i * WORD_BITS + bit)
+        })
+    }
+
+    /// Inserts the indices of `other`, and returns whether the set changed.
+    pub fn union(&mut self, other: &BitSet) -> bool {
+        self.update_words(other, |a, b| a | b)
+    }
+
+    /// Removes the indices of `other`, and returns whether the set changed.
+    pub fn subtract(&mut self, other: &BitSet) -> bool {
+        self.update_words(other, |a, b| a & !b)
+    }
+
+    /// Removes the indices that are not in `other`, and returns whether the set changed.
+    pub fn intersect(&mut self, other: &BitSet) -> bool {
+        self.update_words(other, |a, b| a & b)
+    }
+
+    fn update_words(&mut self, other: &BitSet, op: impl Fn(u64, u64) -> u64) -> bool {
+        assert_eq!(self.domain_size, other.domain_size, "sets of different domains");
+        let mut changed = false;
+        for (word, &other_word) in self.words.iter_mut().zip(&other.words) {
+            let new_word = op(*word, other_word);
+            changed |= new_word != *word;
+            *word = new_word;
+        }
+        changed
+    }
+
+    fn word_and_mask(&self, elem: usize) -> (usize, u64) {
+        assert!(
+            elem < self.domain_size,
+            "index {elem} out of the domain of size {}",
+            self.domain_size
+        );
+        (elem / WORD_BITS, 1 << (elem % WORD_BITS))
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl JoinSemiLattice for BitSet {
+    fn join(&mut self, other: &Self) -> bool {
+        self.union(other)
+    }
+}
+
+impl GenKill<usize> for BitSet {
+    fn gen(&mut self, elem: usize) {
+        self.insert(elem);
+    }
+
+    fn kill(&mut self, elem: usize) {
+        self.remove(elem);
+    }
+}
@@ -0,0 +1,884 @@
+//! Snapshots of the Stable MIR of a crate, to analyze it outside of the compiler.
+//!
+//! A [`Snapshot`] records the items of the local crate and their bodies, along with every
+//! definition, type, layout, span and allocation they refer to. The compiler writes one when it
+//! is invoked with `--emit=stable-mir -Zunstable-options`, and [`Snapshot::run`] answers the
+//! Stable MIR APIs from it, in a process that doesn't run the compiler:
+//!
+//! ```ignore(needs-snapshot-file)
+//! let file = std::fs::File::open("input.smir.json")?;
+//! let snapshot = stable_mir::snapshot::Snapshot::read(std::io::BufReader::new(file))?;
+//! snapshot.run(|| {
+//!     for item in stable_mir::all_local_items() {
+//!         println!("{}: {}", item.name(), item.ty());
+//!     }
+//! })?;
+//! ```
+//!
+//! Snapshots are read-only, and only record what the compiler knows about the items of the crate
+//! and the definitions, types and instances they refer to. The APIs that need information which
+//! isn't recorded return an error or `None` when they can, and panic otherwise:
+//!
+//! - Types and constants can't be created, except that [`Ty::from_rigid_kind`] and
+//!   [`Ty::new_box`] return the types that are already recorded.
+//! - Generic definitions can't be instantiated: [`AdtDef::ty_with_args`] and
+//!   [`FieldDef::ty_with_args`] panic, and the instances and signatures of function types are
+//!   only recorded for the function types of the snapshot. [`Instance::resolve`] and the other
+//!   resolution functions return an error for other function types and for closures.
+//! - Trait solving isn't available, so [`TraitRef::is_implemented`], [`Ty::is_copy`],
+//!   [`Ty::is_sized`] and [`Ty::needs_drop`] panic, and normalization and constant evaluation
+//!   return errors.
+//! - The trait declarations and implementations, the foreign modules and the children of
+//!   modules are only recorded for the local crate, so [`all_trait_decls`](crate::all_trait_decls)
+//!   and [`all_trait_impls`](crate::all_trait_impls) panic.
+//! - The bodies of instances are only recorded for the items of the local crate, and
+//!   [`Instance::body`] returns `None` for the other instances.
+//!
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abi::{FnAbi, Layout, LayoutShape};
+use crate::compiler_interface::{self, Context};
+use crate::mir::alloc::{AllocId, GlobalAlloc};
+use crate::mir::call_graph::CallGraph;
+use crate::mir::mono::{Instance, InstanceDef, StaticDef};
+use crate::mir::{BinOp, Body, Place, ProjectionElem};
+use crate::target::MachineInfo;
+use crate::ty::{
+    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, Const, FieldDef, FnDef, ForeignDef,
+    ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgKind, GenericArgs,
+    GenericPredicates, Generics, ImplDef, ImplTrait, IndexedVal, IntrinsicDef, LineInfo,
+    ModuleChild, ModuleDef, ParamEnv, PolyFnSig, RigidTy, Span, TraitDecl, TraitDef, TraitRef, Ty,
+    TyKind, UintTy, VariantDef, Visibility,
+};
+use crate::{
+    error, mir, Attribute, Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename,
+    ImplTraitDecls, ItemKind, Symbol, TraitDecls,
+};
+
+/// The version of the snapshot format.
+///
+/// It changes whenever the format or the Stable MIR data types change, and snapshots written
+/// with another version can't be read.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The Stable MIR of a crate.
+///
+/// The information about definitions, types, spans, layouts, allocations, instances and
+/// constants is stored in the order of their indices.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The [`FORMAT_VERSION`] the snapshot was written with.
+    pub format_version: u32,
+    pub local_crate: Crate,
+    pub external_crates: Vec<Crate>,
+    /// The root module of the local crate, followed by the ones of the external crates.
+    pub root_modules: Vec<ModuleDef>,
+    pub target: MachineInfo,
+    pub entry_fn: Option<CrateItem>,
+    /// The items of the local crate that have a MIR associated with them.
+    pub items: Vec<ItemInfo>,
+    /// The trait declarations of the local crate.
+    pub trait_decls: TraitDecls,
+    /// The trait implementations of the local crate.
+    pub trait_impls: ImplTraitDecls,
+    /// The foreign modules of the local crate.
+    pub foreign_modules: Vec<ForeignModuleDef>,
+    /// The type of the comparison operators.
+    pub bool_ty: Ty,
+    /// The type of the three-way comparison operator, if the `Ordering` lang item is defined.
+    pub ordering_ty: Option<Ty>,
+    pub defs: Vec<DefInfo>,
+    pub types: Vec<TyInfo>,
+    pub spans: Vec<SpanInfo>,
+    pub layouts: Vec<LayoutShape>,
+    pub allocs: Vec<AllocInfo>,
+    pub instances: Vec<InstanceInfo>,
+    /// The pretty-printed constants.
+    pub consts: Vec<String>,
+}
+
+/// An item of the local crate.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemInfo {
+    pub item: CrateItem,
+    pub kind: ItemKind,
+    pub requires_monomorphization: bool,
+    /// The instance of the item, if it doesn't require monomorphization.
+    pub instance: Option<Instance>,
+    /// The body of the item, if it is available.
+    pub body: Option<Body>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DefInfo {
+    pub name: Symbol,
+    pub trimmed_name: Symbol,
+    pub krate: CrateNum,
+    pub span: Span,
+    pub visibility: Result<Visibility, Error>,
+    pub parent_module: Option<ModuleDef>,
+    pub foreign_item_kind: Option<ForeignItemKind>,
+    /// The attributes of the definition, if it is a kind of definition that has attributes.
+    pub attrs: Option<Vec<Attribute>>,
+    pub doc_comment: Option<String>,
+    /// The type of the definition, if it is a kind of definition that has a type.
+    pub ty: Option<Ty>,
+    /// The generics of the definition, if it is a kind of definition that has generics.
+    pub generics: Option<GenericsInfo>,
+    pub details: DefDetails,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenericsInfo {
+    pub generics: Generics,
+    pub predicates: GenericPredicates,
+    pub explicit_predicates: GenericPredicates,
+}
+
+/// The information that is specific to a kind of definition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DefDetails {
+    Adt(AdtInfo),
+    Fn {
+        intrinsic: Option<IntrinsicInfo>,
+    },
+    Trait(TraitDecl),
+    TraitImpl(ImplTrait),
+    /// A module of the local crate.
+    Module {
+        children: Vec<ModuleChild>,
+        submodules: Vec<ModuleDef>,
+    },
+    ForeignModule {
+        module: ForeignModule,
+        items: Vec<ForeignDef>,
+    },
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdtInfo {
+    pub kind: AdtKind,
+    pub is_box: bool,
+    pub is_simd: bool,
+    pub is_cstr: bool,
+    pub variants: Vec<VariantInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantInfo {
+    pub name: Symbol,
+    pub fields: Vec<FieldDef>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntrinsicInfo {
+    pub name: Symbol,
+    pub must_be_overridden: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TyInfo {
+    pub kind: TyKind,
+    pub pretty: String,
+    /// The layout of the type, or the reason why it isn't available, e.g. because the type is
+    /// generic.
+    pub layout: Result<Layout, Error>,
+    /// The signature of function definition and closure types.
+    pub fn_sig: Option<PolyFnSig>,
+    /// The type of the discriminant of rigid types.
+    pub discriminant_ty: Option<Ty>,
+    /// The instance a function definition type resolves to, if it isn't generic.
+    pub instance: Option<Instance>,
+    /// The instance a function definition type resolves to when it is turned into a function
+    /// pointer, if it isn't generic.
+    pub fn_ptr_instance: Option<Instance>,
+    /// The `drop_in_place` instance of the type, if it isn't generic.
+    pub drop_in_place: Option<Instance>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpanInfo {
+    pub repr: String,
+    pub filename: Filename,
+    pub lines: LineInfo,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocInfo {
+    pub alloc: GlobalAlloc,
+    /// The allocation of the vtable, if this is a vtable.
+    pub vtable: Option<AllocId>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceInfo {
+    pub def_id: DefId,
+    pub ty: Ty,
+    pub args: GenericArgs,
+    pub name: Symbol,
+    pub trimmed_name: Symbol,
+    pub mangled_name: Symbol,
+    pub is_empty_drop_shim: bool,
+    pub is_empty_async_drop_ctor_shim: bool,
+    /// The ABI of the instance, or the reason why it isn't available, e.g. because the instance
+    /// isn't a function.
+    pub abi: Result<FnAbi, Error>,
+    /// The body of the instance, if it is available and the instance belongs to the local crate.
+    pub body: Option<Body>,
+}
+
+impl Snapshot {
+    /// Read a snapshot written in JSON.
+    ///
+    /// Returns an error if the snapshot was written with another [`FORMAT_VERSION`].
+    pub fn read<R: io::Read>(reader: R) -> Result<Snapshot, Error> {
+        // Check the version first, as the rest of the data may not match our data types.
+        let value: serde_json::Value = serde_json::from_reader(reader)
+            .map_err(|err| error!("invalid Stable MIR snapshot: {err}"))?;
+        match value.get("format_version").and_then(|version| version.as_u64()) {
+            Some(version) if version == u64::from(FORMAT_VERSION) => {}
+            Some(version) => {
+                return Err(error!(
+                    "Stable MIR snapshot has format version {version}, \
+                     but the current version is {FORMAT_VERSION}"
+                ));
+            }
+            None => return Err(error!("invalid Stable MIR snapshot: missing format version")),
+        }
+        serde_json::from_value(value).map_err(|err| error!("invalid Stable MIR snapshot: {err}"))
+    }
+
+    /// Write this snapshot in JSON.
+    pub fn write<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+
+    /// Run `f` with the Stable MIR APIs answered from this snapshot.
+    pub fn run<F, T>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce() -> T,
+    {
+        let context = SnapshotContext {
+            snapshot: self,
+            items: self.items.iter().map(|info| (info.item.0, info)).collect(),
+        };
+        compiler_interface::run(&context, f)
+    }
+}
+
+/// The [`Context`] implementation that reads a [`Snapshot`].
+struct SnapshotContext<'a> {
+    snapshot: &'a Snapshot,
+    items: HashMap<DefId, &'a ItemInfo>,
+}
+
+impl SnapshotContext<'_> {
+    fn item(&self, def_id: DefId) -> &ItemInfo {
+        self.items.get(&def_id).unwrap_or_else(|| {
+            panic!("Stable MIR snapshot has no item `{}`", self.def(def_id).name)
+        })
+    }
+
+    fn def(&self, def_id: DefId) -> &DefInfo {
+        &self.snapshot.defs[def_id.to_index()]
+    }
+
+    fn adt(&self, def: AdtDef) -> &AdtInfo {
+        match &self.def(def.0).details {
+            DefDetails::Adt(adt) => adt,
+            _ => self.not_recorded_for("adt", def.0),
+        }
+    }
+
+    fn variant(&self, def: VariantDef) -> &VariantInfo {
+        &self.adt(def.adt_def).variants[def.idx.to_index()]
+    }
+
+    fn generics(&self, query: &str, def_id: DefId) -> &GenericsInfo {
+        self.def(def_id).generics.as_ref().unwrap_or_else(|| self.not_recorded_for(query, def_id))
+    }
+
+    fn ty(&self, ty: Ty) -> &TyInfo {
+        &self.snapshot.types[ty.to_index()]
+    }
+
+    /// Find the recorded type of the given kind.
+    fn find_ty(&self, kind: &TyKind) -> Option<Ty> {
+        self.snapshot.types.iter().position(|info| info.kind == *kind).map(Ty::to_val)
+    }
+
+    fn find_ty_info(&self, kind: TyKind) -> Option<&TyInfo> {
+        self.find_ty(&kind).map(|ty| self.ty(ty))
+    }
+
+    fn fn_def_ty(&self, def: FnDef, args: &GenericArgs) -> Option<&TyInfo> {
+        self.find_ty_info(TyKind::RigidTy(RigidTy::FnDef(def, args.clone())))
+    }
+
+    fn span(&self, span: Span) -> &SpanInfo {
+        &self.snapshot.spans[span.to_index()]
+    }
+
+    fn instance(&self, def: InstanceDef) -> &InstanceInfo {
+        &self.snapshot.instances[def.to_index()]
+    }
+
+    fn crates(&self) -> impl Iterator<Item = &Crate> {
+        std::iter::once(&self.snapshot.local_crate).chain(&self.snapshot.external_crates)
+    }
+
+    fn is_local(&self, crate_num: CrateNum) -> bool {
+        crate_num == self.snapshot.local_crate.id
+    }
+
+    fn not_recorded_for(&self, query: &str, def_id: DefId) -> ! {
+        panic!(
+            "`{query}` of `{}` is not recorded in the Stable MIR snapshot",
+            self.def(def_id).name
+        )
+    }
+}
+
+/// The error of the queries that aren't recorded in snapshots.
+fn not_recorded(query: &str) -> Error {
+    error!("`{query}` is not available in a Stable MIR snapshot")
+}
+
+/// Panic for the queries that can't be answered from a snapshot, which are listed in the
+/// module documentation.
+fn unavailable(query: &str) -> ! {
+    panic!("{}", not_recorded(query))
+}
+
+/// The path of an attribute, e.g. `["rustfmt", "skip"]` for `#[rustfmt::skip]`, or `None` for
+/// doc comments.
+fn attr_path(attr: &Attribute) -> Option<Vec<&str>> {
+    let value = attr.as_str();
+    let value = value.strip_prefix("#!").or_else(|| value.strip_prefix('#'))?;
+    let path = value.strip_prefix('[')?.split(['(', '=', ']']).next()?;
+    Some(path.split("::").map(str::trim).collect())
+}
+
+impl Context for SnapshotContext<'_> {
+    fn entry_fn(&self) -> Option<CrateItem> {
+        self.snapshot.entry_fn
+    }
+
+    fn all_local_items(&self) -> CrateItems {
+        self.snapshot.items.iter().map(|info| info.item).collect()
+    }
+
+    fn mir_body(&self, item: DefId) -> mir::Body {
+        match &self.item(item).body {
+            Some(body) => body.clone(),
+            None => panic!("Stable MIR snapshot has no body for `{}`", self.def(item).name),
+        }
+    }
+
+    fn has_body(&self, item: DefId) -> bool {
+        self.items.get(&item).is_some_and(|info| info.body.is_some())
+    }
+
+    fn foreign_modules(&self, crate_num: CrateNum) -> Vec<ForeignModuleDef> {
+        if !self.is_local(crate_num) {
+            unavailable("foreign_modules");
+        }
+        self.snapshot.foreign_modules.clone()
+    }
+
+    fn foreign_module(&self, mod_def: ForeignModuleDef) -> ForeignModule {
+        match &self.def(mod_def.0).details {
+            DefDetails::ForeignModule { module, .. } => module.clone(),
+            _ => self.not_recorded_for("foreign_module", mod_def.0),
+        }
+    }
+
+    fn foreign_items(&self, mod_def: ForeignModuleDef) -> Vec<ForeignDef> {
+        match &self.def(mod_def.0).details {
+            DefDetails::ForeignModule { items, .. } => items.clone(),
+            _ => self.not_recorded_for("foreign_items", mod_def.0),
+        }
+    }
+
+    fn all_trait_decls(&self) -> TraitDecls {
+        unavailable("all_trait_decls")
+    }
+
+    fn trait_decls(&self, crate_num: CrateNum) -> TraitDecls {
+        if !self.is_local(crate_num) {
+            unavailable("trait_decls");
+        }
+        self.snapshot.trait_decls.clone()
+    }
+
+    fn trait_decl(&self, trait_def: &TraitDef) -> TraitDecl {
+        match &self.def(trait_def.0).details {
+            DefDetails::Trait(decl) => decl.clone(),
+            _ => self.not_recorded_for("trait_decl", trait_def.0),
+        }
+    }
+
+    fn all_trait_impls(&self) -> ImplTraitDecls {
+        unavailable("all_trait_impls")
+    }
+
+    fn trait_impls(&self, crate_num: CrateNum) -> ImplTraitDecls {
+        if !self.is_local(crate_num) {
+            unavailable("trait_impls");
+        }
+        self.snapshot.trait_impls.clone()
+    }
+
+    fn trait_impl(&self, trait_impl: &ImplDef) -> ImplTrait {
+        match &self.def(trait_impl.0).details {
+            DefDetails::TraitImpl(impl_trait) => impl_trait.clone(),
+            _ => self.not_recorded_for("trait_impl", trait_impl.0),
+        }
+    }
+
+    fn generics_of(&self, def_id: DefId) -> Generics {
+        self.generics("generics_of", def_id).generics.clone()
+    }
+
+    fn predicates_of(&self, def_id: DefId) -> GenericPredicates {
+        self.generics("predicates_of", def_id).predicates.clone()
+    }
+
+    fn explicit_predicates_of(&self, def_id: DefId) -> GenericPredicates {
+        self.generics("explicit_predicates_of", def_id).explicit_predicates.clone()
+    }
+
+    fn local_crate(&self) -> Crate {
+        self.snapshot.local_crate.clone()
+    }
+
+    fn external_crates(&self) -> Vec<Crate> {
+        self.snapshot.external_crates.clone()
+    }
+
+    fn find_crates(&self, name: &str) -> Vec<Crate> {
+        self.crates().filter(|krate| krate.name == name).cloned().collect()
+    }
+
+    fn def_name(&self, def_id: DefId, trimmed: bool) -> Symbol {
+        let def = self.def(def_id);
+        if trimmed { def.trimmed_name.clone() } else { def.name.clone() }
+    }
+
+    fn span_to_string(&self, span: Span) -> String {
+        self.span(span).repr.clone()
+    }
+
+    fn get_filename(&self, span: &Span) -> Filename {
+        self.span(*span).filename.clone()
+    }
+
+    fn get_lines(&self, span: &Span) -> LineInfo {
+        self.span(*span).lines
+    }
+
+    fn item_kind(&self, item: CrateItem) -> ItemKind {
+        self.item(item.0).kind
+    }
+
+    fn is_foreign_item(&self, item: DefId) -> bool {
+        self.def(item).foreign_item_kind.is_some()
+    }
+
+    fn foreign_item_kind(&self, def: ForeignDef) -> ForeignItemKind {
+        self.def(def.0)
+            .foreign_item_kind
+            .unwrap_or_else(|| self.not_recorded_for("foreign_item_kind", def.0))
+    }
+
+    fn adt_kind(&self, def: AdtDef) -> AdtKind {
+        self.adt(def).kind
+    }
+
+    fn adt_is_box(&self, def: AdtDef) -> bool {
+        self.adt(def).is_box
+    }
+
+    fn adt_is_simd(&self, def: AdtDef) -> bool {
+        self.adt(def).is_simd
+    }
+
+    fn adt_is_cstr(&self, def: AdtDef) -> bool {
+        self.adt(def).is_cstr
+    }
+
+    fn fn_sig(&self, def: FnDef, args: &GenericArgs) -> PolyFnSig {
+        self.fn_def_ty(def, args)
+            .and_then(|info| info.fn_sig.clone())
+            .unwrap_or_else(|| self.not_recorded_for("fn_sig", def.0))
+    }
+
+    fn intrinsic(&self, item: DefId) -> Option<IntrinsicDef> {
+        match &self.def(item).details {
+            DefDetails::Fn { intrinsic: Some(_) } => Some(IntrinsicDef(item)),
+            _ => None,
+        }
+    }
+
+    fn intrinsic_name(&self, def: IntrinsicDef) -> Symbol {
+        match &self.def(def.0).details {
+            DefDetails::Fn { intrinsic: Some(intrinsic) } => intrinsic.name.clone(),
+            _ => self.not_recorded_for("intrinsic_name", def.0),
+        }
+    }
+
+    fn intrinsic_must_be_overridden(&self, def: IntrinsicDef) -> bool {
+        match &self.def(def.0).details {
+            DefDetails::Fn { intrinsic: Some(intrinsic) } => intrinsic.must_be_overridden,
+            _ => self.not_recorded_for("intrinsic_must_be_overridden", def.0),
+        }
+    }
+
+    fn closure_sig(&self, args: &GenericArgs) -> PolyFnSig {
+        self.snapshot
+            .types
+            .iter()
+            .find_map(|info| match &info.kind {
+                TyKind::RigidTy(RigidTy::Closure(_, closure_args)) if closure_args == args => {
+                    info.fn_sig.clone()
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| unavailable("closure_sig"))
+    }
+
+    fn adt_variants_len(&self, def: AdtDef) -> usize {
+        self.adt(def).variants.len()
+    }
+
+    fn variant_name(&self, def: VariantDef) -> Symbol {
+        self.variant(def).name.clone()
+    }
+
+    fn variant_fields(&self, def: VariantDef) -> Vec<FieldDef> {
+        self.variant(def).fields.clone()
+    }
+
+    fn eval_target_usize(&self, _cnst: &Const) -> Result<u64, Error> {
+        Err(not_recorded("eval_target_usize"))
+    }
+
+    fn try_new_const_zst(&self, _ty: Ty) -> Result<Const, Error> {
+        Err(not_recorded("try_new_const_zst"))
+    }
+
+    fn new_const_str(&self, _value: &str) -> Const {
+        unavailable("new_const_str")
+    }
+
+    fn new_const_bool(&self, _value: bool) -> Const {
+        unavailable("new_const_bool")
+    }
+
+    fn try_new_const_uint(&self, _value: u128, _uint_ty: UintTy) -> Result<Const, Error> {
+        Err(not_recorded("try_new_const_uint"))
+    }
+
+    fn new_rigid_ty(&self, kind: RigidTy) -> Ty {
+        self.find_ty(&TyKind::RigidTy(kind)).unwrap_or_else(|| unavailable("new_rigid_ty"))
+    }
+
+    fn new_box_ty(&self, ty: Ty) -> Ty {
+        let position = self.snapshot.types.iter().position(|info| match &info.kind {
+            TyKind::RigidTy(RigidTy::Adt(def, args)) => {
+                matches!(&self.def(def.0).details, DefDetails::Adt(adt) if adt.is_box)
+                    && args.0.first() == Some(&GenericArgKind::Type(ty))
+            }
+            _ => false,
+        });
+        position.map(Ty::to_val).unwrap_or_else(|| unavailable("new_box_ty"))
+    }
+
+    fn def_ty(&self, item: DefId) -> Ty {
+        self.def(item).ty.unwrap_or_else(|| self.not_recorded_for("def_ty", item))
+    }
+
+    fn def_ty_with_args(&self, _item: DefId, _args: &GenericArgs) -> Ty {
+        unavailable("def_ty_with_args")
+    }
+
+    fn const_pretty(&self, cnst: &Const) -> String {
+        self.snapshot.consts[cnst.id.to_index()].clone()
+    }
+
+    fn span_of_an_item(&self, def_id: DefId) -> Span {
+        self.def(def_id).span
+    }
+
+    fn ty_pretty(&self, ty: Ty) -> String {
+        self.ty(ty).pretty.clone()
+    }
+
+    fn ty_kind(&self, ty: Ty) -> TyKind {
+        self.ty(ty).kind.clone()
+    }
+
+    fn rigid_ty_discriminant_ty(&self, ty: &RigidTy) -> Ty {
+        self.find_ty_info(TyKind::RigidTy(ty.clone()))
+            .and_then(|info| info.discriminant_ty)
+            .unwrap_or_else(|| unavailable("rigid_ty_discriminant_ty"))
+    }
+
+    fn instance_body(&self, instance: InstanceDef) -> Option<Body> {
+        self.instance(instance).body.clone()
+    }
+
+    fn instance_ty(&self, instance: InstanceDef) -> Ty {
+        self.instance(instance).ty
+    }
+
+    fn instance_args(&self, def: InstanceDef) -> GenericArgs {
+        self.instance(def).args.clone()
+    }
+
+    fn instance_def_id(&self, instance: InstanceDef) -> DefId {
+        self.instance(instance).def_id
+    }
+
+    fn instance_mangled_name(&self, instance: InstanceDef) -> Symbol {
+        self.instance(instance).mangled_name.clone()
+    }
+
+    fn is_empty_drop_shim(&self, def: InstanceDef) -> bool {
+        self.instance(def).is_empty_drop_shim
+    }
+
+    fn is_empty_async_drop_ctor_shim(&self, def: InstanceDef) -> bool {
+        self.instance(def).is_empty_async_drop_ctor_shim
+    }
+
+    fn mono_instance(&self, def_id: DefId) -> Instance {
+        self.items
+            .get(&def_id)
+            .and_then(|info| info.instance)
+            .unwrap_or_else(|| self.not_recorded_for("mono_instance", def_id))
+    }
+
+    fn requires_monomorphization(&self, def_id: DefId) -> bool {
+        self.item(def_id).requires_monomorphization
+    }
+
+    fn resolve_instance(&self, def: FnDef, args: &GenericArgs) -> Option<Instance> {
+        self.fn_def_ty(def, args).and_then(|info| info.instance)
+    }
+
+    fn resolve_drop_in_place(&self, ty: Ty) -> Instance {
+        self.ty(ty).drop_in_place.unwrap_or_else(|| unavailable("resolve_drop_in_place"))
+    }
+
+    fn resolve_for_fn_ptr(&self, def: FnDef, args: &GenericArgs) -> Option<Instance> {
+        self.fn_def_ty(def, args).and_then(|info| info.fn_ptr_instance)
+    }
+
+    fn resolve_closure(
+        &self,
+        _def: ClosureDef,
+        _args: &GenericArgs,
+        _kind: ClosureKind,
+    ) -> Option<Instance> {
+        None
+    }
+
+    fn eval_static_initializer(&self, _def: StaticDef) -> Result<Allocation, Error> {
+        Err(not_recorded("eval_static_initializer"))
+    }
+
+    fn eval_instance(&self, _def: InstanceDef, _const_ty: Ty) -> Result<Allocation, Error> {
+        Err(not_recorded("eval_instance"))
+    }
+
+    fn global_alloc(&self, id: AllocId) -> GlobalAlloc {
+        self.snapshot.allocs[id.to_index()].alloc.clone()
+    }
+
+    fn vtable_allocation(&self, global_alloc: &GlobalAlloc) -> Option<AllocId> {
+        self.snapshot
+            .allocs
+            .iter()
+            .find(|info| info.alloc == *global_alloc)
+            .and_then(|info| info.vtable)
+    }
+
+    fn krate(&self, def_id: DefId) -> Crate {
+        let id = self.def(def_id).krate;
+        self.crates().find(|krate| krate.id == id).unwrap().clone()
+    }
+
+    fn instance_name(&self, def: InstanceDef, trimmed: bool) -> Symbol {
+        let instance = self.instance(def);
+        if trimmed { instance.trimmed_name.clone() } else { instance.name.clone() }
+    }
+
+    fn target_info(&self) -> MachineInfo {
+        self.snapshot.target.clone()
+    }
+
+    fn instance_abi(&self, def: InstanceDef) -> Result<FnAbi, Error> {
+        self.instance(def).abi.clone()
+    }
+
+    fn ty_layout(&self, ty: Ty) -> Result<Layout, Error> {
+        self.ty(ty).layout.clone()
+    }
+
+    fn layout_shape(&self, id: Layout) -> LayoutShape {
+        self.snapshot.layouts[id.to_index()].clone()
+    }
+
+    fn place_pretty(&self, place: &Place) -> String {
+        // This follows the `Debug` implementation of places in the compiler.
+        let mut pretty = String::new();
+        for elem in place.projection.iter().rev() {
+            match elem {
+                ProjectionElem::Deref => pretty.push_str("(*"),
+                ProjectionElem::Field(..)
+                | ProjectionElem::Downcast(_)
+                | ProjectionElem::OpaqueCast(_)
+                | ProjectionElem::Subtype(_) => pretty.push('('),
+                ProjectionElem::Index(_)
+                | ProjectionElem::ConstantIndex { .. }
+                | ProjectionElem::Subslice { .. } => {}
+            }
+        }
+        write!(pretty, "_{}", place.local).unwrap();
+        for elem in &place.projection {
+            match elem {
+                ProjectionElem::Deref => pretty.push(')'),
+                ProjectionElem::Field(field, ty) => {
+                    write!(pretty, ".{field}: {})", self.ty(*ty).pretty).unwrap()
+                }
+                ProjectionElem::Index(local) => write!(pretty, "[_{local}]").unwrap(),
+                ProjectionElem::ConstantIndex { offset, min_length, from_end: false } => {
+                    write!(pretty, "[{offset} of {min_length}]").unwrap()
+                }
+                ProjectionElem::ConstantIndex { offset, min_length, from_end: true } => {
+                    write!(pretty, "[-{offset} of {min_length}]").unwrap()
+                }
+                ProjectionElem::Subslice { from, to: 0, from_end: true } => {
+                    write!(pretty, "[{from}:]").unwrap()
+                }
+                ProjectionElem::Subslice { from: 0, to, from_end: true } => {
+                    write!(pretty, "[:-{to}]").unwrap()
+                }
+                ProjectionElem::Subslice { from, to, from_end: true } => {
+                    write!(pretty, "[{from}:-{to}]").unwrap()
+                }
+                ProjectionElem::Subslice { from, to, from_end: false } => {
+                    write!(pretty, "[{from}..{to}]").unwrap()
+                }
+                ProjectionElem::Downcast(idx) => {
+                    write!(pretty, " as variant#{})", idx.to_index()).unwrap()
+                }
+                ProjectionElem::OpaqueCast(ty) => {
+                    write!(pretty, " as {})", self.ty(*ty).pretty).unwrap()
+                }
+                ProjectionElem::Subtype(ty) => {
+                    write!(pretty, " as subtype {})", self.ty(*ty).pretty).unwrap()
+                }
+            }
+        }
+        pretty
+    }
+
+    fn binop_ty(&self, bin_op: BinOp, lhs: Ty, _rhs: Ty) -> Ty {
+        match bin_op {
+            BinOp::Add
+            | BinOp::AddUnchecked
+            | BinOp::Sub
+            | BinOp::SubUnchecked
+            | BinOp::Mul
+            | BinOp::MulUnchecked
+            | BinOp::Div
+            | BinOp::Rem
+            | BinOp::BitXor
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::Shl
+            | BinOp::ShlUnchecked
+            | BinOp::Shr
+            | BinOp::ShrUnchecked
+            | BinOp::Offset => lhs,
+            BinOp::Eq | BinOp::Lt | BinOp::Le | BinOp::Ne | BinOp::Ge | BinOp::Gt => {
+                self.snapshot.bool_ty
+            }
+            BinOp::Cmp => self.snapshot.ordering_ty.unwrap_or_else(|| unavailable("binop_ty")),
+        }
+    }
+
+    fn attrs_by_path(&self, def_id: DefId, attr: &[Symbol]) -> Vec<Attribute> {
+        self.all_attrs(def_id)
+            .into_iter()
+            .filter(|attribute| attr_path(attribute).is_some_and(|path| path == attr))
+            .collect()
+    }
+
+    fn all_attrs(&self, def_id: DefId) -> Vec<Attribute> {
+        self.def(def_id).attrs.clone().unwrap_or_else(|| self.not_recorded_for("all_attrs", def_id))
+    }
+
+    fn doc_comment(&self, def_id: DefId) -> Option<String> {
+        self.def(def_id).doc_comment.clone()
+    }
+
+    fn visibility(&self, def_id: DefId) -> Result<Visibility, Error> {
+        self.def(def_id).visibility.clone()
+    }
+
+    fn parent_module(&self, def_id: DefId) -> Option<ModuleDef> {
+        self.def(def_id).parent_module
+    }
+
+    fn root_module(&self, crate_num: CrateNum) -> ModuleDef {
+        let index = self.crates().position(|krate| krate.id == crate_num).unwrap();
+        self.snapshot.root_modules[index]
+    }
+
+    fn module_children(&self, def: ModuleDef) -> Vec<ModuleChild> {
+        match &self.def(def.0).details {
+            DefDetails::Module { children, .. } => children.clone(),
+            _ => self.not_recorded_for("module_children", def.0),
+        }
+    }
+
+    fn submodules(&self, def: ModuleDef) -> Vec<ModuleDef> {
+        match &self.def(def.0).details {
+            DefDetails::Module { submodules, .. } => submodules.clone(),
+            _ => self.not_recorded_for("submodules", def.0),
+        }
+    }
+
+    fn trait_ref_holds(&self, _trait_ref: &TraitRef, _env: &ParamEnv) -> bool {
+        unavailable("trait_ref_holds")
+    }
+
+    fn normalize_ty(&self, _ty: Ty, _env: &ParamEnv) -> Result<Ty, Error> {
+        Err(not_recorded("normalize_ty"))
+    }
+
+    fn ty_is_copy(&self, _ty: Ty, _env: &ParamEnv) -> bool {
+        unavailable("ty_is_copy")
+    }
+
+    fn ty_is_sized(&self, _ty: Ty, _env: &ParamEnv) -> bool {
+        unavailable("ty_is_sized")
+    }
+
+    fn ty_needs_drop(&self, _ty: Ty, _env: &ParamEnv) -> bool {
+        unavailable("ty_needs_drop")
+    }
+
+    fn call_graph(&self) -> Result<CallGraph, Error> {
+        Err(not_recorded("call_graph"))
+    }
+}
@@ -537,6 +537,223 @@ pub fn sync_data(&self) -> io::Result<()> {
         self.inner.datasync()
     }
 
+    /// Acquires an exclusive advisory lock on the file, blocking until it can
+    /// be acquired.
+    ///
+    /// While this lock is held, no other file handle can acquire a shared or
+    /// an exclusive lock on the same file. The lock is released when
+    /// [`unlock`] is called or when every handle to the file that shares this
+    /// lock, including those created with [`try_clone`], has been closed.
+    ///
+    /// The lock is advisory: it does not prevent reading from or writing to
+    /// the file through handles that don't take part in locking.
+    ///
+    /// If this handle already holds a lock, it is converted into an exclusive
+    /// one. The conversion is not guaranteed to be atomic, so another handle
+    /// may briefly acquire the lock in between.
+    ///
+    /// [`unlock`]: File::unlock
+    /// [`try_clone`]: File::try_clone
+    /// [`try_lock`]: File::try_lock
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` flag, and the `LockFileEx` function on Windows with
+    /// the `LOCKFILE_EXCLUSIVE_LOCK` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// On Unix platforms without `flock`, such as illumos, Solaris and AIX,
+    /// `fcntl` record locks over the whole file are used instead. Those locks
+    /// belong to the process rather than the file handle: they never conflict
+    /// with other locks held by the same process, and they are all released
+    /// as soon as the process closes any handle to the file. An exclusive
+    /// lock also requires the file to be opened for writing there.
+    ///
+    /// On Windows, a lock held by this handle is not converted. In particular,
+    /// an exclusive lock can't be acquired while this handle holds a shared
+    /// lock, so this function then blocks forever, and [`try_lock`] returns
+    /// `false`. Call [`unlock`] first to switch from a shared lock to an
+    /// exclusive one. An exclusive lock also prevents other handles from
+    /// reading or writing the file.
+    ///
+    /// On other platforms, this function returns an error of kind
+    /// [`Unsupported`].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     f.lock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock", alias = "LockFileEx")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquires a shared advisory lock on the file, blocking until it can be
+    /// acquired.
+    ///
+    /// Any number of file handles can hold a shared lock on the same file at
+    /// once, but not while another handle holds an exclusive lock on it. See
+    /// [`lock`] for how long the lock is held and what happens if this handle
+    /// already holds one.
+    ///
+    /// [`lock`]: File::lock
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` flag, and the `LockFileEx` function on Windows.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// On Unix platforms that use `fcntl` record locks instead, a shared lock
+    /// requires the file to be opened for reading. See [`lock`] for the other
+    /// differences.
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.lock")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock", alias = "LockFileEx")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file without
+    /// blocking.
+    ///
+    /// Returns `Ok(false)` if another file handle holds a lock on the file,
+    /// and `Ok(true)` once the lock has been acquired. Otherwise this behaves
+    /// like [`lock`].
+    ///
+    /// [`lock`]: File::lock
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_EX` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_EXCLUSIVE_LOCK` and
+    /// `LOCKFILE_FAIL_IMMEDIATELY` flags. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// On Windows, this returns `Ok(false)` while this handle holds a shared
+    /// lock, see [`lock`].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     if !f.try_lock()? {
+    ///         eprintln!("another process is using foo.lock");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock", alias = "LockFileEx")]
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.inner.try_lock()
+    }
+
+    /// Tries to acquire a shared advisory lock on the file without blocking.
+    ///
+    /// Returns `Ok(false)` if another file handle holds an exclusive lock on
+    /// the file, and `Ok(true)` once the lock has been acquired. Otherwise
+    /// this behaves like [`lock_shared`].
+    ///
+    /// [`lock_shared`]: File::lock_shared
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_SH` and `LOCK_NB` flags, and the `LockFileEx` function
+    /// on Windows with the `LOCKFILE_FAIL_IMMEDIATELY` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.lock")?;
+    ///     if f.try_lock_shared()? {
+    ///         // Read the file while no one else can write to it.
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock", alias = "LockFileEx")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Releases any advisory lock this file handle holds on the file.
+    ///
+    /// Locks are also released automatically once every handle sharing them
+    /// has been closed, so calling this is only needed to release a lock
+    /// early. It is not an error to call this if no lock is held.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix
+    /// with the `LOCK_UN` flag, and the `UnlockFile` function on Windows.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.lock")?;
+    ///     f.lock()?;
+    ///     // Update the files guarded by the lock.
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    #[doc(alias = "flock", alias = "UnlockFile")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
     /// Truncates or extends the underlying file, updating the size of
     /// this file to become `size`.
     ///
@@ -1256,6 +1256,77 @@ fn file_try_clone() {
     check!(f1.write_all(b"!"));
 }
 
+// The platforms where `File::lock` and friends are supported.
+#[cfg(any(
+    windows,
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_vendor = "apple",
+))]
+mod file_lock {
+    use super::*;
+
+    #[test]
+    fn multiple_shared() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("lock");
+        let f1 = check!(File::create(&path));
+        let f2 = check!(OpenOptions::new().write(true).open(&path));
+
+        check!(f1.lock_shared());
+        assert!(check!(f2.try_lock_shared()));
+        assert!(!check!(f2.try_lock()));
+        check!(f1.unlock());
+        check!(f2.unlock());
+
+        assert!(check!(f1.try_lock_shared()));
+        check!(f1.unlock());
+        // Unlocking a file that isn't locked is fine.
+        check!(f1.unlock());
+    }
+
+    #[test]
+    fn exclusive() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("lock");
+        let f1 = check!(File::create(&path));
+        let f2 = check!(OpenOptions::new().write(true).open(&path));
+
+        check!(f1.lock());
+        assert!(!check!(f2.try_lock()));
+        assert!(!check!(f2.try_lock_shared()));
+        check!(f1.unlock());
+
+        assert!(check!(f2.try_lock()));
+        assert!(!check!(f1.try_lock_shared()));
+        // Closing the handle releases its lock.
+        drop(f2);
+        assert!(check!(f1.try_lock()));
+    }
+
+    #[test]
+    fn blocking() {
+        let tmpdir = tmpdir();
+        let path = tmpdir.join("lock");
+        let f1 = check!(File::create(&path));
+        let f2 = check!(OpenOptions::new().write(true).open(&path));
+
+        check!(f1.lock());
+        let start = Instant::now();
+        let t = thread::spawn(move || {
+            check!(f2.lock());
+            start.elapsed()
+        });
+        thread::sleep(Duration::from_millis(100));
+        check!(f1.unlock());
+        assert!(t.join().unwrap() >= Duration::from_millis(100));
+    }
+}
+
 #[test]
 #[cfg(not(windows))]
 fn unlink_readonly() {
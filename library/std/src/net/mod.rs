@@ -7,6 +7,8 @@
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
 //! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`TcpSocket`] and [`UdpSocketBuilder`] configure sockets before they are
+//!   connected, listening or bound
 //! * [`IpAddr`] represents IP addresses of either IPv4 or IPv6; [`Ipv4Addr`] and
 //!   [`Ipv6Addr`] are respectively IPv4 and IPv6 addresses
 //! * [`SocketAddr`] represents socket addresses of either IPv4 or IPv6; [`SocketAddrV4`]
@@ -31,8 +33,12 @@
 pub use self::tcp::IntoIncoming;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{Incoming, TcpListener, TcpStream};
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub use self::tcp::{TcpKeepalive, TcpSocket};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub use self::udp::UdpSocketBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::net::AddrParseError;
 
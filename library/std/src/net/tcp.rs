@@ -1069,3 +1069,605 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A TCP socket that has not yet been turned into a [`TcpStream`] or a
+/// [`TcpListener`].
+///
+/// A `TcpSocket` gives access to socket options that have to be set before
+/// the socket is connected or starts listening, such as `SO_REUSEPORT`,
+/// `IPV6_V6ONLY` or the size of the kernel buffers. It can also listen with
+/// a custom backlog, which [`TcpListener::bind`] does not allow.
+///
+/// A socket is created for one address family with [`new_v4`] or [`new_v6`].
+/// It is then either [`connect`]ed, yielding a [`TcpStream`], or [`bind`]ed
+/// and put into [`listen`]ing mode, yielding a [`TcpListener`].
+///
+/// Unlike [`TcpListener::bind`], no options are set on the socket implicitly.
+/// In particular `SO_REUSEADDR` has to be enabled with [`set_reuseaddr`] if
+/// the listener should be able to rebind a recently used address.
+///
+/// [`bind`]: TcpSocket::bind
+/// [`connect`]: TcpSocket::connect
+/// [`listen`]: TcpSocket::listen
+/// [`new_v4`]: TcpSocket::new_v4
+/// [`new_v6`]: TcpSocket::new_v6
+/// [`set_reuseaddr`]: TcpSocket::set_reuseaddr
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_socket_builder)]
+/// use std::net::TcpSocket;
+///
+/// fn main() -> std::io::Result<()> {
+///     let socket = TcpSocket::new_v4()?;
+///     socket.set_reuseaddr(true)?;
+///     socket.set_recv_buffer_size(1 << 20)?;
+///     socket.bind("127.0.0.1:8080".parse().unwrap())?;
+///
+///     let listener = socket.listen(1024)?;
+///     for stream in listener.incoming() {
+///         let _stream = stream?;
+///         // ...
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub struct TcpSocket(net_imp::UnboundSocket);
+
+impl TcpSocket {
+    /// Creates a new IPv4 TCP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        net_imp::UnboundSocket::new_tcp(false).map(TcpSocket)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v6().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        net_imp::UnboundSocket::new_tcp(true).map(TcpSocket)
+    }
+
+    /// Binds this socket to the specified address.
+    ///
+    /// Binding with a port number of 0 will request that the OS assigns a port
+    /// to this socket. The port allocated can be queried via
+    /// [`TcpSocket::local_addr`].
+    ///
+    /// The address must belong to the family the socket was created for.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.bind("127.0.0.1:0".parse().unwrap()).expect("couldn't bind to address");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[doc(alias = "bind")]
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.bind(&addr)
+    }
+
+    /// Returns the local socket address of this socket.
+    ///
+    /// For a socket that has not been bound yet, this returns the unspecified
+    /// address of its family with a port of 0 on most platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::{Ipv4Addr, SocketAddr, TcpSocket};
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.bind("127.0.0.1:8080".parse().unwrap()).expect("couldn't bind to address");
+    /// assert_eq!(
+    ///     socket.local_addr().unwrap(),
+    ///     SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 8080)
+    /// );
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Opens a TCP connection to `addr`, turning this socket into a
+    /// [`TcpStream`].
+    ///
+    /// If the socket has not been bound, the OS picks a local address.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_nodelay(true).expect("set_nodelay call failed");
+    /// let stream = socket
+    ///     .connect("127.0.0.1:8080".parse().unwrap())
+    ///     .expect("couldn't connect to the server");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[doc(alias = "connect")]
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(&addr).map(TcpStream)
+    }
+
+    /// Starts listening for incoming connections, turning this socket into a
+    /// [`TcpListener`].
+    ///
+    /// `backlog` is a hint for the maximum number of pending connections the
+    /// OS keeps queued before it starts refusing new ones. Values that do not
+    /// fit into the platform's integer type are clamped, and the OS may apply
+    /// a smaller limit of its own.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.bind("127.0.0.1:8080".parse().unwrap()).expect("couldn't bind to address");
+    /// let listener = socket.listen(4096).expect("couldn't listen");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[doc(alias = "listen")]
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix, this allows binding a listener to an address that still has
+    /// connections in the `TIME_WAIT` state, which is what
+    /// [`TcpListener::bind`] enables by default.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows, `SO_REUSEADDR` allows another socket to bind to an address
+    /// that is actively in use, which makes it possible to hijack the
+    /// connections of another process. It should normally not be set there.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_reuseaddr(true).expect("set_reuseaddr call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.0.set_reuseaddr(reuseaddr)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_reuseaddr`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_reuseaddr(true).expect("set_reuseaddr call failed");
+    /// assert_eq!(socket.reuseaddr().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.0.reuseaddr()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same address and port, as
+    /// long as all of them set this option before binding. On Linux, incoming
+    /// connections are then distributed between the listeners.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This option is only available on Android, the BSDs, Linux and Apple
+    /// platforms. On other platforms an error of kind
+    /// [`io::ErrorKind::Unsupported`] is returned.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_reuseport(true).expect("set_reuseport call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.0.set_reuseport(reuseport)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_reuseport`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_reuseport(true).expect("set_reuseport call failed");
+    /// assert_eq!(socket.reuseport().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.0.reuseport()
+    }
+
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// When enabled, the OS periodically probes idle connections and reports
+    /// an error on the stream once the peer stops answering. How long a
+    /// connection has to be idle before it is probed, how often it is probed
+    /// and how many probes may go unanswered default to system-wide settings,
+    /// which can be overridden for this socket with
+    /// [`TcpSocket::set_tcp_keepalive`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_keepalive(true).expect("set_keepalive call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_keepalive`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_keepalive(true).expect("set_keepalive call failed");
+    /// assert_eq!(socket.keepalive().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Enables `SO_KEEPALIVE` on this socket and tunes its probes.
+    ///
+    /// The parameters left unset in `keepalive` keep their system defaults.
+    /// For more information about keepalive probes, see
+    /// [`TcpSocket::set_keepalive`].
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Unix, this sets the `TCP_KEEPIDLE` (`TCP_KEEPALIVE` on Apple
+    /// platforms), `TCP_KEEPINTVL` and `TCP_KEEPCNT` options, rounding the
+    /// durations up to whole seconds. An error of kind
+    /// [`io::ErrorKind::Unsupported`] is returned on Unix platforms lacking
+    /// these options.
+    ///
+    /// On Windows, the durations are set together with `SIO_KEEPALIVE_VALS`,
+    /// which has no way to keep just one of them, so an unset time or interval
+    /// is reset to the Windows default of two hours or one second respectively.
+    /// The number of retries is set with `TCP_KEEPCNT`, which requires Windows
+    /// 10 version 1703 or later.
+    ///
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::{TcpKeepalive, TcpSocket};
+    /// use std::time::Duration;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// let keepalive = TcpKeepalive::new()
+    ///     .with_time(Duration::from_secs(60))
+    ///     .with_interval(Duration::from_secs(10));
+    /// socket.set_tcp_keepalive(&keepalive).expect("set_tcp_keepalive call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_tcp_keepalive(&self, keepalive: &TcpKeepalive) -> io::Result<()> {
+        self.0.set_tcp_keepalive(keepalive.time, keepalive.interval, keepalive.retries)
+    }
+
+    /// Sets the size of the send buffer of this socket (`SO_SNDBUF`).
+    ///
+    /// The OS treats the size as a hint and may round or clamp it.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Linux doubles the requested size to leave room for bookkeeping, so
+    /// [`TcpSocket::send_buffer_size`] reports twice the value set here.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the size of the send buffer of this socket (`SO_SNDBUF`).
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_send_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// println!("{}", socket.send_buffer_size().expect("send_buffer_size call failed"));
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the size of the receive buffer of this socket (`SO_RCVBUF`).
+    ///
+    /// The OS treats the size as a hint and may round or clamp it. On a
+    /// listener, the size is inherited by accepted streams, and it also
+    /// determines the TCP window scale offered during the handshake, so it
+    /// has to be set before [`TcpSocket::listen`] or [`TcpSocket::connect`]
+    /// for large buffers to take full effect.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Linux doubles the requested size to leave room for bookkeeping, so
+    /// [`TcpSocket::recv_buffer_size`] reports twice the value set here.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the size of the receive buffer of this socket (`SO_RCVBUF`).
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_recv_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// println!("{}", socket.recv_buffer_size().expect("recv_buffer_size call failed"));
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. In this case two IPv4 and IPv6 applications
+    /// can bind the same port at the same time.
+    ///
+    /// If this is set to `false` then the socket can be used to send and
+    /// receive packets from an IPv4-mapped IPv6 address.
+    ///
+    /// The option has to be set before the socket is bound, and is only valid
+    /// for sockets created with [`TcpSocket::new_v6`]. The default value
+    /// depends on the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v6().expect("couldn't create the socket");
+    /// socket.set_only_v6(false).expect("set_only_v6 call failed");
+    /// socket.bind("[::]:8080".parse().unwrap()).expect("couldn't bind to address");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_only_v6`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v6().expect("couldn't create the socket");
+    /// socket.set_only_v6(true).expect("set_only_v6 call failed");
+    /// assert_eq!(socket.only_v6().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// The value is kept by the [`TcpStream`] returned from
+    /// [`TcpSocket::connect`], and by streams accepted on a listener on most
+    /// platforms. See [`TcpStream::set_nodelay`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_nodelay(true).expect("set_nodelay call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.0.set_nodelay(nodelay)
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_nodelay`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_nodelay(true).expect("set_nodelay call failed");
+    /// assert_eq!(socket.nodelay().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0.nodelay()
+    }
+}
+
+impl AsInner<net_imp::UnboundSocket> for TcpSocket {
+    #[inline]
+    fn as_inner(&self) -> &net_imp::UnboundSocket {
+        &self.0
+    }
+}
+
+#[unstable(feature = "net_socket_builder", issue = "none")]
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The parameters of TCP keepalive probes, applied with
+/// [`TcpSocket::set_tcp_keepalive`].
+///
+/// Every parameter starts out unset, meaning that the system default is kept.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(net_socket_builder)]
+/// use std::net::TcpKeepalive;
+/// use std::time::Duration;
+///
+/// let keepalive = TcpKeepalive::new()
+///     .with_time(Duration::from_secs(60))
+///     .with_interval(Duration::from_secs(10))
+///     .with_retries(5);
+/// ```
+#[unstable(feature = "net_socket_builder", issue = "none")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TcpKeepalive {
+    time: Option<Duration>,
+    interval: Option<Duration>,
+    retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    /// Creates a set of keepalive parameters that keeps all system defaults.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[must_use]
+    pub const fn new() -> TcpKeepalive {
+        TcpKeepalive { time: None, interval: None, retries: None }
+    }
+
+    /// Sets how long the connection has to be idle before the first probe is
+    /// sent.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[must_use]
+    pub const fn with_time(self, time: Duration) -> TcpKeepalive {
+        TcpKeepalive { time: Some(time), ..self }
+    }
+
+    /// Sets the time between two probes while the peer doesn't answer.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[must_use]
+    pub const fn with_interval(self, interval: Duration) -> TcpKeepalive {
+        TcpKeepalive { interval: Some(interval), ..self }
+    }
+
+    /// Sets how many probes may go unanswered before the connection is
+    /// considered dead.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[must_use]
+    pub const fn with_retries(self, retries: u32) -> TcpKeepalive {
+        TcpKeepalive { retries: Some(retries), ..self }
+    }
+}
@@ -917,3 +917,112 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_smoke_test() {
+    each_ip(&mut |addr| {
+        let socket = t!(if addr.is_ipv4() { TcpSocket::new_v4() } else { TcpSocket::new_v6() });
+        t!(socket.bind(addr));
+        assert_eq!(addr, t!(socket.local_addr()));
+        let acceptor = t!(socket.listen(16));
+        assert_eq!(addr, t!(acceptor.local_addr()));
+
+        let _t = thread::spawn(move || {
+            let socket = t!(if addr.is_ipv4() { TcpSocket::new_v4() } else { TcpSocket::new_v6() });
+            let mut stream = t!(socket.connect(addr));
+            assert_eq!(addr, t!(stream.peer_addr()));
+            t!(stream.write(&[99]));
+        });
+
+        let mut stream = t!(acceptor.accept()).0;
+        let mut buf = [0];
+        t!(stream.read(&mut buf));
+        assert!(buf[0] == 99);
+    })
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_options() {
+    let socket = t!(TcpSocket::new_v4());
+
+    t!(socket.set_reuseaddr(true));
+    assert_eq!(true, t!(socket.reuseaddr()));
+    t!(socket.set_reuseaddr(false));
+    assert_eq!(false, t!(socket.reuseaddr()));
+
+    t!(socket.set_keepalive(true));
+    assert_eq!(true, t!(socket.keepalive()));
+    t!(socket.set_keepalive(false));
+    assert_eq!(false, t!(socket.keepalive()));
+
+    t!(socket.set_nodelay(true));
+    assert_eq!(true, t!(socket.nodelay()));
+
+    // The OS may round the buffer sizes up, Linux even doubles them.
+    t!(socket.set_send_buffer_size(64 * 1024));
+    assert!(t!(socket.send_buffer_size()) >= 64 * 1024);
+    t!(socket.set_recv_buffer_size(64 * 1024));
+    assert!(t!(socket.recv_buffer_size()) >= 64 * 1024);
+
+    // The option survives the conversion into a stream.
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+    let stream = t!(socket.connect(addr));
+    assert_eq!(true, t!(stream.nodelay()));
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+    windows,
+))]
+fn tcp_socket_keepalive_params() {
+    let socket = t!(TcpSocket::new_v4());
+    let keepalive = TcpKeepalive::new()
+        .with_time(Duration::from_secs(60))
+        .with_interval(Duration::from_secs(5));
+    t!(socket.set_tcp_keepalive(&keepalive));
+    assert_eq!(true, t!(socket.keepalive()));
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn tcp_socket_only_v6() {
+    let socket = t!(TcpSocket::new_v6());
+    t!(socket.set_only_v6(true));
+    assert_eq!(true, t!(socket.only_v6()));
+    t!(socket.set_only_v6(false));
+    assert_eq!(false, t!(socket.only_v6()));
+}
+
+#[test]
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_vendor = "apple",
+))]
+fn tcp_socket_reuseport() {
+    let addr = next_test_ip4();
+
+    let first = t!(TcpSocket::new_v4());
+    t!(first.set_reuseport(true));
+    assert_eq!(true, t!(first.reuseport()));
+    t!(first.bind(addr));
+    let _first = t!(first.listen(16));
+
+    let second = t!(TcpSocket::new_v4());
+    t!(second.set_reuseport(true));
+    t!(second.bind(addr));
+    let _second = t!(second.listen(16));
+}
@@ -840,3 +840,325 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A UDP socket that has not been bound yet.
+///
+/// `UdpSocketBuilder` gives access to socket options that have to be set
+/// before the socket is bound, such as `SO_REUSEPORT`, `IPV6_V6ONLY` or the
+/// size of the kernel buffers. Once configured, it is turned into a
+/// [`UdpSocket`] with [`bind`].
+///
+/// [`bind`]: UdpSocketBuilder::bind
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_socket_builder)]
+/// use std::net::UdpSocketBuilder;
+///
+/// fn main() -> std::io::Result<()> {
+///     let builder = UdpSocketBuilder::new_v6()?;
+///     builder.set_only_v6(false)?;
+///     builder.set_recv_buffer_size(1 << 20)?;
+///
+///     let socket = builder.bind("[::]:34254".parse().unwrap())?;
+///     let mut buf = [0; 1500];
+///     let (amt, src) = socket.recv_from(&mut buf)?;
+///     socket.send_to(&buf[..amt], &src)?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub struct UdpSocketBuilder(net_imp::UnboundSocket);
+
+impl UdpSocketBuilder {
+    /// Creates a new, unbound IPv4 UDP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn new_v4() -> io::Result<UdpSocketBuilder> {
+        net_imp::UnboundSocket::new_udp(false).map(UdpSocketBuilder)
+    }
+
+    /// Creates a new, unbound IPv6 UDP socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v6().expect("couldn't create the socket");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn new_v6() -> io::Result<UdpSocketBuilder> {
+        net_imp::UnboundSocket::new_udp(true).map(UdpSocketBuilder)
+    }
+
+    /// Binds the socket to the specified address, turning it into a
+    /// [`UdpSocket`].
+    ///
+    /// Binding with a port number of 0 will request that the OS assigns a port
+    /// to this socket. The address must belong to the family the socket was
+    /// created for.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// let socket = builder
+    ///     .bind("127.0.0.1:34254".parse().unwrap())
+    ///     .expect("couldn't bind to address");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[doc(alias = "bind")]
+    pub fn bind(self, addr: SocketAddr) -> io::Result<UdpSocket> {
+        self.0.bind(&addr)?;
+        Ok(UdpSocket(self.0.into_udp_socket()))
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On most platforms this allows several sockets to bind to the same
+    /// multicast address and port.
+    ///
+    /// See [`TcpSocket::set_reuseaddr`] for the caveats on Windows.
+    ///
+    /// [`TcpSocket::set_reuseaddr`]: crate::net::TcpSocket::set_reuseaddr
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// builder.set_reuseaddr(true).expect("set_reuseaddr call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.0.set_reuseaddr(reuseaddr)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_reuseaddr`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// builder.set_reuseaddr(true).expect("set_reuseaddr call failed");
+    /// assert_eq!(builder.reuseaddr().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.0.reuseaddr()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same address and port, as
+    /// long as all of them set this option before binding. On Linux, incoming
+    /// datagrams are then distributed between the sockets.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This option is only available on Android, the BSDs, Linux and Apple
+    /// platforms. On other platforms an error of kind
+    /// [`io::ErrorKind::Unsupported`] is returned.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// builder.set_reuseport(true).expect("set_reuseport call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.0.set_reuseport(reuseport)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_reuseport`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// builder.set_reuseport(true).expect("set_reuseport call failed");
+    /// assert_eq!(builder.reuseport().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.0.reuseport()
+    }
+
+    /// Sets the size of the send buffer of this socket (`SO_SNDBUF`).
+    ///
+    /// The OS treats the size as a hint and may round or clamp it.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Linux doubles the requested size to leave room for bookkeeping, so
+    /// [`UdpSocketBuilder::send_buffer_size`] reports twice the value set here.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// builder.set_send_buffer_size(1 << 20).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the size of the send buffer of this socket (`SO_SNDBUF`).
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_send_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// println!("{}", builder.send_buffer_size().expect("send_buffer_size call failed"));
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the size of the receive buffer of this socket (`SO_RCVBUF`).
+    ///
+    /// The OS treats the size as a hint and may round or clamp it. Datagrams
+    /// that arrive while the buffer is full are dropped.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Linux doubles the requested size to leave room for bookkeeping, so
+    /// [`UdpSocketBuilder::recv_buffer_size`] reports twice the value set here.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// builder.set_recv_buffer_size(1 << 20).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the size of the receive buffer of this socket (`SO_RCVBUF`).
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_recv_buffer_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v4().expect("couldn't create the socket");
+    /// println!("{}", builder.recv_buffer_size().expect("recv_buffer_size call failed"));
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `true` then the socket is restricted to sending and
+    /// receiving IPv6 packets only. If it is set to `false` then the socket
+    /// can also exchange datagrams with IPv4 peers through IPv4-mapped IPv6
+    /// addresses.
+    ///
+    /// The option has to be set before the socket is bound, and is only valid
+    /// for sockets created with [`UdpSocketBuilder::new_v6`]. The default value
+    /// depends on the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v6().expect("couldn't create the socket");
+    /// builder.set_only_v6(false).expect("set_only_v6 call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.0.set_only_v6(only_v6)
+    }
+
+    /// Gets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`UdpSocketBuilder::set_only_v6`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::UdpSocketBuilder;
+    ///
+    /// let builder = UdpSocketBuilder::new_v6().expect("couldn't create the socket");
+    /// builder.set_only_v6(true).expect("set_only_v6 call failed");
+    /// assert_eq!(builder.only_v6().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0.only_v6()
+    }
+}
+
+#[unstable(feature = "net_socket_builder", issue = "none")]
+impl fmt::Debug for UdpSocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -365,3 +365,40 @@ fn set_nonblocking() {
         }
     })
 }
+
+#[test]
+fn builder_smoke_test() {
+    each_ip(&mut |server_ip, client_ip| {
+        let new =
+            if server_ip.is_ipv4() { UdpSocketBuilder::new_v4 } else { UdpSocketBuilder::new_v6 };
+
+        let server = t!(new());
+        t!(server.set_recv_buffer_size(64 * 1024));
+        assert!(t!(server.recv_buffer_size()) >= 64 * 1024);
+        let server = t!(server.bind(server_ip));
+        assert_eq!(server_ip, t!(server.local_addr()));
+
+        let client = t!(t!(new()).bind(client_ip));
+        t!(client.send_to(&[99], &server_ip));
+
+        let mut buf = [0];
+        let (nread, src) = t!(server.recv_from(&mut buf));
+        assert_eq!(nread, 1);
+        assert_eq!(buf[0], 99);
+        assert_eq!(src, client_ip);
+    })
+}
+
+#[test]
+fn builder_options() {
+    let builder = t!(UdpSocketBuilder::new_v6());
+
+    t!(builder.set_reuseaddr(true));
+    assert_eq!(true, t!(builder.reuseaddr()));
+    t!(builder.set_only_v6(true));
+    assert_eq!(true, t!(builder.only_v6()));
+    t!(builder.set_only_v6(false));
+    assert_eq!(false, t!(builder.only_v6()));
+    t!(builder.set_send_buffer_size(64 * 1024));
+    assert!(t!(builder.send_buffer_size()) >= 64 * 1024);
+}
@@ -10,3 +10,6 @@
 
 #[unstable(feature = "tcp_quickack", issue = "96256")]
 pub use crate::os::net::linux_ext::tcp::TcpStreamExt;
+
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub use crate::os::net::linux_ext::tcp::TcpSocketExt;
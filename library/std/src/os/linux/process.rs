@@ -162,6 +162,45 @@ pub trait CommandExt: Sealed {
     /// [`pidfd`]: fn@ChildExt::pidfd
     /// [`take_pidfd`]: ChildExt::take_pidfd
     fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+
+    /// Sets a signal to be sent to the child process when its parent thread
+    /// exits. Equivalent to a `prctl(PR_SET_PDEATHSIG, signal)` call in the
+    /// child process.
+    ///
+    /// This ensures the child doesn't outlive a parent that crashes or is
+    /// killed before it could tear the child down itself. If the parent
+    /// already exited by the time the child is set up, the signal is raised
+    /// right away instead.
+    ///
+    /// Note that the signal is tied to the *thread* that spawned the child,
+    /// not the whole process, so it is also sent if that thread exits while
+    /// the rest of the process keeps running. Only spawn children with this
+    /// set from threads that live as long as the children should.
+    ///
+    /// The setting is cleared when the child executes a set-user-ID or
+    /// set-group-ID binary, but not by changing credentials with
+    /// [`uid`] and [`gid`], as the signal is set up after those.
+    ///
+    /// [`uid`]: crate::os::unix::process::CommandExt::uid
+    /// [`gid`]: crate::os::unix::process::CommandExt::gid
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_pidfd, linux_parent_death_signal)]
+    /// use std::os::linux::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// const SIGKILL: i32 = 9;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").parent_death_signal(SIGKILL).spawn()?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "linux_parent_death_signal", issue = "none")]
+    #[doc(alias = "PR_SET_PDEATHSIG")]
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command;
 }
 
 impl CommandExt for process::Command {
@@ -169,4 +208,9 @@ fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
         self.as_inner_mut().create_pidfd(val);
         self
     }
+
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command {
+        self.as_inner_mut().parent_death_signal(signal);
+        self
+    }
 }
@@ -6,6 +6,7 @@
 use crate::net;
 use crate::sealed::Sealed;
 use crate::sys_common::AsInner;
+use crate::time::Duration;
 
 /// Os-specific extensions for [`TcpStream`]
 ///
@@ -54,6 +55,58 @@ pub trait TcpStreamExt: Sealed {
     #[unstable(feature = "tcp_quickack", issue = "96256")]
     fn quickack(&self) -> io::Result<bool>;
 
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// This is the maximum amount of time that transmitted data may remain
+    /// unacknowledged before the connection is forcibly closed. Passing `None`
+    /// restores the system default. The timeout is rounded up to whole
+    /// milliseconds.
+    ///
+    /// An [`Err`] is returned if the zero [`Duration`] is passed to this
+    /// method.
+    ///
+    /// See [`man 7 tcp`](https://man7.org/linux/man-pages/man7/tcp.7.html)
+    /// for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpStream;
+    /// use std::os::linux::net::TcpStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///         .expect("Couldn't connect to the server...");
+    /// stream.set_user_timeout(Some(Duration::from_secs(30)))
+    ///         .expect("set_user_timeout call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// `None` means that the system default is used.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_user_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpStream;
+    /// use std::os::linux::net::TcpStreamExt;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///         .expect("Couldn't connect to the server...");
+    /// stream.set_user_timeout(Some(Duration::from_secs(30)))
+    ///         .expect("set_user_timeout call failed");
+    /// assert_eq!(stream.user_timeout().unwrap_or(None), Some(Duration::from_secs(30)));
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    fn user_timeout(&self) -> io::Result<Option<Duration>>;
+
     /// A socket listener will be awakened solely when data arrives.
     ///
     /// The `accept` argument set the delay in seconds until the
@@ -113,6 +166,167 @@ fn quickack(&self) -> io::Result<bool> {
         self.as_inner().as_inner().quickack()
     }
 
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.as_inner().as_inner().set_user_timeout(timeout)
+    }
+
+    fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        self.as_inner().as_inner().user_timeout()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn set_deferaccept(&self, accept: u32) -> io::Result<()> {
+        self.as_inner().as_inner().set_deferaccept(accept)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn deferaccept(&self) -> io::Result<u32> {
+        self.as_inner().as_inner().deferaccept()
+    }
+}
+
+/// Os-specific extensions for [`TcpSocket`]
+///
+/// These set the same options as [`TcpStreamExt`], but before the socket is
+/// connected or starts listening. Options set on a listening socket are
+/// inherited by the streams it accepts.
+///
+/// [`TcpSocket`]: net::TcpSocket
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub trait TcpSocketExt: Sealed {
+    /// Enable or disable `TCP_QUICKACK`.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_quickack`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    /// use std::os::linux::net::TcpSocketExt;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_quickack(true).expect("set_quickack call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    fn set_quickack(&self, quickack: bool) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_QUICKACK` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_quickack`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    /// use std::os::linux::net::TcpSocketExt;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_quickack(true).expect("set_quickack call failed");
+    /// assert_eq!(socket.quickack().unwrap_or(false), true);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    fn quickack(&self) -> io::Result<bool>;
+
+    /// Sets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_user_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    /// use std::os::linux::net::TcpSocketExt;
+    /// use std::time::Duration;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_user_timeout(Some(Duration::from_secs(30)))
+    ///         .expect("set_user_timeout call failed");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+
+    /// Gets the value of the `TCP_USER_TIMEOUT` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_user_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    /// use std::os::linux::net::TcpSocketExt;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// assert_eq!(socket.user_timeout().unwrap_or(None), None);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    fn user_timeout(&self) -> io::Result<Option<Duration>>;
+
+    /// Sets the `TCP_DEFER_ACCEPT` delay, in seconds, of a socket that is
+    /// going to listen.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_deferaccept`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    /// use std::os::linux::net::TcpSocketExt;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_deferaccept(1).expect("set_deferaccept call failed");
+    /// socket.bind("127.0.0.1:8080".parse().unwrap()).expect("couldn't bind to address");
+    /// let listener = socket.listen(128).expect("couldn't listen");
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[cfg(target_os = "linux")]
+    fn set_deferaccept(&self, accept: u32) -> io::Result<()>;
+
+    /// Gets the accept delay value (in seconds) of the `TCP_DEFER_ACCEPT` option.
+    ///
+    /// For more information about this option, see [`TcpStreamExt::set_deferaccept`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::TcpSocket;
+    /// use std::os::linux::net::TcpSocketExt;
+    ///
+    /// let socket = TcpSocket::new_v4().expect("couldn't create the socket");
+    /// socket.set_deferaccept(1).expect("set_deferaccept call failed");
+    /// assert_eq!(socket.deferaccept().unwrap_or(0), 1);
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    #[cfg(target_os = "linux")]
+    fn deferaccept(&self) -> io::Result<u32>;
+}
+
+#[unstable(feature = "net_socket_builder", issue = "none")]
+impl Sealed for net::TcpSocket {}
+
+#[unstable(feature = "net_socket_builder", issue = "none")]
+impl TcpSocketExt for net::TcpSocket {
+    fn set_quickack(&self, quickack: bool) -> io::Result<()> {
+        self.as_inner().as_inner().set_quickack(quickack)
+    }
+
+    fn quickack(&self) -> io::Result<bool> {
+        self.as_inner().as_inner().quickack()
+    }
+
+    fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.as_inner().as_inner().set_user_timeout(timeout)
+    }
+
+    fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        self.as_inner().as_inner().user_timeout()
+    }
+
     #[cfg(target_os = "linux")]
     fn set_deferaccept(&self, accept: u32) -> io::Result<()> {
         self.as_inner().as_inner().set_deferaccept(accept)
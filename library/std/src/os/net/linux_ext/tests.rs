@@ -52,3 +52,42 @@ macro_rules! t {
     stream.set_deferaccept(0).expect("set_deferaccept failed");
     assert_eq!(stream.deferaccept().unwrap(), 0);
 }
+
+#[test]
+fn user_timeout() {
+    use crate::{
+        net::{test::next_test_ip4, TcpListener, TcpSocket, TcpStream},
+        os::net::linux_ext::tcp::{TcpSocketExt, TcpStreamExt},
+        time::Duration,
+    };
+
+    macro_rules! t {
+        ($e:expr) => {
+            match $e {
+                Ok(t) => t,
+                Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+            }
+        };
+    }
+
+    let addr = next_test_ip4();
+    let _listener = t!(TcpListener::bind(&addr));
+    let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+    assert_eq!(None, t!(stream.user_timeout()));
+    t!(stream.set_user_timeout(Some(Duration::from_millis(1500))));
+    assert_eq!(Some(Duration::from_millis(1500)), t!(stream.user_timeout()));
+    t!(stream.set_user_timeout(Some(Duration::from_micros(1))));
+    assert_eq!(Some(Duration::from_millis(1)), t!(stream.user_timeout()));
+    t!(stream.set_user_timeout(None));
+    assert_eq!(None, t!(stream.user_timeout()));
+    assert!(stream.set_user_timeout(Some(Duration::ZERO)).is_err());
+
+    let socket = t!(TcpSocket::new_v4());
+    t!(socket.set_user_timeout(Some(Duration::from_secs(5))));
+    assert_eq!(Some(Duration::from_secs(5)), t!(socket.user_timeout()));
+    t!(socket.set_quickack(true));
+    assert_eq!(true, t!(socket.quickack()));
+    let stream = t!(socket.connect(addr));
+    assert_eq!(Some(Duration::from_secs(5)), t!(stream.user_timeout()));
+}
@@ -194,6 +194,40 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     /// ```
     #[stable(feature = "process_set_process_group", since = "1.64.0")]
     fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets whether the child process runs in a new session. Equivalent to a
+    /// `setsid` call in the child process.
+    ///
+    /// The child becomes the leader of both a new session and a new process
+    /// group, whose IDs are its process ID, and it is detached from the
+    /// controlling terminal of the parent. This keeps it from receiving
+    /// signals meant for the parent's terminal, and allows signalling it and
+    /// all of its descendants at once with [`ChildExt::signal_process_group`].
+    ///
+    /// This can't be combined with [`process_group`], as the leader of a
+    /// session can't change its process group: spawning the child fails
+    /// instead. Spawning the child also fails with an error of kind
+    /// [`Unsupported`] on Fuchsia and VxWorks, which don't have sessions.
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_setsid)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::CommandExt;
+    ///
+    /// Command::new("make")
+    ///     .setsid(true)
+    ///     .spawn()?
+    ///     .wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_setsid", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -239,6 +273,126 @@ fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
         self.as_inner_mut().pgroup(pgroup);
         self
     }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+}
+
+/// Unix-specific extensions to [`process::Child`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "unix_child_signal", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Sends the given signal to the child process.
+    ///
+    /// Like [`Child::kill`], this does nothing and returns `Ok(())` if the
+    /// child has already been waited on, as its process ID may have been
+    /// reused by an unrelated process since.
+    ///
+    /// On Linux, this uses the child's [`PidFd`] if it has one.
+    ///
+    /// [`Child::kill`]: process::Child::kill
+    /// [`PidFd`]: crate::os::linux::process::PidFd
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signal)]
+    /// use std::os::unix::process::ChildExt;
+    /// use std::process::Command;
+    ///
+    /// const SIGTERM: i32 = 15;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").spawn()?;
+    /// // Ask the child to exit before resorting to `kill`.
+    /// child.signal(SIGTERM)?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "unix_child_signal", issue = "none")]
+    #[doc(alias = "kill")]
+    fn signal(&mut self, signal: i32) -> io::Result<()>;
+
+    /// Sends the given signal to every process in the child's process group.
+    ///
+    /// This reaches the child along with all the descendants it spawned that
+    /// haven't moved to another process group, making it possible to tear
+    /// down a whole process tree. It is not an error if no process is left in
+    /// the group.
+    ///
+    /// Like [`signal`], this does nothing and returns `Ok(())` if the child
+    /// has already been waited on, as the process group ID may have been
+    /// reused since. To reach the descendants that outlive the child, signal
+    /// the group before waiting on the child.
+    ///
+    /// The child must have been spawned with [`CommandExt::process_group`] or
+    /// [`CommandExt::setsid`]. Otherwise it shares the process group of the
+    /// parent, which would receive the signal as well, so an error of kind
+    /// [`InvalidInput`] is returned instead.
+    ///
+    /// [`signal`]: ChildExt::signal
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signal)]
+    /// use std::os::unix::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    ///
+    /// const SIGTERM: i32 = 15;
+    ///
+    /// let mut child = Command::new("make").process_group(0).spawn()?;
+    /// child.signal_process_group(SIGTERM)?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "unix_child_signal", issue = "none")]
+    #[doc(alias = "killpg")]
+    fn signal_process_group(&mut self, signal: i32) -> io::Result<()>;
+
+    /// Forces every process in the child's process group to exit.
+    ///
+    /// This is equivalent to sending `SIGKILL` with
+    /// [`signal_process_group`], and has the same requirements.
+    ///
+    /// [`signal_process_group`]: ChildExt::signal_process_group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(unix_child_signal)]
+    /// use std::os::unix::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    ///
+    /// let mut child = Command::new("make").process_group(0).spawn()?;
+    /// child.kill_process_group()?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "unix_child_signal", issue = "none")]
+    fn kill_process_group(&mut self) -> io::Result<()>;
+}
+
+#[unstable(feature = "unix_child_signal", issue = "none")]
+impl ChildExt for process::Child {
+    fn signal(&mut self, signal: i32) -> io::Result<()> {
+        self.handle.send_signal(signal)
+    }
+
+    fn signal_process_group(&mut self, signal: i32) -> io::Result<()> {
+        self.handle.send_signal_to_group(signal)
+    }
+
+    fn kill_process_group(&mut self) -> io::Result<()> {
+        self.handle.send_signal_to_group(libc::SIGKILL)
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`] and
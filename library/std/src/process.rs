@@ -166,6 +166,7 @@
 #[stable(feature = "command_access", since = "1.57.0")]
 pub use crate::sys_common::process::CommandEnvs;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::{Duration, Instant};
 
 /// Representation of a running or exited child process.
 ///
@@ -2200,6 +2201,98 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `timeout`, collecting its exit
+    /// status if it did.
+    ///
+    /// If the child exits before the timeout elapses, then `Ok(Some(status))`
+    /// is returned, and on Unix the process ID is reaped. If the timeout
+    /// elapses first, then `Ok(None)` is returned and the child keeps running.
+    /// If an error occurs, then that error is returned.
+    ///
+    /// Like [`try_wait`] and unlike [`wait`], this function will not attempt
+    /// to drop stdin, as the child may still need it once this returns.
+    ///
+    /// [`try_wait`]: Child::try_wait
+    /// [`wait`]: Child::wait
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux, this waits for the child's [`PidFd`] to become readable,
+    /// opening one for the duration of the call if the child wasn't spawned
+    /// with one. On macOS and the BSDs, this waits for a `NOTE_EXIT` event on
+    /// a kqueue. Where neither is possible, and on other Unix platforms except
+    /// Fuchsia, the child is instead polled with an increasing delay of up to
+    /// 50 milliseconds, so that no `SIGCHLD` handler has to be installed. On
+    /// Windows, this uses `WaitForSingleObject` on the process handle. Note
+    /// that, this [may change in the future][changes].
+    ///
+    /// [`PidFd`]: crate::os::linux::process::PidFd
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    ///
+    /// match child.wait_timeout(Duration::from_secs(1)) {
+    ///     Ok(Some(status)) => println!("exited with: {status}"),
+    ///     Ok(None) => {
+    ///         println!("still running, killing it");
+    ///         child.kill().unwrap();
+    ///         child.wait().unwrap();
+    ///     }
+    ///     Err(e) => println!("error attempting to wait: {e}"),
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_deadline(deadline),
+            // A deadline that can't be represented will never be reached.
+            None => Ok(Some(ExitStatus(self.handle.wait()?))),
+        }
+    }
+
+    /// Waits for the child to exit until `deadline`, collecting its exit
+    /// status if it did.
+    ///
+    /// This behaves like [`wait_timeout`], but takes the point in time at
+    /// which to stop waiting. This makes it easy to share a single deadline
+    /// between several children, or to retry after being interrupted. A
+    /// deadline in the past only checks whether the child has exited, like
+    /// [`try_wait`].
+    ///
+    /// [`wait_timeout`]: Child::wait_timeout
+    /// [`try_wait`]: Child::try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// let mut children = vec![
+    ///     Command::new("make").spawn().unwrap(),
+    ///     Command::new("make").arg("doc").spawn().unwrap(),
+    /// ];
+    ///
+    /// for child in &mut children {
+    ///     if child.wait_deadline(deadline).unwrap().is_none() {
+    ///         child.kill().unwrap();
+    ///     }
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        Ok(self.handle.wait_deadline(deadline)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
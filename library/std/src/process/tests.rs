@@ -4,6 +4,7 @@
 use crate::io::{BorrowedBuf, ErrorKind};
 use crate::mem::MaybeUninit;
 use crate::str;
+use crate::time::{Duration, Instant};
 
 fn known_command() -> Command {
     if cfg!(windows) { Command::new("help") } else { Command::new("echo") }
@@ -247,6 +248,42 @@ fn test_wait_with_output_once() {
     assert_eq!(stderr, Vec::new());
 }
 
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout() {
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "set /p a="]).stdin(Stdio::piped()).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("read a").stdin(Stdio::piped()).spawn().unwrap()
+    };
+
+    // The child is blocked reading stdin, which `wait_timeout` leaves open.
+    let start = Instant::now();
+    assert!(prog.wait_timeout(Duration::from_millis(100)).unwrap().is_none());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert!(prog.stdin.is_some());
+
+    drop(prog.stdin.take());
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert_eq!(prog.try_wait().unwrap(), Some(status));
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_deadline() {
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "exit 1"]).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("false").spawn().unwrap()
+    };
+
+    let status = prog.wait_deadline(Instant::now() + Duration::from_secs(60)).unwrap().unwrap();
+    assert_eq!(status.code(), Some(1));
+    // Once the child has exited, a deadline in the past still reports its status.
+    assert_eq!(prog.wait_deadline(Instant::now()).unwrap(), Some(status));
+    assert_eq!(prog.wait_timeout(Duration::MAX).unwrap(), Some(status));
+}
+
 #[cfg(all(unix, not(target_os = "android")))]
 pub fn env_cmd() -> Command {
     Command::new("env")
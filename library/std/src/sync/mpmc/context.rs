@@ -1,6 +1,7 @@
 //! Thread-local channel context.
 
 use super::select::Selected;
+use super::utils::Backoff;
 use super::waker::current_thread_id;
 
 use crate::cell::Cell;
@@ -100,6 +101,12 @@ pub fn try_select(&self, select: Selected) -> Result<(), Selected> {
             .map_err(|e| e.into())
     }
 
+    /// Returns the selected operation.
+    #[inline]
+    pub fn selected(&self) -> Selected {
+        Selected::from(self.inner.select.load(Ordering::Acquire))
+    }
+
     /// Stores a packet.
     ///
     /// This method must be called after `try_select` succeeds and there is a packet to provide.
@@ -110,6 +117,19 @@ pub fn store_packet(&self, packet: *mut ()) {
         }
     }
 
+    /// Waits until a packet is provided and returns it.
+    #[inline]
+    pub fn wait_packet(&self) -> *mut () {
+        let backoff = Backoff::new();
+        loop {
+            let packet = self.inner.packet.load(Ordering::Acquire);
+            if !packet.is_null() {
+                return packet;
+            }
+            backoff.spin_heavy();
+        }
+    }
+
     /// Waits until an operation is selected and returns it.
     ///
     /// If the deadline is reached, `Selected::Aborted` will be selected.
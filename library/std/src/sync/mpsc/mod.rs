@@ -41,6 +41,13 @@
 //!
 //! [`unwrap`]: Result::unwrap
 //!
+//! ## Selection
+//!
+//! A thread can wait on several send and receive operations at once, on
+//! channels of any flavor, with the [`select!`] macro or the [`Select`]
+//! builder. Whichever operation becomes ready first is executed, optionally
+//! with a timeout or deadline after which the thread stops waiting.
+//!
 //! # Examples
 //!
 //! Simple usage:
@@ -143,6 +150,8 @@
 #[cfg(all(test, not(target_os = "emscripten")))]
 mod sync_tests;
 
+mod select;
+
 // MPSC channels are built as a wrapper around MPMC channels, which
 // were ported from the `crossbeam-channel` crate. MPMC channels are
 // not exposed publicly, but if you are curious about the implementation,
@@ -153,6 +162,11 @@
 use crate::sync::mpmc;
 use crate::time::{Duration, Instant};
 
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub use self::select::{
+    select, Select, SelectSender, SelectTimeoutError, SelectedOperation, TrySelectError,
+};
+
 /// The receiving half of Rust's [`channel`] (or [`sync_channel`]) type.
 /// This half can only be owned by one thread.
 ///
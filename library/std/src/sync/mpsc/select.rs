@@ -0,0 +1,586 @@
+//! Waiting on several channel operations at once.
+
+use super::{Receiver, RecvError, SendError, Sender, SyncSender};
+use crate::error;
+use crate::fmt;
+use crate::sync::mpmc;
+use crate::time::{Duration, Instant};
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests;
+
+mod private {
+    use crate::sync::mpmc;
+
+    pub trait Sealed<T> {
+        fn as_mpmc(&self) -> &mpmc::Sender<T>;
+    }
+}
+
+/// The sending half of a channel, which can be used with [`Select`] and [`select!`].
+///
+/// This trait is implemented by [`Sender`], [`SyncSender`] and references to them, and can't be
+/// implemented outside of the standard library.
+///
+/// [`select!`]: crate::sync::mpsc::select
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub trait SelectSender<T>: private::Sealed<T> {}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl<T, S: SelectSender<T>> SelectSender<T> for &S {}
+
+impl<T, S: SelectSender<T>> private::Sealed<T> for &S {
+    fn as_mpmc(&self) -> &mpmc::Sender<T> {
+        (**self).as_mpmc()
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl<T> SelectSender<T> for Sender<T> {}
+
+impl<T> private::Sealed<T> for Sender<T> {
+    fn as_mpmc(&self) -> &mpmc::Sender<T> {
+        &self.inner
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl<T> SelectSender<T> for SyncSender<T> {}
+
+impl<T> private::Sealed<T> for SyncSender<T> {
+    fn as_mpmc(&self) -> &mpmc::Sender<T> {
+        &self.inner
+    }
+}
+
+/// Selects from a set of channel operations.
+///
+/// `Select` allows you to define a set of send and receive operations on
+/// channels, wait until any one of them becomes ready, and finally execute it.
+/// If multiple operations are ready at the same time, a random one among them
+/// is selected, so that no operation is starved.
+///
+/// An operation is considered to be ready if it doesn't have to block. Note
+/// that it is ready even when it will simply return an error because the
+/// channel is disconnected.
+///
+/// The selected operation must be completed with [`SelectedOperation::send`]
+/// or [`SelectedOperation::recv`]. The [`select!`] macro is a more convenient
+/// way to do all of this.
+///
+/// [`select!`]: crate::sync::mpsc::select
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{channel, Select};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let (tx1, rx1) = channel();
+/// let (tx2, rx2) = channel();
+///
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(100));
+///     tx1.send(10).unwrap();
+/// });
+/// thread::spawn(move || tx2.send(20).unwrap());
+///
+/// let mut sel = Select::new();
+/// let oper1 = sel.recv(&rx1);
+/// let oper2 = sel.recv(&rx2);
+///
+/// // The second operation will be selected because it becomes ready first.
+/// let oper = sel.select();
+/// match oper.index() {
+///     i if i == oper1 => panic!("the first operation was selected"),
+///     i if i == oper2 => assert_eq!(oper.recv(&rx2), Ok(20)),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(Clone, Default)]
+pub struct Select<'a> {
+    inner: mpmc::Select<'a>,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty list of channel operations for selection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::Select;
+    ///
+    /// let mut sel = Select::new();
+    ///
+    /// // The list of operations is empty, which means no operation can be selected.
+    /// assert!(sel.try_select().is_err());
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn new() -> Select<'a> {
+        Select { inner: mpmc::Select::new() }
+    }
+
+    /// Adds a send operation on a [`Sender`] or a [`SyncSender`].
+    ///
+    /// Returns the index of the added operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{sync_channel, Select};
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.send(&tx);
+    ///
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), index);
+    /// oper.send(&tx, 42).unwrap();
+    /// assert_eq!(rx.recv(), Ok(42));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(&mut self, sender: &'a impl SelectSender<T>) -> usize {
+        self.inner.send(sender.as_mpmc())
+    }
+
+    /// Adds a receive operation.
+    ///
+    /// Returns the index of the added operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx, rx) = channel();
+    /// tx.send(1).unwrap();
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.recv(&rx);
+    ///
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), index);
+    /// assert_eq!(oper.recv(&rx), Ok(1));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(&mut self, receiver: &'a Receiver<T>) -> usize {
+        self.inner.recv(&receiver.inner)
+    }
+
+    /// Removes a previously added operation.
+    ///
+    /// This is useful when an operation is selected because the channel got
+    /// disconnected and we want to try again to select a different operation
+    /// instead.
+    ///
+    /// # Panics
+    ///
+    /// An attempt to remove a non-existing or already removed operation will
+    /// panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx1, rx1) = channel::<i32>();
+    /// let (_tx2, rx2) = channel::<i32>();
+    /// drop(tx1);
+    ///
+    /// let mut sel = Select::new();
+    /// let oper1 = sel.recv(&rx1);
+    /// let _oper2 = sel.recv(&rx2);
+    ///
+    /// // The first channel is disconnected, so its operation is ready.
+    /// let oper = sel.select();
+    /// assert_eq!(oper.index(), oper1);
+    /// assert!(oper.recv(&rx1).is_err());
+    ///
+    /// // Only the second operation is left, and it isn't ready.
+    /// sel.remove(oper1);
+    /// assert!(sel.try_select().is_err());
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn remove(&mut self, index: usize) {
+        self.inner.remove(index)
+    }
+
+    /// Attempts to select one of the operations without blocking.
+    ///
+    /// If an operation is ready, it is selected and returned. If multiple
+    /// operations are ready at the same time, a random one among them is
+    /// selected. If none of the operations are ready, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// let mut sel = Select::new();
+    /// let index = sel.recv(&rx);
+    /// assert!(sel.try_select().is_err());
+    ///
+    /// tx.send("hello").unwrap();
+    /// let oper = sel.try_select().unwrap();
+    /// assert_eq!(oper.index(), index);
+    /// assert_eq!(oper.recv(&rx), Ok("hello"));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        self.inner.try_select().map(|inner| SelectedOperation { inner })
+    }
+
+    /// Blocks until one of the operations becomes ready and selects it.
+    ///
+    /// Once an operation becomes ready, it is selected and returned. If
+    /// multiple operations are ready at the same time, a random one among them
+    /// is selected.
+    ///
+    /// If the list of operations is empty, this method blocks forever.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        SelectedOperation { inner: self.inner.select() }
+    }
+
+    /// Blocks for a limited time until one of the operations becomes ready and
+    /// selects it.
+    ///
+    /// If an operation becomes ready, it is selected and returned. If multiple
+    /// operations are ready at the same time, a random one among them is
+    /// selected. If none of the operations become ready for the specified
+    /// duration, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::time::Duration;
+    ///
+    /// let (_tx, rx) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    /// assert!(sel.select_timeout(Duration::from_millis(10)).is_err());
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.inner.select_timeout(timeout).map(|inner| SelectedOperation { inner })
+    }
+
+    /// Blocks until a given deadline, or until one of the operations becomes
+    /// ready and selects it.
+    ///
+    /// If an operation becomes ready, it is selected and returned. If multiple
+    /// operations are ready at the same time, a random one among them is
+    /// selected. If none of the operations become ready before the given
+    /// deadline, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let (_tx, rx) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert!(sel.select_deadline(deadline).is_err());
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_deadline(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.inner.select_deadline(deadline).map(|inner| SelectedOperation { inner })
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").finish_non_exhaustive()
+    }
+}
+
+/// A selected operation that needs to be completed.
+///
+/// To complete the operation, call [`send`] or [`recv`] with the channel the
+/// operation was added for.
+///
+/// # Panics
+///
+/// Forgetting to complete the operation is an error and might lead to
+/// deadlocks. If a `SelectedOperation` is dropped without completion, a panic
+/// occurs.
+///
+/// [`send`]: SelectedOperation::send
+/// [`recv`]: SelectedOperation::recv
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[must_use = "the selected operation must be completed with `send` or `recv`"]
+pub struct SelectedOperation<'a> {
+    inner: mpmc::SelectedOperation<'a>,
+}
+
+impl SelectedOperation<'_> {
+    /// Returns the index of the selected operation.
+    ///
+    /// This is the index that was returned by [`Select::send`] or
+    /// [`Select::recv`] when the operation was added.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn index(&self) -> usize {
+        self.inner.index()
+    }
+
+    /// Completes the send operation.
+    ///
+    /// This returns an error if the receiving half of the channel is
+    /// disconnected, and the selected operation is never blocked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sender` isn't the same [`Sender`] or [`SyncSender`] that was
+    /// passed to [`Select::send`] for the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(self, sender: &impl SelectSender<T>, msg: T) -> Result<(), SendError<T>> {
+        self.inner.send(sender.as_mpmc(), msg)
+    }
+
+    /// Completes the receive operation.
+    ///
+    /// This returns an error if the channel is empty and its sending half is
+    /// disconnected, and the selected operation is never blocked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `receiver` isn't the same [`Receiver`] that was passed to
+    /// [`Select::recv`] for the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(self, receiver: &Receiver<T>) -> Result<T, RecvError> {
+        self.inner.recv(&receiver.inner)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for SelectedOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectedOperation").field("index", &self.index()).finish_non_exhaustive()
+    }
+}
+
+/// An error returned from [`Select::try_select`] when none of the operations
+/// are ready.
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct TrySelectError;
+
+/// An error returned from [`Select::select_timeout`] and
+/// [`Select::select_deadline`] when none of the operations became ready in
+/// time.
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SelectTimeoutError;
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for TrySelectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "all operations in select would block".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for TrySelectError {}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "timed out waiting on select".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for SelectTimeoutError {}
+
+/// Waits on several channel operations at once, and runs the code of the one
+/// that completes first.
+///
+/// The `select!` macro takes a list of arms, separated by commas:
+///
+/// * `recv(receiver) -> result => body` receives a message from `receiver`,
+///   a [`Receiver`], as an operation that returns a `Result<T, RecvError>`.
+/// * `send(sender, message) -> result => body` sends `message` into `sender`,
+///   a [`Sender`] or a [`SyncSender`], as an operation that returns a
+///   `Result<(), SendError<T>>`. `message` is only evaluated if this
+///   operation is selected.
+/// * `default => body`, if present, is run when none of the operations are
+///   ready right away, instead of blocking.
+/// * `default(timeout) => body`, if present, is run when none of the
+///   operations become ready within `timeout`, a [`Duration`].
+///
+/// The macro blocks until one of the operations becomes ready, or until the
+/// `default` arm applies. If multiple operations are ready at the same time, a
+/// random one among them is selected. The result of the selected operation is
+/// bound to the pattern `result`, which must be irrefutable, and the `body` of
+/// its arm is evaluated as the value of the whole macro.
+///
+/// Like with [`Select`], an operation is ready when it is complete with an
+/// error because its channel is disconnected.
+///
+/// # Examples
+///
+/// Receiving from whichever channel has a message first:
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{channel, select};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let (tx1, rx1) = channel::<&str>();
+/// let (tx2, rx2) = channel::<i32>();
+///
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(100));
+///     tx1.send("late").unwrap();
+/// });
+/// thread::spawn(move || tx2.send(42).unwrap());
+///
+/// let msg = select! {
+///     recv(rx1) -> msg => format!("first channel: {}", msg.unwrap()),
+///     recv(rx2) -> msg => format!("second channel: {}", msg.unwrap()),
+/// };
+/// assert_eq!(msg, "second channel: 42");
+/// ```
+///
+/// Sending with a timeout:
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{select, sync_channel};
+/// use std::time::Duration;
+///
+/// // Nobody receives from this rendezvous channel, so the message can't be sent.
+/// let (tx, _rx) = sync_channel(0);
+///
+/// select! {
+///     send(tx, "hello") -> res => panic!("sent a message: {res:?}"),
+///     default(Duration::from_millis(10)) => println!("timed out"),
+/// }
+/// ```
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[rustc_macro_transparency = "semitransparent"]
+#[allow_internal_unstable(mpsc_select)]
+pub macro select {
+    // All arms are parsed. Every operation arm is given a handle and an index
+    // variable, whose names are unique because they come from separate
+    // expansions of this macro.
+    (@parse [$((
+        ($handle:ident $index:ident $kind:ident ($chan:expr) ($($msg:tt)*) $res:pat)
+        $body:tt
+    ))*] $default:tt) => {{
+        let mut sel = $crate::sync::mpsc::Select::new();
+        $(
+            let $handle = &$chan;
+            let $index = sel.$kind($handle);
+        )*
+        $crate::sync::mpsc::select!(@select sel $default oper => {
+            let index = oper.index();
+            $(
+                if index == $index {
+                    let $res = oper.$kind($handle $($msg)*);
+                    $body
+                } else
+            )*
+            {
+                $crate::unreachable!()
+            }
+        })
+    }},
+    (@parse $ops:tt $default:tt recv($r:expr) -> $res:pat => $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(
+            @body $ops $default op (handle index recv ($r) () $res) $($rest)*
+        )
+    },
+    (@parse $ops:tt $default:tt send($s:expr, $m:expr) -> $res:pat => $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(
+            @body $ops $default op (handle index send ($s) (, $m) $res) $($rest)*
+        )
+    },
+    (@parse $ops:tt $default:tt default => $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@body $ops $default default (default) $($rest)*)
+    },
+    (@parse $ops:tt $default:tt default($timeout:expr) => $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@body $ops $default default (default($timeout)) $($rest)*)
+    },
+    (@parse $ops:tt $default:tt $($rest:tt)+) => {
+        $crate::compile_error!($crate::concat!(
+            "expected a `recv(..) -> .. => ..`, `send(.., ..) -> .. => ..` or `default => ..` ",
+            "arm in `select!`, found `",
+            $crate::stringify!($($rest)+),
+            "`",
+        ))
+    },
+
+    // Parse the body of an arm, which is followed by a comma unless it's a block.
+    (@body $ops:tt $default:tt $kind:ident $head:tt $body:block, $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@push $ops $default $kind ($head $body) $($rest)*)
+    },
+    (@body $ops:tt $default:tt $kind:ident $head:tt $body:block $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@push $ops $default $kind ($head $body) $($rest)*)
+    },
+    (@body $ops:tt $default:tt $kind:ident $head:tt $body:expr, $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@push $ops $default $kind ($head $body) $($rest)*)
+    },
+    (@body $ops:tt $default:tt $kind:ident $head:tt $body:expr) => {
+        $crate::sync::mpsc::select!(@push $ops $default $kind ($head $body))
+    },
+
+    (@push [$($ops:tt)*] $default:tt op $arm:tt $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@parse [$($ops)* $arm] $default $($rest)*)
+    },
+    (@push $ops:tt [] default $arm:tt $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@parse $ops [$arm] $($rest)*)
+    },
+    (@push $ops:tt [$default:tt] default $arm:tt $($rest:tt)*) => {
+        $crate::compile_error!("`select!` can't have more than one `default` arm")
+    },
+
+    // Select an operation, depending on the `default` arm.
+    (@select $sel:ident [] $oper:ident => $dispatch:block) => {{
+        let $oper = $sel.select();
+        $dispatch
+    }},
+    (@select $sel:ident [((default) $body:tt)] $oper:ident => $dispatch:block) => {
+        match $sel.try_select() {
+            $crate::result::Result::Ok($oper) => $dispatch,
+            $crate::result::Result::Err(_) => $body,
+        }
+    },
+    (@select $sel:ident [((default($timeout:expr)) $body:tt)] $oper:ident => $dispatch:block) => {
+        match $sel.select_timeout($timeout) {
+            $crate::result::Result::Ok($oper) => $dispatch,
+            $crate::result::Result::Err(_) => $body,
+        }
+    },
+
+    ($($arms:tt)*) => {
+        $crate::sync::mpsc::select!(@parse [] [] $($arms)*)
+    },
+}
@@ -0,0 +1,268 @@
+use super::*;
+use crate::sync::mpsc::{channel, sync_channel};
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(1);
+
+    tx1.send(1).unwrap();
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+
+    tx2.send(2).unwrap();
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+}
+
+#[test]
+fn try_select() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    assert_eq!(sel.try_select().err(), Some(TrySelectError));
+
+    // A rendezvous channel is only ready if there is a sender on the other side.
+    let t = thread::spawn(move || tx2.send(2).unwrap());
+    thread::sleep(Duration::from_millis(100));
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.recv(&rx2), Ok(2));
+    t.join().unwrap();
+
+    // Both channels are disconnected now, receive from the first one.
+    sel.remove(oper2);
+    drop(tx1);
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.recv(&rx1), Err(RecvError));
+}
+
+#[test]
+fn select_timeout() {
+    let (tx, rx) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(100)).err(), Some(SelectTimeoutError));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        tx.send(1).unwrap();
+    });
+    let oper = sel.select_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(oper.recv(&rx), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+fn select_deadline() {
+    let (_tx, rx) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let deadline = Instant::now() + Duration::from_millis(100);
+    assert_eq!(sel.select_deadline(deadline).err(), Some(SelectTimeoutError));
+    assert!(Instant::now() >= deadline);
+
+    // An empty select waits until the deadline.
+    let deadline = Instant::now() + Duration::from_millis(100);
+    assert_eq!(Select::new().select_deadline(deadline).err(), Some(SelectTimeoutError));
+    assert!(Instant::now() >= deadline);
+}
+
+#[test]
+fn send() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (tx2, rx2) = channel::<i32>();
+
+    // The bounded channel is full, so only the unbounded one is ready.
+    tx1.send(0).unwrap();
+    let mut sel = Select::new();
+    sel.send(&tx1);
+    let oper2 = sel.send(&tx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    oper.send(&tx2, 2).unwrap();
+    assert_eq!(rx2.recv(), Ok(2));
+
+    drop(rx1);
+    sel.remove(oper2);
+    let oper = sel.select();
+    assert_eq!(oper.send(&tx1, 1), Err(SendError(1)));
+}
+
+#[test]
+fn rendezvous() {
+    let (tx1, rx1) = sync_channel::<i32>(0);
+    let (tx2, rx2) = sync_channel::<i32>(0);
+
+    let t = thread::spawn(move || {
+        let mut sel = Select::new();
+        sel.recv(&rx1);
+        let oper = sel.select();
+        assert_eq!(oper.recv(&rx1), Ok(1));
+
+        rx2.recv().unwrap()
+    });
+
+    // Both sides of the first channel select, the second one pairs up with a blocking receiver.
+    let mut sel = Select::new();
+    sel.send(&tx1);
+    let oper = sel.select();
+    oper.send(&tx1, 1).unwrap();
+
+    let mut sel = Select::new();
+    sel.send(&tx2);
+    let oper = sel.select();
+    oper.send(&tx2, 2).unwrap();
+
+    assert_eq!(t.join().unwrap(), 2);
+}
+
+#[test]
+fn both_ready() {
+    const COUNT: usize = 1000;
+
+    let (tx1, rx1) = channel::<()>();
+    let (tx2, rx2) = channel::<()>();
+    for _ in 0..COUNT {
+        tx1.send(()).unwrap();
+        tx2.send(()).unwrap();
+    }
+
+    // Operations that are ready at the same time are selected randomly.
+    let mut hits = [0; 2];
+    for _ in 0..COUNT {
+        let mut sel = Select::new();
+        let oper1 = sel.recv(&rx1);
+        let oper2 = sel.recv(&rx2);
+        let oper = sel.select();
+        match oper.index() {
+            i if i == oper1 => {
+                oper.recv(&rx1).unwrap();
+                hits[0] += 1;
+            }
+            i if i == oper2 => {
+                oper.recv(&rx2).unwrap();
+                hits[1] += 1;
+            }
+            _ => unreachable!(),
+        }
+    }
+    assert!(hits.iter().all(|&hits| hits >= COUNT / 10), "{hits:?}");
+}
+
+#[test]
+#[should_panic(expected = "passed a receiver that wasn't selected")]
+fn wrong_receiver() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.recv(&rx2);
+    let _ = sel.select().recv(&rx2);
+}
+
+#[test]
+#[should_panic(expected = "dropped `SelectedOperation` without completing the operation")]
+fn incomplete_operation() {
+    let (tx, rx) = channel::<i32>();
+    tx.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let _ = sel.select();
+}
+
+#[test]
+fn macro_recv() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<&str>(0);
+
+    let t = thread::spawn(move || tx2.send("hello").unwrap());
+    let msg = select! {
+        recv(rx1) -> msg => panic!("received {msg:?}"),
+        recv(rx2) -> msg => msg.unwrap(),
+    };
+    assert_eq!(msg, "hello");
+    t.join().unwrap();
+
+    drop(tx1);
+    select! {
+        recv(rx1) -> msg => assert_eq!(msg, Err(RecvError)),
+    }
+}
+
+#[test]
+fn macro_send() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (tx2, rx2) = channel::<i32>();
+    let tx2 = &tx2;
+
+    tx1.send(0).unwrap();
+    let mut evaluated = false;
+    select! {
+        send(tx1, { evaluated = true; 1 }) -> res => panic!("sent into a full channel: {res:?}"),
+        send(tx2, 2) -> res => res.unwrap(),
+    }
+    assert_eq!(rx2.recv(), Ok(2));
+    // The message of an operation is only evaluated if it's selected.
+    assert!(!evaluated);
+    assert_eq!(rx1.recv(), Ok(0));
+}
+
+#[test]
+fn macro_default() {
+    let (tx, rx) = channel::<i32>();
+
+    let res = select! {
+        recv(rx) -> _ => "received",
+        default => "default",
+    };
+    assert_eq!(res, "default");
+
+    tx.send(1).unwrap();
+    let res = select! {
+        default => 0,
+        recv(rx) -> msg => msg.unwrap(),
+    };
+    assert_eq!(res, 1);
+
+    let start = Instant::now();
+    select! {
+        recv(rx) -> msg => panic!("received {msg:?}"),
+        default(Duration::from_millis(100)) => {}
+    }
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn macro_blocks() {
+    let (tx, rx) = channel::<i32>();
+    tx.send(1).unwrap();
+
+    // Arms with a block as their body don't need a comma.
+    let mut received = None;
+    select! {
+        recv(rx) -> msg => {
+            received = Some(msg.unwrap());
+        }
+        default => {
+            panic!("nothing received");
+        }
+    }
+    assert_eq!(received, Some(1));
+}
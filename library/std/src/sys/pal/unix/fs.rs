@@ -1244,6 +1244,83 @@ pub fn truncate(&self, size: u64) -> io::Result<()> {
         cvt_r(|| unsafe { ftruncate64(self.as_raw_fd(), size) }).map(drop)
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_file(LockKind::Exclusive, true).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_file(LockKind::Shared, true).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.lock_file(LockKind::Exclusive, false)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.lock_file(LockKind::Shared, false)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.lock_file(LockKind::Unlock, true).map(drop)
+    }
+
+    /// Changes the advisory lock held on the whole file.
+    ///
+    /// Returns `false` if `blocking` is unset and a conflicting lock is held
+    /// elsewhere.
+    fn lock_file(&self, kind: LockKind, blocking: bool) -> io::Result<bool> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "linux",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_vendor = "apple",
+            ))] {
+                let mut operation = match kind {
+                    LockKind::Shared => libc::LOCK_SH,
+                    LockKind::Exclusive => libc::LOCK_EX,
+                    LockKind::Unlock => libc::LOCK_UN,
+                };
+                if !blocking {
+                    operation |= libc::LOCK_NB;
+                }
+                match cvt_r(|| unsafe { libc::flock(self.as_raw_fd(), operation) }) {
+                    Ok(_) => Ok(true),
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(false),
+                    Err(err) => Err(err),
+                }
+            } else if #[cfg(any(target_os = "aix", target_os = "illumos", target_os = "solaris"))] {
+                // These platforms lack `flock`, so fall back to a POSIX record
+                // lock. A zero `l_start` and `l_len` cover the whole file,
+                // including any data appended later.
+                let mut lock: libc::flock = unsafe { mem::zeroed() };
+                lock.l_type = match kind {
+                    LockKind::Shared => libc::F_RDLCK,
+                    LockKind::Exclusive => libc::F_WRLCK,
+                    LockKind::Unlock => libc::F_UNLCK,
+                } as _;
+                lock.l_whence = libc::SEEK_SET as _;
+                let cmd = if blocking { libc::F_SETLKW } else { libc::F_SETLK };
+                match cvt_r(|| unsafe { libc::fcntl(self.as_raw_fd(), cmd, &lock) }) {
+                    Ok(_) => Ok(true),
+                    Err(err) if matches!(err.raw_os_error(), Some(libc::EACCES | libc::EAGAIN)) => {
+                        Ok(false)
+                    }
+                    Err(err) => Err(err),
+                }
+            } else {
+                let _ = (kind, blocking);
+                Err(io::const_io_error!(
+                    io::ErrorKind::Unsupported,
+                    "file locking is not supported on this platform",
+                ))
+            }
+        }
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
@@ -1406,6 +1483,13 @@ pub fn set_times(&self, times: FileTimes) -> io::Result<()> {
     }
 }
 
+#[derive(Clone, Copy)]
+enum LockKind {
+    Shared,
+    Exclusive,
+    Unlock,
+}
+
 impl DirBuilder {
     pub fn new() -> DirBuilder {
         DirBuilder { mode: 0o777 }
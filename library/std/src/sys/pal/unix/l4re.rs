@@ -521,6 +521,109 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
     }
 
+    pub struct UnboundSocket(!);
+
+    impl UnboundSocket {
+        pub fn new_tcp(_: bool) -> io::Result<UnboundSocket> {
+            unimpl!();
+        }
+
+        pub fn new_udp(_: bool) -> io::Result<UnboundSocket> {
+            unimpl!();
+        }
+
+        pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+            self.0
+        }
+
+        pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+            self.0
+        }
+
+        pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+            self.0
+        }
+
+        pub fn into_udp_socket(self) -> UdpSocket {
+            self.0
+        }
+
+        pub fn set_reuseaddr(&self, _: bool) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn reuseaddr(&self) -> io::Result<bool> {
+            self.0
+        }
+
+        pub fn set_reuseport(&self, _: bool) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn reuseport(&self) -> io::Result<bool> {
+            self.0
+        }
+
+        pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn keepalive(&self) -> io::Result<bool> {
+            self.0
+        }
+
+        pub fn set_tcp_keepalive(
+            &self,
+            _: Option<Duration>,
+            _: Option<Duration>,
+            _: Option<u32>,
+        ) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            self.0
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            self.0
+        }
+
+        pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn only_v6(&self) -> io::Result<bool> {
+            self.0
+        }
+
+        pub fn set_nodelay(&self, _: bool) -> io::Result<()> {
+            self.0
+        }
+
+        pub fn nodelay(&self) -> io::Result<bool> {
+            self.0
+        }
+    }
+
+    impl fmt::Debug for UnboundSocket {
+        fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0
+        }
+    }
+
     pub struct LookupHost {
         original: *mut libc::addrinfo,
         cur: *mut libc::addrinfo,
@@ -431,6 +431,54 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_tcp_keepalive(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+        retries: Option<u32>,
+    ) -> io::Result<()> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(
+                target_os = "android",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "linux",
+                target_os = "netbsd",
+                target_vendor = "apple",
+            ))] {
+                #[cfg(target_vendor = "apple")]
+                use libc::TCP_KEEPALIVE as TCP_KEEPIDLE;
+                #[cfg(not(target_vendor = "apple"))]
+                use libc::TCP_KEEPIDLE;
+
+                // Round up so that sub-second durations don't turn into 0.
+                fn secs(dur: Duration) -> c_int {
+                    let secs = dur.as_secs() + (dur.subsec_nanos() != 0) as u64;
+                    cmp::min(secs, c_int::MAX as u64) as c_int
+                }
+
+                setsockopt(self, libc::SOL_SOCKET, libc::SO_KEEPALIVE, 1 as c_int)?;
+                if let Some(time) = time {
+                    setsockopt(self, libc::IPPROTO_TCP, TCP_KEEPIDLE, secs(time))?;
+                }
+                if let Some(interval) = interval {
+                    setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL, secs(interval))?;
+                }
+                if let Some(retries) = retries {
+                    let retries = cmp::min(retries, c_int::MAX as u32) as c_int;
+                    setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, retries)?;
+                }
+                Ok(())
+            } else {
+                let _ = (time, interval, retries);
+                Err(io::const_io_error!(
+                    io::ErrorKind::Unsupported,
+                    "tuning keepalive probes is not supported on this platform",
+                ))
+            }
+        }
+    }
+
     #[cfg(any(target_os = "android", target_os = "linux",))]
     pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
         setsockopt(self, libc::IPPROTO_TCP, libc::TCP_QUICKACK, quickack as c_int)
@@ -442,6 +490,32 @@ pub fn quickack(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn set_user_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        let timeout = match timeout {
+            Some(dur) => {
+                if dur == Duration::ZERO {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::InvalidInput,
+                        "cannot set a 0 duration timeout",
+                    ));
+                }
+                // Round up so that sub-millisecond timeouts do not turn into
+                // "use the system default".
+                let millis = dur.as_millis() + (dur.subsec_nanos() % 1_000_000 != 0) as u128;
+                cmp::min(millis, libc::c_uint::MAX as u128) as libc::c_uint
+            }
+            None => 0,
+        };
+        setsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT, timeout)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux",))]
+    pub fn user_timeout(&self) -> io::Result<Option<Duration>> {
+        let raw: libc::c_uint = getsockopt(self, libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)?;
+        Ok((raw != 0).then(|| Duration::from_millis(raw as u64)))
+    }
+
     // bionic libc makes no use of this flag
     #[cfg(target_os = "linux")]
     pub fn set_deferaccept(&self, accept: u32) -> io::Result<()> {
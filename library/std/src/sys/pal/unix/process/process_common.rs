@@ -107,7 +107,14 @@ pub struct Command {
     stderr: Option<Stdio>,
     #[cfg(target_os = "linux")]
     create_pidfd: bool,
+    #[cfg(target_os = "linux")]
+    parent_death_signal: Option<c_int>,
+    /// The process ID of the parent at the time `parent_death_signal` is
+    /// set up in the child, used to detect that it already exited.
+    #[cfg(target_os = "linux")]
+    parent_pid: pid_t,
     pgroup: Option<pid_t>,
+    setsid: bool,
 }
 
 // Create a new type for argv, so that we can make it `Send` and `Sync`
@@ -199,6 +206,7 @@ pub fn new(program: &OsStr) -> Command {
             stdout: None,
             stderr: None,
             pgroup: None,
+            setsid: false,
         }
     }
 
@@ -223,7 +231,10 @@ pub fn new(program: &OsStr) -> Command {
             stdout: None,
             stderr: None,
             create_pidfd: false,
+            parent_death_signal: None,
+            parent_pid: 0,
             pgroup: None,
+            setsid: false,
         }
     }
 
@@ -262,6 +273,9 @@ pub fn groups(&mut self, groups: &[gid_t]) {
     pub fn pgroup(&mut self, pgroup: pid_t) {
         self.pgroup = Some(pgroup);
     }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
 
     #[cfg(target_os = "linux")]
     pub fn create_pidfd(&mut self, val: bool) {
@@ -279,6 +293,26 @@ pub fn get_create_pidfd(&self) -> bool {
         self.create_pidfd
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn parent_death_signal(&mut self, signal: c_int) {
+        self.parent_death_signal = Some(signal);
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn get_parent_death_signal(&self) -> Option<c_int> {
+        self.parent_death_signal
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_parent_pid(&mut self, pid: pid_t) {
+        self.parent_pid = pid;
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn get_parent_pid(&self) -> pid_t {
+        self.parent_pid
+    }
+
     pub fn saw_nul(&self) -> bool {
         self.saw_nul
     }
@@ -334,6 +368,10 @@ pub fn get_groups(&self) -> Option<&[gid_t]> {
     pub fn get_pgroup(&self) -> Option<pid_t> {
         self.pgroup
     }
+    #[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -575,10 +613,16 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             if self.pgroup.is_some() {
                 debug_command.field("pgroup", &self.pgroup);
             }
+            if self.setsid {
+                debug_command.field("setsid", &self.setsid);
+            }
 
             #[cfg(target_os = "linux")]
             {
                 debug_command.field("create_pidfd", &self.create_pidfd);
+                if self.parent_death_signal.is_some() {
+                    debug_command.field("parent_death_signal", &self.parent_death_signal);
+                }
             }
 
             debug_command.finish()
@@ -3,6 +3,7 @@
 use crate::mem;
 use crate::num::NonZero;
 use crate::ptr;
+use crate::time::Instant;
 
 use crate::sys::process::process_common::*;
 use crate::sys::process::zircon::{zx_handle_t, Handle};
@@ -28,6 +29,13 @@ pub fn spawn(
             ));
         }
 
+        if self.get_setsid() {
+            return Err(io::const_io_error!(
+                io::ErrorKind::Unsupported,
+                "creating a new session is not supported on Fuchsia",
+            ));
+        }
+
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
         let process_handle = unsafe { self.do_exec(theirs, envp.as_ref())? };
@@ -165,6 +173,25 @@ pub fn kill(&mut self) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn send_signal(&mut self, signal: c_int) -> io::Result<()> {
+        // Fuchsia has no signals, but the process can still be killed.
+        if signal == libc::SIGKILL {
+            self.kill()
+        } else {
+            Err(io::const_io_error!(
+                io::ErrorKind::Unsupported,
+                "sending signals is not supported on Fuchsia",
+            ))
+        }
+    }
+
+    pub fn send_signal_to_group(&mut self, _signal: c_int) -> io::Result<()> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "process groups are not supported on Fuchsia",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::process::zircon::*;
 
@@ -233,6 +260,26 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        use crate::sys::process::zircon::*;
+
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let timeout = zx_duration_t::try_from(timeout.as_nanos()).unwrap_or(zx_duration_t::MAX);
+        let status = unsafe {
+            zx_object_wait_one(
+                self.handle.raw(),
+                ZX_TASK_TERMINATED,
+                zx_deadline_after(timeout),
+                ptr::null_mut(),
+            )
+        };
+        if status == ERR_TIMED_OUT {
+            return Ok(None);
+        }
+        zx_cvt(status)?;
+        self.try_wait()
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
@@ -5,6 +5,7 @@
 use crate::sys;
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
+use crate::time::Instant;
 
 #[cfg(target_os = "linux")]
 use crate::os::linux::process::PidFd;
@@ -75,7 +76,8 @@ pub fn spawn(
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
-        if let Some(ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+        if let Some(mut ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+            ret.pgroup = self.child_pgroup(ret.pid);
             return Ok((ret, ours));
         }
 
@@ -96,6 +98,8 @@ pub fn spawn(
         // The child calls `mem::forget` to leak the lock, which is crucial because
         // releasing a lock is not async-signal-safe.
         let env_lock = sys::os::env_read_lock();
+        #[cfg(target_os = "linux")]
+        self.set_parent_pid(unsafe { libc::getpid() });
         let pid = unsafe { self.do_fork()? };
 
         if pid == 0 {
@@ -137,6 +141,7 @@ pub fn spawn(
 
         // Safety: We obtained the pidfd (on Linux) using SOCK_SEQPACKET, so it's valid.
         let mut p = unsafe { Process::new(pid, pidfd) };
+        p.pgroup = self.child_pgroup(pid);
         let mut bytes = [0; 8];
 
         // loop to handle EINTR
@@ -174,6 +179,16 @@ pub fn output(&mut self) -> io::Result<(ExitStatus, Vec<u8>, Vec<u8>)> {
         crate::sys_common::process::wait_with_output(proc, pipes)
     }
 
+    /// Returns the process group that the child with the given process ID was
+    /// placed in, unless it inherited the parent's.
+    fn child_pgroup(&self, pid: pid_t) -> Option<pid_t> {
+        match self.get_pgroup() {
+            _ if self.get_setsid() => Some(pid),
+            Some(0) => Some(pid),
+            pgroup => pgroup,
+        }
+    }
+
     // WatchOS and TVOS headers mark the `fork`/`exec*` functions with
     // `__WATCHOS_PROHIBITED __TVOS_PROHIBITED`, and indicate that the
     // `posix_spawn*` functions should be used instead. It isn't entirely clear
@@ -250,6 +265,8 @@ pub fn exec(&mut self, default: Stdio) -> io::Error {
                     // environment lock before we try to exec.
                     let _lock = sys::os::env_read_lock();
 
+                    #[cfg(target_os = "linux")]
+                    self.set_parent_pid(libc::getppid());
                     let Err(e) = self.do_exec(theirs, envp.as_ref());
                     e
                 }
@@ -343,10 +360,25 @@ unsafe fn do_exec(
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
 
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        }
         if let Some(pgroup) = self.get_pgroup() {
             cvt(libc::setpgid(0, pgroup))?;
         }
 
+        // This has to come after changing credentials, which resets the
+        // parent death signal.
+        #[cfg(target_os = "linux")]
+        if let Some(signal) = self.get_parent_death_signal() {
+            cvt(libc::prctl(libc::PR_SET_PDEATHSIG, signal as libc::c_ulong, 0, 0, 0))?;
+            // If the parent already exited, the signal will never be sent, so
+            // behave as if it had been.
+            if libc::getppid() != self.get_parent_pid() {
+                cvt(libc::raise(signal))?;
+            }
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -458,9 +490,14 @@ fn posix_spawn(
             || !self.get_closures().is_empty()
             || self.get_groups().is_some()
             || self.get_create_pidfd()
+            || self.get_setsid()
         {
             return Ok(None);
         }
+        #[cfg(target_os = "linux")]
+        if self.get_parent_death_signal().is_some() {
+            return Ok(None);
+        }
 
         // Only glibc 2.24+ posix_spawn() supports returning ENOENT directly.
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
@@ -778,6 +815,8 @@ union Cmsg {
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    // The process group the child was spawned in, if it isn't the parent's.
+    pgroup: Option<pid_t>,
     // On Linux, stores the pidfd created for this child.
     // This is None if the user did not request pidfd creation,
     // or if the pidfd could not be created for some reason
@@ -793,12 +832,12 @@ unsafe fn new(pid: pid_t, pidfd: pid_t) -> Self {
         use crate::sys_common::FromInner;
         // Safety: If `pidfd` is nonnegative, we assume it's valid and otherwise unowned.
         let pidfd = (pidfd >= 0).then(|| PidFd::from_inner(sys::fd::FileDesc::from_raw_fd(pidfd)));
-        Process { pid, status: None, pidfd }
+        Process { pid, status: None, pgroup: None, pidfd }
     }
 
     #[cfg(not(target_os = "linux"))]
     unsafe fn new(pid: pid_t, _pidfd: pid_t) -> Self {
-        Process { pid, status: None }
+        Process { pid, status: None, pgroup: None }
     }
 
     pub fn id(&self) -> u32 {
@@ -806,6 +845,10 @@ pub fn id(&self) -> u32 {
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&mut self, signal: c_int) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
         // random processes, so return Ok because the process has exited already.
@@ -819,14 +862,34 @@ pub fn kill(&mut self) -> io::Result<()> {
                 libc::syscall(
                     libc::SYS_pidfd_send_signal,
                     pid_fd.as_raw_fd(),
-                    libc::SIGKILL,
+                    signal,
                     crate::ptr::null::<()>(),
                     0,
                 )
             })
             .map(drop);
         }
-        cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
+        cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
+    }
+
+    pub fn send_signal_to_group(&mut self, signal: c_int) -> io::Result<()> {
+        let Some(pgroup) = self.pgroup else {
+            return Err(io::const_io_error!(
+                ErrorKind::InvalidInput,
+                "the child was spawned in the process group of its parent",
+            ));
+        };
+        // Once the child was reaped and the rest of the group has exited, the
+        // process group ID can be reused, like the child's pid, so do nothing
+        // for the same reason as `send_signal`.
+        if self.status.is_some() {
+            return Ok(());
+        }
+        match cvt(unsafe { libc::killpg(pgroup, signal) }) {
+            // Every process in the group has exited already.
+            Err(err) if err.raw_os_error() == Some(libc::ESRCH) => Ok(()),
+            result => result.map(drop),
+        }
     }
 
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
@@ -883,6 +946,124 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        {
+            use crate::os::fd::{FromRawFd, OwnedFd};
+
+            // A pidfd becomes readable once the child exits. If none was
+            // requested, open one just for this wait: the child hasn't been
+            // reaped yet, so its pid can't have been reused.
+            let opened;
+            let pidfd = match self.pidfd.as_ref() {
+                Some(pidfd) => Some(pidfd.as_raw_fd()),
+                None => {
+                    opened = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, self.pid, 0) })
+                        .ok()
+                        .map(|fd| unsafe { OwnedFd::from_raw_fd(fd as c_int) });
+                    opened.as_ref().map(|fd| fd.as_raw_fd())
+                }
+            };
+            if let Some(pidfd) = pidfd {
+                loop {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    // Round up so that `poll` doesn't return just before the deadline.
+                    let timeout_ms =
+                        timeout.as_nanos().div_ceil(1_000_000).min(c_int::MAX as u128) as c_int;
+                    let mut pollfd = libc::pollfd { fd: pidfd, events: libc::POLLIN, revents: 0 };
+                    match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout_ms) }) {
+                        Ok(0) if timeout_ms > 0 => {}
+                        Ok(_) => return self.try_wait(),
+                        Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+        }
+        #[cfg(any(
+            target_vendor = "apple",
+            target_os = "freebsd",
+            target_os = "dragonfly",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        ))]
+        {
+            use crate::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+            // A kqueue reports `NOTE_EXIT` once the child exits. Registering
+            // fails if the child is already gone, in which case the polling
+            // below reaps it right away.
+            let registered = cvt(unsafe { libc::kqueue() }).ok().and_then(|kq| {
+                let kq = unsafe { OwnedFd::from_raw_fd(kq) };
+                let mut change: libc::kevent = unsafe { mem::zeroed() };
+                change.ident = self.pid as _;
+                change.filter = libc::EVFILT_PROC as _;
+                change.flags = (libc::EV_ADD | libc::EV_ONESHOT) as _;
+                change.fflags = libc::NOTE_EXIT as _;
+                cvt(unsafe {
+                    libc::kevent(
+                        kq.as_raw_fd(),
+                        &change,
+                        1,
+                        crate::ptr::null_mut(),
+                        0,
+                        crate::ptr::null(),
+                    )
+                })
+                .ok()
+                .map(|_| kq)
+            });
+            if let Some(kq) = registered {
+                loop {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    let timespec = libc::timespec {
+                        tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+                        tv_nsec: timeout.subsec_nanos() as _,
+                    };
+                    let mut event: libc::kevent = unsafe { mem::zeroed() };
+                    match cvt(unsafe {
+                        libc::kevent(
+                            kq.as_raw_fd(),
+                            crate::ptr::null(),
+                            0,
+                            &mut event,
+                            1,
+                            &timespec,
+                        )
+                    }) {
+                        Ok(0) if !timeout.is_zero() => {}
+                        Ok(0) => return self.try_wait(),
+                        // `NOTE_EXIT` may be delivered just before the child
+                        // becomes waitable, so block until it is.
+                        Ok(_) => return self.wait().map(Some),
+                        Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+        }
+        // Otherwise there is no way to be notified of the child's exit without
+        // handling `SIGCHLD`, whose disposition belongs to the application, so
+        // as a last resort poll for it with an exponential backoff. This is
+        // only reached on platforms with neither pidfds nor kqueues, or when
+        // the kernel refuses to create either.
+        let mut delay = crate::time::Duration::from_millis(1);
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            crate::thread::sleep(delay.min(deadline - now));
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            delay = (delay * 2).min(crate::time::Duration::from_millis(50));
+        }
+    }
 }
 
 /// Unix exit statuses
@@ -114,3 +114,78 @@ fn test_command_pidfd() {
         .pidfd()
         .expect_err("pidfd should not have been created");
 }
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_command_signal() {
+    use crate::io::ErrorKind;
+    use crate::os::unix::process::ChildExt;
+    use crate::process::Stdio;
+
+    let mut child = Command::new("cat").stdin(Stdio::piped()).spawn().unwrap();
+    // The child shares our process group, so signalling the group is refused.
+    assert_eq!(
+        child.signal_process_group(libc::SIGTERM).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    child.signal(libc::SIGTERM).unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+
+    // Signalling a child that was already waited on does nothing.
+    child.signal(libc::SIGTERM).unwrap();
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_command_kill_process_group() {
+    use crate::os::unix::process::ChildExt;
+    use crate::process::Stdio;
+
+    // The shell spawns a grandchild in the same process group, which would be
+    // left behind by killing only the shell.
+    let mut child = Command::new("/bin/sh")
+        .arg("-c")
+        .arg("sleep 1000 & wait")
+        .stdout(Stdio::null())
+        .process_group(0)
+        .spawn()
+        .unwrap();
+    child.kill_process_group().unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+
+    // Signalling the group after the child was waited on does nothing, as its
+    // process group ID may have been reused.
+    child.kill_process_group().unwrap();
+}
+
+#[test]
+#[cfg_attr(target_os = "emscripten", ignore)]
+fn test_command_setsid() {
+    use crate::process::Stdio;
+
+    let mut child = Command::new("cat").stdin(Stdio::piped()).setsid(true).spawn().unwrap();
+    let pid = child.id() as libc::pid_t;
+    assert_eq!(unsafe { libc::getsid(pid) }, pid);
+    assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+    assert_ne!(unsafe { libc::getsid(0) }, pid);
+    drop(child.stdin.take());
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_command_parent_death_signal() {
+    use crate::os::linux::process::CommandExt;
+    use crate::thread;
+
+    // The signal is sent once the spawning thread exits.
+    let mut child = thread::spawn(|| {
+        Command::new("sleep").arg("1000").parent_death_signal(libc::SIGKILL).spawn().unwrap()
+    })
+    .join()
+    .unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+}
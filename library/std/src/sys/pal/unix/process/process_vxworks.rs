@@ -5,6 +5,7 @@
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
 use crate::sys_common::thread;
+use crate::time::{Duration, Instant};
 use libc::RTP_ID;
 use libc::{self, c_char, c_int};
 
@@ -27,6 +28,12 @@ pub fn spawn(
                 "nul byte found in provided data",
             ));
         }
+        if self.get_setsid() {
+            return Err(io::const_io_error!(
+                ErrorKind::Unsupported,
+                "creating a new session is not supported on VxWorks",
+            ));
+        }
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
@@ -141,16 +148,27 @@ pub fn id(&self) -> u32 {
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&mut self, signal: c_int) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
         // random processes, so return Ok because the process has exited already.
         if self.status.is_some() {
             Ok(())
         } else {
-            cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
+            cvt(unsafe { libc::kill(self.pid, signal) }).map(drop)
         }
     }
 
+    pub fn send_signal_to_group(&mut self, _signal: c_int) -> io::Result<()> {
+        Err(io::const_io_error!(
+            ErrorKind::Unsupported,
+            "process groups are not supported on VxWorks",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::cvt_r;
         if let Some(status) = self.status {
@@ -175,6 +193,23 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        // Poll with an exponential backoff, as being notified of the child's
+        // exit would mean handling `SIGCHLD`.
+        let mut delay = Duration::from_millis(1);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            crate::thread::sleep(delay.min(deadline - now));
+            delay = (delay * 2).min(Duration::from_millis(50));
+        }
+    }
 }
 
 /// Unix exit statuses
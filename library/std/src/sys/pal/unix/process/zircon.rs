@@ -16,6 +16,8 @@
 pub type zx_time_t = i64;
 pub const ZX_TIME_INFINITE: zx_time_t = i64::MAX;
 
+pub type zx_duration_t = i64;
+
 pub type zx_signals_t = u32;
 
 pub const ZX_OBJECT_SIGNAL_3: zx_signals_t = 1 << 3;
@@ -88,6 +90,8 @@ pub fn zx_handle_duplicate(
         out: *const zx_handle_t,
     ) -> zx_handle_t;
 
+    pub fn zx_deadline_after(nanoseconds: zx_duration_t) -> zx_time_t;
+
     pub fn zx_object_wait_one(
         handle: zx_handle_t,
         signals: zx_signals_t,
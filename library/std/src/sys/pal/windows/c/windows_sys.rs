@@ -386,6 +386,17 @@ pub fn InitializeProcThreadAttributeList(
     pub fn LocalFree(hmem: HLOCAL) -> HLOCAL;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn LockFileEx(
+        hfile: HANDLE,
+        dwflags: LOCK_FILE_FLAGS,
+        dwreserved: u32,
+        nnumberofbytestolocklow: u32,
+        nnumberofbytestolockhigh: u32,
+        lpoverlapped: *mut OVERLAPPED,
+    ) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn MoveFileExW(
         lpexistingfilename: PCWSTR,
@@ -569,6 +580,16 @@ pub fn SleepConditionVariableSRW(
     pub fn TryAcquireSRWLockShared(srwlock: *mut SRWLOCK) -> BOOLEAN;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn UnlockFile(
+        hfile: HANDLE,
+        dwfileoffsetlow: u32,
+        dwfileoffsethigh: u32,
+        nnumberofbytestounlocklow: u32,
+        nnumberofbytestounlockhigh: u32,
+    ) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn UpdateProcThreadAttribute(
         lpattributelist: LPPROC_THREAD_ATTRIBUTE_LIST,
@@ -707,6 +728,20 @@ pub fn WSADuplicateSocketW(
     pub fn WSAGetLastError() -> WSA_ERROR;
 }
 #[link(name = "ws2_32")]
+extern "system" {
+    pub fn WSAIoctl(
+        s: SOCKET,
+        dwiocontrolcode: u32,
+        lpvinbuffer: *const core::ffi::c_void,
+        cbinbuffer: u32,
+        lpvoutbuffer: *mut core::ffi::c_void,
+        cboutbuffer: u32,
+        lpcbbytesreturned: *mut u32,
+        lpoverlapped: *mut OVERLAPPED,
+        lpcompletionroutine: LPWSAOVERLAPPED_COMPLETION_ROUTINE,
+    ) -> i32;
+}
+#[link(name = "ws2_32")]
 extern "system" {
     pub fn WSARecv(
         s: SOCKET,
@@ -3640,6 +3675,9 @@ fn clone(&self) -> Self {
         *self
     }
 }
+pub const LOCKFILE_EXCLUSIVE_LOCK: LOCK_FILE_FLAGS = 2u32;
+pub const LOCKFILE_FAIL_IMMEDIATELY: LOCK_FILE_FLAGS = 1u32;
+pub type LOCK_FILE_FLAGS = u32;
 pub type LPOVERLAPPED_COMPLETION_ROUTINE = Option<
     unsafe extern "system" fn(
         dwerrorcode: u32,
@@ -3842,6 +3880,7 @@ fn clone(&self) -> Self {
 pub const SECURITY_VALID_SQOS_FLAGS: FILE_FLAGS_AND_ATTRIBUTES = 2031616u32;
 pub type SEND_RECV_FLAGS = i32;
 pub type SET_FILE_POINTER_MOVE_METHOD = u32;
+pub const SIO_KEEPALIVE_VALS: u32 = 2550136836u32;
 #[repr(C)]
 pub struct SOCKADDR {
     pub sa_family: ADDRESS_FAMILY,
@@ -3874,8 +3913,12 @@ fn clone(&self) -> Self {
 pub const SOL_SOCKET: i32 = 65535i32;
 pub const SO_BROADCAST: i32 = 32i32;
 pub const SO_ERROR: i32 = 4103i32;
+pub const SO_KEEPALIVE: i32 = 8i32;
 pub const SO_LINGER: i32 = 128i32;
+pub const SO_RCVBUF: i32 = 4098i32;
 pub const SO_RCVTIMEO: i32 = 4102i32;
+pub const SO_REUSEADDR: i32 = 4i32;
+pub const SO_SNDBUF: i32 = 4097i32;
 pub const SO_SNDTIMEO: i32 = 4101i32;
 pub const SPECIFIC_RIGHTS_ALL: FILE_ACCESS_RIGHTS = 65535u32;
 #[repr(C)]
@@ -4004,6 +4047,7 @@ fn clone(&self) -> Self {
         *self
     }
 }
+pub const TCP_KEEPCNT: i32 = 16i32;
 pub const TCP_NODELAY: i32 = 1i32;
 pub const THREAD_CREATE_RUN_IMMEDIATELY: THREAD_CREATION_FLAGS = 0u32;
 pub const THREAD_CREATE_SUSPENDED: THREAD_CREATION_FLAGS = 4u32;
@@ -4341,4 +4385,16 @@ fn clone(&self) -> Self {
         *self
     }
 }
+#[repr(C)]
+pub struct tcp_keepalive {
+    pub onoff: u32,
+    pub keepalivetime: u32,
+    pub keepaliveinterval: u32,
+}
+impl Copy for tcp_keepalive {}
+impl Clone for tcp_keepalive {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 // ignore-tidy-filelength
@@ -354,6 +354,70 @@ pub fn truncate(&self, size: u64) -> io::Result<()> {
         api::set_file_information_by_handle(self.handle.as_raw_handle(), &info).io_result()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_file(c::LOCKFILE_EXCLUSIVE_LOCK).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_file(0).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.lock_file(c::LOCKFILE_EXCLUSIVE_LOCK | c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.lock_file(c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        // A handle may hold both a shared and an exclusive lock on the same
+        // range, and each `UnlockFile` call only releases one of them. The
+        // handle may also hold no lock at all, which isn't an error.
+        for _ in 0..2 {
+            let result = cvt(unsafe {
+                c::UnlockFile(self.handle.as_raw_handle(), 0, 0, u32::MAX, u32::MAX)
+            });
+            match result {
+                Ok(_) => {}
+                Err(err) if err.raw_os_error() == Some(c::ERROR_NOT_LOCKED as i32) => return Ok(()),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Locks the largest possible byte range, which covers the whole file.
+    ///
+    /// Returns `false` if `LOCKFILE_FAIL_IMMEDIATELY` is set and a
+    /// conflicting lock is held elsewhere.
+    fn lock_file(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<bool> {
+        let handle = self.handle.as_raw_handle();
+        let mut overlapped: c::OVERLAPPED = unsafe { mem::zeroed() };
+        let mut result =
+            cvt(unsafe { c::LockFileEx(handle, flags, 0, u32::MAX, u32::MAX, &mut overlapped) });
+        // Handles opened with `FILE_FLAG_OVERLAPPED` complete the request
+        // asynchronously, so wait for it to finish.
+        if let Err(err) = &result
+            && err.raw_os_error() == Some(c::ERROR_IO_PENDING as i32)
+        {
+            let mut transferred = 0;
+            result = cvt(unsafe {
+                c::GetOverlappedResult(handle, &overlapped, &mut transferred, c::TRUE)
+            });
+        }
+        match result {
+            Ok(_) => Ok(true),
+            Err(err)
+                if flags & c::LOCKFILE_FAIL_IMMEDIATELY != 0
+                    && err.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     #[cfg(not(target_vendor = "uwp"))]
     pub fn file_attr(&self) -> io::Result<FileAttr> {
         unsafe {
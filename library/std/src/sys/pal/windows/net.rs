@@ -422,6 +422,49 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_tcp_keepalive(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+        retries: Option<u32>,
+    ) -> io::Result<()> {
+        fn millis(dur: Duration) -> u32 {
+            cmp::min(dur.as_millis(), u32::MAX as u128) as u32
+        }
+
+        net::setsockopt(self, c::SOL_SOCKET, c::SO_KEEPALIVE, 1 as c::BOOL)?;
+        if time.is_some() || interval.is_some() {
+            // `SIO_KEEPALIVE_VALS` always sets both durations, so fall back to
+            // the Windows defaults for the one that wasn't given.
+            let vals = c::tcp_keepalive {
+                onoff: 1,
+                keepalivetime: time.map_or(7_200_000, millis),
+                keepaliveinterval: interval.map_or(1_000, millis),
+            };
+            let mut returned = 0;
+            let result = unsafe {
+                c::WSAIoctl(
+                    self.as_raw(),
+                    c::SIO_KEEPALIVE_VALS,
+                    &vals as *const _ as *const _,
+                    mem::size_of_val(&vals) as u32,
+                    ptr::null_mut(),
+                    0,
+                    &mut returned,
+                    ptr::null_mut(),
+                    None,
+                )
+            };
+            if result == c::SOCKET_ERROR {
+                return Err(last_error());
+            }
+        }
+        if let Some(retries) = retries {
+            net::setsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPCNT, retries)?;
+        }
+        Ok(())
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = net::getsockopt(self, c::SOL_SOCKET, c::SO_ERROR)?;
         if raw == 0 { Ok(None) } else { Ok(Some(io::Error::from_raw_os_error(raw as i32))) }
@@ -20,14 +20,15 @@
 use crate::sync::Mutex;
 use crate::sys::args::{self, Arg};
 use crate::sys::c::{self, NonZeroDWORD, EXIT_FAILURE, EXIT_SUCCESS};
-use crate::sys::cvt;
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::path;
 use crate::sys::pipe::{self, AnonPipe};
 use crate::sys::stdio;
+use crate::sys::{cvt, dur2timeout};
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
 use crate::sys_common::IntoInner;
+use crate::time::Instant;
 
 use core::ffi::c_void;
 
@@ -691,6 +692,16 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
     }
 
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        // Deadlines more than `u32::MAX` milliseconds away wait indefinitely.
+        let timeout = dur2timeout(deadline.saturating_duration_since(Instant::now()));
+        match unsafe { c::WaitForSingleObject(self.handle.as_raw_handle(), timeout) } {
+            c::WAIT_OBJECT_0 => self.try_wait(),
+            c::WAIT_TIMEOUT => Ok(None),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+
     pub fn handle(&self) -> &Handle {
         &self.handle
     }
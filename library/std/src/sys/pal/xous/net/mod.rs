@@ -9,6 +9,9 @@
 mod udp;
 pub use udp::*;
 
+mod unbound;
+pub use unbound::*;
+
 // this structure needs to be synchronized with what's in net/src/api.rs
 #[repr(C)]
 #[derive(Debug)]
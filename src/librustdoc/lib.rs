@@ -445,7 +445,7 @@ fn opts() -> Vec<RustcOptGroup> {
                 "",
                 "error-format",
                 "How errors and other messages are produced",
-                "human|json|short",
+                "human|json|short|sarif",
             )
         }),
         stable("diagnostic-width", |o| {
@@ -0,0 +1,8 @@
+pub mod linted {
+    pub fn foo() {}
+}
+
+#[allow(company::no_foo)]
+pub mod allowed {
+    pub fn foo() {}
+}
@@ -0,0 +1,37 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_driver;
+extern crate rustc_lint;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::ast;
+use rustc_lint::{EarlyContext, EarlyLintPass, LintContext, LintStore};
+use rustc_session::{declare_lint_pass, declare_tool_lint, Session};
+use rustc_span::Symbol;
+
+declare_tool_lint! {
+    pub company::NO_FOO,
+    Warn,
+    "functions named `foo`"
+}
+
+declare_lint_pass!(NoFoo => [NO_FOO]);
+
+impl EarlyLintPass for NoFoo {
+    fn check_item(&mut self, cx: &EarlyContext<'_>, item: &ast::Item) {
+        if let ast::ItemKind::Fn(..) = item.kind {
+            if item.ident.name.as_str() == "foo" {
+                cx.span_lint(NO_FOO, item.span, "function named `foo`", |_| {});
+            }
+        }
+    }
+}
+
+#[no_mangle]
+pub fn __rustc_lint_plugin(_sess: &Session, lint_store: &mut LintStore) {
+    lint_store.register_lint_tool(Symbol::intern("company"));
+    lint_store.register_lints(&[NO_FOO]);
+    lint_store.register_early_pass(|| Box::new(NoFoo));
+}
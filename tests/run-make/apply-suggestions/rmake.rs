@@ -0,0 +1,73 @@
+//! This checks that `-Zapply-suggestions` applies the machine-applicable suggestions of all the
+//! source files of a crate, and writes the patched files or their diff without touching the
+//! original sources.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+const LIB: &str = "mod util;\npub fn f() -> u32 {\n    let mut a = 1;\n    a + util::g()\n}\n";
+const UTIL: &str = "pub fn g() -> u32 {\n    let mut b = (2);\n    b\n}\n";
+
+fn main() {
+    let src = tmp_dir().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("lib.rs"), LIB).unwrap();
+    fs::write(src.join("util.rs"), UTIL).unwrap();
+
+    let output = rustc()
+        .current_dir(tmp_dir())
+        .input("src/lib.rs")
+        .crate_type("lib")
+        .arg("-Zapply-suggestions=fixed")
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    // The warnings are still emitted.
+    assert!(stderr.contains("warning: variable does not need to be mutable"), "{stderr}");
+    assert!(
+        stderr.contains("applied 3 machine-applicable suggestions to 2 files, written to `fixed`"),
+        "{stderr}"
+    );
+    // The patched sources compile.
+    assert!(!stderr.contains("don't compile"), "{stderr}");
+
+    let fixed = tmp_dir().join("fixed/src");
+    assert_eq!(
+        fs::read_to_string(fixed.join("lib.rs")).unwrap(),
+        "mod util;\npub fn f() -> u32 {\n    let a = 1;\n    a + util::g()\n}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(fixed.join("util.rs")).unwrap(),
+        "pub fn g() -> u32 {\n    let b = 2;\n    b\n}\n"
+    );
+    assert_eq!(fs::read_to_string(src.join("lib.rs")).unwrap(), LIB);
+    assert_eq!(fs::read_to_string(src.join("util.rs")).unwrap(), UTIL);
+
+    rustc()
+        .current_dir(tmp_dir())
+        .input("src/lib.rs")
+        .crate_type("lib")
+        .arg("-Zapply-suggestions=diff")
+        .arg("-Zapply-suggestions-diff")
+        .run();
+    let diff = fs::read_to_string(tmp_dir().join("diff/suggestions.diff")).unwrap();
+    assert_eq!(
+        diff,
+        "--- a/src/lib.rs\n\
+        +++ b/src/lib.rs\n\
+        @@ -1,5 +1,5 @@\n \
+        mod util;\n \
+        pub fn f() -> u32 {\n\
+        -    let mut a = 1;\n\
+        +    let a = 1;\n \
+        \x20   a + util::g()\n \
+        }\n\
+        --- a/src/util.rs\n\
+        +++ b/src/util.rs\n\
+        @@ -1,4 +1,4 @@\n \
+        pub fn g() -> u32 {\n\
+        -    let mut b = (2);\n\
+        +    let b = 2;\n \
+        \x20   b\n \
+        }\n"
+    );
+}
@@ -0,0 +1,58 @@
+//! This checks that `-Zdump-mono-bloat` groups the instances of a generic function, and records
+//! the chain of items that caused each instantiation, including for items in a cycle.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    let src = tmp_dir().join("lib.rs");
+    fs::write(
+        &src,
+        r#"
+fn generic<T: Default>() -> T {
+    T::default()
+}
+
+fn middle() -> u32 {
+    generic()
+}
+
+pub fn first() -> (u8, u16) {
+    (generic(), generic())
+}
+
+pub fn second() -> (u32, u64) {
+    (middle(), generic())
+}
+
+pub fn ping(n: u32) -> u32 {
+    if n == 0 { 0 } else { pong(n - 1) }
+}
+
+fn pong(n: u32) -> u32 {
+    ping(n)
+}
+"#,
+    )
+    .unwrap();
+
+    rustc()
+        .input(&src)
+        .crate_type("lib")
+        .arg(format!("-Zdump-mono-bloat={}", tmp_dir().join("report").display()))
+        .run();
+
+    let report = fs::read_to_string(tmp_dir().join("report").join("lib.mono_bloat.json")).unwrap();
+    assert!(report.contains(r#""name": "generic","#), "{report}");
+    assert!(report.contains(r#""instantiations": 4,"#), "{report}");
+    assert!(report.contains(r#""name": "generic::<u32>","#), "{report}");
+    let chain = r#""used_by": [
+            "second",
+            "middle"
+          ]"#;
+    assert!(report.contains(chain), "{report}");
+    let chain = r#""used_by": [
+            "ping"
+          ]"#;
+    assert!(report.contains(chain), "{report}");
+}
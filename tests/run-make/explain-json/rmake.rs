@@ -0,0 +1,35 @@
+//! This checks that `--explain` with `--explain-format=json` prints a structured explanation,
+//! and that `--json=explanations` includes it in JSON diagnostics.
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    let output = rustc()
+        .arg("--explain")
+        .arg("E0308")
+        .arg("--explain-format=json")
+        .arg("-Zunstable-options")
+        .run();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for expected in [
+        r#""code": "E0308""#,
+        r#""title": "Expected type did not match the received type.""#,
+        r#""summary": "This error occurs when an expression was used"#,
+        r#""kind": "erroneous""#,
+        r#""error_code": "E0308""#,
+    ] {
+        assert!(stdout.contains(expected), "output doesn't contain {expected}: {stdout}");
+    }
+
+    let src = tmp_dir().join("lib.rs");
+    std::fs::write(&src, "pub fn f() -> u8 { 1u16 }\n").unwrap();
+    let output = rustc()
+        .input(&src)
+        .crate_type("lib")
+        .error_format("json")
+        .arg("--json=explanations")
+        .arg("-Zunstable-options")
+        .run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(r#""structured_explanation":{"code":"E0308""#), "{stderr}");
+}
@@ -0,0 +1,35 @@
+//! This checks that `-Zincremental-report` writes a report of the root causes of the queries
+//! re-executed by an incremental session, pointing to the edited source file.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+use std::path::Path;
+
+fn compile(src: &Path) {
+    rustc()
+        .input(src)
+        .crate_type("lib")
+        .arg(format!("-Cincremental={}", tmp_dir().join("incr").display()))
+        .arg(format!("-Zincremental-report={}", tmp_dir().join("report").display()))
+        .run();
+}
+
+fn main() {
+    let src = tmp_dir().join("lib.rs");
+    let report = tmp_dir().join("report").join("lib.incr_report.json");
+
+    fs::write(&src, "pub fn a() -> u32 {\n    1\n}\n\npub fn b() -> u32 {\n    2\n}\n").unwrap();
+    compile(&src);
+    // Without a previous session, nothing is re-executed.
+    let json = fs::read_to_string(&report).unwrap();
+    assert!(json.contains("\"reexecuted_queries\": 0"), "{json}");
+    assert!(json.contains("\"root_causes\": []"), "{json}");
+
+    fs::write(&src, "pub fn a() -> u32 {\n    3\n}\n\npub fn b() -> u32 {\n    2\n}\n").unwrap();
+    compile(&src);
+    let json = fs::read_to_string(&report).unwrap();
+    assert!(json.contains("\"kind\": \"hir\""), "{json}");
+    assert!(json.contains("lib.rs"), "{json}");
+    assert!(json.contains("(a)"), "{json}");
+    assert!(!json.contains("(b)\""), "{json}");
+}
@@ -0,0 +1,61 @@
+//! This checks that `-Zinspect-incr-cache` prints the contents of an incremental compilation
+//! session directory, and that `-Zinspect-incr-cache-baseline` reports the dep nodes that
+//! changed since an older session, along with the source code change that caused it.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Returns the latest session directory of the only crate compiled with
+/// `-Cincremental=<incr_dir>`.
+fn session_dir(incr_dir: &Path) -> PathBuf {
+    let crate_dir = fs::read_dir(incr_dir).unwrap().next().unwrap().unwrap().path();
+    fs::read_dir(crate_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .max_by_key(|path| fs::metadata(path).unwrap().modified().unwrap())
+        .unwrap()
+}
+
+fn compile(src: &Path, incr_dir: &Path) {
+    rustc().input(src).crate_type("lib").arg(format!("-Cincremental={}", incr_dir.display())).run();
+}
+
+fn main() {
+    let src = tmp_dir().join("lib.rs");
+    let incr_dir = tmp_dir().join("incr");
+    let baseline_dir = tmp_dir().join("baseline");
+
+    fs::write(&src, "pub fn a() -> u32 {\n    1\n}\n\npub fn b() -> u32 {\n    2\n}\n").unwrap();
+    compile(&src, &incr_dir);
+
+    // The old session directory is garbage collected by the next session, so keep a copy.
+    fs::create_dir(&baseline_dir).unwrap();
+    for entry in fs::read_dir(session_dir(&incr_dir)).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, baseline_dir.join(path.file_name().unwrap())).unwrap();
+    }
+
+    let output = rustc().arg(format!("-Zinspect-incr-cache={}", baseline_dir.display())).run();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("nodes by kind:"), "{stdout}");
+    assert!(stdout.contains("work products: "), "{stdout}");
+
+    fs::write(&src, "pub fn a() -> u32 {\n    3\n}\n\npub fn b() -> u32 {\n    2\n}\n").unwrap();
+    compile(&src, &incr_dir);
+
+    let output = rustc()
+        .arg(format!("-Zinspect-incr-cache={}", session_dir(&incr_dir).display()))
+        .arg(format!("-Zinspect-incr-cache-baseline={}", baseline_dir.display()))
+        .run();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("changed nodes by kind:"), "{stdout}");
+    assert!(stdout.contains("(source code)"), "{stdout}");
+    assert!(!stdout.contains("the command-line arguments changed"), "{stdout}");
+
+    // A session directory without a dep graph is rejected.
+    let output = rustc().arg(format!("-Zinspect-incr-cache={}", tmp_dir().display())).run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("`dep-graph.bin` is missing"), "{stderr}");
+}
@@ -0,0 +1,37 @@
+//! This checks that `-Zitem-time-report` writes a report of the time spent on each item, which
+//! includes the number of instantiations of generic functions, and of the time spent optimizing
+//! each codegen unit.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    let src = tmp_dir().join("lib.rs");
+    fs::write(
+        &src,
+        r#"
+pub fn generic<T: Default>() -> T {
+    T::default()
+}
+
+pub fn caller() -> (u8, u16, u32, u64, String) {
+    (generic(), generic(), generic(), generic(), generic())
+}
+"#,
+    )
+    .unwrap();
+
+    rustc()
+        .input(&src)
+        .crate_type("lib")
+        .arg(format!("-Zitem-time-report={}", tmp_dir().join("report").display()))
+        .run();
+
+    let report = fs::read_to_string(tmp_dir().join("report").join("lib.item_times.json")).unwrap();
+    assert!(report.contains(r#""name": "generic","#), "{report}");
+    assert!(report.contains(r#""instantiations": 5,"#), "{report}");
+    assert!(report.contains(r#""name": "typeck","#), "{report}");
+    assert!(report.contains(r#""name": "codegen","#), "{report}");
+    assert!(report.contains("lib.rs:2:1"), "{report}");
+    assert!(report.contains(r#""optimization_time": "#), "{report}");
+}
@@ -0,0 +1,52 @@
+//! This checks that `-Zwrite-lint-baseline` records the existing lint warnings of a crate
+//! and that `-Zlint-baseline` suppresses them on later builds, while still reporting new ones.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    let src = tmp_dir().join("lib.rs");
+    let baseline = tmp_dir().join("baseline.json");
+
+    fs::write(&src, "pub fn f() {\n    let x = 1;\n}\n").unwrap();
+    let output = rustc()
+        .input(&src)
+        .crate_type("lib")
+        .arg(format!("-Zwrite-lint-baseline={}", baseline.display()))
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("unused variable: `x`"), "{stderr}");
+    let contents = fs::read_to_string(&baseline).unwrap();
+    assert!(contents.contains(r#""lint": "unused_variables""#), "{contents}");
+
+    // The existing warning is suppressed, even though it moved, but the new one isn't.
+    fs::write(&src, "pub fn f() {\n    let y = 2;\n\n    let x = 1;\n}\n").unwrap();
+    let output = rustc()
+        .input(&src)
+        .crate_type("lib")
+        .arg(format!("-Zlint-baseline={}", baseline.display()))
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("unused variable: `x`"), "{stderr}");
+    assert!(stderr.contains("unused variable: `y`"), "{stderr}");
+
+    // When every warning is suppressed, no diagnostic is printed, even though the message of
+    // the warning names a type.
+    let src = tmp_dir().join("must_use.rs");
+    let baseline = tmp_dir().join("must_use.json");
+    fs::write(&src, "pub fn f() {\n    \"1\".parse::<u8>();\n}\n").unwrap();
+    let output = rustc()
+        .input(&src)
+        .crate_type("lib")
+        .arg(format!("-Zwrite-lint-baseline={}", baseline.display()))
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("unused `Result` that must be used"), "{stderr}");
+    let output = rustc()
+        .input(&src)
+        .crate_type("lib")
+        .arg(format!("-Zlint-baseline={}", baseline.display()))
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.is_empty(), "{stderr}");
+}
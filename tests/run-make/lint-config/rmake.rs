@@ -0,0 +1,66 @@
+//! This checks that `-Zlint-config` sets lint levels for the whole crate and, with path globs,
+//! for the modules in specific source files, and that the command line and attributes take
+//! precedence over it where they should.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+
+fn main() {
+    let src = tmp_dir().join("src");
+    fs::create_dir_all(src.join("generated")).unwrap();
+    fs::write(
+        src.join("lib.rs"),
+        "mod generated;\nmod handwritten;\npub fn f() {\n    let a = 1;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        src.join("generated/mod.rs"),
+        "pub fn g() {\n    let b = 2;\n}\n#[warn(unused_variables)]\npub fn h() {\n    let c = 3;\n}\n",
+    )
+    .unwrap();
+    fs::write(src.join("handwritten.rs"), "pub fn k() {\n    let d = 4;\n}\n").unwrap();
+    fs::write(
+        tmp_dir().join("lints.json"),
+        r#"{
+            "lints": { "unused_variables": "deny", "dead_code": "allow", "no_such_lint": "warn" },
+            "overrides": [
+                { "paths": ["src/generated/**"], "lints": { "unused_variables": "allow" } }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let output = rustc()
+        .current_dir(tmp_dir())
+        .input("src/lib.rs")
+        .crate_type("lib")
+        .arg("-Zlint-config=lints.json")
+        .run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    // The crate-wide level applies to every module, except for the generated ones.
+    assert!(stderr.contains("error: unused variable: `a`"), "{stderr}");
+    assert!(stderr.contains("error: unused variable: `d`"), "{stderr}");
+    assert!(
+        stderr.contains(
+            r#"requested in the lint configuration file with `"unused_variables": "deny"`"#
+        ),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("unused variable: `b`"), "{stderr}");
+    // Attributes take precedence over the configuration file.
+    assert!(stderr.contains("warning: unused variable: `c`"), "{stderr}");
+    assert!(stderr.contains("unknown lint: `no_such_lint`"), "{stderr}");
+
+    // The command line takes precedence over the crate-wide levels.
+    let output = rustc()
+        .current_dir(tmp_dir())
+        .input("src/lib.rs")
+        .crate_type("lib")
+        .arg("-Zlint-config=lints.json")
+        .arg("-Aunused_variables")
+        .run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("unused variable: `a`"), "{stderr}");
+    assert!(!stderr.contains("unused variable: `d`"), "{stderr}");
+    assert!(stderr.contains("warning: unused variable: `c`"), "{stderr}");
+}
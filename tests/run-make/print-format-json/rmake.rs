@@ -0,0 +1,55 @@
+//! This checks that `--print-format=json` wraps the output of `--print`
+//! options in a versioned JSON document.
+
+use run_make_support::{rustc, target};
+
+struct Option<'a> {
+    target: &'a str,
+    option: &'static str,
+    includes: &'static [&'static str],
+}
+
+fn main() {
+    // Printed by rustc_driver_impl
+    check(Option {
+        target: &target(),
+        option: "calling-conventions",
+        includes: &[r#""kind": "calling-conventions""#, r#""C""#],
+    });
+    check(Option { target: &target(), option: "cfg", includes: &[r#""name": "target_os""#] });
+
+    // Printed from CodegenBackend trait impl in rustc_codegen_llvm/src/lib.rs
+    check(Option {
+        target: &target(),
+        option: "relocation-models",
+        includes: &[r#""dynamic-no-pic""#],
+    });
+
+    // Printed by compiler/rustc_codegen_llvm/src/llvm_util.rs
+    check(Option {
+        target: "wasm32-unknown-unknown",
+        option: "target-features",
+        includes: &[r#""rustc": ["#, r#""llvm": ["#, r#""name": "simd128""#],
+    });
+    check(Option {
+        target: "wasm32-unknown-unknown",
+        option: "target-cpus",
+        includes: &[r#""name": "generic""#],
+    });
+}
+
+fn check(args: Option) {
+    let output = rustc()
+        .target(args.target)
+        .print(args.option)
+        .arg("--print-format=json")
+        .arg("-Zunstable-options")
+        .run();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with('{'), "output isn't a JSON object: {stdout}");
+    assert!(stdout.contains(r#""format_version": 1"#), "output isn't versioned: {stdout}");
+    for i in args.includes {
+        assert!(stdout.contains(i), "output doesn't contains: {}", i);
+    }
+}
@@ -0,0 +1,42 @@
+//! This checks that `--error-format=sarif` writes all diagnostics of a compilation as a single
+//! SARIF log, with error codes and lints as rules, and that the flag is unstable.
+
+use run_make_support::{rustc, tmp_dir};
+
+fn main() {
+    let src = tmp_dir().join("lib.rs");
+    std::fs::write(
+        &src,
+        "pub fn f() -> u8 {\n    1u16\n}\n\npub fn g() {\n    let unused = 0;\n}\n",
+    )
+    .unwrap();
+
+    let output = rustc()
+        .input(&src)
+        .crate_type("lib")
+        .error_format("sarif")
+        .arg("-Zunstable-options")
+        .run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    // Only one log is written, and the "aborting due to" summary isn't part of it.
+    assert_eq!(stderr.matches(r#""$schema""#).count(), 1, "{stderr}");
+    assert!(!stderr.contains("aborting due to"), "{stderr}");
+    for expected in [
+        r#""version": "2.1.0""#,
+        r#""name": "rustc""#,
+        r#""id": "E0308""#,
+        r#""helpUri": "https://doc.rust-lang.org/error_codes/E0308.html""#,
+        r#""id": "unused_variables""#,
+        r#""level": "error""#,
+        r#""level": "warning""#,
+        r#""startLine": 2"#,
+        r#""startLine": 6"#,
+        r#""applicability": "MachineApplicable""#,
+    ] {
+        assert!(stderr.contains(expected), "output doesn't contain {expected}: {stderr}");
+    }
+
+    let output = rustc().input(&src).crate_type("lib").error_format("sarif").run_fail();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("`--error-format=sarif` is unstable"), "{stderr}");
+}
@@ -0,0 +1,47 @@
+//! This checks that `-Zself-profile-summary` and `-Zself-profile-chrome-trace` write a summary
+//! table and a Chrome trace of the self-profile next to the raw profile data.
+
+use run_make_support::{rustc, tmp_dir};
+use std::fs;
+use std::path::PathBuf;
+
+/// Returns the only file in the profile directory with the given extension.
+fn output_file(extension: &str) -> PathBuf {
+    let mut files = fs::read_dir(tmp_dir().join("profile"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_str().unwrap().ends_with(extension));
+    let file = files.next().unwrap();
+    assert!(files.next().is_none());
+    file
+}
+
+fn main() {
+    let src = tmp_dir().join("foo.rs");
+    fs::write(&src, "pub fn foo() -> u32 {\n    1\n}\n").unwrap();
+
+    rustc()
+        .input(&src)
+        .crate_type("lib")
+        // The crate name is not known when the profiler is created, unless it's passed explicitly.
+        .arg("--crate-name=foo")
+        .arg(format!("-Zself-profile={}", tmp_dir().join("profile").display()))
+        .arg("-Zself-profile-events=default,query-cache-hit")
+        .arg("-Zself-profile-summary")
+        .arg("-Zself-profile-chrome-trace")
+        .run();
+
+    output_file(".mm_profdata");
+
+    let summary = fs::read_to_string(output_file(".summary.txt")).unwrap();
+    assert!(summary.starts_with("self-profile summary of `foo`"), "{summary}");
+    assert!(summary.contains("Incremental load time"), "{summary}");
+    assert!(summary.lines().any(|line| line.starts_with("typeck ")), "{summary}");
+    assert!(!summary.contains("query cache hits are only recorded"), "{summary}");
+
+    let trace = fs::read_to_string(output_file(".chrome_trace.json")).unwrap();
+    assert!(trace.starts_with("[\n"), "{trace}");
+    assert!(trace.trim_end().ends_with(']'), "{trace}");
+    assert!(trace.contains(r#""name":"typeck","cat":"Query","ph":"X""#), "{trace}");
+    assert!(trace.contains(r#""cat":"QueryCacheHit","ph":"i""#), "{trace}");
+}
@@ -0,0 +1,77 @@
+//! This checks that `-Zshared-cache` stores the compiled codegen units and the trait selection
+//! results of a crate in the cache directory, and that a second, independent compilation of the
+//! same crate reuses them instead of adding new entries. It also checks that the cache is garbage
+//! collected at the end of a session that doesn't reach codegen.
+
+use run_make_support::{rustc, tmp_dir};
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn entries(cache: &Path) -> BTreeSet<PathBuf> {
+    fs::read_dir(cache.join("entries")).unwrap().map(|entry| entry.unwrap().path()).collect()
+}
+
+fn last_used(entry: &Path) -> SystemTime {
+    fs::metadata(entry.join("last-used")).unwrap().modified().unwrap()
+}
+
+fn is_module(entry: &Path) -> bool {
+    entry.join("module.o").exists()
+}
+
+fn is_query_result(entry: &Path) -> bool {
+    entry.join("query-result").exists()
+}
+
+fn main() {
+    let src = tmp_dir().join("lib.rs");
+    let cache = tmp_dir().join("cache");
+    fs::write(&src, "pub fn f(v: &Vec<u32>) -> Vec<u32> {\n    v.clone()\n}\n").unwrap();
+
+    let compile = |out_dir: &str| {
+        rustc()
+            .input(&src)
+            .crate_type("lib")
+            .arg("--out-dir")
+            .arg(tmp_dir().join(out_dir))
+            .arg(format!("-Zshared-cache={}", cache.display()))
+            .run();
+        assert!(tmp_dir().join(out_dir).join("liblib.rlib").exists());
+        assert!(fs::read_dir(cache.join("tmp")).unwrap().next().is_none());
+    };
+
+    compile("a");
+    let stored = entries(&cache);
+    assert!(stored.iter().any(|entry| is_module(entry)));
+    assert!(stored.iter().any(|entry| is_query_result(entry)));
+
+    // Pretend that the entries haven't been used in a long time, to see which ones the second
+    // compilation uses.
+    for entry in &stored {
+        File::options()
+            .write(true)
+            .open(entry.join("last-used"))
+            .unwrap()
+            .set_modified(UNIX_EPOCH)
+            .unwrap();
+    }
+
+    compile("b");
+    assert_eq!(entries(&cache), stored);
+    let used: Vec<_> = stored.iter().filter(|entry| last_used(entry) > UNIX_EPOCH).collect();
+    assert!(used.iter().any(|entry| is_module(entry)));
+    assert!(used.iter().any(|entry| is_query_result(entry)));
+
+    rustc()
+        .input(&src)
+        .crate_type("lib")
+        .emit("metadata")
+        .arg("--out-dir")
+        .arg(tmp_dir().join("c"))
+        .arg(format!("-Zshared-cache={}", cache.display()))
+        .arg("-Zshared-cache-max-size=0")
+        .run();
+    assert!(entries(&cache).is_empty());
+}
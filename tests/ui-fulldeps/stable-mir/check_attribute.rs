@@ -0,0 +1,136 @@
+//@ run-pass
+//! Test attributes, documentation, visibility and the module tree of items.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::Visibility;
+use stable_mir::{CrateDef, CrateItems};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    test_attrs(&items);
+    test_modules(&items);
+    ControlFlow::Continue(())
+}
+
+fn test_attrs(items: &CrateItems) {
+    let spec = get_item(items, "inner::spec").unwrap();
+    let attrs = spec.attrs_by_path(&["rustfmt".to_string(), "skip".to_string()]);
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(attrs[0].as_str(), "#[rustfmt::skip]");
+
+    let attrs = spec.attrs_by_path(&["inline".to_string()]);
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(attrs[0].as_str(), "#[inline(always)]");
+
+    assert_eq!(spec.all_attrs().len(), 5);
+    assert_eq!(spec.doc_comment().unwrap(), "Some documentation.\n\nMore documentation.");
+
+    let private = get_item(items, "inner::private").unwrap();
+    assert!(private.all_attrs().is_empty());
+    assert_eq!(private.doc_comment(), None);
+
+    // The crate metadata doesn't have the attributes of foreign modules.
+    let std = stable_mir::find_crates("std").pop().unwrap();
+    let foreign_module = std.foreign_modules().pop().unwrap();
+    assert!(foreign_module.all_attrs().is_empty());
+    assert!(foreign_module.attrs_by_path(&["link".to_string()]).is_empty());
+    assert_eq!(foreign_module.doc_comment(), None);
+}
+
+fn test_modules(items: &CrateItems) {
+    let root = stable_mir::local_crate().root_module();
+    assert_eq!(root.parent_module(), None);
+    assert_eq!(root.visibility().unwrap(), Visibility::Public);
+
+    let submodules = root.submodules();
+    assert_eq!(submodules.len(), 1);
+    let inner = submodules[0];
+    assert_eq!(inner.name(), "inner");
+    assert_eq!(inner.parent_module(), Some(root));
+    assert_eq!(inner.visibility().unwrap(), Visibility::Restricted(root));
+
+    let spec = get_item(items, "inner::spec").unwrap();
+    assert_eq!(spec.parent_module(), Some(inner));
+    assert_eq!(spec.visibility().unwrap(), Visibility::Public);
+    let private = get_item(items, "inner::private").unwrap();
+    assert_eq!(private.visibility().unwrap(), Visibility::Restricted(inner));
+    let krate = get_item(items, "inner::krate").unwrap();
+    assert_eq!(krate.visibility().unwrap(), Visibility::Restricted(root));
+
+    let mut children: Vec<_> = inner.children().into_iter().map(|child| child.name).collect();
+    children.sort();
+    assert_eq!(children, ["krate", "private", "spec"]);
+
+    let reexport = root.children().into_iter().find(|child| child.name == "spec").unwrap();
+    assert!(reexport.is_import);
+    assert_eq!(reexport.def_id, spec.0);
+    assert_eq!(reexport.vis, Visibility::Public);
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "attribute_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub use inner::spec;
+
+        mod inner {{
+            /// Some documentation.
+            ///
+            /// More documentation.
+            #[rustfmt::skip]
+            #[inline(always)]
+            pub fn spec() {{}}
+
+            fn private() {{}}
+
+            pub(crate) fn krate() {{
+                private();
+            }}
+        }}
+        "#
+    )?;
+    Ok(())
+}
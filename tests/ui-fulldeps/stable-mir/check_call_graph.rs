@@ -0,0 +1,144 @@
+//@ run-pass
+//! Test the call graph of the mono items collected for a crate.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::call_graph::{CallGraph, CollectionReason, EdgeKind};
+use stable_mir::mir::mono::{Instance, MonoItem};
+use stable_mir::CrateDef;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to build the call graph of the test crate.
+fn test_call_graph() -> ControlFlow<()> {
+    let graph = CallGraph::collect().unwrap();
+
+    // `entry` is exported, so the collection starts from it.
+    let entry = find_fn(&graph, "entry");
+    assert!(graph.roots().contains(&entry));
+    assert_eq!(graph.collection_reasons(&entry), [CollectionReason::Root]);
+
+    // Generic functions are only collected for the arguments they are used with.
+    let generic = find_fn(&graph, "generic::<u32>");
+    assert!(!graph.roots().contains(&generic));
+    let reasons = graph.collection_reasons(&generic);
+    assert_eq!(reasons.len(), 1);
+    assert_matches!(reasons[0], CollectionReason::Used(edge)
+        if edge.user == entry && edge.kind == EdgeKind::Call);
+
+    let uses_of = |user: &MonoItem, used: &MonoItem| {
+        graph.uses(user).filter(|edge| edge.used == *used).map(|edge| edge.kind).collect::<Vec<_>>()
+    };
+    assert_eq!(uses_of(&entry, &find_fn(&graph, "double")), [EdgeKind::FnPointer]);
+    assert_eq!(
+        uses_of(&entry, &find_fn(&graph, "<Square as Shape>::area")),
+        [EdgeKind::VtableMethod]
+    );
+    let table = graph
+        .items()
+        .iter()
+        .find(|item| matches!(item, MonoItem::Static(def) if def.trimmed_name() == "TABLE"))
+        .unwrap();
+    assert_eq!(uses_of(&entry, table), [EdgeKind::Static]);
+
+    // The `_guard` argument is dropped at the end of `entry`.
+    let MonoItem::Fn(entry_instance) = &entry else { unreachable!() };
+    let guard_ty = entry_instance.body().unwrap().arg_locals()[0].ty;
+    let drop_glue = MonoItem::Fn(Instance::resolve_drop_in_place(guard_ty));
+    assert_eq!(uses_of(&entry, &drop_glue), [EdgeKind::DropGlue]);
+    assert_eq!(uses_of(&drop_glue, &find_fn(&graph, "<Guard as Drop>::drop")), [EdgeKind::Call]);
+
+    // Every edge is between collected items.
+    for edge in &graph.edges() {
+        assert!(graph.items().contains(&edge.user));
+        assert!(graph.items().contains(&edge.used));
+    }
+    ControlFlow::Continue(())
+}
+
+fn find_fn(graph: &CallGraph, name: &str) -> MonoItem {
+    graph
+        .items()
+        .iter()
+        .find(|item| matches!(item, MonoItem::Fn(instance) if instance.trimmed_name() == name))
+        .unwrap_or_else(|| panic!("`{name}` was not collected"))
+        .clone()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "call_graph_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_call_graph).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub trait Shape {{
+            fn area(&self) -> u32;
+        }}
+
+        pub struct Square(pub u32);
+
+        impl Shape for Square {{
+            fn area(&self) -> u32 {{
+                self.0.wrapping_mul(self.0)
+            }}
+        }}
+
+        pub struct Guard;
+
+        impl Drop for Guard {{
+            fn drop(&mut self) {{}}
+        }}
+
+        static TABLE: [u32; 2] = [1, 2];
+
+        fn generic<T: Copy>(value: T) -> T {{
+            value
+        }}
+
+        fn double(value: u32) -> u32 {{
+            value.wrapping_mul(2)
+        }}
+
+        pub fn entry(_guard: Guard, value: u32) -> u32 {{
+            let shape: &dyn Shape = &Square(value);
+            let f: fn(u32) -> u32 = double;
+            generic(shape.area()).wrapping_add(f(TABLE[0]))
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//@ run-pass
+//! Test the dataflow analyses of Stable MIR bodies.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::dataflow::{
+    Analysis, MaybeBorrowedLocals, MaybeInitializedLocals, MaybeLiveLocals, ProgramPoint,
+};
+use stable_mir::mir::{Body, TerminatorKind};
+use stable_mir::CrateDef;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to analyze the body of `foo`.
+fn test_dataflow() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let foo = items.iter().find(|item| item.name() == "foo").unwrap();
+    let body = foo.body();
+    let ret = return_point(&body);
+
+    // The arguments `a` and `b` are read later, and only the return value is read by `return`.
+    let mut live = MaybeLiveLocals.iterate_to_fixpoint(&body).into_results_cursor(&body);
+    live.seek_to_block_start(0);
+    assert!(live.get().contains(1));
+    assert!(live.get().contains(2));
+    live.seek_before(ret);
+    assert_eq!(live.get().iter().collect::<Vec<_>>(), [0]);
+    live.seek_after(ret);
+    assert!(live.get().is_empty());
+
+    // The arguments are initialized at the start, and the return value is set before `return`.
+    let mut init = MaybeInitializedLocals.iterate_to_fixpoint(&body).into_results_cursor(&body);
+    init.seek_to_block_start(0);
+    assert_eq!(init.get().iter().collect::<Vec<_>>(), [1, 2]);
+    init.seek_before(ret);
+    assert!(init.get().contains(0));
+
+    // Only `b` is borrowed.
+    let mut borrowed = MaybeBorrowedLocals.iterate_to_fixpoint(&body).into_results_cursor(&body);
+    borrowed.seek_to_block_start(0);
+    assert!(borrowed.get().is_empty());
+    borrowed.seek_before(ret);
+    assert_eq!(borrowed.get().iter().collect::<Vec<_>>(), [2]);
+
+    test_call_unwind();
+    ControlFlow::Continue(())
+}
+
+/// The destination of a call is only written when the call returns, so it keeps its previous
+/// value in the cleanup block of `bar`, which reads it.
+fn test_call_unwind() {
+    let items = stable_mir::all_local_items();
+    let bar = items.iter().find(|item| item.name() == "bar").unwrap();
+    let body = bar.body();
+    let call = ProgramPoint::terminator_of(&body, 0);
+    assert!(matches!(body.blocks[0].terminator.kind, TerminatorKind::Call { .. }));
+
+    let mut live = MaybeLiveLocals.iterate_to_fixpoint(&body).into_results_cursor(&body);
+    live.seek_before(call);
+    assert_eq!(live.get().iter().collect::<Vec<_>>(), [1]);
+
+    let mut init = MaybeInitializedLocals.iterate_to_fixpoint(&body).into_results_cursor(&body);
+    init.seek_to_block_start(1);
+    assert!(init.get().contains(1));
+    init.seek_to_block_start(2);
+    assert!(!init.get().contains(1));
+}
+
+/// The terminator that returns from the body.
+fn return_point(body: &Body) -> ProgramPoint {
+    let block = body
+        .blocks
+        .iter()
+        .position(|block| matches!(block.terminator.kind, TerminatorKind::Return))
+        .unwrap();
+    ProgramPoint::terminator_of(body, block)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "dataflow_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_dataflow).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        #![feature(custom_mir, core_intrinsics)]
+        use std::intrinsics::mir::*;
+
+        pub fn foo(a: u32, b: u32) -> u32 {{
+            let r = &b;
+            let x = a / 2;
+            x + *r
+        }}
+
+        pub fn seven() -> u32 {{
+            7
+        }}
+
+        #[custom_mir(dialect = "runtime", phase = "optimized")]
+        pub fn bar() -> u32 {{
+            mir!(
+                let y: u32;
+                {{
+                    Call(y = seven(), ReturnTo(bb1), UnwindCleanup(bb2))
+                }}
+                bb1 = {{
+                    RET = y;
+                    Return()
+                }}
+                bb2 (cleanup) = {{
+                    RET = y;
+                    UnwindResume()
+                }}
+            )
+        }}
+        "#
+    )?;
+    Ok(())
+}
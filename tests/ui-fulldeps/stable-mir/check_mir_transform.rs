@@ -0,0 +1,134 @@
+//@ run-pass
+//! Test that MIR transformations registered through `rustc_internal` apply to codegen.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal::{self, MirTransform};
+use stable_mir::mir::call_graph::CallGraph;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::{Body, Constant, MutMirVisitor};
+use stable_mir::ty::{Const, RigidTy, TyKind};
+use std::io::Write;
+use std::ops::ControlFlow;
+use std::process::Command;
+
+const CRATE_NAME: &str = "input";
+
+/// Replaces every boolean constant by `true`.
+struct ReplaceBools;
+
+impl MutMirVisitor for ReplaceBools {
+    fn visit_constant(&mut self, constant: &mut Constant, location: Location) {
+        if constant.literal.ty().kind() == TyKind::RigidTy(RigidTy::Bool) {
+            constant.literal = Const::from_bool(true);
+        }
+        self.super_constant(constant, location);
+    }
+}
+
+/// Applies `ReplaceBools` to the body of `answer`.
+struct TransformAnswer;
+
+impl MirTransform for TransformAnswer {
+    fn applies_to(&self, instance: Instance) -> bool {
+        instance.trimmed_name() == "answer"
+    }
+
+    fn transform(&self, _instance: Instance, body: &mut Body) {
+        ReplaceBools.visit_body(body);
+    }
+}
+
+/// The transformation is only applied to the generated code, so the body returned by the
+/// instance is left unchanged.
+fn test_transform() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let answer = items.iter().find(|item| item.name() == "answer").unwrap();
+    let instance = Instance::try_from(*answer).unwrap();
+    assert!(TransformAnswer.applies_to(instance));
+    let mut body = instance.body().unwrap();
+    let original = body.clone();
+    TransformAnswer.transform(instance, &mut body);
+    assert_ne!(body, original);
+    assert_eq!(instance.body().unwrap(), original);
+    rustc_internal::register_mir_transform(TransformAnswer).unwrap();
+    ControlFlow::Continue(())
+}
+
+/// Collecting the mono items computes the bodies that are codegened, so it is too late to register
+/// a transformation after that.
+fn test_late_transform() -> ControlFlow<()> {
+    CallGraph::collect().unwrap();
+    assert!(rustc_internal::register_mir_transform(TransformAnswer).is_err());
+    ControlFlow::Continue(())
+}
+
+fn compile(path: &str, output: &str, callback: fn() -> ControlFlow<()>) {
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=bin".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "-o".to_string(),
+        output.to_string(),
+        path.to_string(),
+    ];
+    run!(args, callback).unwrap();
+}
+
+/// This test will generate a dummy binary, whose `answer` function returns `true` only if the
+/// transformation was applied when generating its code, and then run it. The transformation must
+/// not apply to a later compilation, which is checked by compiling the binary again, nor if it is
+/// registered too late.
+fn main() {
+    let path = "mir_transform_input.rs";
+    let output = "mir_transform_output";
+    generate_input(&path).unwrap();
+    compile(path, output, test_transform);
+    let status = Command::new(format!("./{output}")).status().unwrap();
+    assert!(status.success(), "the transformation wasn't applied: {status}");
+
+    let untransformed = "mir_transform_untransformed_output";
+    compile(path, untransformed, || ControlFlow::Continue(()));
+    let status = Command::new(format!("./{untransformed}")).status().unwrap();
+    assert!(!status.success(), "the transformation was applied to another compilation");
+
+    let late = "mir_transform_late_output";
+    compile(path, late, test_late_transform);
+    let status = Command::new(format!("./{late}")).status().unwrap();
+    assert!(!status.success(), "the transformation was registered too late");
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        #[inline(never)]
+        fn answer() -> bool {{
+            false
+        }}
+
+        fn main() {{
+            if !answer() {{
+                std::process::exit(1);
+            }}
+        }}
+        "#
+    )?;
+    Ok(())
+}
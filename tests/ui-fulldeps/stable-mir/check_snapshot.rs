@@ -0,0 +1,197 @@
+//@ run-pass
+//! Test that a crate can be analyzed from the Stable MIR snapshot emitted by the compiler.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate stable_mir;
+
+use rustc_driver::{Callbacks, RunCompiler};
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{BinOp, Operand, Rvalue, StatementKind, TerminatorKind};
+use stable_mir::snapshot::Snapshot;
+use stable_mir::ty::{AdtKind, ConstantKind, RigidTy, TyKind, UintTy};
+use stable_mir::{CrateDef, ItemKind};
+use std::assert_matches::assert_matches;
+use std::fs::File;
+use std::io::{BufReader, Write};
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs outside of the compiler, with the snapshot.
+fn test_snapshot() {
+    assert_eq!(stable_mir::local_crate().name, CRATE_NAME);
+    let items = stable_mir::all_local_items();
+
+    let add = items.iter().find(|item| item.name() == "add").unwrap();
+    assert_eq!(add.kind(), ItemKind::Fn);
+    assert_eq!(add.span().get_lines().start_line, 2);
+    let body = add.body();
+    let arg = &body.arg_locals()[0];
+    assert_eq!(arg.ty.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    assert_eq!(arg.ty.to_string(), "u32");
+    assert_eq!(arg.ty.layout().unwrap().shape().size.bytes(), 4);
+
+    // The layout of generic types isn't known.
+    let identity = items.iter().find(|item| item.name() == "identity").unwrap();
+    assert!(identity.requires_monomorphization());
+    assert!(identity.body().arg_locals()[0].ty.layout().is_err());
+
+    // The string constant points to an allocation that holds its bytes.
+    let greeting = items.iter().find(|item| item.name() == "greeting").unwrap();
+    let body = greeting.body();
+    let constant = body.blocks[0]
+        .statements
+        .iter()
+        .find_map(|statement| match &statement.kind {
+            StatementKind::Assign(_, Rvalue::Use(Operand::Constant(constant))) => Some(constant),
+            _ => None,
+        })
+        .unwrap();
+    let ConstantKind::Allocated(allocation) = constant.literal.kind() else { unreachable!() };
+    let (_, prov) = allocation.provenance.ptrs[0];
+    assert_matches!(GlobalAlloc::from(prov.0), GlobalAlloc::Memory(memory)
+        if memory.raw_bytes().unwrap() == b"hello");
+
+    // The definitions the items refer to are recorded.
+    let sum = items.iter().find(|item| item.name() == "sum").unwrap();
+    let body = sum.body();
+    let point_ty = body.arg_locals()[0].ty;
+    let TyKind::RigidTy(RigidTy::Adt(point, _)) = point_ty.kind() else { unreachable!() };
+    assert_eq!(point.kind(), AdtKind::Struct);
+    assert!(point.doc_comment().unwrap().contains("A point."));
+    let fields = point.variants()[0].fields();
+    assert_eq!(fields.iter().map(|field| field.name.as_str()).collect::<Vec<_>>(), ["x", "y"]);
+    assert_eq!(fields[0].ty().kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+
+    // The calls of monomorphic functions can be resolved, and the instances of the local crate
+    // have a body.
+    let TerminatorKind::Call { func, .. } = &body.blocks[0].terminator.kind else { unreachable!() };
+    let TyKind::RigidTy(RigidTy::FnDef(def, args)) = func.ty(body.locals()).unwrap().kind() else {
+        unreachable!()
+    };
+    let instance = Instance::resolve(def, &args).unwrap();
+    assert_eq!(instance.trimmed_name(), "add");
+    assert!(instance.body().is_some());
+
+    // Places are printed like in the compiler.
+    let field = body.blocks[0]
+        .statements
+        .iter()
+        .find_map(|statement| match &statement.kind {
+            StatementKind::Assign(_, Rvalue::Use(Operand::Copy(place))) => Some(place),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(format!("{field:?}"), "(_1.0: u32)");
+
+    let is_zero = items.iter().find(|item| item.name() == "is_zero").unwrap();
+    assert_eq!(is_zero.attrs_by_path(&["must_use".to_string()]).len(), 1);
+    let body = is_zero.body();
+    let comparison = body.blocks[0]
+        .statements
+        .iter()
+        .find_map(|statement| match &statement.kind {
+            StatementKind::Assign(_, rvalue @ Rvalue::BinaryOp(BinOp::Eq, ..)) => Some(rvalue),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(comparison.ty(body.locals()).unwrap().kind(), TyKind::RigidTy(RigidTy::Bool));
+
+    // The trait declarations and implementations of the local crate are recorded.
+    let krate = stable_mir::local_crate();
+    assert_eq!(krate.trait_decls().len(), 1);
+    let shape = krate.trait_decls()[0];
+    assert_eq!(shape.trimmed_name(), "Shape");
+    assert!(krate.trait_impls().iter().any(|impl_def| impl_def.trait_impl().value.def_id == shape));
+}
+
+struct EmitSnapshot;
+
+impl Callbacks for EmitSnapshot {}
+
+/// This test will generate a dummy crate, emit its Stable MIR snapshot with the compiler,
+/// and then analyze the snapshot without running the compiler.
+fn main() {
+    let path = "snapshot_input.rs";
+    let snapshot_path = "snapshot_input.smir.json";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        format!("--emit=stable-mir={snapshot_path}"),
+        "-Zunstable-options".to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| RunCompiler::new(&args, &mut EmitSnapshot).run())
+        .unwrap()
+        .unwrap();
+
+    let file = File::open(snapshot_path).unwrap();
+    let snapshot = Snapshot::read(BufReader::new(file)).unwrap();
+    snapshot.run(test_snapshot).unwrap();
+
+    // Snapshots with another format version are rejected.
+    let mut outdated = snapshot.clone();
+    outdated.format_version += 1;
+    let mut json = Vec::new();
+    outdated.write(&mut json).unwrap();
+    assert!(Snapshot::read(&json[..]).is_err());
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub fn add(a: u32, b: u32) -> u32 {{
+            a.wrapping_add(b)
+        }}
+
+        pub fn identity<T>(value: T) -> T {{
+            value
+        }}
+
+        pub fn greeting() -> &'static str {{
+            "hello"
+        }}
+
+        /// A point.
+        #[derive(Clone, Copy)]
+        pub struct Point {{
+            pub x: u32,
+            pub y: u32,
+        }}
+
+        pub fn sum(point: Point) -> u32 {{
+            add(point.x, point.y)
+        }}
+
+        #[must_use]
+        pub fn is_zero(value: u32) -> bool {{
+            value == 0
+        }}
+
+        pub trait Shape {{
+            fn area(&self) -> u32;
+        }}
+
+        impl Shape for Point {{
+            fn area(&self) -> u32 {{
+                0
+            }}
+        }}
+        "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+//@ run-pass
+//! Test the trait-solver queries of Stable MIR.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{
+    AliasKind, ClauseKind, GenericArgs, ParamEnv, PredicatePolarity, RigidTy, TraitPredicate,
+    TraitRef, TyKind, UintTy,
+};
+use stable_mir::{CrateDef, CrateItems};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to query the trait solver about the test crate.
+fn test_trait_solver() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    test_implements(&items);
+    test_normalize(&items);
+    test_ty_queries(&items);
+    ControlFlow::Continue(())
+}
+
+fn test_implements(items: &CrateItems) {
+    let traits = stable_mir::all_trait_decls();
+    let shape = traits.iter().find(|def| def.name() == "Shape").unwrap();
+    let area = get_item(items, "area_of_square").unwrap();
+    let square = area.body().arg_locals()[0].ty;
+    let no_args = GenericArgs(vec![]);
+    assert!(TraitRef::new(*shape, square, &no_args).is_implemented(&ParamEnv::RevealAll));
+    let u32_ty = stable_mir::ty::Ty::unsigned_ty(UintTy::U32);
+    assert!(!TraitRef::new(*shape, u32_ty, &no_args).is_implemented(&ParamEnv::RevealAll));
+
+    // `T: Shape` only holds in an environment that assumes it.
+    let generic = get_item(items, "generic").unwrap();
+    let param = generic.body().arg_locals()[0].ty;
+    let param_is_shape = TraitRef::new(*shape, param, &no_args);
+    let env = ParamEnv::Predicates(vec![ClauseKind::Trait(TraitPredicate {
+        trait_ref: param_is_shape.clone(),
+        polarity: PredicatePolarity::Positive,
+    })]);
+    assert!(param_is_shape.is_implemented(&env));
+    assert!(!param_is_shape.is_implemented(&ParamEnv::RevealAll));
+}
+
+fn test_normalize(items: &CrateItems) {
+    // The signature refers to `<Square as Shape>::Area`, which is `u64`.
+    let area = get_item(items, "area_of_square").unwrap();
+    let output = area.ty().kind().fn_sig().unwrap().skip_binder().output();
+    assert_matches!(output.kind(), TyKind::Alias(AliasKind::Projection, _));
+    let normalized = output.normalize(&ParamEnv::RevealAll).unwrap();
+    assert_eq!(normalized.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U64)));
+}
+
+fn test_ty_queries(items: &CrateItems) {
+    let generic = get_item(items, "generic").unwrap();
+    let body = generic.body();
+    let env = ParamEnv::Item(generic.def_id());
+    let param = body.arg_locals()[0].ty;
+    let string = body.arg_locals()[1].ty;
+    let slice_ref = body.arg_locals()[2].ty;
+
+    // `T: Copy` only holds with the where clauses of `generic`.
+    assert!(param.is_copy(&env));
+    assert!(!param.is_copy(&ParamEnv::RevealAll));
+    assert!(param.is_sized(&env));
+    assert!(!param.needs_drop(&env));
+
+    assert!(!string.is_copy(&env));
+    assert!(string.needs_drop(&env));
+
+    assert!(slice_ref.is_copy(&env));
+    let TyKind::RigidTy(RigidTy::Ref(_, slice, _)) = slice_ref.kind() else { unreachable!() };
+    assert!(!slice.is_sized(&env));
+    assert!(!slice.needs_drop(&env));
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a stable_mir::CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "trait_solver_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_trait_solver).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub trait Shape {{
+            type Area;
+            fn area(&self) -> Self::Area;
+        }}
+
+        pub struct Square(pub u32);
+
+        impl Shape for Square {{
+            type Area = u64;
+            fn area(&self) -> u64 {{
+                u64::from(self.0) * u64::from(self.0)
+            }}
+        }}
+
+        pub fn area_of_square(square: Square) -> <Square as Shape>::Area {{
+            square.area()
+        }}
+
+        pub fn generic<T: Copy>(value: T, _text: String, _bytes: &[u8]) -> T {{
+            value
+        }}
+        "#
+    )?;
+    Ok(())
+}
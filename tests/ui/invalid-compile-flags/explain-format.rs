@@ -0,0 +1 @@
+//@ compile-flags: --explain E0308 --explain-format=yaml -Zunstable-options
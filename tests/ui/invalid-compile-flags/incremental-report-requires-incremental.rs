@@ -0,0 +1,5 @@
+// Verifies that `-Zincremental-report` requires `-Cincremental`.
+//
+//@ compile-flags: -Zincremental-report
+
+fn main() {}
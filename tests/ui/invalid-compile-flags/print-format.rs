@@ -0,0 +1 @@
+//@ compile-flags: --print cfg --print-format=yaml -Zunstable-options
@@ -0,0 +1,60 @@
+// Checks that the `large_futures` lint reports the futures larger than `-Zfuture-size-limit`,
+// and the values held across `.await` points that make them large.
+
+//@ edition:2021
+//@ compile-flags: -Zfuture-size-limit=1024
+//@ normalize-stderr-test: "future is \d+ bytes" -> "future is $$SIZE bytes"
+//@ normalize-stderr-test: "future takes \d+ bytes" -> "future takes $$SIZE bytes"
+
+#![deny(large_futures)]
+
+async fn yield_now() {}
+
+fn consume(_: &[u8]) {}
+
+async fn small() {
+    let buffer = [0u8; 512];
+    yield_now().await;
+    consume(&buffer);
+}
+
+async fn large_local() {
+    //~^ ERROR this future is
+    let buffer = [0u8; 2048];
+    yield_now().await;
+    yield_now().await;
+    consume(&buffer);
+}
+
+async fn dropped_before_await() {
+    {
+        let buffer = [0u8; 2048];
+        consume(&buffer);
+    }
+    yield_now().await;
+}
+
+async fn large_awaitee() {
+    //~^ ERROR this future is
+    large_local().await;
+}
+
+fn large_block() -> impl std::future::Future<Output = ()> {
+    async {
+        //~^ ERROR this future is
+        let buffer = [1u8; 4096];
+        yield_now().await;
+        consume(&buffer);
+    }
+}
+
+#[allow(large_futures)]
+async fn allowed() {
+    let buffer = [0u8; 2048];
+    yield_now().await;
+    consume(&buffer);
+}
+
+fn main() {
+    let _ = (small(), dropped_before_await(), large_awaitee(), large_block(), allowed());
+}